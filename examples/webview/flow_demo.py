@@ -163,6 +163,7 @@ def main():
                         print(f"      • {source_method} → {sink_method}")
                         print(f"        Confidence: {df.confidence:.2f}")
                         print(f"        Hops: {len(df.flow_path)}")
+                        print(f"        Evidence: {'; '.join(df.evidence)}")
             else:
                 print("      No Intent→WebView data flows detected")
                 print("      (WebView may use hardcoded URLs or other data sources)")
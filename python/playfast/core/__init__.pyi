@@ -0,0 +1,851 @@
+import os
+from collections.abc import Callable
+from typing import Any
+
+from playfast.core import legacy as legacy
+
+# ============================================================================
+# Google Play Store Types
+# ============================================================================
+
+class RustAppInfo:
+    app_id: str
+    title: str
+    developer: str
+    icon: str
+    score: float
+    ratings: int
+    price: float
+    currency: str
+    free: bool
+    description: str | None
+    summary: str | None
+    installs: str | None
+    min_installs: int | None
+    score_text: str | None
+    released: str | None
+    updated: str | None
+    version: str | None
+    required_android_version: str | None
+    content_rating: str | None
+    content_rating_description: str | None
+    ad_supported: bool | None
+    contains_ads: bool | None
+    in_app_purchases: bool | None
+    editors_choice: bool | None
+    developer_id: str | None
+    developer_email: str | None
+    developer_website: str | None
+    developer_address: str | None
+    privacy_policy: str | None
+    genre: str | None
+    genre_id: str | None
+    category: str | None
+    video: str | None
+    video_image: str | None
+    screenshots: list[str]
+    similar: list[str]
+    permissions: list[RustPermission]
+
+class RustReview:
+    review_id: str
+    user_name: str
+    user_image: str
+    content: str
+    score: int
+    thumbs_up: int
+    created_at: int
+    reply_content: str | None
+    reply_at: int | None
+
+class RustSearchResult:
+    app_id: str
+    title: str
+    developer: str
+    icon: str
+    score: float | None
+    review_count: int | None
+    price: float
+    currency: str
+
+class RustPermission:
+    group: str
+    permissions: list[str]
+    def __len__(self) -> int: ...
+
+def parse_app_page(html: str, app_id: str) -> RustAppInfo: ...
+def parse_review_batch(html: str) -> list[RustReview]: ...
+def parse_search_results(html: str) -> list[RustSearchResult]: ...
+def extract_continuation_token(html: str) -> str | None: ...
+def parse_batchexecute_list_response(response_text: str) -> list[RustSearchResult]: ...
+def build_list_request_body(
+    category: str | None, collection: str, num: int = 100
+) -> str: ...
+def build_reviews_request_body(
+    app_id: str, sort: int, continuation_token: str | None, lang: str, country: str
+) -> str: ...
+def parse_play_url(url: str) -> str | None: ...
+def parse_batchexecute_reviews_response(
+    response_text: str,
+) -> tuple[list[RustReview], str | None]: ...
+def build_similar_request_body(
+    app_id: str, num: int = 100, continuation_token: str | None = None
+) -> str: ...
+def parse_batchexecute_similar_response(
+    response_text: str,
+) -> tuple[list[RustSearchResult], str | None]: ...
+
+def configure_base_url(url: str) -> None: ...
+
+# Single request functions (HTTP + parsing)
+def fetch_and_parse_app(
+    app_id: str,
+    lang: str,
+    country: str,
+    timeout: int = 30,
+    _timeout: int | None = None,
+) -> RustAppInfo: ...
+def fetch_and_parse_reviews(
+    app_id: str,
+    lang: str,
+    country: str,
+    sort: int = 1,
+    continuation_token: str | None = None,
+    timeout: int = 30,
+    _timeout: int | None = None,
+) -> tuple[list[RustReview], str | None]: ...
+def fetch_reviews_to_jsonl(
+    app_id: str,
+    lang: str,
+    country: str,
+    sort: int,
+    max_reviews: int,
+    path: str | os.PathLike[str],
+    timeout: int = 30,
+) -> int: ...
+def fetch_similar_apps(
+    app_id: str, lang: str, country: str, timeout: int = 30
+) -> list[RustSearchResult]: ...
+def fetch_and_parse_search(
+    query: str,
+    lang: str,
+    country: str,
+    timeout: int = 30,
+    _timeout: int | None = None,
+) -> list[RustSearchResult]: ...
+def fetch_and_parse_list(
+    category: str | None,
+    collection: str,
+    lang: str,
+    country: str,
+    num: int = 100,
+    timeout: int = 30,
+    _timeout: int | None = None,
+) -> list[RustSearchResult]: ...
+def fetch_and_parse_list_page(
+    category: str | None,
+    collection: str,
+    lang: str,
+    country: str,
+    num: int = 100,
+    token: str | None = None,
+    timeout: int = 30,
+) -> tuple[list[RustSearchResult], str | None]: ...
+
+# Batch functions for parallel processing (recommended for multiple requests)
+def fetch_and_parse_apps_batch(
+    requests: list[tuple[str, str, str]],
+) -> list[RustAppInfo]: ...
+def fetch_and_parse_list_batch(
+    requests: list[tuple[str | None, str, str, str, int]],
+) -> list[list[RustSearchResult]]: ...
+def fetch_and_parse_search_batch(
+    requests: list[tuple[str, str, str]],
+) -> list[list[RustSearchResult]]: ...
+def fetch_and_parse_reviews_batch(
+    requests: list[tuple[str, str, str, int, str | None]],
+) -> list[tuple[list[RustReview], str | None]]: ...
+
+# ============================================================================
+# APK Analysis Types
+# ============================================================================
+
+class RustDexClass:
+    class_name: str
+    package_name: str
+    simple_name: str
+    access_flags: int
+    superclass: str | None
+    interfaces: list[str]
+    source_file: str | None
+    methods: list[RustDexMethod]
+    fields: list[RustDexField]
+    method_count: int
+    field_count: int
+    references: RustReferencePool
+    source_module: str | None
+    is_public: bool
+    is_final: bool
+    is_abstract: bool
+    is_interface: bool
+    is_enum: bool
+    def is_androidx(self) -> bool: ...
+    def is_support_lib(self) -> bool: ...
+    def is_kotlin_stdlib(self) -> bool: ...
+    def is_kotlin_metadata(self) -> bool: ...
+    def is_test(self) -> bool: ...
+    def total_referenced_methods(self) -> int: ...
+    def total_referenced_strings(self) -> int: ...
+    def method_references(self) -> RustReferencePool: ...
+    def to_dict(self) -> dict[str, Any]: ...
+
+class RustDexMethod:
+    declaring_class: str
+    name: str
+    signature: str
+    parameters: list[str]
+    return_type: str
+    access_flags: int
+    references: RustReferencePool
+    is_public: bool
+    is_private: bool
+    is_protected: bool
+    is_static: bool
+    is_final: bool
+    is_constructor: bool
+    is_static_initializer: bool
+    def to_dict(self) -> dict[str, Any]: ...
+
+class RustDexField:
+    class_name: str
+    field_name: str
+    type_descriptor: str
+    access_flags: int
+
+class RustReferencePool:
+    strings: list[str]
+    types: list[str]
+    fields: list[str]
+    methods: list[str]
+    def merge(self, other: RustReferencePool) -> None: ...
+    def __len__(self) -> int: ...
+
+class ClassIterator:
+    def __iter__(self) -> ClassIterator: ...
+    def __next__(self) -> RustDexClass: ...
+
+class MethodIterator:
+    def __iter__(self) -> MethodIterator: ...
+    def __next__(self) -> tuple[RustDexClass, RustDexMethod]: ...
+
+class DeclaredPermission:
+    name: str
+    protection_level: str
+    def is_signature(self) -> bool: ...
+
+class ProviderInfo:
+    name: str
+    authorities: list[str]
+    grant_uri_permissions: bool
+    permission: str | None
+    read_permission: str | None
+    write_permission: str | None
+    exported: bool
+    def is_world_readable(self) -> bool: ...
+    def is_world_writable(self) -> bool: ...
+
+class RustManifestInfo:
+    package_name: str
+    version_code: str | None
+    version_name: str | None
+    min_sdk_version: int | None
+    target_sdk_version: int | None
+    application_label: str | None
+    permissions: list[str]
+    activities: list[str]
+    services: list[str]
+    receivers: list[str]
+    providers: list[str]
+    intent_filters: list[Any]
+    allow_backup: bool | None
+    debuggable: bool | None
+    uses_cleartext_traffic: bool | None
+    network_security_config: str | None
+    component_processes: dict[str, str]
+    exported_components: list[str]
+    application_permission: str | None
+    component_permissions: dict[str, str]
+    declared_permissions: list[DeclaredPermission]
+    component_labels: dict[str, str]
+    providers_detail: list[ProviderInfo]
+    activity_aliases: dict[str, str]
+    def get_deeplinks(self) -> list[Any]: ...
+    def components_by_process(self) -> dict[str, list[str]]: ...
+    def get_launcher_activities(self) -> list[str]: ...
+    def security_flags(self) -> list[str]: ...
+    def unprotected_exported_components(self) -> list[str]: ...
+    def to_dict(self) -> dict[str, Any]: ...
+
+class IntentFilterData:
+    scheme: str | None
+    host: str | None
+    path: str | None
+    path_prefix: str | None
+    path_pattern: str | None
+    def to_dict(self) -> dict[str, Any]: ...
+
+class ActivityIntentFilter:
+    activity: str
+    component_type: str
+    actions: list[str]
+    categories: list[str]
+    data: list[IntentFilterData]
+    def is_deeplink(self) -> bool: ...
+    def to_dict(self) -> dict[str, Any]: ...
+
+class RustInstruction:
+    opcode: str
+    dest: int | None
+    value: int | None
+    string_idx: int | None
+    method_idx: int | None
+    args: list[int]
+    raw: str
+
+class MethodSignature:
+    class_name: str
+    method_name: str
+    descriptor: str
+
+# ============================================================================
+# Entry Point Analysis
+# ============================================================================
+
+class ComponentType:
+    Activity: str
+    Service: str
+    BroadcastReceiver: str
+    ContentProvider: str
+
+class EntryPoint:
+    class_name: str
+    component_type: str
+    lifecycle_methods: list[str]
+    is_exported: bool
+    intent_filters: list[IntentFilterData]
+    is_deeplink_handler: bool
+    authorities: list[str]
+
+class PyEntryPointAnalyzer:
+    def analyze(self) -> list[EntryPoint]: ...
+    def get_deeplink_handlers(self) -> list[EntryPoint]: ...
+    def get_analyzable_deeplink_handlers(self) -> list[tuple[EntryPoint, DecompiledClass]]: ...
+    def get_found_entry_points(self, exported_only: bool = False) -> list[EntryPoint]: ...
+    def get_stats(self) -> dict[str, int]: ...
+
+def analyze_entry_points_from_apk(apk_path: str) -> PyEntryPointAnalyzer: ...
+
+# ============================================================================
+# Call Graph Analysis
+# ============================================================================
+
+class MethodCall:
+    caller: str
+    callee: str
+    call_sites: int
+
+class CallPath:
+    methods: list[str]
+    length: int
+
+class CallGraph:
+    def find_methods_matching(self, pattern: str) -> list[str]: ...
+    def find_paths(
+        self,
+        source: str,
+        target: str,
+        max_depth: int | None = None,
+        max_paths: int | None = None,
+    ) -> list[CallPath]: ...
+    def find_shortest_path(
+        self, source: str, target: str, max_depth: int | None = None
+    ) -> CallPath | None: ...
+    def is_reachable(self, source: str, target: str) -> bool: ...
+    def reachable_set(self, source: str) -> set[str]: ...
+    def callers_transitive(self, target: str) -> set[str]: ...
+    def dead_methods(self, roots: list[str]) -> list[str]: ...
+    def get_stats(self) -> dict[str, int]: ...
+    def add_calls(self, calls: list[MethodCall]) -> None: ...
+    def merge(self, other: CallGraph) -> None: ...
+    def find_cycles(self, max_len: int = 10) -> list[list[str]]: ...
+    def to_json(self) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> CallGraph: ...
+    def to_dot(self, highlight: list[str] | None = None) -> str: ...
+
+class PyCallGraphBuilder:
+    def build(self) -> CallGraph: ...
+
+def build_call_graph_from_apk(
+    apk_path: str,
+    class_filter: list[str] | None = None,
+    exclude_framework: bool = False,
+    framework_prefixes: list[str] | None = None,
+    keep_callees: list[str] | None = None,
+) -> CallGraph: ...
+def build_call_graph_from_apk_parallel(
+    apk_path: str,
+    class_filter: list[str] | None = None,
+    exclude_framework: bool = False,
+    framework_prefixes: list[str] | None = None,
+    keep_callees: list[str] | None = None,
+) -> CallGraph: ...
+
+class BuildStats:
+    classes_processed: int
+    classes_skipped: int
+    edges_added: int
+    dex_files: int
+
+def build_call_graph_with_stats(
+    apk_path: str,
+    class_filter: list[str] | None = None,
+    exclude_framework: bool = False,
+    framework_prefixes: list[str] | None = None,
+    keep_callees: list[str] | None = None,
+) -> tuple[CallGraph, BuildStats]: ...
+
+# ============================================================================
+# Data Flow Analysis (New Generic API)
+# ============================================================================
+
+class Flow:
+    entry_point: str
+    component_type: str
+    sink_method: str
+    paths: list[CallPath]
+    is_deeplink_handler: bool
+    min_path_length: int
+    path_count: int
+    sink_category: str
+
+    def get_shortest_path(self) -> CallPath | None: ...
+    def get_lifecycle_methods(self) -> list[str]: ...
+
+class DataSourceKind:
+    IntentExtra: DataSourceKind
+    IntentData: DataSourceKind
+    IntentUri: DataSourceKind
+    Bundle: DataSourceKind
+    NetworkResponse: DataSourceKind
+    FileRead: DataSourceKind
+    Unknown: DataSourceKind
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __int__(self) -> int: ...
+
+class TaintMode:
+    Heuristic: TaintMode
+    Register: TaintMode
+
+class DataFlow:
+    source: str
+    source_kind: DataSourceKind
+    sink: str
+    flow_path: list[str]
+    confidence: float
+    evidence: list[str]
+    sanitized: bool
+
+class DeeplinkFinding:
+    component: str
+    deeplink_patterns: list[str]
+    reaches_webview: bool
+    reaches_file: bool
+    reaches_network: bool
+    reaches_sql: bool
+    sinks: list[str]
+    shortest_path: CallPath | None
+
+    def is_exploitable(self) -> bool: ...
+
+class WeakCryptoUse:
+    method: str
+    expression: str
+    algorithms: list[str]
+    reachable: bool
+
+class DataFlowAnalyzer:
+    sanitizer_patterns: list[str]
+    def set_sanitizer_patterns(self, patterns: list[str]) -> None: ...
+    def add_sanitizer_pattern(self, pattern: str) -> None: ...
+    include_initializers: bool
+    def set_include_initializers(self, include: bool) -> None: ...
+    scan_callees: bool
+    def set_scan_callees(self, scan: bool) -> None: ...
+    source_patterns: list[str]
+    sink_patterns: list[str]
+    def with_rules(self, sources: list[str], sinks: list[str]) -> DataFlowAnalyzer: ...
+    def find_custom_flows(
+        self,
+        sources: list[str] | None = None,
+        sinks: list[str] | None = None,
+        max_depth: int = 10,
+    ) -> list[DataFlow]: ...
+    def find_flows_to(
+        self, sink_patterns: list[str], max_depth: int = 10
+    ) -> list[Flow]: ...
+    def find_flows_to_class(
+        self, class_name: str, max_depth: int = 10
+    ) -> list[Flow]: ...
+    def find_webview_flows(self, max_depth: int = 10) -> list[Flow]: ...
+    def find_file_flows(self, max_depth: int = 10) -> list[Flow]: ...
+    def find_network_flows(self, max_depth: int = 10) -> list[Flow]: ...
+    def find_sql_flows(self, max_depth: int = 10) -> list[Flow]: ...
+    def find_crypto_flows(self, max_depth: int = 10) -> list[Flow]: ...
+    def find_reflection_flows(self, max_depth: int = 10) -> list[Flow]: ...
+    def deeplink_attack_surface(self, max_depth: int = 10) -> list[DeeplinkFinding]: ...
+
+def create_data_flow_analyzer(apk_path: str) -> DataFlowAnalyzer: ...
+def find_flows_from_apk(
+    apk_path: str, sink_patterns: list[str], max_depth: int = 10
+) -> list[Flow]: ...
+def find_data_flows_from_apk(
+    apk_path: str,
+    sink_patterns: list[str],
+    max_depth: int = 10,
+    follow_fields: bool = False,
+    sink_arguments: dict[str, list[int]] | None = None,
+    mode: TaintMode = TaintMode.Heuristic,
+) -> list[DataFlow]: ...
+def find_webview_flows_from_apk(apk_path: str, max_depth: int = 10) -> list[Flow]: ...
+def find_file_flows_from_apk(apk_path: str, max_depth: int = 10) -> list[Flow]: ...
+def find_network_flows_from_apk(apk_path: str, max_depth: int = 10) -> list[Flow]: ...
+def find_weak_crypto_from_apk(
+    apk_path: str, max_depth: int = 10
+) -> list[WeakCryptoUse]: ...
+def deeplink_attack_surface(
+    apk_path: str, max_depth: int = 10
+) -> list[DeeplinkFinding]: ...
+
+# ============================================================================
+# Backward Compatibility (Deprecated - use DataFlowAnalyzer instead)
+# ============================================================================
+
+# Deprecated aliases live in the core.legacy submodule (core/legacy.pyi)
+
+# ============================================================================
+# APK Extraction Functions
+# ============================================================================
+
+def configure_parallelism(threads: int) -> None: ...
+def extract_apk_info(apk_path: str) -> tuple[int, bool, bool, list[str]]: ...
+def analyze_bundle(path: str) -> dict[str, Any]: ...
+def extract_manifest_raw(apk_path: str) -> bytes: ...
+def list_apk_entries(apk_path: str) -> list[dict[str, Any]]: ...
+def extract_apk_entry(apk_path: str, name: str) -> bytes: ...
+def parse_manifest_from_apk(
+    apk_path: str, resolve_resources: bool = True
+) -> RustManifestInfo: ...
+def find_main_activity(apk_path: str) -> str | None: ...
+def find_unprotected_exported_components(apk_path: str) -> list[str]: ...
+def extract_classes_from_apk(
+    apk_path: str, parallel: bool = True, include_splits: bool = True
+) -> list[RustDexClass]: ...
+def iter_classes_from_apk(apk_path: str) -> ClassIterator: ...
+def iter_methods_from_apk(
+    apk_path: str, class_filter: ClassFilter, method_filter: MethodFilter
+) -> MethodIterator: ...
+def find_class_dex(apk_path: str, class_name: str) -> tuple[str, int] | None: ...
+def classes_per_dex(apk_path: str) -> dict[str, int]: ...
+def find_method_by_descriptor(
+    apk_path: str, descriptor: str
+) -> tuple[RustDexClass, RustDexMethod] | None: ...
+def list_all_methods(apk_path: str) -> list[str]: ...
+def list_all_fields(apk_path: str) -> list[str]: ...
+def list_defined_methods(apk_path: str) -> list[str]: ...
+def list_external_methods(apk_path: str) -> list[str]: ...
+def search_classes(
+    apk_path: str,
+    filter: ClassFilter,
+    limit: int | None = None,
+    parallel: bool = True,
+) -> list[RustDexClass]: ...
+def search_methods(
+    apk_path: str,
+    class_filter: ClassFilter,
+    method_filter: MethodFilter,
+    limit: int | None = None,
+    parallel: bool = True,
+) -> list[RustDexMethod]: ...
+
+# ============================================================================
+# Bytecode Analysis Functions
+# ============================================================================
+
+def decode_bytecode(bytecode: bytes, strict: bool = False) -> list[RustInstruction]: ...
+def decode_instruction_at(bytecode: list[int], word_offset: int) -> tuple[RustInstruction, int]: ...
+def extract_constants(bytecode: bytes) -> list[str]: ...
+def extract_string_constants(bytecode: list[int], dex_data: bytes) -> list[str]: ...
+def extract_method_calls(bytecode: bytes) -> list[str]: ...
+def opcode_histogram(bytecode: list[int]) -> dict[str, int]: ...
+def extract_methods_bytecode(classes: list[RustDexClass]) -> dict[str, bytes]: ...
+def get_method_bytecode_from_apk(
+    apk_path: str, class_name: str, method_name: str
+) -> bytes | None: ...
+def extract_method_calls_from_apk(
+    apk_path: str, class_name: str, method_name: str
+) -> list[tuple[int, int]]: ...
+def preview_method(
+    apk_path: str, class_name: str, method_name: str, max_instructions: int = 10
+) -> tuple[list[RustInstruction], bool]: ...
+def apk_opcode_profile(apk_path: str) -> dict[str, int]: ...
+
+# ============================================================================
+# Method Resolution Functions
+# ============================================================================
+
+class MethodResolverPy:
+    def resolve(
+        self, method_idx: int, dex_index: int | None = None
+    ) -> MethodSignature: ...
+    def resolve_many(
+        self, method_indices: list[int], dex_index: int | None = None
+    ) -> list[MethodSignature | None]: ...
+    def resolve_all(self, dex_index: int = 0) -> list[MethodSignature | None]: ...
+    def resolve_refs(
+        self, method_refs: list[tuple[int, int]]
+    ) -> list[MethodSignature | None]: ...
+    def dex_count(self) -> int: ...
+
+def create_method_resolver(dex_data: bytes) -> MethodResolverPy: ...
+def create_apk_method_resolver(apk_path: str) -> MethodResolverPy: ...
+def resolve_method_from_apk(
+    apk_path: str, method_idx: int, dex_index: int | None = None
+) -> MethodSignature: ...
+
+class DexInspector:
+    def __init__(self, dex_data: bytes) -> None: ...
+    def string_count(self) -> int: ...
+    def get_string(self, index: int) -> str: ...
+    def type_count(self) -> int: ...
+    def get_type(self, index: int) -> str: ...
+    def method_count(self) -> int: ...
+    def get_method_signature(self, index: int) -> str: ...
+
+# ============================================================================
+# Expression Reconstruction Functions
+# ============================================================================
+
+class ReconstructedExpression:
+    method: str
+    expressions: list[str]
+
+class ExpressionBuilderPy:
+    def reconstruct_expressions(
+        self, class_name: str, method_name: str
+    ) -> ReconstructedExpression | None: ...
+
+def create_expression_builder(apk_path: str) -> ExpressionBuilderPy: ...
+def reconstruct_expressions_from_apk(
+    apk_path: str, class_name: str, method_name: str
+) -> ReconstructedExpression | None: ...
+
+# ============================================================================
+# Class Decompilation Functions
+# ============================================================================
+
+class DecompiledMethod:
+    name: str
+    descriptor: str
+    access_flags: int
+    code: str
+    source_file: str | None
+    line_numbers: list[tuple[int, int]]
+    caught_exceptions: list[str]
+    def line_at(self, address: int) -> int | None: ...
+    def location(self, address: int) -> str | None: ...
+
+class DecompiledClass:
+    class_name: str
+    access_flags: int
+    superclass: str | None
+    interfaces: list[str]
+    fields: list[str]
+    methods: list[DecompiledMethod]
+    static_field_values: dict[str, str]
+    def to_pseudo_source(self) -> str: ...
+
+def decompile_class_from_apk(
+    apk_path: str, class_name: str
+) -> DecompiledClass | None: ...
+
+# ============================================================================
+# Resources Functions
+# ============================================================================
+
+class PyResolvedResource:
+    id: int
+    type_name: str
+    name: str
+    value_type: str
+    value: str
+    values: list[str]
+    entries: dict[str, str]
+    def is_string(self) -> bool: ...
+    def is_boolean(self) -> bool: ...
+    def is_array(self) -> bool: ...
+
+class PyResourceResolver:
+    def resolve(self, resource_id: int) -> PyResolvedResource | None: ...
+    def resolve_deref(
+        self, resource_id: int, max_depth: int = 8
+    ) -> PyResolvedResource | None: ...
+    def get_by_name(self, type_name: str, name: str) -> PyResolvedResource | None: ...
+    @staticmethod
+    def is_resource_id(value: int) -> bool: ...
+    def get_all_strings(self) -> list[PyResolvedResource]: ...
+    def get_by_type(self, type_name: str) -> list[PyResolvedResource]: ...
+    def count(self) -> int: ...
+
+def parse_resources_from_apk(
+    apk_path: str, locale: str | None = None
+) -> PyResourceResolver: ...
+
+# ============================================================================
+# Network Security Functions
+# ============================================================================
+
+class DomainConfig:
+    domains: list[str]
+    include_subdomains: bool
+    cleartext_permitted: bool | None
+    pins: list[str]
+    pin_expiration: str | None
+
+class NetworkSecurityReport:
+    uses_cleartext_traffic: bool | None
+    config_path: str | None
+    config_missing: bool
+    base_cleartext_permitted: bool | None
+    trusts_user_certificates: bool
+    domain_configs: list[DomainConfig]
+    def cleartext_domains(self) -> list[str]: ...
+    def pinned_domains(self) -> list[str]: ...
+    def allows_cleartext(self) -> bool: ...
+
+def network_security_report(apk_path: str) -> NetworkSecurityReport: ...
+
+def apk_size_breakdown(apk_path: str) -> dict[str, Any]: ...
+
+class ApkHealth:
+    is_valid_zip: bool
+    has_manifest: bool
+    has_resources: bool
+    dex_count: int
+    invalid_dex: list[str]
+    signature_schemes: list[str]
+    package_name: str | None
+    diagnostics: list[str]
+    def is_healthy(self) -> bool: ...
+
+def validate_apk(apk_path: str) -> ApkHealth: ...
+
+class CertInfo:
+    subject: str
+    issuer: str
+    serial_number: str
+    not_before: str
+    not_after: str
+    sha256_fingerprint: str
+    schemes: list[str]
+    def is_self_signed(self) -> bool: ...
+    def to_dict(self) -> dict[str, Any]: ...
+
+def extract_signing_info(apk_path: str) -> list[dict[str, Any]]: ...
+
+class DexIntegrity:
+    name: str
+    checksum_valid: bool
+    signature_valid: bool
+    stored_checksum: int
+    computed_checksum: int | None
+    error: str | None
+    def is_valid(self) -> bool: ...
+
+def verify_dex_integrity(apk_path: str) -> list[DexIntegrity]: ...
+
+class ApiLevelViolation:
+    caller: str
+    api: str
+    api_level: int
+    min_sdk: int
+
+def find_api_level_violations(
+    apk_path: str, extra_levels: dict[str, int] | None = None
+) -> list[ApiLevelViolation]: ...
+
+class SecretFinding:
+    kind: str
+    value: str
+    location: str
+    source: str
+
+def find_secrets(
+    apk_path: str, extra_patterns: list[tuple[str, str, int, int]] | None = None
+) -> list[SecretFinding]: ...
+
+# ============================================================================
+# DEX Filter Classes
+# ============================================================================
+
+class ClassFilter:
+    def __init__(
+        self,
+        packages: list[str] | None = None,
+        exclude_packages: list[str] | None = None,
+        class_name: str | None = None,
+        modifiers: int | None = None,
+        exclude_framework: bool = False,
+    ) -> None: ...
+
+class MethodFilter:
+    def __init__(
+        self,
+        method_name: str | None = None,
+        param_count: int | None = None,
+        param_types: list[str] | None = None,
+        return_type: str | None = None,
+        modifiers: int | None = None,
+    ) -> None: ...
+
+# ============================================================================
+# Google Play APK Download (Low-level API)
+# ============================================================================
+
+class GpapiClient:
+    email: str
+    """Google account email address"""
+
+    def __init__(
+        self,
+        email: str,
+        oauth_token: str | None = None,
+        aas_token: str | None = None,
+        device: str = "px_9a",
+        locale: str = "en_US",
+        timezone: str = "America/New_York",
+    ) -> None: ...
+    def login(self) -> None: ...
+    def get_aas_token(self) -> str: ...
+    def save_credentials(self, path: str) -> None: ...
+    @staticmethod
+    def from_credentials(path: str) -> GpapiClient: ...
+    def download_apk(
+        self,
+        package_id: str,
+        dest_path: str,
+        version_code: int | None = None,
+        progress_callback: Callable[[int, int], None] | None = None,
+    ) -> str: ...
+    def get_package_details(self, package_id: str) -> str: ...
@@ -0,0 +1,13 @@
+"""Deprecated aliases kept for backward compatibility; see playfast.core for replacements."""
+
+from typing_extensions import deprecated
+
+from playfast.core import DataFlowAnalyzer, Flow
+
+WebViewFlow = Flow
+WebViewFlowAnalyzer = DataFlowAnalyzer
+
+@deprecated("analyze_webview_flows_from_apk is deprecated; use find_webview_flows_from_apk")
+def analyze_webview_flows_from_apk(apk_path: str, max_depth: int = 5) -> list[Flow]: ...
+@deprecated("create_webview_analyzer_from_apk is deprecated; use create_data_flow_analyzer")
+def create_webview_analyzer_from_apk(apk_path: str) -> DataFlowAnalyzer: ...
@@ -433,6 +433,8 @@ class SearchResult(BaseModel):
 
     score: float | None = Field(None, ge=0, le=5)
 
+    review_count: int | None = Field(None, ge=0, description="Number of ratings")
+
     price: float = Field(ge=0)
 
     currency: str = Field(default="USD", min_length=3, max_length=3)
@@ -476,6 +478,7 @@ class SearchResult(BaseModel):
             developer=rust_obj.developer,
             icon=rust_obj.icon,
             score=rust_obj.score,
+            review_count=rust_obj.review_count,
             price=rust_obj.price,
             currency=rust_obj.currency,
         )
@@ -241,7 +241,7 @@ class RustClient:
             category: Optional category code (e.g., "GAME_ACTION")
             lang: Language code (default: client lang)
             country: Country code (default: "us")
-            num: Number of results (default: 100, max: 250)
+            num: Number of results (default: 100; above 250, pages are merged)
 
         Returns:
             list[SearchResult]: List of apps (may be empty in current version)
@@ -287,7 +287,7 @@ class RustClient:
                        Also accepts lowercase (e.g., "topselling_free")
             lang: Language code (default: client lang)
             country: Country code (default: "us")
-            num: Number of results (default: 100, max: 250)
+            num: Number of results (default: 100; above 250, pages are merged)
 
         Returns:
             list[SearchResult]: List of apps
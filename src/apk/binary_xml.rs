@@ -0,0 +1,327 @@
+//! Minimal reader for Android binary XML (AXML)
+//!
+//! `rusty-axml` covers the manifest fields we extract by name, but it exposes
+//! attribute values as display strings and has no text nodes. Resource XML
+//! files such as `res/xml/network_security_config.xml` keep meaningful data
+//! in element text, and security checks need typed attribute values
+//! (booleans, resource references), so this reader builds a small element
+//! tree preserving both.
+
+use crate::apk::error::{ApkError, Result};
+
+const RES_STRING_POOL_TYPE: u16 = 0x0001;
+const RES_XML_TYPE: u16 = 0x0003;
+const RES_XML_START_ELEMENT_TYPE: u16 = 0x0102;
+const RES_XML_END_ELEMENT_TYPE: u16 = 0x0103;
+const RES_XML_CDATA_TYPE: u16 = 0x0104;
+
+const UTF8_FLAG: u32 = 0x100;
+const NO_INDEX: u32 = 0xFFFF_FFFF;
+
+// Res_value data types
+const TYPE_REFERENCE: u8 = 0x01;
+const TYPE_STRING: u8 = 0x03;
+const TYPE_INT_DEC: u8 = 0x10;
+const TYPE_INT_HEX: u8 = 0x11;
+const TYPE_INT_BOOLEAN: u8 = 0x12;
+
+/// Typed attribute value
+#[derive(Debug, Clone, PartialEq)]
+pub enum XmlValue {
+    String(String),
+    Bool(bool),
+    Int(i32),
+    /// Resource reference (e.g. `@xml/network_security_config` -> 0x7f110003)
+    Reference(u32),
+    /// Any other Res_value type, kept as (data_type, data)
+    Other(u8, u32),
+}
+
+impl XmlValue {
+    /// Boolean view: typed booleans, plus the literal strings "true"/"false"
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            XmlValue::Bool(b) => Some(*b),
+            XmlValue::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// String view of the value as it would appear in source XML
+    pub fn to_display_string(&self) -> String {
+        match self {
+            XmlValue::String(s) => s.clone(),
+            XmlValue::Bool(b) => b.to_string(),
+            XmlValue::Int(i) => i.to_string(),
+            XmlValue::Reference(id) => format!("@0x{:08x}", id),
+            XmlValue::Other(data_type, data) => format!("(type 0x{:02x})0x{:08x}", data_type, data),
+        }
+    }
+}
+
+/// Attribute on an element; `name` is the local name without namespace
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmlAttribute {
+    pub name: String,
+    pub value: XmlValue,
+}
+
+/// Element with its attributes, children and concatenated text content
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct XmlElement {
+    pub name: String,
+    pub attributes: Vec<XmlAttribute>,
+    pub children: Vec<XmlElement>,
+    pub text: String,
+}
+
+impl XmlElement {
+    /// Look up an attribute by local name (`usesCleartextTraffic`, not
+    /// `android:usesCleartextTraffic`)
+    pub fn attr(&self, name: &str) -> Option<&XmlValue> {
+        self.attributes.iter().find(|a| a.name == name).map(|a| &a.value)
+    }
+
+    /// Attribute as a boolean, if present and boolean-valued
+    pub fn attr_bool(&self, name: &str) -> Option<bool> {
+        self.attr(name).and_then(XmlValue::as_bool)
+    }
+
+    /// Attribute rendered as a string
+    pub fn attr_string(&self, name: &str) -> Option<String> {
+        self.attr(name).map(XmlValue::to_display_string)
+    }
+
+    /// Direct children with the given element name
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// All descendants (depth-first, excluding self) with the given name
+    pub fn descendants_named<'a>(&'a self, name: &str) -> Vec<&'a XmlElement> {
+        let mut found = Vec::new();
+        for child in &self.children {
+            if child.name == name {
+                found.push(child);
+            }
+            found.extend(child.descendants_named(name));
+        }
+        found
+    }
+}
+
+/// Parse a binary XML document and return its root element
+pub fn parse_binary_xml(data: &[u8]) -> Result<XmlElement> {
+    let reader = Reader { data };
+
+    if reader.u16(0)? != RES_XML_TYPE {
+        return Err(ApkError::InvalidApk("Not a binary XML document".to_string()));
+    }
+    let header_size = reader.u16(2)? as usize;
+    let total_size = (reader.u32(4)? as usize).min(data.len());
+
+    let mut strings = Vec::new();
+    let mut stack: Vec<XmlElement> = Vec::new();
+    let mut root: Option<XmlElement> = None;
+    let mut offset = header_size;
+
+    while offset + 8 <= total_size {
+        let chunk_type = reader.u16(offset)?;
+        let chunk_header_size = reader.u16(offset + 2)? as usize;
+        let chunk_size = reader.u32(offset + 4)? as usize;
+        if chunk_size < 8 || offset + chunk_size > total_size {
+            return Err(ApkError::InvalidApk(format!("Invalid XML chunk at offset {}", offset)));
+        }
+
+        match chunk_type {
+            RES_STRING_POOL_TYPE => {
+                strings = reader.string_pool(offset)?;
+            }
+            RES_XML_START_ELEMENT_TYPE => {
+                let ext = offset + chunk_header_size;
+                let name = reader.string_at(&strings, reader.u32(ext + 4)?);
+                let attribute_start = reader.u16(ext + 8)? as usize;
+                let attribute_size = reader.u16(ext + 10)? as usize;
+                let attribute_count = reader.u16(ext + 12)? as usize;
+
+                // Counts come from the file; never reserve more than the
+                // remaining bytes could hold
+                let remaining = data.len().saturating_sub(ext + attribute_start);
+                let mut attributes = Vec::with_capacity(attribute_count.min(remaining / attribute_size.max(1)));
+                for i in 0..attribute_count {
+                    let attr = ext + attribute_start + i * attribute_size;
+                    let name = reader.string_at(&strings, reader.u32(attr + 4)?);
+                    let raw_value = reader.u32(attr + 8)?;
+                    let data_type = reader.u8(attr + 15)?;
+                    let data = reader.u32(attr + 16)?;
+
+                    let value = if raw_value != NO_INDEX {
+                        XmlValue::String(reader.string_at(&strings, raw_value))
+                    } else {
+                        match data_type {
+                            TYPE_STRING => XmlValue::String(reader.string_at(&strings, data)),
+                            TYPE_INT_BOOLEAN => XmlValue::Bool(data != 0),
+                            TYPE_INT_DEC | TYPE_INT_HEX => XmlValue::Int(data as i32),
+                            TYPE_REFERENCE => XmlValue::Reference(data),
+                            other => XmlValue::Other(other, data),
+                        }
+                    };
+                    attributes.push(XmlAttribute { name, value });
+                }
+
+                stack.push(XmlElement {
+                    name,
+                    attributes,
+                    ..Default::default()
+                });
+            }
+            RES_XML_END_ELEMENT_TYPE => {
+                let element = stack
+                    .pop()
+                    .ok_or_else(|| ApkError::InvalidApk("Unbalanced end element".to_string()))?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => root = Some(element),
+                }
+            }
+            RES_XML_CDATA_TYPE => {
+                let ext = offset + chunk_header_size;
+                let text = reader.string_at(&strings, reader.u32(ext)?);
+                if let Some(current) = stack.last_mut() {
+                    current.text.push_str(text.trim());
+                }
+            }
+            // Namespaces, resource map and unknown chunks carry nothing we need
+            _ => {}
+        }
+
+        offset += chunk_size;
+    }
+
+    root.ok_or_else(|| ApkError::InvalidApk("Binary XML has no root element".to_string()))
+}
+
+/// Bounds-checked little-endian reads over the document
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn bytes(&self, offset: usize, len: usize) -> Result<&[u8]> {
+        self.data
+            .get(offset..offset + len)
+            .ok_or_else(|| ApkError::InvalidApk(format!("Binary XML truncated at offset {}", offset)))
+    }
+
+    fn u8(&self, offset: usize) -> Result<u8> {
+        Ok(self.bytes(offset, 1)?[0])
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16> {
+        let b = self.bytes(offset, 2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32> {
+        let b = self.bytes(offset, 4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn string_at(&self, strings: &[String], index: u32) -> String {
+        strings.get(index as usize).cloned().unwrap_or_default()
+    }
+
+    /// Decode a ResStringPool chunk starting at `chunk`
+    fn string_pool(&self, chunk: usize) -> Result<Vec<String>> {
+        let header_size = self.u16(chunk + 2)? as usize;
+        let string_count = self.u32(chunk + 8)? as usize;
+        let flags = self.u32(chunk + 16)?;
+        let strings_start = chunk + self.u32(chunk + 20)? as usize;
+        let utf8 = flags & UTF8_FLAG != 0;
+
+        let remaining = self.data.len().saturating_sub(chunk + header_size);
+        let mut strings = Vec::with_capacity(string_count.min(remaining / 4));
+        for i in 0..string_count {
+            let offset = strings_start + self.u32(chunk + header_size + i * 4)? as usize;
+            let string = if utf8 {
+                self.utf8_string(offset)?
+            } else {
+                self.utf16_string(offset)?
+            };
+            strings.push(string);
+        }
+        Ok(strings)
+    }
+
+    fn utf8_string(&self, offset: usize) -> Result<String> {
+        // Character count (skipped), then byte count; each 1 or 2 bytes
+        let (_, offset) = self.utf8_length(offset)?;
+        let (len, offset) = self.utf8_length(offset)?;
+        Ok(String::from_utf8_lossy(self.bytes(offset, len)?).into_owned())
+    }
+
+    fn utf8_length(&self, offset: usize) -> Result<(usize, usize)> {
+        let first = self.u8(offset)? as usize;
+        if first & 0x80 != 0 {
+            let second = self.u8(offset + 1)? as usize;
+            Ok((((first & 0x7F) << 8) | second, offset + 2))
+        } else {
+            Ok((first, offset + 1))
+        }
+    }
+
+    fn utf16_string(&self, offset: usize) -> Result<String> {
+        let first = self.u16(offset)? as usize;
+        let (len, offset) = if first & 0x8000 != 0 {
+            let second = self.u16(offset + 2)? as usize;
+            ((((first & 0x7FFF) << 16) | second), offset + 4)
+        } else {
+            (first, offset + 2)
+        };
+
+        let units = (0..len)
+            .map(|i| self.u16(offset + i * 2))
+            .collect::<Result<Vec<u16>>>()?;
+        Ok(String::from_utf16_lossy(&units))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apk::test_support::{element, encode_binary_xml};
+
+    #[test]
+    fn test_round_trip_attributes_and_text() {
+        let doc = element("network-security-config")
+            .with_child(
+                element("domain-config")
+                    .with_attr("cleartextTrafficPermitted", XmlValue::Bool(true))
+                    .with_child(element("domain").with_attr("includeSubdomains", XmlValue::Bool(false)).with_text("example.com")),
+            )
+            .with_child(element("base-config").with_attr("ref", XmlValue::Reference(0x7f110003)))
+            .with_child(element("meta").with_attr("label", XmlValue::String("hello".to_string())).with_attr("n", XmlValue::Int(-3)));
+
+        let parsed = parse_binary_xml(&encode_binary_xml(&doc)).unwrap();
+        assert_eq!(parsed, doc);
+
+        let domain = &parsed.descendants_named("domain")[0];
+        assert_eq!(domain.text, "example.com");
+        assert_eq!(domain.attr_bool("includeSubdomains"), Some(false));
+        assert_eq!(parsed.children[1].attr_string("ref").as_deref(), Some("@0x7f110003"));
+    }
+
+    #[test]
+    fn test_oversized_counts_are_not_trusted() {
+        let mut data = encode_binary_xml(&element("manifest").with_attr("package", XmlValue::String("a".to_string())));
+        // String pool follows the 8-byte document header; claim 2^32-1 strings
+        data[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(parse_binary_xml(&data).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_xml() {
+        assert!(parse_binary_xml(b"").is_err());
+        assert!(parse_binary_xml(&[0x02, 0x00, 0x0c, 0x00, 0x0c, 0x00, 0x00, 0x00]).is_err());
+    }
+}
@@ -1,21 +1,38 @@
+use crate::apk::binary_xml::parse_binary_xml;
 use crate::apk::error::{ApkError, Result};
 use std::fs::File;
-use std::io::Read;
+use std::io::{Cursor, Read, Seek};
 use std::path::{Path, PathBuf};
-use zip::ZipArchive;
+use std::sync::Arc;
+use zip::{CompressionMethod, ZipArchive};
 
 /// Represents a single DEX file within an APK
 #[derive(Debug, Clone)]
 pub struct DexEntry {
     pub name: String,
     pub index: usize,
-    pub data: Vec<u8>,
+    /// Shared so parsers over the same DEX reuse one buffer
+    pub data: Arc<Vec<u8>>,
+    /// Bundle module the DEX came from ("base" or the split name); None for
+    /// a plain APK
+    pub module: Option<String>,
 }
 
 impl DexEntry {
     /// Create a new DexEntry
     pub fn new(name: String, index: usize, data: Vec<u8>) -> Self {
-        Self { name, index, data }
+        Self { name, index, data: Arc::new(data), module: None }
+    }
+
+    /// Tag the entry with the bundle module it belongs to
+    pub fn with_module(mut self, module: &str) -> Self {
+        self.module = Some(module.to_string());
+        self
+    }
+
+    /// Whether the entry belongs to the base module (always true outside bundles)
+    pub fn is_base_module(&self) -> bool {
+        self.module.as_deref().is_none_or(|m| m == BASE_MODULE)
     }
 
     /// Check if this is the primary DEX file (classes.dex)
@@ -39,88 +56,287 @@ impl DexEntry {
     }
 }
 
-/// APK file extractor for DEX files and resources
-pub struct ApkExtractor {
-    apk_path: PathBuf,
-    dex_entries: Vec<DexEntry>,
-    has_manifest: bool,
-    has_resources: bool,
+/// Metadata of one ZIP entry in an APK
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApkEntry {
+    pub name: String,
+    /// Uncompressed size in bytes
+    pub size: u64,
+    pub compressed_size: u64,
+    pub crc32: u32,
 }
 
-impl ApkExtractor {
-    /// Open an APK file and scan for DEX files
-    pub fn new<P: AsRef<Path>>(apk_path: P) -> Result<Self> {
-        let apk_path = apk_path.as_ref().to_path_buf();
+/// Module name given to DEX files of a bundle's base APK
+pub const BASE_MODULE: &str = "base";
 
-        if !apk_path.exists() {
-            return Err(ApkError::FileOpenError(format!(
-                "File does not exist: {}",
-                apk_path.display()
-            )));
-        }
+/// Reader over either the APK on disk or a base APK held in memory
+pub(crate) trait ArchiveSource: Read + Seek {}
 
-        let file = File::open(&apk_path)
-            .map_err(|e| ApkError::FileOpenError(e.to_string()))?;
+impl<T: Read + Seek> ArchiveSource for T {}
 
-        let mut archive = ZipArchive::new(file)?;
+/// Result of scanning one ZIP archive's top-level entries
+#[derive(Default)]
+struct ArchiveScan {
+    entries: Vec<ApkEntry>,
+    dex_entries: Vec<DexEntry>,
+    has_manifest: bool,
+    has_resources: bool,
+    /// Names of nested `*.apk` entries (split bundles)
+    apk_entries: Vec<String>,
+}
 
-        let mut dex_entries = Vec::new();
-        let mut has_manifest = false;
-        let mut has_resources = false;
+impl ArchiveScan {
+    /// Scan the entries, keeping at most `dex_limit` bytes of each DEX
+    fn read<R: Read + Seek>(archive: &mut ZipArchive<R>, dex_limit: Option<u64>) -> Result<Self> {
+        let mut scan = Self::default();
 
         // Scan ZIP entries
         for i in 0..archive.len() {
             let mut entry = archive.by_index(i)?;
             let entry_name = entry.name().to_string();
 
+            scan.entries.push(ApkEntry {
+                name: entry_name.clone(),
+                size: entry.size(),
+                compressed_size: entry.compressed_size(),
+                crc32: entry.crc32(),
+            });
+
             // Detect DEX files
             if entry_name.ends_with(".dex") && !entry_name.contains("/") {
                 let mut data = Vec::new();
-                entry.read_to_end(&mut data)?;
+                match dex_limit {
+                    Some(limit) => (&mut entry).take(limit).read_to_end(&mut data)?,
+                    None => entry.read_to_end(&mut data)?,
+                };
 
-                dex_entries.push(DexEntry::new(entry_name.clone(), i, data));
+                scan.dex_entries.push(DexEntry::new(entry_name.clone(), i, data));
             }
 
             // Check for manifest
             if entry_name == "AndroidManifest.xml" {
-                has_manifest = true;
+                scan.has_manifest = true;
             }
 
             // Check for resources
             if entry_name == "resources.arsc" {
-                has_resources = true;
+                scan.has_resources = true;
+            }
+
+            // Nested APKs (.xapk / .apks bundles)
+            if entry_name.ends_with(".apk") {
+                scan.apk_entries.push(entry_name);
             }
         }
 
+        // Sort DEX entries by name (classes.dex, classes2.dex, ...)
+        scan.dex_entries.sort_by(|a, b| {
+            match (a.dex_number(), b.dex_number()) {
+                (Some(na), Some(nb)) => na.cmp(&nb),
+                _ => a.name.cmp(&b.name),
+            }
+        });
+
+        Ok(scan)
+    }
+
+    /// A bundle has nested APKs and no top-level code or manifest
+    fn is_bundle(&self) -> bool {
+        !self.apk_entries.is_empty() && self.dex_entries.is_empty() && !self.has_manifest
+    }
+}
+
+/// Run `f` on the APK nested in `archive` as `name`
+///
+/// A stored entry is read in place; a compressed one has to be inflated
+/// into memory first.
+fn with_nested_apk<'a, R: Read + Seek, T>(
+    archive: &'a mut ZipArchive<R>,
+    name: &str,
+    f: impl FnOnce(&mut ZipArchive<Box<dyn ArchiveSource + 'a>>) -> Result<T>,
+) -> Result<T> {
+    let stored = archive.by_name(name)?.compression() == CompressionMethod::Stored;
+    let source: Box<dyn ArchiveSource + 'a> = if stored {
+        Box::new(archive.by_name_seek(name)?)
+    } else {
+        let mut data = Vec::new();
+        archive.by_name(name)?.read_to_end(&mut data)?;
+        Box::new(Cursor::new(data))
+    };
+    f(&mut ZipArchive::new(source)?)
+}
+
+/// `split` attribute of an APK's manifest; None for a base APK
+fn split_attribute<R: Read + Seek>(apk: &mut ZipArchive<R>) -> Result<Option<String>> {
+    let mut manifest = Vec::new();
+    apk.by_name("AndroidManifest.xml")?.read_to_end(&mut manifest)?;
+    Ok(parse_binary_xml(&manifest)?.attr_string("split"))
+}
+
+/// Split APK bundle layout (`.xapk` / `.apks`)
+#[derive(Debug, Clone)]
+pub struct BundleLayout {
+    /// Entry name of the base APK inside the bundle
+    pub base_apk: String,
+    /// Entry names of the other split APKs
+    pub splits: Vec<String>,
+}
+
+/// APK file extractor for DEX files and resources
+///
+/// Also accepts `.xapk`/`.apks` bundles: the base APK (the one whose
+/// manifest has no `split` attribute) is read into memory and used for manifest,
+/// resource and file extraction, while DEX files from feature splits are
+/// appended to `dex_entries` as `"<split>!classes.dex"`.
+pub struct ApkExtractor {
+    apk_path: PathBuf,
+    /// Every entry of the APK (of the base APK for bundles)
+    entries: Vec<ApkEntry>,
+    dex_entries: Vec<DexEntry>,
+    has_manifest: bool,
+    has_resources: bool,
+    /// Base APK bytes when `apk_path` is a split bundle
+    base_apk: Option<Arc<[u8]>>,
+    bundle: Option<BundleLayout>,
+}
+
+impl ApkExtractor {
+    /// Open an APK (or split bundle) and scan for DEX files
+    pub fn new<P: AsRef<Path>>(apk_path: P) -> Result<Self> {
+        Self::open(apk_path, None)
+    }
+
+    /// Open like `new`, but read only the header of each DEX file
+    ///
+    /// For structural checks that must not decompress the code. The DEX
+    /// entries hold the first 112 bytes only, so they cannot be parsed
+    /// beyond `DexParser::parse_header`.
+    pub(crate) fn open_dex_headers<P: AsRef<Path>>(apk_path: P) -> Result<Self> {
+        Self::open(apk_path, Some(crate::dex::constants::structure::HEADER_SIZE as u64))
+    }
+
+    fn open<P: AsRef<Path>>(apk_path: P, dex_limit: Option<u64>) -> Result<Self> {
+        let apk_path = apk_path.as_ref().to_path_buf();
+
+        if !apk_path.exists() {
+            return Err(ApkError::FileOpenError(format!(
+                "File does not exist: {}",
+                apk_path.display()
+            )));
+        }
+
+        let file = File::open(&apk_path)
+            .map_err(|e| ApkError::FileOpenError(e.to_string()))?;
+
+        let mut archive = ZipArchive::new(file)?;
+        let scan = ArchiveScan::read(&mut archive, dex_limit)?;
+
+        let (scan, base_apk, bundle) = if scan.is_bundle() {
+            let (scan, base_apk, layout) = Self::open_bundle(&mut archive, &scan.apk_entries, dex_limit)?;
+            (scan, Some(base_apk), Some(layout))
+        } else {
+            (scan, None, None)
+        };
+
         // Validate APK
-        if !has_manifest {
+        if !scan.has_manifest {
             return Err(ApkError::InvalidApk(
                 "No AndroidManifest.xml found".to_string()
             ));
         }
 
-        if dex_entries.is_empty() {
+        if scan.dex_entries.is_empty() {
             return Err(ApkError::InvalidApk(
                 "No DEX files found".to_string()
             ));
         }
 
-        // Sort DEX entries by name (classes.dex, classes2.dex, ...)
-        dex_entries.sort_by(|a, b| {
-            match (a.dex_number(), b.dex_number()) {
-                (Some(na), Some(nb)) => na.cmp(&nb),
-                _ => a.name.cmp(&b.name),
-            }
-        });
-
         Ok(Self {
             apk_path,
-            dex_entries,
-            has_manifest,
-            has_resources,
+            entries: scan.entries,
+            dex_entries: scan.dex_entries,
+            has_manifest: scan.has_manifest,
+            has_resources: scan.has_resources,
+            base_apk,
+            bundle,
+        })
+    }
+
+    /// Locate the base APK in a bundle and merge DEX files from the splits
+    ///
+    /// The base is the nested APK whose manifest has no `split` attribute.
+    /// Only the manifest and DEX files of each split are read; the base APK
+    /// is the one kept in memory.
+    fn open_bundle<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+        apk_entries: &[String],
+        dex_limit: Option<u64>,
+    ) -> Result<(ArchiveScan, Arc<[u8]>, BundleLayout)> {
+        let mut base = None;
+        let mut split_scans = Vec::new();
+        for name in apk_entries {
+            let (scan, is_base) = with_nested_apk(archive, name, |apk| {
+                let scan = ArchiveScan::read(apk, dex_limit)?;
+                let is_base = scan.has_manifest && split_attribute(apk).is_ok_and(|split| split.is_none());
+                Ok((scan, is_base))
+            })?;
+            if is_base && base.is_none() {
+                base = Some((name.clone(), scan));
+            } else {
+                split_scans.push((name.clone(), scan));
+            }
+        }
+
+        let (base_name, mut merged) =
+            base.ok_or_else(|| ApkError::InvalidApk("Bundle contains no base APK".to_string()))?;
+        let mut base_data = Vec::new();
+        archive.by_name(&base_name)?.read_to_end(&mut base_data)?;
+        let splits: Vec<String> = split_scans.iter().map(|(name, _)| name.clone()).collect();
+
+        merged.dex_entries = merged
+            .dex_entries
+            .into_iter()
+            .map(|dex| dex.with_module(BASE_MODULE))
+            .collect();
+        for (split_name, split_scan) in split_scans {
+            let split = file_stem(&split_name);
+            merged.dex_entries.extend(split_scan.dex_entries.into_iter().map(|dex| {
+                DexEntry { name: format!("{}!{}", split, dex.name), ..dex }.with_module(split)
+            }));
+        }
+
+        let layout = BundleLayout {
+            base_apk: base_name,
+            splits,
+        };
+        Ok((merged, Arc::from(base_data), layout))
+    }
+
+    /// Open the archive that manifest/resources/files are read from
+    fn archive(&self) -> Result<ZipArchive<Box<dyn ArchiveSource>>> {
+        Ok(ZipArchive::new(self.archive_source()?)?)
+    }
+
+    /// Seekable reader over that archive's raw bytes, without reading them
+    pub(crate) fn archive_source(&self) -> Result<Box<dyn ArchiveSource>> {
+        Ok(match &self.base_apk {
+            Some(data) => Box::new(Cursor::new(data.clone())),
+            None => Box::new(File::open(&self.apk_path)?),
         })
     }
 
+    /// Split bundle layout, if this extractor was opened on a bundle
+    pub fn bundle(&self) -> Option<&BundleLayout> {
+        self.bundle.as_ref()
+    }
+
+    /// Metadata of every ZIP entry, in archive order
+    ///
+    /// For a bundle these are the base APK's entries.
+    pub fn list_entries(&self) -> &[ApkEntry] {
+        &self.entries
+    }
+
     /// Get all DEX entries
     pub fn dex_entries(&self) -> &[DexEntry] {
         &self.dex_entries
@@ -148,8 +364,7 @@ impl ApkExtractor {
 
     /// Extract AndroidManifest.xml as raw bytes
     pub fn extract_manifest(&self) -> Result<Vec<u8>> {
-        let file = File::open(&self.apk_path)?;
-        let mut archive = ZipArchive::new(file)?;
+        let mut archive = self.archive()?;
 
         let mut manifest = archive.by_name("AndroidManifest.xml")
             .map_err(|_| ApkError::ManifestNotFound)?;
@@ -167,8 +382,7 @@ impl ApkExtractor {
             ));
         }
 
-        let file = File::open(&self.apk_path)?;
-        let mut archive = ZipArchive::new(file)?;
+        let mut archive = self.archive()?;
 
         let mut resources = archive.by_name("resources.arsc")
             .map_err(|e| ApkError::ZipReadError(e.to_string()))?;
@@ -185,8 +399,7 @@ impl ApkExtractor {
 
     /// Extract a specific file from the APK
     pub fn extract_file(&self, filename: &str) -> Result<Vec<u8>> {
-        let file = File::open(&self.apk_path)?;
-        let mut archive = ZipArchive::new(file)?;
+        let mut archive = self.archive()?;
 
         let mut entry = archive.by_name(filename)
             .map_err(|_| ApkError::ZipReadError(format!("File not found: {}", filename)))?;
@@ -197,6 +410,47 @@ impl ApkExtractor {
     }
 }
 
+/// Metadata of every entry in a ZIP file, read from its central directory
+///
+/// Nothing is decompressed, so this stays cheap for large APKs. Unlike
+/// `ApkExtractor::list_entries`, a split bundle lists its own entries.
+pub fn read_zip_entries<P: AsRef<Path>>(path: P) -> Result<Vec<ApkEntry>> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+
+    (0..archive.len())
+        .map(|i| {
+            let entry = archive.by_index_raw(i)?;
+            Ok(ApkEntry {
+                name: entry.name().to_string(),
+                size: entry.size(),
+                compressed_size: entry.compressed_size(),
+                crc32: entry.crc32(),
+            })
+        })
+        .collect()
+}
+
+/// Uncompressed contents of the entry `name` in a ZIP file
+///
+/// Only the central directory and that entry are read. Like
+/// `read_zip_entries`, a split bundle is read as the outer archive.
+pub fn read_zip_entry<P: AsRef<Path>>(path: P, name: &str) -> Result<Vec<u8>> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| ApkError::ZipReadError(format!("File not found: {}", name)))?;
+
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// Entry name without directories or the `.apk` extension
+fn file_stem(entry_name: &str) -> &str {
+    let name = entry_name.rsplit('/').next().unwrap_or(entry_name);
+    name.strip_suffix(".apk").unwrap_or(name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,4 +468,104 @@ mod tests {
         let dex15 = DexEntry::new("classes15.dex".to_string(), 2, vec![]);
         assert_eq!(dex15.dex_number(), Some(15));
     }
+
+    #[test]
+    fn test_list_and_extract_entries() {
+        use crate::dex::test_support::{sample_dex, write_test_apk_with_entries};
+
+        let config = br#"{"endpoint": "https://api.example.com"}"#;
+        let apk = write_test_apk_with_entries(
+            "entries",
+            &[sample_dex()],
+            &[
+                ("AndroidManifest.xml", b"".as_slice()),
+                ("assets/config.json", config.as_slice()),
+                ("res/raw/seed.bin", &[7u8; 32]),
+            ],
+        );
+        let extractor = ApkExtractor::new(&apk).unwrap();
+
+        let names: Vec<&str> = extractor.list_entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["AndroidManifest.xml", "assets/config.json", "res/raw/seed.bin", "classes.dex"]);
+        let seed = &extractor.list_entries()[2];
+        assert_eq!(seed.size, 32);
+        assert_eq!(seed.crc32, 0xa3d824cb);
+        assert_eq!(read_zip_entries(&apk).unwrap(), extractor.list_entries());
+
+        // Header-only scans keep the listing but not the code
+        let headers = ApkExtractor::open_dex_headers(&apk).unwrap();
+        assert_eq!(headers.list_entries(), extractor.list_entries());
+        assert_eq!(headers.dex_entries()[0].data.len(), 112);
+        assert!(extractor.dex_entries()[0].data.len() > 112);
+
+        assert_eq!(extractor.extract_file("assets/config.json").unwrap(), config);
+        assert!(matches!(extractor.extract_file("assets/missing.json"), Err(ApkError::ZipReadError(_))));
+        assert_eq!(read_zip_entry(&apk, "assets/config.json").unwrap(), config);
+        assert!(matches!(read_zip_entry(&apk, "assets/missing.json"), Err(ApkError::ZipReadError(_))));
+        std::fs::remove_file(&apk).ok();
+
+        // Plain ZIP reads need neither a manifest nor DEX
+        let bare = write_test_apk_with_entries("entries-bare", &[], &[("assets/config.json", config.as_slice())]);
+        assert!(ApkExtractor::new(&bare).is_err());
+        assert_eq!(read_zip_entries(&bare).unwrap().len(), 1);
+        assert_eq!(read_zip_entry(&bare, "assets/config.json").unwrap(), config);
+        std::fs::remove_file(&bare).ok();
+    }
+
+    #[test]
+    fn test_open_apks_bundle() {
+        use crate::apk::test_support::{element, encode_binary_xml, s};
+        use crate::dex::test_support::{sample_dex, write_test_apk_with_entries};
+        use std::io::Write;
+
+        let apk = |name: &str, split: Option<&str>, dex: &[Vec<u8>]| {
+            let mut manifest = element("manifest").with_attr("package", s("com.example.app"));
+            if let Some(split) = split {
+                manifest = manifest.with_attr("split", s(split));
+            }
+            let path = write_test_apk_with_entries(name, dex, &[("AndroidManifest.xml", &encode_binary_xml(&manifest))]);
+            let bytes = std::fs::read(&path).unwrap();
+            std::fs::remove_file(path).ok();
+            bytes
+        };
+        let base = apk("bundle-base", None, &[sample_dex()]);
+        let feature = apk("bundle-feature", Some("feature_chat"), &[sample_dex()]);
+        let config = apk("bundle-config", Some("config.arm64_v8a"), &[]);
+
+        // XAPK layout: the base is named after the package and comes after
+        // a feature split that also has code; the base is stored, so it is
+        // read in place
+        let bundle = std::env::temp_dir().join(format!("playfast-test-bundle-{}.xapk", std::process::id()));
+        let mut zip = zip::ZipWriter::new(File::create(&bundle).unwrap());
+        let deflated = zip::write::SimpleFileOptions::default();
+        let stored = deflated.compression_method(CompressionMethod::Stored);
+        for (name, data, options) in [
+            ("manifest.json", b"{}".as_slice(), deflated),
+            ("config.arm64_v8a.apk", &config, deflated),
+            ("feature_chat.apk", &feature, deflated),
+            ("com.example.app.apk", &base, stored),
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let extractor = ApkExtractor::new(&bundle).unwrap();
+        std::fs::remove_file(&bundle).ok();
+
+        let layout = extractor.bundle().unwrap();
+        assert_eq!(layout.base_apk, "com.example.app.apk");
+        assert_eq!(layout.splits, vec!["config.arm64_v8a.apk", "feature_chat.apk"]);
+
+        let names: Vec<&str> = extractor.dex_entries().iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["classes.dex", "feature_chat!classes.dex"]);
+        assert!(extractor.has_manifest());
+        assert!(extractor.extract_manifest().is_ok());
+
+        // Without a manifest free of `split`, there is no base
+        let bundle = write_test_apk_with_entries("bundle-no-base", &[], &[("feature_chat.apk", &feature)]);
+        let result = ApkExtractor::new(&bundle);
+        std::fs::remove_file(&bundle).ok();
+        assert!(matches!(result, Err(ApkError::InvalidApk(_))));
+    }
 }
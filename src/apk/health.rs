@@ -0,0 +1,220 @@
+//! Quick APK sanity check
+//!
+//! Aggregates cheap structural checks (ZIP validity, required entries, DEX
+//! headers, signing schemes, manifest package) into a go/no-go report, so
+//! broken inputs can be rejected before running a full analysis.
+
+use pyo3::prelude::*;
+use std::io::Cursor;
+use zip::ZipArchive;
+
+use crate::apk::manifest::parse_manifest;
+use crate::apk::signing::{is_v1_signature_file, signing_block_pairs, SIGNATURE_SCHEME_IDS};
+use crate::apk::ApkExtractor;
+use crate::dex::parser::DexParser;
+
+/// Result of `validate_apk`
+#[pyclass]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApkHealth {
+    /// The file opens as a ZIP archive
+    #[pyo3(get)]
+    pub is_valid_zip: bool,
+    #[pyo3(get)]
+    pub has_manifest: bool,
+    #[pyo3(get)]
+    pub has_resources: bool,
+    /// Number of DEX files, including those of bundle splits
+    #[pyo3(get)]
+    pub dex_count: usize,
+    /// DEX files whose header fails validation, as "name: reason"
+    #[pyo3(get)]
+    pub invalid_dex: Vec<String>,
+    /// Signing schemes present: "v1", "v2", "v3", "v3.1"
+    #[pyo3(get)]
+    pub signature_schemes: Vec<String>,
+    /// Package name from the manifest, None if it could not be parsed
+    #[pyo3(get)]
+    pub package_name: Option<String>,
+    /// Human-readable explanation of each failed check
+    #[pyo3(get)]
+    pub diagnostics: Vec<String>,
+}
+
+#[pymethods]
+impl ApkHealth {
+    /// Whether the APK is fit for analysis: a valid archive with a parseable
+    /// manifest and at least one DEX file, all with valid headers
+    ///
+    /// A missing signature or resources.arsc is reported but not fatal.
+    pub fn is_healthy(&self) -> bool {
+        self.is_valid_zip
+            && self.has_manifest
+            && self.package_name.is_some()
+            && self.dex_count > 0
+            && self.invalid_dex.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ApkHealth(healthy={}, dex_count={}, signature_schemes={:?}, diagnostics={})",
+            self.is_healthy(),
+            self.dex_count,
+            self.signature_schemes,
+            self.diagnostics.len()
+        )
+    }
+}
+
+impl ApkHealth {
+    /// Run every check on the raw bytes of the file at `apk_path`
+    pub fn check(apk_path: &str, data: &[u8]) -> Self {
+        let mut health = Self::default();
+
+        let archive = match ZipArchive::new(Cursor::new(data)) {
+            Ok(archive) => archive,
+            Err(e) => {
+                health.diagnostics.push(format!("not a valid ZIP archive: {}", e));
+                return health;
+            }
+        };
+        health.is_valid_zip = true;
+
+        let names: Vec<&str> = archive.file_names().collect();
+        if names.iter().any(|name| is_v1_signature_file(name)) {
+            health.signature_schemes.push("v1".to_string());
+        }
+        health.signature_schemes.extend(signing_block_schemes(data).into_iter().map(str::to_string));
+        if health.signature_schemes.is_empty() {
+            health.diagnostics.push("no APK signature found".to_string());
+        }
+
+        // ApkExtractor rejects archives without a manifest or DEX; the
+        // listing still tells which one is missing. Only DEX headers are
+        // read, so the code is never decompressed.
+        let extractor = match ApkExtractor::open_dex_headers(apk_path) {
+            Ok(extractor) => extractor,
+            Err(e) => {
+                health.has_manifest = names.contains(&"AndroidManifest.xml");
+                health.has_resources = names.contains(&"resources.arsc");
+                health.diagnostics.push(e.to_string());
+                return health;
+            }
+        };
+        health.has_manifest = extractor.has_manifest();
+        health.has_resources = extractor.has_resources();
+        health.dex_count = extractor.dex_count();
+        if !health.has_resources {
+            health.diagnostics.push("resources.arsc not found".to_string());
+        }
+
+        for dex_entry in extractor.dex_entries() {
+            if let Err(e) = DexParser::parse_header(&dex_entry.data) {
+                health.invalid_dex.push(format!("{}: {}", dex_entry.name, e));
+            }
+        }
+        if !health.invalid_dex.is_empty() {
+            health.diagnostics.push(format!("{} DEX file(s) failed header validation", health.invalid_dex.len()));
+        }
+
+        let manifest = extractor.extract_manifest().and_then(|bytes| parse_manifest(&bytes));
+        match manifest.map(|manifest| manifest.package_name) {
+            Ok(package) if !package.is_empty() => health.package_name = Some(package),
+            Ok(_) => health.diagnostics.push("manifest declares no package name".to_string()),
+            Err(e) => health.diagnostics.push(format!("manifest could not be parsed: {}", e)),
+        }
+
+        health
+    }
+}
+
+/// Schemes found in the APK Signing Block preceding the central directory
+fn signing_block_schemes(data: &[u8]) -> Vec<&'static str> {
+    signing_block_pairs(data)
+        .into_iter()
+        .filter_map(|(id, _)| SIGNATURE_SCHEME_IDS.iter().find(|(scheme_id, _)| *scheme_id == id))
+        .map(|(_, scheme)| *scheme)
+        .collect()
+}
+
+/// Check an APK before analysis
+///
+/// Cheap structural checks only: whether the file is a valid ZIP, has a
+/// manifest and resources.arsc, how many DEX files it has and whether
+/// their headers are valid, which signing schemes are present, and
+/// whether the manifest's package name can be read.
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     ApkHealth: Check results; `is_healthy()` gives the go/no-go signal
+///     and `diagnostics` explains each failed check
+///
+/// Raises:
+///     IOError: If the file cannot be read
+#[pyfunction]
+pub fn validate_apk(apk_path: &str) -> PyResult<ApkHealth> {
+    let data = std::fs::read(apk_path).map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+    Ok(ApkHealth::check(apk_path, &data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apk::test_support::{element, encode_binary_xml, s, with_signing_block};
+    use crate::dex::test_support::{sample_dex, write_test_apk_with_entries};
+
+    #[test]
+    fn test_validate_apk() {
+        let manifest = encode_binary_xml(&element("manifest").with_attr("package", s("com.example.app")));
+        let apk = write_test_apk_with_entries(
+            "health",
+            &[sample_dex(), b"dex\n035\0 not really a dex".to_vec()],
+            &[
+                ("AndroidManifest.xml", manifest.as_slice()),
+                ("resources.arsc", &[0u8; 16]),
+                ("META-INF/CERT.RSA", b"certificate".as_slice()),
+            ],
+        );
+        let health = validate_apk(apk.to_str().unwrap()).unwrap();
+
+        assert!(health.is_valid_zip && health.has_manifest && health.has_resources);
+        assert_eq!(health.dex_count, 2);
+        assert_eq!(health.invalid_dex.len(), 1);
+        assert!(health.invalid_dex[0].starts_with("classes2.dex: "));
+        assert_eq!(health.signature_schemes, vec!["v1"]);
+        assert_eq!(health.package_name.as_deref(), Some("com.example.app"));
+        assert!(!health.is_healthy());
+
+        // Cut off the central directory
+        let data = std::fs::read(&apk).unwrap();
+        std::fs::write(&apk, &data[..data.len() / 2]).unwrap();
+        let truncated = validate_apk(apk.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        assert!(!truncated.is_valid_zip);
+        assert!(!truncated.is_healthy());
+        assert_eq!(truncated.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn test_healthy_apk_with_signing_block() {
+        let manifest = encode_binary_xml(&element("manifest").with_attr("package", s("com.example.app")));
+        let apk = write_test_apk_with_entries("health-signed", &[sample_dex()], &[("AndroidManifest.xml", manifest.as_slice())]);
+        let zip = std::fs::read(&apk).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        // A signing block with a v2 and a v3 pair
+        let signed = with_signing_block(&zip, &[(0x7109_871a, vec![0u8; 4]), (0xf053_68c0, vec![0u8; 4])]);
+
+        let path = std::env::temp_dir().join(format!("playfast-test-health-signed-{}.apk", std::process::id()));
+        std::fs::write(&path, &signed).unwrap();
+        let health = validate_apk(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(health.signature_schemes, vec!["v2", "v3"]);
+        assert!(health.is_healthy());
+        assert_eq!(health.diagnostics, vec!["resources.arsc not found"]);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::apk::error::{ApkError, Result};
+use crate::apk::resources::ResourceResolver;
 use pyo3::prelude::*;
+use rusty_axml::parser::XmlNode;
+use std::collections::HashMap;
 use std::io::Cursor;
 use serde::{Deserialize, Serialize};
 
@@ -34,14 +37,32 @@ impl IntentFilterData {
         }
         format!("IntentFilterData({})", parts.join(", "))
     }
+
+    /// Convert to Python dictionary
+    pub fn to_dict(&self, py: Python) -> PyResult<Py<pyo3::types::PyAny>> {
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("scheme", &self.scheme)?;
+        dict.set_item("host", &self.host)?;
+        dict.set_item("path", &self.path)?;
+        dict.set_item("path_prefix", &self.path_prefix)?;
+        dict.set_item("path_pattern", &self.path_pattern)?;
+        Ok(dict.into())
+    }
 }
 
-/// Activity with intent filters
+/// Intent filter of a component
+///
+/// Despite the name, filters of services, receivers and providers are
+/// recorded too; `activity` holds the declaring component either way and
+/// `component_type` tells them apart.
 #[pyclass]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActivityIntentFilter {
     #[pyo3(get)]
     pub activity: String,
+    /// "activity" (also for an `<activity-alias>`), "service", "receiver" or "provider"
+    #[pyo3(get)]
+    pub component_type: String,
     #[pyo3(get)]
     pub actions: Vec<String>,
     #[pyo3(get)]
@@ -63,14 +84,148 @@ impl ActivityIntentFilter {
 
     pub fn is_deeplink(&self) -> bool {
         // Check if this is a deeplink intent filter
-        // Must have VIEW action and BROWSABLE or DEFAULT category
+        // Must be on an activity, with VIEW action and BROWSABLE or DEFAULT category
+        if self.component_type != "activity" {
+            return false;
+        }
         let has_view_action = self.actions.iter().any(|a| a.contains("VIEW"));
         let has_browsable = self.categories.iter().any(|c| c.contains("BROWSABLE") || c.contains("DEFAULT"));
 
         has_view_action && has_browsable && !self.data.is_empty()
     }
+
+    /// Convert to Python dictionary
+    pub fn to_dict(&self, py: Python) -> PyResult<Py<pyo3::types::PyAny>> {
+        let data_list = pyo3::types::PyList::empty(py);
+        for data in &self.data {
+            data_list.append(data.to_dict(py)?)?;
+        }
+
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("activity", &self.activity)?;
+        dict.set_item("component_type", &self.component_type)?;
+        dict.set_item("actions", &self.actions)?;
+        dict.set_item("categories", &self.categories)?;
+        dict.set_item("data", data_list)?;
+        dict.set_item("is_deeplink", self.is_deeplink())?;
+        Ok(dict.into())
+    }
+}
+
+/// Custom permission declared with `<permission>`
+#[pyclass]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeclaredPermission {
+    #[pyo3(get)]
+    pub name: String,
+    /// Level in manifest syntax, e.g. "normal", "signature|privileged"
+    #[pyo3(get)]
+    pub protection_level: String,
+}
+
+#[pymethods]
+impl DeclaredPermission {
+    /// Whether only apps signed with the declaring app's key can hold it
+    pub fn is_signature(&self) -> bool {
+        self.protection_level.split('|').any(|level| level == "signature" || level == "signatureOrSystem")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("DeclaredPermission(name='{}', protection_level='{}')", self.name, self.protection_level)
+    }
+}
+
+/// Access settings of a `<provider>`
+#[pyclass]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderInfo {
+    #[pyo3(get)]
+    pub name: String,
+    /// `android:authorities`, split on ';'
+    #[pyo3(get)]
+    pub authorities: Vec<String>,
+    /// `android:grantUriPermissions`, defaulting to false
+    #[pyo3(get)]
+    pub grant_uri_permissions: bool,
+    /// `android:permission`, or the `<application>` one when absent,
+    /// guarding both reads and writes
+    #[pyo3(get)]
+    pub permission: Option<String>,
+    #[pyo3(get)]
+    pub read_permission: Option<String>,
+    #[pyo3(get)]
+    pub write_permission: Option<String>,
+    #[pyo3(get)]
+    pub exported: bool,
+}
+
+#[pymethods]
+impl ProviderInfo {
+    /// Whether any app can query the provider without holding a permission
+    pub fn is_world_readable(&self) -> bool {
+        self.exported && self.read_permission.is_none() && self.permission.is_none()
+    }
+
+    /// Whether any app can modify the provider without holding a permission
+    pub fn is_world_writable(&self) -> bool {
+        self.exported && self.write_permission.is_none() && self.permission.is_none()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ProviderInfo(name='{}', authorities={:?}, exported={}, grant_uri_permissions={})",
+            self.name, self.authorities, self.exported, self.grant_uri_permissions
+        )
+    }
+}
+
+/// Base levels and flags of `android:protectionLevel`, by value
+const PROTECTION_BASES: &[(u32, &str)] = &[
+    (0x0, "normal"),
+    (0x1, "dangerous"),
+    (0x2, "signature"),
+    (0x3, "signatureOrSystem"),
+    (0x4, "internal"),
+];
+const PROTECTION_FLAGS: &[(u32, &str)] = &[
+    (0x10, "privileged"),
+    (0x20, "development"),
+    (0x40, "appop"),
+    (0x80, "pre23"),
+    (0x100, "installer"),
+    (0x200, "verifier"),
+    (0x400, "preinstalled"),
+    (0x800, "setup"),
+];
+
+/// Render a decoded `android:protectionLevel` in manifest syntax
+///
+/// Compiled manifests store the level as an integer; readers that already
+/// resolved it to names are passed through. An absent level is "normal".
+fn protection_level_name(value: Option<&str>) -> String {
+    let Some(value) = value.map(str::trim) else { return "normal".to_string() };
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse::<u32>().ok(),
+    };
+    let Some(bits) = parsed else { return value.to_string() };
+
+    let base = PROTECTION_BASES
+        .iter()
+        .find(|(mask, _)| *mask == bits & 0xF)
+        .map_or_else(|| format!("0x{:x}", bits & 0xF), |(_, name)| name.to_string());
+    std::iter::once(base)
+        .chain(PROTECTION_FLAGS.iter().filter(|(flag, _)| bits & flag != 0).map(|(_, name)| name.to_string()))
+        .collect::<Vec<_>>()
+        .join("|")
 }
 
+/// Component elements under `<application>`
+const COMPONENT_TYPES: [&str; 4] = ["activity", "service", "receiver", "provider"];
+
+const ACTION_MAIN: &str = "android.intent.action.MAIN";
+const CATEGORY_LAUNCHER: &str = "android.intent.category.LAUNCHER";
+
 /// Parsed AndroidManifest.xml information
 #[pyclass]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +254,50 @@ pub struct RustManifestInfo {
     pub application_label: Option<String>,
     #[pyo3(get)]
     pub intent_filters: Vec<ActivityIntentFilter>,
+    /// `android:allowBackup` on `<application>` (defaults to true)
+    #[pyo3(get)]
+    pub allow_backup: Option<bool>,
+    /// `android:debuggable` on `<application>` (defaults to false)
+    #[pyo3(get)]
+    pub debuggable: Option<bool>,
+    /// `android:usesCleartextTraffic` on `<application>`, if declared; the
+    /// platform default depends on targetSdkVersion
+    #[pyo3(get)]
+    pub uses_cleartext_traffic: Option<bool>,
+    /// `android:networkSecurityConfig` on `<application>` as decoded, e.g.
+    /// "@xml/network_security_config" or a raw resource reference
+    #[pyo3(get)]
+    pub network_security_config: Option<String>,
+    /// Process of each component that does not run in the default process,
+    /// from `android:process` on the component or `<application>`
+    #[pyo3(get)]
+    pub component_processes: HashMap<String, String>,
+    /// Components and activity aliases reachable from other apps, from
+    /// `android:exported` or the platform default when it is absent
+    #[pyo3(get)]
+    pub exported_components: Vec<String>,
+    /// `android:permission` on `<application>`, the default for components
+    /// that declare none
+    #[pyo3(get)]
+    pub application_permission: Option<String>,
+    /// `android:permission` guarding each component, inherited from
+    /// `<application>` when the component declares none; activity aliases
+    /// fall back to their target's permission. Provider read/write
+    /// permissions are reported in `providers_detail`
+    #[pyo3(get)]
+    pub component_permissions: HashMap<String, String>,
+    /// Custom permissions the app declares with `<permission>`
+    #[pyo3(get)]
+    pub declared_permissions: Vec<DeclaredPermission>,
+    /// `android:label` of each component that declares one
+    #[pyo3(get)]
+    pub component_labels: HashMap<String, String>,
+    /// Authorities, permissions and URI grants of each provider
+    #[pyo3(get)]
+    pub providers_detail: Vec<ProviderInfo>,
+    /// `<activity-alias>` names mapped to their `android:targetActivity`
+    #[pyo3(get)]
+    pub activity_aliases: HashMap<String, String>,
 }
 
 #[pymethods]
@@ -118,7 +317,38 @@ impl RustManifestInfo {
             providers: Vec::new(),
             application_label: None,
             intent_filters: Vec::new(),
+            allow_backup: None,
+            debuggable: None,
+            uses_cleartext_traffic: None,
+            network_security_config: None,
+            component_processes: HashMap::new(),
+            exported_components: Vec::new(),
+            application_permission: None,
+            component_permissions: HashMap::new(),
+            declared_permissions: Vec::new(),
+            component_labels: HashMap::new(),
+            providers_detail: Vec::new(),
+            activity_aliases: HashMap::new(),
+        }
+    }
+
+    /// Names of risky `<application>` flags that are in effect
+    ///
+    /// Reports `allowBackup` (app data can be pulled with `adb backup`),
+    /// `debuggable` (a debugger can attach to release builds) and an
+    /// explicit `usesCleartextTraffic="true"` (plain HTTP is allowed).
+    pub fn security_flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+        if self.allow_backup == Some(true) {
+            flags.push("allowBackup".to_string());
         }
+        if self.debuggable == Some(true) {
+            flags.push("debuggable".to_string());
+        }
+        if self.uses_cleartext_traffic == Some(true) {
+            flags.push("usesCleartextTraffic".to_string());
+        }
+        flags
     }
 
     /// Get only deeplink intent filters
@@ -130,6 +360,62 @@ impl RustManifestInfo {
             .collect()
     }
 
+    /// Activities with a MAIN/LAUNCHER intent filter, in manifest order
+    ///
+    /// A launcher declared on an `<activity-alias>` counts for the activity
+    /// the alias targets.
+    pub fn get_launcher_activities(&self) -> Vec<String> {
+        let mut launchers: Vec<String> = Vec::new();
+        for filter in &self.intent_filters {
+            let is_launcher = filter.component_type == "activity"
+                && filter.actions.iter().any(|a| a == ACTION_MAIN)
+                && filter.categories.iter().any(|c| c == CATEGORY_LAUNCHER);
+            if is_launcher && !launchers.contains(&filter.activity) {
+                launchers.push(filter.activity.clone());
+            }
+        }
+        launchers
+    }
+
+    /// Group all components by the process they run in
+    ///
+    /// Components without an explicit `android:process` run in the default
+    /// process, named after the package.
+    pub fn components_by_process(&self) -> HashMap<String, Vec<String>> {
+        let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+        let components = self
+            .activities
+            .iter()
+            .chain(self.services.iter())
+            .chain(self.receivers.iter())
+            .chain(self.providers.iter());
+
+        for component in components {
+            let process = self.component_processes.get(component).unwrap_or(&self.package_name);
+            groups.entry(process.clone()).or_default().push(component.clone());
+        }
+        groups
+    }
+
+    /// Exported components and activity aliases that no permission guards
+    ///
+    /// Any app on the device can start or bind these, so they are the
+    /// first place to look for attack surface. A provider counts as guarded
+    /// when `android:permission` covers it or when it declares both a read
+    /// and a write permission.
+    pub fn unprotected_exported_components(&self) -> Vec<String> {
+        self.exported_components
+            .iter()
+            .filter(|component| !self.component_permissions.contains_key(*component))
+            .filter(|component| {
+                !self.providers_detail.iter().any(|provider| {
+                    provider.name == **component && provider.read_permission.is_some() && provider.write_permission.is_some()
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Convert to Python dictionary
     pub fn to_dict(&self, py: Python) -> PyResult<Py<pyo3::types::PyAny>> {
         let dict = pyo3::types::PyDict::new(py);
@@ -144,6 +430,52 @@ impl RustManifestInfo {
         dict.set_item("receivers", &self.receivers)?;
         dict.set_item("providers", &self.providers)?;
         dict.set_item("application_label", &self.application_label)?;
+        dict.set_item("component_labels", &self.component_labels)?;
+        dict.set_item("allow_backup", self.allow_backup)?;
+        dict.set_item("debuggable", self.debuggable)?;
+        dict.set_item("uses_cleartext_traffic", self.uses_cleartext_traffic)?;
+        dict.set_item("network_security_config", &self.network_security_config)?;
+        dict.set_item("component_processes", &self.component_processes)?;
+        dict.set_item("activity_aliases", &self.activity_aliases)?;
+        dict.set_item("security_flags", self.security_flags())?;
+        dict.set_item("exported_components", &self.exported_components)?;
+        dict.set_item("application_permission", &self.application_permission)?;
+        dict.set_item("component_permissions", &self.component_permissions)?;
+        let declared = pyo3::types::PyList::empty(py);
+        for permission in &self.declared_permissions {
+            let detail = pyo3::types::PyDict::new(py);
+            detail.set_item("name", &permission.name)?;
+            detail.set_item("protection_level", &permission.protection_level)?;
+            declared.append(detail)?;
+        }
+        dict.set_item("declared_permissions", declared)?;
+        let providers_detail = pyo3::types::PyList::empty(py);
+        for provider in &self.providers_detail {
+            let detail = pyo3::types::PyDict::new(py);
+            detail.set_item("name", &provider.name)?;
+            detail.set_item("authorities", &provider.authorities)?;
+            detail.set_item("grant_uri_permissions", provider.grant_uri_permissions)?;
+            detail.set_item("permission", &provider.permission)?;
+            detail.set_item("read_permission", &provider.read_permission)?;
+            detail.set_item("write_permission", &provider.write_permission)?;
+            detail.set_item("exported", provider.exported)?;
+            providers_detail.append(detail)?;
+        }
+        dict.set_item("providers_detail", providers_detail)?;
+
+        let filters_list = pyo3::types::PyList::empty(py);
+        for filter in &self.intent_filters {
+            filters_list.append(filter.to_dict(py)?)?;
+        }
+        dict.set_item("intent_filters", filters_list)?;
+
+        let mut deeplink_activities: Vec<String> = Vec::new();
+        for filter in self.get_deeplinks() {
+            if !deeplink_activities.contains(&filter.activity) {
+                deeplink_activities.push(filter.activity);
+            }
+        }
+        dict.set_item("deeplink_activities", deeplink_activities)?;
         Ok(dict.into())
     }
 
@@ -161,7 +493,19 @@ impl RustManifestInfo {
 }
 
 /// Parse AndroidManifest.xml from binary data
+///
+/// Reference-valued labels are kept as "0x7f0b0001"; use
+/// `parse_manifest_with_resources` to resolve them.
 pub fn parse_manifest(data: &[u8]) -> Result<RustManifestInfo> {
+    parse_manifest_with_resources(data, None)
+}
+
+/// Parse AndroidManifest.xml, resolving labels that reference a string
+/// resource (`android:label="@string/app_name"`) with `resources`
+///
+/// Labels that are plain text, or whose reference does not end in a string
+/// resource, are kept as decoded.
+pub fn parse_manifest_with_resources(data: &[u8], resources: Option<&ResourceResolver>) -> Result<RustManifestInfo> {
     // Parse binary XML using rusty-axml
     let cursor = Cursor::new(data.to_vec());
     let axml = rusty_axml::parse_from_cursor(cursor)
@@ -177,62 +521,112 @@ pub fn parse_manifest(data: &[u8]) -> Result<RustManifestInfo> {
 
     let mut manifest = RustManifestInfo::new(package_name.clone());
 
-    // Get version info from root attributes (try with and without android: prefix)
-    let root_borrowed = root.borrow();
-    manifest.version_code = root_borrowed.get_attr("android:versionCode")
-        .or_else(|| root_borrowed.get_attr("versionCode"))
-        .map(|s| s.to_string());
-    manifest.version_name = root_borrowed.get_attr("android:versionName")
-        .or_else(|| root_borrowed.get_attr("versionName"))
-        .map(|s| s.to_string());
+    // Get version info from root attributes
+    manifest.version_code = attr(root, "versionCode");
+    manifest.version_name = attr(root, "versionName");
 
-    // Get SDK versions (try with and without android: prefix)
+    // Get SDK versions
     let uses_sdk_nodes = rusty_axml::find_nodes_by_type(&axml, "uses-sdk");
     if let Some(uses_sdk) = uses_sdk_nodes.first() {
-        let uses_sdk_borrowed = uses_sdk.borrow();
-        manifest.min_sdk_version = uses_sdk_borrowed.get_attr("android:minSdkVersion")
-            .or_else(|| uses_sdk_borrowed.get_attr("minSdkVersion"))
-            .map(|s| s.to_string());
-        manifest.target_sdk_version = uses_sdk_borrowed.get_attr("android:targetSdkVersion")
-            .or_else(|| uses_sdk_borrowed.get_attr("targetSdkVersion"))
-            .map(|s| s.to_string());
+        manifest.min_sdk_version = attr(uses_sdk, "minSdkVersion");
+        manifest.target_sdk_version = attr(uses_sdk, "targetSdkVersion");
     }
 
     // Get permissions using helper function
     manifest.permissions = rusty_axml::get_requested_permissions(&axml);
-
-    // Get activities
-    let activities = rusty_axml::get_activities_names(&axml);
-    manifest.activities = activities
-        .into_iter()
-        .map(|name| normalize_component_name(&package_name, &name))
+    manifest.declared_permissions = rusty_axml::find_nodes_by_type(&axml, "permission")
+        .iter()
+        .filter_map(|node| {
+            let name = attr(node, "name")?;
+            let protection_level = protection_level_name(attr(node, "protectionLevel").as_deref());
+            Some(DeclaredPermission { name, protection_level })
+        })
         .collect();
 
-    // Get services
-    let services = rusty_axml::get_services_names(&axml);
-    manifest.services = services
-        .into_iter()
-        .map(|name| normalize_component_name(&package_name, &name))
-        .collect();
-
-    // Get receivers
-    let receivers = rusty_axml::get_receivers_names(&axml);
-    manifest.receivers = receivers
-        .into_iter()
-        .map(|name| normalize_component_name(&package_name, &name))
-        .collect();
-
-    // Get providers
-    let providers = rusty_axml::get_providers_names(&axml);
-    manifest.providers = providers
-        .into_iter()
-        .map(|name| normalize_component_name(&package_name, &name))
-        .collect();
+    let component_names = |component_type: &str| -> Vec<String> {
+        rusty_axml::find_nodes_by_type(&axml, component_type)
+            .iter()
+            .filter_map(|node| attr(node, "name"))
+            .map(|name| normalize_component_name(&package_name, &name))
+            .collect()
+    };
+    manifest.activities = component_names("activity");
+    manifest.services = component_names("service");
+    manifest.receivers = component_names("receiver");
+    manifest.providers = component_names("provider");
 
-    // Get application label
+    // Get application label, process, permission and flags
     let app_nodes = rusty_axml::find_nodes_by_type(&axml, "application");
+    let app_process = app_nodes
+        .first()
+        .and_then(|node| attr(node, "process"))
+        .map(|p| resolve_process_name(&package_name, &p));
+    let app_permission = app_nodes.first().and_then(|node| attr(node, "permission"));
+    manifest.application_permission = app_permission.clone();
     if let Some(app_node) = app_nodes.first() {
-        manifest.application_label = app_node.borrow().get_attr("android:label").map(|s| s.to_string());
+        manifest.application_label = label(app_node, resources);
+        apply_application_flags(&mut manifest, |name| attr(app_node, name));
+    }
+
+    // targetSdkVersion defaults to minSdkVersion
+    let target_sdk = manifest
+        .target_sdk_version
+        .as_deref()
+        .or(manifest.min_sdk_version.as_deref())
+        .and_then(|v| v.parse::<u32>().ok());
+
+    // Record per-component process, exported state and permission guard
+    for component_type in COMPONENT_TYPES {
+        for node in rusty_axml::find_nodes_by_type(&axml, component_type) {
+            let Some(name) = attr(&node, "name") else { continue };
+            let name = normalize_component_name(&package_name, &name);
+
+            let process = attr(&node, "process")
+                .map(|p| resolve_process_name(&package_name, &p))
+                .or_else(|| app_process.clone());
+            if let Some(process) = process {
+                manifest.component_processes.insert(name.clone(), process);
+            }
+
+            let explicit = attr(&node, "exported").and_then(|v| parse_bool_attr(&v));
+            let exported = is_exported(component_type, explicit, has_intent_filter(&node), target_sdk);
+            if exported {
+                manifest.exported_components.push(name.clone());
+            }
+
+            if let Some(label) = label(&node, resources) {
+                manifest.component_labels.insert(name.clone(), label);
+            }
+            if let Some(permission) = attr(&node, "permission").or_else(|| app_permission.clone()) {
+                manifest.component_permissions.insert(name.clone(), permission);
+            }
+            if component_type == "provider" {
+                let mut provider = provider_info(name, exported, |name| attr(&node, name));
+                provider.permission = provider.permission.or_else(|| app_permission.clone());
+                manifest.providers_detail.push(provider);
+            }
+        }
+    }
+
+    // Aliases are exported and guarded on their own; an alias without a
+    // permission takes the one of the activity it targets
+    for alias in rusty_axml::find_nodes_by_type(&axml, "activity-alias") {
+        let (Some(name), Some(target)) = (attr(&alias, "name"), attr(&alias, "targetActivity")) else {
+            continue;
+        };
+        let name = normalize_component_name(&package_name, &name);
+        let target = normalize_component_name(&package_name, &target);
+
+        let explicit = attr(&alias, "exported").and_then(|v| parse_bool_attr(&v));
+        if is_exported("activity", explicit, has_intent_filter(&alias), target_sdk) {
+            manifest.exported_components.push(name.clone());
+        }
+        let permission = attr(&alias, "permission")
+            .or_else(|| manifest.component_permissions.get(&target).cloned());
+        if let Some(permission) = permission {
+            manifest.component_permissions.insert(name.clone(), permission);
+        }
+        manifest.activity_aliases.insert(name, target);
     }
 
     // Parse intent filters for deeplinks
@@ -241,75 +635,189 @@ pub fn parse_manifest(data: &[u8]) -> Result<RustManifestInfo> {
     Ok(manifest)
 }
 
-/// Parse intent filters from activities
+/// Attribute of `node` by name, with or without the `android:` prefix
+///
+/// rusty-axml renders integer values as "(type 0x10) 0x22"; those are
+/// decoded to the number as written in the source manifest.
+fn attr(node: &XmlNode, name: &str) -> Option<String> {
+    let borrowed = node.borrow();
+    let value = borrowed
+        .get_attr(&format!("android:{}", name))
+        .or_else(|| borrowed.get_attr(name))?;
+    Some(decode_typed_int(value).unwrap_or_else(|| value.to_string()))
+}
+
+/// Decode rusty-axml's rendering of an integer attribute
+fn decode_typed_int(value: &str) -> Option<String> {
+    let (data_type, data) = value.strip_prefix("(type 0x")?.split_once(") 0x")?;
+    let data = u32::from_str_radix(data, 16).ok()?;
+    match data_type {
+        "10" => Some((data as i32).to_string()),
+        "11" => Some(format!("0x{:x}", data)),
+        _ => None,
+    }
+}
+
+/// Whether `node` has an `<intent-filter>` child
+fn has_intent_filter(node: &XmlNode) -> bool {
+    node.borrow()
+        .children()
+        .iter()
+        .any(|child| child.borrow().element_type() == "intent-filter")
+}
+
+/// `android:label` of `node`, with a string resource reference resolved
+/// when `resources` has it
+///
+/// rusty-axml renders references as "0x7f0b0001".
+fn label(node: &XmlNode, resources: Option<&ResourceResolver>) -> Option<String> {
+    let value = attr(node, "label")?;
+    let resolved = value
+        .strip_prefix("0x")
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .filter(|id| ResourceResolver::is_resource_id(*id as i64))
+        .and_then(|id| resources.and_then(|resources| resources.resolve_string(id)));
+    Some(resolved.unwrap_or(value))
+}
+
+/// Fill the security flags from `<application>` attributes, applying the
+/// platform defaults for `allow_backup` and `debuggable` when absent
+fn apply_application_flags<F>(manifest: &mut RustManifestInfo, get_attr: F)
+where
+    F: Fn(&str) -> Option<String>,
+{
+    manifest.allow_backup = Some(get_attr("allowBackup").and_then(|v| parse_bool_attr(&v)).unwrap_or(true));
+    manifest.debuggable = Some(get_attr("debuggable").and_then(|v| parse_bool_attr(&v)).unwrap_or(false));
+    manifest.uses_cleartext_traffic = get_attr("usesCleartextTraffic").and_then(|v| parse_bool_attr(&v));
+    manifest.network_security_config = get_attr("networkSecurityConfig");
+}
+
+/// Read a `<provider>`'s access settings from its attributes
+fn provider_info<F>(name: String, exported: bool, get_attr: F) -> ProviderInfo
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let authorities = get_attr("authorities")
+        .map(|value| value.split(';').map(str::trim).filter(|a| !a.is_empty()).map(String::from).collect())
+        .unwrap_or_default();
+    ProviderInfo {
+        name,
+        authorities,
+        grant_uri_permissions: get_attr("grantUriPermissions").and_then(|v| parse_bool_attr(&v)).unwrap_or(false),
+        permission: get_attr("permission"),
+        read_permission: get_attr("readPermission"),
+        write_permission: get_attr("writePermission"),
+        exported,
+    }
+}
+
+/// Whether a component is exported
+///
+/// An explicit `android:exported` wins. Without it, components with an
+/// intent filter are exported (apps targeting API 31+ must declare the
+/// attribute in that case), and providers are exported when targeting
+/// API 16 or lower. A missing target SDK means API 1.
+fn is_exported(component_type: &str, explicit: Option<bool>, has_intent_filter: bool, target_sdk: Option<u32>) -> bool {
+    if let Some(exported) = explicit {
+        return exported;
+    }
+    if component_type == "provider" {
+        return target_sdk.is_none_or(|sdk| sdk <= 16);
+    }
+    has_intent_filter
+}
+
+/// Interpret a decoded boolean attribute ("true", "false", or the raw
+/// TYPE_INT_BOOLEAN data where any non-zero value is true)
+fn parse_bool_attr(value: &str) -> Option<bool> {
+    match value.trim() {
+        "true" => Some(true),
+        "false" => Some(false),
+        other => {
+            let parsed = match other.strip_prefix("0x") {
+                Some(hex) => i64::from_str_radix(hex, 16).ok(),
+                None => other.parse::<i64>().ok(),
+            };
+            parsed.map(|n| n != 0)
+        }
+    }
+}
+
+/// Parse intent filters from activities, services, receivers and providers
+///
+/// Filters on an `<activity-alias>` are recorded against the alias's target
+/// activity, the class that actually receives the intent.
 fn parse_intent_filters(axml: &rusty_axml::parser::Axml, package_name: &str) -> Vec<ActivityIntentFilter> {
     let mut intent_filters = Vec::new();
 
-    // Get all activity nodes
-    let activity_nodes = rusty_axml::find_nodes_by_type(axml, "activity");
-
-    for activity_node in activity_nodes {
-        let activity_borrowed = activity_node.borrow();
+    let component_nodes = COMPONENT_TYPES
+        .into_iter()
+        .chain(["activity-alias"])
+        .flat_map(|component_type| rusty_axml::find_nodes_by_type(axml, component_type));
 
-        // Get activity name
-        let activity_name = if let Some(name) = activity_borrowed.get_attr("android:name") {
-            normalize_component_name(package_name, name)
-        } else {
-            continue;
+    for component_node in component_nodes {
+        let component_borrowed = component_node.borrow();
+        let component_type = match component_borrowed.element_type() {
+            "activity-alias" => "activity",
+            element_type => element_type,
         };
 
+        // Get component name
+        let name_attr = if component_borrowed.element_type() == "activity-alias" { "targetActivity" } else { "name" };
+        let Some(name) = attr(&component_node, name_attr) else { continue };
+        let component_name = normalize_component_name(package_name, &name);
+
         // Look for intent-filter children
-        for child in activity_borrowed.children() {
-            let child_borrowed = child.borrow();
-            if child_borrowed.element_type() == "intent-filter" {
-                let mut actions = Vec::new();
-                let mut categories = Vec::new();
-                let mut data_list = Vec::new();
-
-                // Parse intent-filter children
-                for intent_child in child_borrowed.children() {
-                    let intent_child_borrowed = intent_child.borrow();
-                    match intent_child_borrowed.element_type() {
-                        "action" => {
-                            if let Some(name) = intent_child_borrowed.get_attr("android:name") {
-                                actions.push(name.to_string());
-                            }
-                        }
-                        "category" => {
-                            if let Some(name) = intent_child_borrowed.get_attr("android:name") {
-                                categories.push(name.to_string());
-                            }
-                        }
-                        "data" => {
-                            let data = IntentFilterData {
-                                scheme: intent_child_borrowed.get_attr("android:scheme").map(|s| s.to_string()),
-                                host: intent_child_borrowed.get_attr("android:host").map(|s| s.to_string()),
-                                path: intent_child_borrowed.get_attr("android:path").map(|s| s.to_string()),
-                                path_prefix: intent_child_borrowed.get_attr("android:pathPrefix").map(|s| s.to_string()),
-                                path_pattern: intent_child_borrowed.get_attr("android:pathPattern").map(|s| s.to_string()),
-                            };
-                            data_list.push(data);
-                        }
-                        _ => {}
-                    }
-                }
+        for child in component_borrowed.children() {
+            if child.borrow().element_type() != "intent-filter" {
+                continue;
+            }
+            let mut actions = Vec::new();
+            let mut categories = Vec::new();
+            let mut data_list = Vec::new();
 
-                // Only add if we have actual data
-                if !actions.is_empty() || !data_list.is_empty() {
-                    intent_filters.push(ActivityIntentFilter {
-                        activity: activity_name.clone(),
-                        actions,
-                        categories,
-                        data: data_list,
-                    });
+            // Parse intent-filter children
+            for intent_child in child.borrow().children() {
+                let element_type = intent_child.borrow().element_type().to_string();
+                match element_type.as_str() {
+                    "action" => actions.extend(attr(intent_child, "name")),
+                    "category" => categories.extend(attr(intent_child, "name")),
+                    "data" => data_list.push(IntentFilterData {
+                        scheme: attr(intent_child, "scheme"),
+                        host: attr(intent_child, "host"),
+                        path: attr(intent_child, "path"),
+                        path_prefix: attr(intent_child, "pathPrefix"),
+                        path_pattern: attr(intent_child, "pathPattern"),
+                    }),
+                    _ => {}
                 }
             }
+
+            // Only add if we have actual data
+            if !actions.is_empty() || !data_list.is_empty() {
+                intent_filters.push(ActivityIntentFilter {
+                    activity: component_name.clone(),
+                    component_type: component_type.to_string(),
+                    actions,
+                    categories,
+                    data: data_list,
+                });
+            }
         }
     }
 
     intent_filters
 }
 
+/// Expand a private process name (":remote" -> "com.example:remote")
+fn resolve_process_name(package_name: &str, process: &str) -> String {
+    if process.starts_with(':') {
+        format!("{}{}", package_name, process)
+    } else {
+        process.to_string()
+    }
+}
+
 /// Normalize component name (handle relative names like ".MainActivity")
 fn normalize_component_name(package_name: &str, component_name: &str) -> String {
     if component_name.starts_with('.') {
@@ -327,6 +835,528 @@ fn normalize_component_name(package_name: &str, component_name: &str) -> String
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::apk::binary_xml::{XmlElement, XmlValue};
+    use crate::apk::test_support::{element, encode_binary_xml, s};
+
+    /// Attribute lookup over literal `(name, value)` pairs
+    fn attrs(pairs: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| pairs.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+    }
+
+    #[test]
+    fn test_application_flags() {
+        // Debuggable build that explicitly disables backup
+        let mut manifest = RustManifestInfo::new("com.example.app".to_string());
+        apply_application_flags(&mut manifest, attrs(&[("debuggable", "true"), ("allowBackup", "false")]));
+        assert_eq!(manifest.debuggable, Some(true));
+        assert_eq!(manifest.allow_backup, Some(false));
+        assert_eq!(manifest.security_flags(), vec!["debuggable"]);
+        assert_eq!(manifest.uses_cleartext_traffic, None);
+
+        // Debuggable build that opts out of cleartext through a config
+        let mut manifest = RustManifestInfo::new("com.example.app".to_string());
+        apply_application_flags(
+            &mut manifest,
+            attrs(&[("debuggable", "true"), ("usesCleartextTraffic", "false"), ("networkSecurityConfig", "@xml/network_security_config")]),
+        );
+        assert_eq!(manifest.debuggable, Some(true));
+        assert_eq!(manifest.uses_cleartext_traffic, Some(false));
+        assert_eq!(manifest.network_security_config.as_deref(), Some("@xml/network_security_config"));
+        assert_eq!(manifest.security_flags(), vec!["allowBackup", "debuggable"]);
+
+        let mut manifest = RustManifestInfo::new("com.example.app".to_string());
+        apply_application_flags(&mut manifest, attrs(&[("allowBackup", "false"), ("usesCleartextTraffic", "true")]));
+        assert_eq!(manifest.security_flags(), vec!["usesCleartextTraffic"]);
+
+        // Defaults: backup allowed, not debuggable
+        let mut manifest = RustManifestInfo::new("com.example.app".to_string());
+        apply_application_flags(&mut manifest, attrs(&[]));
+        assert_eq!(manifest.allow_backup, Some(true));
+        assert_eq!(manifest.debuggable, Some(false));
+        assert_eq!(manifest.security_flags(), vec!["allowBackup"]);
+
+        // Raw boolean data as decoded by some AXML readers
+        assert_eq!(parse_bool_attr("-1"), Some(true));
+        assert_eq!(parse_bool_attr("0xffffffff"), Some(true));
+        assert_eq!(parse_bool_attr("0"), Some(false));
+        assert_eq!(parse_bool_attr("@0x7f010000x"), None);
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = encode_binary_xml(
+            &element("manifest")
+                .with_attr("package", s("com.example.app"))
+                .with_attr("versionCode", XmlValue::Int(42))
+                .with_attr("versionName", s("1.4.2"))
+                .with_child(
+                    element("uses-sdk")
+                        .with_attr("minSdkVersion", XmlValue::Int(24))
+                        .with_attr("targetSdkVersion", XmlValue::Int(34)),
+                )
+                .with_child(element("uses-permission").with_attr("name", s("android.permission.INTERNET")))
+                .with_child(
+                    element("application")
+                        .with_attr("label", XmlValue::Reference(0x7f0b0001))
+                        .with_attr("debuggable", XmlValue::Bool(true))
+                        .with_child(
+                            element("activity").with_attr("name", s(".MainActivity")).with_child(
+                                element("intent-filter")
+                                    .with_child(element("action").with_attr("name", s(ACTION_MAIN)))
+                                    .with_child(element("category").with_attr("name", s(CATEGORY_LAUNCHER))),
+                            ),
+                        )
+                        .with_child(
+                            element("service")
+                                .with_attr("name", s("com.example.app.SyncService"))
+                                .with_attr("process", s(":sync"))
+                                .with_attr("exported", XmlValue::Bool(false)),
+                        ),
+                ),
+        );
+
+        let manifest = parse_manifest(&manifest).unwrap();
+        assert_eq!(manifest.package_name, "com.example.app");
+        assert_eq!(manifest.version_code.as_deref(), Some("42"));
+        assert_eq!(manifest.version_name.as_deref(), Some("1.4.2"));
+        assert_eq!(manifest.min_sdk_version.as_deref(), Some("24"));
+        assert_eq!(manifest.target_sdk_version.as_deref(), Some("34"));
+        assert_eq!(manifest.permissions, vec!["android.permission.INTERNET"]);
+        assert_eq!(manifest.application_label.as_deref(), Some("0x7f0b0001"));
+        assert_eq!(manifest.debuggable, Some(true));
+        assert_eq!(manifest.activities, vec!["com.example.app.MainActivity"]);
+        assert_eq!(manifest.services, vec!["com.example.app.SyncService"]);
+        assert_eq!(manifest.get_launcher_activities(), vec!["com.example.app.MainActivity"]);
+        assert_eq!(manifest.exported_components, vec!["com.example.app.MainActivity"]);
+        assert_eq!(manifest.component_processes["com.example.app.SyncService"], "com.example.app:sync");
+
+        assert!(parse_manifest(&encode_binary_xml(&element("manifest"))).is_err());
+    }
+
+    #[test]
+    fn test_parse_application_flags() {
+        let parse = |application: XmlElement| {
+            let manifest = element("manifest").with_attr("package", s("com.example.app")).with_child(application);
+            parse_manifest(&encode_binary_xml(&manifest)).unwrap()
+        };
+
+        // Typed booleans as aapt writes them (true is stored as -1)
+        let manifest = parse(
+            element("application")
+                .with_attr("debuggable", XmlValue::Bool(true))
+                .with_attr("allowBackup", XmlValue::Bool(false)),
+        );
+        assert_eq!(manifest.debuggable, Some(true));
+        assert_eq!(manifest.allow_backup, Some(false));
+        assert_eq!(manifest.security_flags(), vec!["debuggable"]);
+
+        // Any non-zero boolean data is true; string literals are accepted too
+        let manifest = parse(
+            element("application")
+                .with_attr("debuggable", XmlValue::Other(0x12, 1))
+                .with_attr("usesCleartextTraffic", s("true")),
+        );
+        assert_eq!(manifest.debuggable, Some(true));
+        assert_eq!(manifest.uses_cleartext_traffic, Some(true));
+        assert_eq!(manifest.security_flags(), vec!["allowBackup", "debuggable", "usesCleartextTraffic"]);
+
+        // Defaults when the attributes are absent
+        let manifest = parse(element("application"));
+        assert_eq!(manifest.allow_backup, Some(true));
+        assert_eq!(manifest.debuggable, Some(false));
+        assert_eq!(manifest.uses_cleartext_traffic, None);
+    }
+
+    #[test]
+    fn test_launcher_activities() {
+        let filter = |activity: &str, action: &str, category: &str| ActivityIntentFilter {
+            activity: activity.to_string(),
+            component_type: "activity".to_string(),
+            actions: vec![action.to_string()],
+            categories: vec![category.to_string()],
+            data: Vec::new(),
+        };
+
+        let mut manifest = RustManifestInfo::new("com.example.app".to_string());
+        manifest.activities = vec!["com.example.app.SettingsActivity".to_string(), "com.example.app.MainActivity".to_string()];
+        manifest.intent_filters = vec![
+            filter("com.example.app.SettingsActivity", "android.intent.action.MAIN", "android.intent.category.DEFAULT"),
+            filter("com.example.app.MainActivity", "android.intent.action.MAIN", "android.intent.category.LAUNCHER"),
+        ];
+
+        assert_eq!(manifest.get_launcher_activities(), vec!["com.example.app.MainActivity"]);
+    }
+
+    #[test]
+    fn test_launcher_activity_alias() {
+        let launcher_filter = || {
+            element("intent-filter")
+                .with_child(element("action").with_attr("name", s(ACTION_MAIN)))
+                .with_child(element("category").with_attr("name", s(CATEGORY_LAUNCHER)))
+        };
+        let manifest = encode_binary_xml(
+            &element("manifest").with_attr("package", s("com.example.app")).with_child(
+                element("application")
+                    .with_child(element("activity").with_attr("name", s(".HomeActivity")))
+                    .with_child(
+                        element("activity-alias")
+                            .with_attr("name", s(".Launcher"))
+                            .with_attr("targetActivity", s(".HomeActivity"))
+                            .with_child(launcher_filter()),
+                    ),
+            ),
+        );
+
+        let manifest = parse_manifest(&manifest).unwrap();
+        assert_eq!(manifest.activities, vec!["com.example.app.HomeActivity"]);
+        assert_eq!(manifest.activity_aliases["com.example.app.Launcher"], "com.example.app.HomeActivity");
+        assert_eq!(manifest.get_launcher_activities(), vec!["com.example.app.HomeActivity"]);
+    }
+
+    #[test]
+    fn test_receiver_view_filter_is_not_a_deeplink() {
+        let view_filter = || {
+            element("intent-filter")
+                .with_child(element("action").with_attr("name", s("android.intent.action.VIEW")))
+                .with_child(element("category").with_attr("name", s("android.intent.category.BROWSABLE")))
+                .with_child(element("data").with_attr("scheme", s("example")))
+        };
+        let manifest = encode_binary_xml(
+            &element("manifest").with_attr("package", s("com.example.app")).with_child(
+                element("application")
+                    .with_child(element("activity").with_attr("name", s(".LinkActivity")).with_child(view_filter()))
+                    .with_child(element("receiver").with_attr("name", s(".LinkReceiver")).with_child(view_filter())),
+            ),
+        );
+
+        let manifest = parse_manifest(&manifest).unwrap();
+        let mut types: Vec<(&str, &str)> =
+            manifest.intent_filters.iter().map(|f| (f.activity.as_str(), f.component_type.as_str())).collect();
+        types.sort();
+        assert_eq!(
+            types,
+            vec![("com.example.app.LinkActivity", "activity"), ("com.example.app.LinkReceiver", "receiver")]
+        );
+
+        let deeplinks: Vec<String> = manifest.get_deeplinks().into_iter().map(|f| f.activity).collect();
+        assert_eq!(deeplinks, vec!["com.example.app.LinkActivity"]);
+    }
+
+    #[test]
+    fn test_components_by_process() {
+        let package = "com.example.app";
+        let parse = |application: XmlElement| {
+            let manifest = element("manifest").with_attr("package", s(package)).with_child(application);
+            parse_manifest(&encode_binary_xml(&manifest)).unwrap()
+        };
+
+        let manifest = parse(
+            element("application")
+                .with_child(element("activity").with_attr("name", s(".MainActivity")))
+                .with_child(element("service").with_attr("name", s(".SyncService")))
+                .with_child(element("service").with_attr("name", s(".RemoteService")).with_attr("process", s(":remote"))),
+        );
+        let groups = manifest.components_by_process();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[package], vec!["com.example.app.MainActivity", "com.example.app.SyncService"]);
+        assert_eq!(groups["com.example.app:remote"], vec!["com.example.app.RemoteService"]);
+
+        // An <application> process is inherited unless the component overrides it
+        let manifest = parse(
+            element("application")
+                .with_attr("process", s("com.other.shared"))
+                .with_child(element("activity").with_attr("name", s(".MainActivity")))
+                .with_child(element("receiver").with_attr("name", s(".BootReceiver")).with_attr("process", s(":boot"))),
+        );
+        assert_eq!(manifest.component_processes["com.example.app.MainActivity"], "com.other.shared");
+        assert_eq!(manifest.component_processes["com.example.app.BootReceiver"], "com.example.app:boot");
+        assert!(!manifest.components_by_process().contains_key(package));
+    }
+
+    #[test]
+    fn test_to_dict_includes_intent_filters() {
+        use pyo3::types::{PyDict, PyList};
+
+        let mut manifest = RustManifestInfo::new("com.example.app".to_string());
+        manifest.activities = vec!["com.example.app.LinkActivity".to_string()];
+        manifest.intent_filters = vec![ActivityIntentFilter {
+            activity: "com.example.app.LinkActivity".to_string(),
+            component_type: "activity".to_string(),
+            actions: vec!["android.intent.action.VIEW".to_string()],
+            categories: vec!["android.intent.category.BROWSABLE".to_string()],
+            data: vec![IntentFilterData {
+                scheme: Some("example".to_string()),
+                host: Some("open".to_string()),
+                path: None,
+                path_prefix: None,
+                path_pattern: None,
+            }],
+        }];
+        manifest.debuggable = Some(true);
+
+        Python::initialize();
+        Python::attach(|py| {
+            let dict = manifest.to_dict(py).unwrap();
+            let dict = dict.bind(py).cast::<PyDict>().unwrap();
+
+            let filters = dict.get_item("intent_filters").unwrap().unwrap();
+            let filters = filters.cast::<PyList>().unwrap();
+            assert_eq!(filters.len(), 1);
+            let filter = filters.get_item(0).unwrap();
+            let filter = filter.cast::<PyDict>().unwrap();
+            assert_eq!(filter.get_item("activity").unwrap().unwrap().extract::<String>().unwrap(), "com.example.app.LinkActivity");
+            assert!(filter.get_item("is_deeplink").unwrap().unwrap().extract::<bool>().unwrap());
+            let data = filter.get_item("data").unwrap().unwrap();
+            let scheme = data.get_item(0).unwrap().get_item("scheme").unwrap();
+            assert_eq!(scheme.extract::<String>().unwrap(), "example");
+
+            let deeplinks: Vec<String> = dict.get_item("deeplink_activities").unwrap().unwrap().extract().unwrap();
+            assert_eq!(deeplinks, vec!["com.example.app.LinkActivity"]);
+            let flags: Vec<String> = dict.get_item("security_flags").unwrap().unwrap().extract().unwrap();
+            assert_eq!(flags, vec!["debuggable"]);
+        });
+    }
+
+    #[test]
+    fn test_unprotected_exported_components() {
+        let exported = |tag: &str, name: &str| element(tag).with_attr("name", s(name)).with_attr("exported", XmlValue::Bool(true));
+        let alias = |name: &str, target: &str| {
+            element("activity-alias")
+                .with_attr("name", s(name))
+                .with_attr("targetActivity", s(target))
+                .with_attr("exported", XmlValue::Bool(true))
+        };
+        let parse = |application: XmlElement| {
+            let manifest = element("manifest").with_attr("package", s("com.example.app")).with_child(application);
+            parse_manifest(&encode_binary_xml(&manifest)).unwrap()
+        };
+        let components = || {
+            element("application")
+                .with_child(exported("activity", ".ShareActivity"))
+                .with_child(exported("activity", ".AdminActivity").with_attr("permission", s("com.example.app.permission.ADMIN")))
+                .with_child(element("activity").with_attr("name", s(".InternalActivity")))
+                .with_child(
+                    exported("provider", ".DataProvider")
+                        .with_attr("readPermission", s("com.example.app.READ"))
+                        .with_attr("writePermission", s("com.example.app.WRITE")),
+                )
+                .with_child(exported("provider", ".FeedProvider").with_attr("readPermission", s("com.example.app.READ")))
+                .with_child(alias(".ShareAlias", ".ShareActivity"))
+                .with_child(alias(".AdminAlias", ".AdminActivity"))
+        };
+
+        let manifest = parse(components());
+        assert_eq!(
+            manifest.unprotected_exported_components(),
+            vec!["com.example.app.ShareActivity", "com.example.app.FeedProvider", "com.example.app.ShareAlias"]
+        );
+        assert_eq!(manifest.component_permissions["com.example.app.AdminAlias"], "com.example.app.permission.ADMIN");
+
+        // An <application> permission guards every component that sets none
+        let manifest = parse(components().with_attr("permission", s("com.example.app.permission.APP")));
+        assert_eq!(manifest.application_permission.as_deref(), Some("com.example.app.permission.APP"));
+        assert!(manifest.unprotected_exported_components().is_empty());
+        assert_eq!(manifest.component_permissions["com.example.app.ShareAlias"], "com.example.app.permission.APP");
+        assert_eq!(manifest.component_permissions["com.example.app.AdminActivity"], "com.example.app.permission.ADMIN");
+        let feed = manifest.providers_detail.iter().find(|p| p.name == "com.example.app.FeedProvider").unwrap();
+        assert_eq!(feed.permission.as_deref(), Some("com.example.app.permission.APP"));
+        assert!(!feed.is_world_readable() && !feed.is_world_writable());
+    }
+
+    #[test]
+    fn test_component_permissions() {
+        let manifest = encode_binary_xml(
+            &element("manifest").with_attr("package", s("com.example.app")).with_child(
+                element("application")
+                    .with_child(
+                        element("service")
+                            .with_attr("name", s(".SyncService"))
+                            .with_attr("permission", s("android.permission.BIND_JOB_SERVICE")),
+                    )
+                    .with_child(element("service").with_attr("name", s(".PublicService")))
+                    .with_child(
+                        element("provider")
+                            .with_attr("name", s(".DataProvider"))
+                            .with_attr("readPermission", s("com.example.app.READ")),
+                    ),
+            ),
+        );
+
+        let manifest = parse_manifest(&manifest).unwrap();
+        assert_eq!(manifest.component_permissions.len(), 1);
+        assert_eq!(manifest.component_permissions["com.example.app.SyncService"], "android.permission.BIND_JOB_SERVICE");
+        assert!(!manifest.component_permissions.contains_key("com.example.app.PublicService"));
+        // A read permission alone does not guard the provider as a whole
+        assert!(!manifest.component_permissions.contains_key("com.example.app.DataProvider"));
+    }
+
+    #[test]
+    fn test_declared_permission_levels() {
+        let declared = |name: &str, level: Option<&str>| DeclaredPermission {
+            name: name.to_string(),
+            protection_level: protection_level_name(level),
+        };
+
+        // Compiled manifests carry the level as an integer
+        let admin = declared("com.example.app.permission.ADMIN", Some("0x2"));
+        let sync = declared("com.example.app.permission.SYNC", Some("0"));
+        assert_eq!(admin.protection_level, "signature");
+        assert!(admin.is_signature());
+        assert_eq!(sync.protection_level, "normal");
+        assert!(!sync.is_signature());
+
+        assert_eq!(protection_level_name(None), "normal");
+        assert_eq!(protection_level_name(Some("18")), "signature|privileged");
+        assert_eq!(protection_level_name(Some("signature|privileged")), "signature|privileged");
+        assert!(declared("p", Some("3")).is_signature());
+        assert_eq!(protection_level_name(Some("1")), "dangerous");
+    }
+
+    #[test]
+    fn test_declared_permissions_from_manifest() {
+        use pyo3::types::{PyDict, PyList};
+
+        let manifest = encode_binary_xml(
+            &element("manifest")
+                .with_attr("package", s("com.example.app"))
+                .with_child(
+                    element("permission")
+                        .with_attr("name", s("com.example.app.permission.ADMIN"))
+                        .with_attr("protectionLevel", XmlValue::Int(2)),
+                )
+                .with_child(element("permission").with_attr("name", s("com.example.app.permission.SYNC")))
+                .with_child(element("permission").with_attr("protectionLevel", XmlValue::Int(1)))
+                .with_child(element("uses-permission").with_attr("name", s("android.permission.INTERNET"))),
+        );
+        let mut manifest = parse_manifest(&manifest).unwrap();
+        // rusty-axml visits sibling elements last to first
+        manifest.declared_permissions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Nameless declarations are skipped; uses-permission is not a declaration
+        assert_eq!(
+            manifest.declared_permissions,
+            vec![
+                DeclaredPermission { name: "com.example.app.permission.ADMIN".to_string(), protection_level: "signature".to_string() },
+                DeclaredPermission { name: "com.example.app.permission.SYNC".to_string(), protection_level: "normal".to_string() },
+            ]
+        );
+
+        Python::initialize();
+        Python::attach(|py| {
+            let dict = manifest.to_dict(py).unwrap();
+            let dict = dict.bind(py).cast::<PyDict>().unwrap();
+            let declared = dict.get_item("declared_permissions").unwrap().unwrap();
+            let declared = declared.cast::<PyList>().unwrap();
+            assert_eq!(declared.len(), 2);
+            let admin = declared.get_item(0).unwrap();
+            assert_eq!(admin.get_item("name").unwrap().extract::<String>().unwrap(), "com.example.app.permission.ADMIN");
+            assert_eq!(admin.get_item("protection_level").unwrap().extract::<String>().unwrap(), "signature");
+        });
+    }
+
+    #[test]
+    fn test_resolve_label_references() {
+        use crate::apk::resources::{ResolvedResource, ResourceData};
+
+        let resource = |id: u32, name: &str, value: ResourceData| ResolvedResource {
+            id,
+            type_name: "string".to_string(),
+            name: name.to_string(),
+            value,
+        };
+        let resolver = ResourceResolver::from_resources(vec![
+            resource(0x7f0b0001, "app_name", ResourceData::String("Playfast Demo".to_string())),
+            // @string/launcher_name -> @string/app_name
+            resource(0x7f0b0002, "launcher_name", ResourceData::Reference(0x7f0b0001)),
+            resource(0x7f0b0003, "loop", ResourceData::Reference(0x7f0b0003)),
+        ]);
+
+        let activity = |name: &str, label: XmlValue| element("activity").with_attr("name", s(name)).with_attr("label", label);
+        let manifest = encode_binary_xml(
+            &element("manifest").with_attr("package", s("com.example.app")).with_child(
+                element("application")
+                    .with_attr("label", XmlValue::Reference(0x7f0b0001))
+                    .with_child(activity(".MainActivity", XmlValue::Reference(0x7f0b0002)))
+                    .with_child(activity(".LoopActivity", XmlValue::Reference(0x7f0b0003)))
+                    .with_child(activity(".PlainActivity", s("Settings"))),
+            ),
+        );
+
+        let resolved = parse_manifest_with_resources(&manifest, Some(&resolver)).unwrap();
+        assert_eq!(resolved.application_label.as_deref(), Some("Playfast Demo"));
+        assert_eq!(resolved.component_labels["com.example.app.MainActivity"], "Playfast Demo");
+        assert_eq!(resolved.component_labels["com.example.app.LoopActivity"], "0x7f0b0003");
+        assert_eq!(resolved.component_labels["com.example.app.PlainActivity"], "Settings");
+
+        let raw = parse_manifest(&manifest).unwrap();
+        assert_eq!(raw.application_label.as_deref(), Some("0x7f0b0001"));
+        assert_eq!(raw.component_labels["com.example.app.MainActivity"], "0x7f0b0002");
+    }
+
+    #[test]
+    fn test_provider_details() {
+        let files = provider_info(
+            "com.example.app.FileProvider".to_string(),
+            true,
+            attrs(&[
+                ("authorities", "com.example.app.files; com.example.app.share"),
+                ("grantUriPermissions", "true"),
+                ("writePermission", "com.example.app.WRITE"),
+            ]),
+        );
+        assert_eq!(files.authorities, vec!["com.example.app.files", "com.example.app.share"]);
+        assert!(files.grant_uri_permissions);
+        assert!(files.is_world_readable());
+        assert!(!files.is_world_writable());
+
+        assert_eq!(files.write_permission.as_deref(), Some("com.example.app.WRITE"));
+        assert_eq!(files.read_permission, None);
+
+        let settings = provider_info(
+            "com.example.app.SettingsProvider".to_string(),
+            false,
+            attrs(&[("authorities", "com.example.app.settings")]),
+        );
+        assert!(!settings.grant_uri_permissions);
+        assert!(!settings.is_world_readable());
+    }
+
+    #[test]
+    fn test_exported_defaults() {
+        assert!(is_exported("activity", None, true, Some(30)));
+        assert!(is_exported("activity", Some(true), false, Some(30)));
+        assert!(!is_exported("activity", None, false, Some(30)));
+        assert!(!is_exported("activity", Some(false), true, Some(30)));
+        assert!(is_exported("service", Some(true), false, Some(34)));
+        assert!(is_exported("provider", None, false, Some(16)));
+        assert!(!is_exported("provider", None, false, Some(17)));
+        assert!(is_exported("provider", None, false, None));
+        assert!(!is_exported("provider", Some(false), false, None));
+    }
+
+    #[test]
+    fn test_provider_exported_without_target_sdk() {
+        let parse = |uses_sdk: Option<XmlElement>| {
+            let mut manifest = element("manifest").with_attr("package", s("com.example.app"));
+            if let Some(uses_sdk) = uses_sdk {
+                manifest = manifest.with_child(uses_sdk);
+            }
+            let manifest = manifest.with_child(
+                element("application")
+                    .with_child(element("provider").with_attr("name", s(".DataProvider")))
+                    .with_child(element("activity").with_attr("name", s(".MainActivity"))),
+            );
+            parse_manifest(&encode_binary_xml(&manifest)).unwrap().exported_components
+        };
+
+        assert_eq!(parse(None), vec!["com.example.app.DataProvider"]);
+        // targetSdkVersion falls back to minSdkVersion
+        assert!(parse(Some(element("uses-sdk").with_attr("minSdkVersion", XmlValue::Int(21)))).is_empty());
+        assert_eq!(
+            parse(Some(element("uses-sdk").with_attr("minSdkVersion", XmlValue::Int(9)))),
+            vec!["com.example.app.DataProvider"]
+        );
+    }
 
     #[test]
     fn test_normalize_component_name() {
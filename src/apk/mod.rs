@@ -1,9 +1,20 @@
+pub mod binary_xml;
 pub mod error;
 pub mod extractor;
+pub mod health;
 pub mod manifest;
+pub mod network_security;
 pub mod resources;
+pub mod signing;
+pub mod size;
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use error::ApkError;
 pub use extractor::{ApkExtractor, DexEntry};
-pub use manifest::{RustManifestInfo, parse_manifest, IntentFilterData, ActivityIntentFilter};
+pub use size::apk_size_breakdown;
+pub use health::{ApkHealth, validate_apk};
+pub use signing::{CertInfo, extract_signing_info};
+pub use network_security::{DomainConfig, NetworkSecurityReport, network_security_report};
+pub use manifest::{RustManifestInfo, parse_manifest, parse_manifest_with_resources, IntentFilterData, ActivityIntentFilter, DeclaredPermission, ProviderInfo};
 pub use resources::{PyResourceResolver, PyResolvedResource, parse_resources_from_apk};
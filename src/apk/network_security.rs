@@ -0,0 +1,338 @@
+//! Cleartext traffic and network security config analysis
+//!
+//! Reads `android:usesCleartextTraffic` and `android:networkSecurityConfig`
+//! from the manifest's `<application>` element and, when a config is
+//! referenced, parses the compiled `res/xml/...` file to report which domains
+//! allow cleartext HTTP and which are certificate-pinned.
+
+use crate::apk::binary_xml::{parse_binary_xml, XmlElement, XmlValue};
+use crate::apk::error::Result;
+use crate::apk::resources::{ResourceData, ResourceResolver};
+use crate::apk::ApkExtractor;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Path used when the config reference cannot be resolved via resources.arsc
+const DEFAULT_CONFIG_PATH: &str = "res/xml/network_security_config.xml";
+
+/// A `<domain-config>` block with inherited settings applied
+#[pyclass]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainConfig {
+    #[pyo3(get)]
+    pub domains: Vec<String>,
+    #[pyo3(get)]
+    pub include_subdomains: bool,
+    /// Effective cleartextTrafficPermitted (None if never set)
+    #[pyo3(get)]
+    pub cleartext_permitted: Option<bool>,
+    /// Pin digests as "SHA-256/<base64>"
+    #[pyo3(get)]
+    pub pins: Vec<String>,
+    #[pyo3(get)]
+    pub pin_expiration: Option<String>,
+}
+
+#[pymethods]
+impl DomainConfig {
+    fn __repr__(&self) -> String {
+        format!(
+            "DomainConfig(domains={:?}, cleartext_permitted={:?}, pins={})",
+            self.domains,
+            self.cleartext_permitted,
+            self.pins.len()
+        )
+    }
+}
+
+/// Network security findings for an APK
+#[pyclass]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkSecurityReport {
+    /// `android:usesCleartextTraffic` on `<application>`, if declared
+    #[pyo3(get)]
+    pub uses_cleartext_traffic: Option<bool>,
+    /// APK path of the network security config file, if one is referenced
+    #[pyo3(get)]
+    pub config_path: Option<String>,
+    /// The manifest references a config but `config_path` is not in the APK
+    #[pyo3(get)]
+    pub config_missing: bool,
+    /// cleartextTrafficPermitted on `<base-config>`
+    #[pyo3(get)]
+    pub base_cleartext_permitted: Option<bool>,
+    /// `<base-config>` trusts user-installed CA certificates
+    #[pyo3(get)]
+    pub trusts_user_certificates: bool,
+    #[pyo3(get)]
+    pub domain_configs: Vec<DomainConfig>,
+}
+
+#[pymethods]
+impl NetworkSecurityReport {
+    /// Domains whose effective config permits cleartext traffic
+    pub fn cleartext_domains(&self) -> Vec<String> {
+        self.domain_configs
+            .iter()
+            .filter(|c| c.cleartext_permitted == Some(true))
+            .flat_map(|c| c.domains.iter().cloned())
+            .collect()
+    }
+
+    /// Domains with a certificate pin-set
+    pub fn pinned_domains(&self) -> Vec<String> {
+        self.domain_configs
+            .iter()
+            .filter(|c| !c.pins.is_empty())
+            .flat_map(|c| c.domains.iter().cloned())
+            .collect()
+    }
+
+    /// Whether cleartext HTTP is explicitly allowed anywhere
+    ///
+    /// The config file takes precedence over the manifest attribute, but any
+    /// domain-level permission also counts.
+    pub fn allows_cleartext(&self) -> bool {
+        let base = self.base_cleartext_permitted.or(self.uses_cleartext_traffic);
+        base == Some(true) || !self.cleartext_domains().is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "NetworkSecurityReport(uses_cleartext_traffic={:?}, config_path={:?}, config_missing={}, domain_configs={})",
+            self.uses_cleartext_traffic,
+            self.config_path,
+            self.config_missing,
+            self.domain_configs.len()
+        )
+    }
+}
+
+/// Parse a compiled `<network-security-config>` document into `report`
+pub fn apply_network_security_config(config: &XmlElement, report: &mut NetworkSecurityReport) {
+    if let Some(base) = config.children_named("base-config").next() {
+        report.base_cleartext_permitted = base.attr_bool("cleartextTrafficPermitted");
+        report.trusts_user_certificates = trusts_user_certificates(base);
+    }
+
+    for domain_config in config.children_named("domain-config") {
+        collect_domain_configs(domain_config, report.base_cleartext_permitted, &mut report.domain_configs);
+    }
+}
+
+/// Flatten nested `<domain-config>` blocks, inheriting cleartext settings
+fn collect_domain_configs(element: &XmlElement, inherited_cleartext: Option<bool>, out: &mut Vec<DomainConfig>) {
+    let cleartext_permitted = element.attr_bool("cleartextTrafficPermitted").or(inherited_cleartext);
+
+    let domains: Vec<&XmlElement> = element.children_named("domain").collect();
+    let pin_set = element.children_named("pin-set").next();
+
+    out.push(DomainConfig {
+        domains: domains.iter().map(|d| d.text.clone()).collect(),
+        include_subdomains: domains.iter().any(|d| d.attr_bool("includeSubdomains") == Some(true)),
+        cleartext_permitted,
+        pins: pin_set
+            .map(|set| {
+                set.children_named("pin")
+                    .map(|pin| {
+                        let digest = pin.attr_string("digest").unwrap_or_else(|| "SHA-256".to_string());
+                        format!("{}/{}", digest, pin.text)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        pin_expiration: pin_set.and_then(|set| set.attr_string("expiration")),
+    });
+
+    for nested in element.children_named("domain-config") {
+        collect_domain_configs(nested, cleartext_permitted, out);
+    }
+}
+
+fn trusts_user_certificates(config: &XmlElement) -> bool {
+    config
+        .descendants_named("certificates")
+        .iter()
+        .any(|c| c.attr_string("src").as_deref() == Some("user"))
+}
+
+/// Build the network security report for an APK
+pub fn network_security_report_for(extractor: &ApkExtractor) -> Result<NetworkSecurityReport> {
+    let manifest = parse_binary_xml(&extractor.extract_manifest()?)?;
+    let mut report = NetworkSecurityReport::default();
+
+    let Some(application) = manifest.children_named("application").next() else {
+        return Ok(report);
+    };
+
+    report.uses_cleartext_traffic = application.attr_bool("usesCleartextTraffic");
+
+    let config_path = match application.attr("networkSecurityConfig") {
+        Some(XmlValue::Reference(id)) => {
+            let resolver = extractor
+                .extract_resources()
+                .ok()
+                .and_then(|bytes| ResourceResolver::from_bytes(bytes).ok());
+            Some(resolve_xml_path(resolver.as_ref(), *id))
+        }
+        Some(XmlValue::String(path)) if path.starts_with("res/") => Some(path.clone()),
+        Some(_) => Some(DEFAULT_CONFIG_PATH.to_string()),
+        None => None,
+    };
+
+    if let Some(path) = config_path {
+        if extractor.list_entries().iter().any(|entry| entry.name == path) {
+            let data = extractor.extract_file(&path)?;
+            apply_network_security_config(&parse_binary_xml(&data)?, &mut report);
+        } else {
+            report.config_missing = true;
+        }
+        report.config_path = Some(path);
+    }
+
+    Ok(report)
+}
+
+/// Map an `@xml/...` resource id to its file path inside the APK
+///
+/// Falls back to the conventional path when resources.arsc is unavailable
+/// or does not define the id.
+fn resolve_xml_path(resolver: Option<&ResourceResolver>, resource_id: u32) -> String {
+    resolver
+        .and_then(|resolver| resolver.resolve(resource_id))
+        .map(|res| match &res.value {
+            ResourceData::String(path) => path.clone(),
+            _ => format!("res/xml/{}.xml", res.name),
+        })
+        .unwrap_or_else(|| DEFAULT_CONFIG_PATH.to_string())
+}
+
+/// Report cleartext-traffic settings and the network security config
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     NetworkSecurityReport: Manifest flag plus parsed config (domains that
+///     permit cleartext, pinned domains, user-CA trust)
+///
+/// Raises:
+///     IOError: If the APK cannot be opened
+///     ValueError: If the manifest or config cannot be parsed
+#[pyfunction]
+pub fn network_security_report(apk_path: &str) -> PyResult<NetworkSecurityReport> {
+    let extractor = ApkExtractor::new(apk_path)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+
+    network_security_report_for(&extractor)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apk::test_support::{element, encode_binary_xml, s};
+    use crate::dex::test_support::{sample_dex, write_test_apk_with_entries};
+
+    fn manifest(application: XmlElement) -> Vec<u8> {
+        encode_binary_xml(&element("manifest").with_attr("package", s("com.example")).with_child(application))
+    }
+
+    #[test]
+    fn test_cleartext_and_config_reported() {
+        let manifest = manifest(
+            element("application")
+                .with_attr("usesCleartextTraffic", XmlValue::Bool(true))
+                .with_attr("networkSecurityConfig", XmlValue::Reference(0x7f110000)),
+        );
+        let config = encode_binary_xml(
+            &element("network-security-config")
+                .with_child(
+                    element("base-config")
+                        .with_attr("cleartextTrafficPermitted", XmlValue::Bool(false))
+                        .with_child(element("trust-anchors").with_child(element("certificates").with_attr("src", s("user")))),
+                )
+                .with_child(
+                    element("domain-config")
+                        .with_attr("cleartextTrafficPermitted", XmlValue::Bool(true))
+                        .with_child(element("domain").with_attr("includeSubdomains", XmlValue::Bool(true)).with_text("legacy.example.com"))
+                        .with_child(element("domain-config").with_child(element("domain").with_text("cdn.legacy.example.com"))),
+                )
+                .with_child(
+                    element("domain-config")
+                        .with_child(element("domain").with_text("api.example.com"))
+                        .with_child(
+                            element("pin-set")
+                                .with_attr("expiration", s("2030-01-01"))
+                                .with_child(element("pin").with_attr("digest", s("SHA-256")).with_text("7HIpactkIAq2Y49orFOOQKurWxmmSFZhBCoQYcRhJ3Y=")),
+                        ),
+                ),
+        );
+
+        let apk = write_test_apk_with_entries(
+            "network-security",
+            &[sample_dex()],
+            &[("AndroidManifest.xml", &manifest), ("res/xml/network_security_config.xml", &config)],
+        );
+        let report = network_security_report(apk.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        assert_eq!(report.uses_cleartext_traffic, Some(true));
+        assert_eq!(report.config_path.as_deref(), Some(DEFAULT_CONFIG_PATH));
+        assert_eq!(report.base_cleartext_permitted, Some(false));
+        assert!(report.trusts_user_certificates);
+        assert_eq!(report.cleartext_domains(), vec!["legacy.example.com", "cdn.legacy.example.com"]);
+        assert_eq!(report.pinned_domains(), vec!["api.example.com"]);
+        assert!(report.domain_configs[0].include_subdomains);
+        assert_eq!(report.domain_configs[2].pins, vec!["SHA-256/7HIpactkIAq2Y49orFOOQKurWxmmSFZhBCoQYcRhJ3Y="]);
+        assert_eq!(report.domain_configs[2].pin_expiration.as_deref(), Some("2030-01-01"));
+        assert!(report.allows_cleartext());
+    }
+
+    #[test]
+    fn test_manifest_flag_only() {
+        let manifest = manifest(element("application").with_attr("usesCleartextTraffic", XmlValue::Bool(true)));
+        let apk = write_test_apk_with_entries("cleartext-only", &[sample_dex()], &[("AndroidManifest.xml", &manifest)]);
+        let report = network_security_report(apk.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        assert_eq!(report.uses_cleartext_traffic, Some(true));
+        assert_eq!(report.config_path, None);
+        assert!(report.domain_configs.is_empty());
+        assert!(report.allows_cleartext());
+    }
+
+    #[test]
+    fn test_missing_config_reported() {
+        let manifest = manifest(element("application").with_attr("networkSecurityConfig", XmlValue::Reference(0x7f110000)));
+        let apk = write_test_apk_with_entries("missing-config", &[sample_dex()], &[("AndroidManifest.xml", &manifest)]);
+        let report = network_security_report(apk.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        assert_eq!(report.config_path.as_deref(), Some(DEFAULT_CONFIG_PATH));
+        assert!(report.config_missing);
+        assert!(report.domain_configs.is_empty());
+        assert!(!report.allows_cleartext());
+    }
+
+    #[test]
+    fn test_resolve_xml_path_through_resources() {
+        use crate::apk::resources::ResolvedResource;
+
+        let resource = |id: u32, name: &str, value: ResourceData| ResolvedResource {
+            id,
+            type_name: "xml".to_string(),
+            name: name.to_string(),
+            value,
+        };
+        let resolver = ResourceResolver::from_resources(vec![
+            resource(0x7f110000, "network_config", ResourceData::String("res/xml-v24/network_config.xml".to_string())),
+            resource(0x7f110001, "legacy_config", ResourceData::Integer(0)),
+        ]);
+
+        assert_eq!(resolve_xml_path(Some(&resolver), 0x7f110000), "res/xml-v24/network_config.xml");
+        assert_eq!(resolve_xml_path(Some(&resolver), 0x7f110001), "res/xml/legacy_config.xml");
+        assert_eq!(resolve_xml_path(Some(&resolver), 0x7f110002), DEFAULT_CONFIG_PATH);
+        assert_eq!(resolve_xml_path(None, 0x7f110000), DEFAULT_CONFIG_PATH);
+    }
+}
@@ -6,7 +6,7 @@
 use arsc::components::{Arsc, ResourceValue, Value};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
 
 /// Resolved resource data
@@ -16,9 +16,61 @@ pub enum ResourceData {
     Integer(i32),
     Boolean(bool),
     Reference(u32),
+    /// `<string-array>`, `<integer-array>` or `<array>` items, in order
+    Array(Vec<ResourceData>),
+    /// Styles, plurals and other bags: attribute or quantity key -> value
+    Bag {
+        parent: u32,
+        entries: Vec<(u32, ResourceData)>,
+    },
     Unknown,
 }
 
+/// Key of the first array item in a bag (`Res_MAKEARRAY(0)`)
+const ARRAY_KEY_BASE: u32 = 0x0200_0000;
+
+/// Quantity keys of `<plurals>` bags
+const PLURAL_KEYS: &[(u32, &str)] = &[
+    (0x0100_0004, "other"),
+    (0x0100_0005, "zero"),
+    (0x0100_0006, "one"),
+    (0x0100_0007, "two"),
+    (0x0100_0008, "few"),
+    (0x0100_0009, "many"),
+];
+
+impl ResourceData {
+    /// Render the value the way the Python bindings show it
+    fn display(&self) -> String {
+        match self {
+            ResourceData::String(s) => s.clone(),
+            ResourceData::Integer(i) => i.to_string(),
+            ResourceData::Boolean(b) => b.to_string(),
+            ResourceData::Reference(ref_id) => format!("0x{:08x}", ref_id),
+            ResourceData::Array(items) => {
+                format!("[{}]", items.iter().map(ResourceData::display).collect::<Vec<_>>().join(", "))
+            }
+            ResourceData::Bag { entries, .. } => format!(
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", bag_key_name(*key), value.display()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ResourceData::Unknown => "?".to_string(),
+        }
+    }
+}
+
+/// Name of a bag key: the quantity for plurals, else the attribute ID
+fn bag_key_name(key: u32) -> String {
+    PLURAL_KEYS
+        .iter()
+        .find(|(id, _)| *id == key)
+        .map_or_else(|| format!("0x{:08x}", key), |(_, name)| name.to_string())
+}
+
 /// A resolved resource with all metadata
 #[derive(Debug, Clone)]
 pub struct ResolvedResource {
@@ -42,6 +94,13 @@ pub struct PyResolvedResource {
     pub value_type: String,
     #[pyo3(get)]
     pub value: String,
+    /// Items of an array resource, rendered as strings
+    #[pyo3(get)]
+    pub values: Vec<String>,
+    /// Entries of a style or plurals resource, keyed by quantity ("one",
+    /// "other") or attribute ID ("0x01010098")
+    #[pyo3(get)]
+    pub entries: HashMap<String, String>,
 }
 
 #[pymethods]
@@ -62,27 +121,74 @@ impl PyResolvedResource {
     pub fn is_boolean(&self) -> bool {
         self.type_name == "bool" || self.value_type == "boolean"
     }
+
+    /// Check if this is an array resource
+    pub fn is_array(&self) -> bool {
+        self.value_type == "array"
+    }
 }
 
+/// Hops followed through reference-typed values before giving up
+pub const MAX_REFERENCE_DEPTH: usize = 8;
+
 /// Resource resolver
 pub struct ResourceResolver {
-    #[allow(dead_code)]
-    arsc: Arsc,
     /// Cache: resource_id -> ResolvedResource
     cache: HashMap<u32, ResolvedResource>,
+    /// Index: (type_name, name) -> resource_id
+    by_name: HashMap<(String, String), u32>,
 }
 
 impl ResourceResolver {
     /// Create ResourceResolver from raw ARSC bytes
+    ///
+    /// Entries defined in several configurations resolve to the default
+    /// configuration's value.
     pub fn from_bytes(arsc_bytes: Vec<u8>) -> Result<Self, String> {
+        Self::from_bytes_with_locale(arsc_bytes, None)
+    }
+
+    /// Create ResourceResolver from raw ARSC bytes, preferring values from
+    /// configurations for `locale` (e.g. "en", "pt-BR")
+    ///
+    /// Entries without a value for that locale fall back to the default
+    /// configuration, then to the first configuration that defines them.
+    pub fn from_bytes_with_locale(arsc_bytes: Vec<u8>, locale: Option<&str>) -> Result<Self, String> {
         let cursor = Cursor::new(arsc_bytes);
 
         let arsc = arsc::parse_from(cursor)
             .map_err(|e| format!("Failed to parse ARSC: {:?}", e))?;
 
-        let cache = Self::build_cache(&arsc)?;
+        let cache = Self::build_cache(&arsc, locale)?;
+
+        Ok(Self::with_cache(cache))
+    }
+
+    /// Create a resolver over already-resolved resources
+    #[cfg(test)]
+    pub(crate) fn from_resources(resources: Vec<ResolvedResource>) -> Self {
+        Self::with_cache(resources.into_iter().map(|r| (r.id, r)).collect())
+    }
+
+    /// Wrap a cache, indexing it by type and name
+    ///
+    /// The cache already holds one configuration's value per ID. When two
+    /// IDs share a type and name (e.g. an app resource shadowing a
+    /// framework one), the app package wins, then the lower ID.
+    fn with_cache(cache: HashMap<u32, ResolvedResource>) -> Self {
+        let rank = |id: u32| (id >> 24 == 0x7f, std::cmp::Reverse(id));
+        let mut by_name: HashMap<(String, String), u32> = HashMap::new();
+        for resource in cache.values() {
+            let key = (resource.type_name.clone(), resource.name.clone());
+            match by_name.get(&key) {
+                Some(&existing) if rank(existing) >= rank(resource.id) => {}
+                _ => {
+                    by_name.insert(key, resource.id);
+                }
+            }
+        }
 
-        Ok(Self { arsc, cache })
+        Self { cache, by_name }
     }
 
     /// Check if a value looks like a resource ID
@@ -110,6 +216,43 @@ impl ResourceResolver {
         self.cache.get(&resource_id)
     }
 
+    /// Look up a resource by type and name, e.g. ("string", "app_name")
+    pub fn get_by_name(&self, type_name: &str, name: &str) -> Option<&ResolvedResource> {
+        let id = self.by_name.get(&(type_name.to_string(), name.to_string()))?;
+        self.cache.get(id)
+    }
+
+    /// Resolve a resource ID, following `Reference` values to the concrete
+    /// value they end in
+    ///
+    /// The result keeps the requested resource's id and name. Returns None
+    /// for dangling references, cycles, and chains of more than
+    /// `max_depth` hops.
+    pub fn resolve_deref(&self, resource_id: u32, max_depth: usize) -> Option<ResolvedResource> {
+        let resource = self.resolve(resource_id)?;
+        let mut visited = HashSet::from([resource_id]);
+        let mut value = &resource.value;
+
+        while let ResourceData::Reference(next) = value {
+            if visited.len() > max_depth || !visited.insert(*next) {
+                return None;
+            }
+            value = &self.resolve(*next)?.value;
+        }
+
+        Some(ResolvedResource { value: value.clone(), ..resource.clone() })
+    }
+
+    /// Resolve a resource ID to a string, following references
+    ///
+    /// `@string/app_name` may itself point at another string resource.
+    pub fn resolve_string(&self, resource_id: u32) -> Option<String> {
+        match self.resolve_deref(resource_id, MAX_REFERENCE_DEPTH)?.value {
+            ResourceData::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
     /// Get all resources of a specific type
     pub fn get_by_type(&self, type_name: &str) -> Vec<&ResolvedResource> {
         self.cache
@@ -123,9 +266,10 @@ impl ResourceResolver {
         self.get_by_type("string")
     }
 
-    /// Build resource cache from ARSC
-    fn build_cache(arsc: &Arsc) -> Result<HashMap<u32, ResolvedResource>, String> {
-        let mut cache = HashMap::new();
+    /// Build resource cache from ARSC, keeping the best-ranked
+    /// configuration's value for each entry
+    fn build_cache(arsc: &Arsc, locale: Option<&str>) -> Result<HashMap<u32, ResolvedResource>, String> {
+        let mut cache: HashMap<u32, (u8, ResolvedResource)> = HashMap::new();
 
         for package in &arsc.packages {
             for resource_type in &package.types {
@@ -138,6 +282,7 @@ impl ResourceResolver {
                     .unwrap_or_else(|| format!("type_{}", resource_type.id));
 
                 for config in &resource_type.configs {
+                    let rank = config_rank(&config.id, locale);
                     for entry in &config.resources.resources {
                         // Build resource ID: 0xPPTTEEEE
                         let resource_id = Self::build_resource_id(
@@ -157,21 +302,28 @@ impl ResourceResolver {
                         // Extract value
                         let value = Self::extract_value(&entry.value, &arsc.global_string_pool);
 
+                        // Earlier configs win ties
+                        if cache.get(&resource_id).is_some_and(|(best, _)| *best >= rank) {
+                            continue;
+                        }
                         cache.insert(
                             resource_id,
-                            ResolvedResource {
-                                id: resource_id,
-                                type_name: type_name.clone(),
-                                name,
-                                value,
-                            },
+                            (
+                                rank,
+                                ResolvedResource {
+                                    id: resource_id,
+                                    type_name: type_name.clone(),
+                                    name,
+                                    value,
+                                },
+                            ),
                         );
                     }
                 }
             }
         }
 
-        Ok(cache)
+        Ok(cache.into_iter().map(|(id, (_, resource))| (id, resource)).collect())
     }
 
     /// Build resource ID from components
@@ -186,7 +338,28 @@ impl ResourceResolver {
     ) -> ResourceData {
         match resource_value {
             ResourceValue::Plain(value) => Self::extract_plain_value(value, string_pool),
-            ResourceValue::Bag { .. } => ResourceData::Unknown,
+            ResourceValue::Bag { parent, values } => Self::extract_bag(*parent, values, string_pool),
+        }
+    }
+
+    /// Extract value from a bag: an `Array` when every key is an array
+    /// index, otherwise a `Bag` of key -> value
+    fn extract_bag(parent: u32, values: &[(u32, Value)], string_pool: &arsc::components::StringPool) -> ResourceData {
+        let is_array = !values.is_empty() && values.iter().all(|(key, _)| key & 0xFFFF_0000 == ARRAY_KEY_BASE);
+        if is_array {
+            let mut items: Vec<_> = values.iter().collect();
+            items.sort_by_key(|(key, _)| *key);
+            return ResourceData::Array(
+                items.into_iter().map(|(_, value)| Self::extract_plain_value(value, string_pool)).collect(),
+            );
+        }
+
+        ResourceData::Bag {
+            parent,
+            entries: values
+                .iter()
+                .map(|(key, value)| (*key, Self::extract_plain_value(value, string_pool)))
+                .collect(),
         }
     }
 
@@ -225,6 +398,75 @@ impl ResourceResolver {
     }
 }
 
+/// Locale of a configuration, e.g. "en" or "en-US"
+///
+/// `config_id` is the raw `ResTable_config` after its size field: mcc and
+/// mnc (2 bytes each), then the language and country codes (2 bytes each).
+/// Packed three-letter codes are not decoded.
+fn config_locale(config_id: &[u8]) -> Option<String> {
+    let code = |bytes: &[u8]| {
+        bytes.iter().all(u8::is_ascii_alphabetic).then(|| String::from_utf8_lossy(bytes).into_owned())
+    };
+    let language = code(config_id.get(4..6)?)?;
+    match config_id.get(6..8).and_then(code) {
+        Some(country) => Some(format!("{}-{}", language, country)),
+        None => Some(language),
+    }
+}
+
+/// How well a configuration matches the requested locale
+///
+/// 3 = exact locale, 2 = same language, 1 = default configuration
+/// (no qualifiers), 0 = any other configuration.
+fn config_rank(config_id: &[u8], locale: Option<&str>) -> u8 {
+    if let (Some(wanted), Some(actual)) = (locale, config_locale(config_id)) {
+        // Accept resource-directory style qualifiers ("pt-rBR")
+        let wanted = wanted.replace("-r", "-");
+        if actual.eq_ignore_ascii_case(&wanted) {
+            return 3;
+        }
+        let language = |locale: &str| locale.split('-').next().unwrap_or_default().to_ascii_lowercase();
+        if language(&actual) == language(&wanted) {
+            return 2;
+        }
+    }
+    u8::from(config_id.iter().all(|b| *b == 0))
+}
+
+impl From<&ResolvedResource> for PyResolvedResource {
+    fn from(r: &ResolvedResource) -> Self {
+        let value_type = match &r.value {
+            ResourceData::String(_) => "string",
+            ResourceData::Integer(_) => "integer",
+            ResourceData::Boolean(_) => "boolean",
+            ResourceData::Reference(_) => "reference",
+            ResourceData::Array(_) => "array",
+            ResourceData::Bag { .. } => "bag",
+            ResourceData::Unknown => "unknown",
+        };
+        let values = match &r.value {
+            ResourceData::Array(items) => items.iter().map(ResourceData::display).collect(),
+            _ => Vec::new(),
+        };
+        let entries = match &r.value {
+            ResourceData::Bag { entries, .. } => {
+                entries.iter().map(|(key, value)| (bag_key_name(*key), value.display())).collect()
+            }
+            _ => HashMap::new(),
+        };
+
+        PyResolvedResource {
+            id: r.id,
+            type_name: r.type_name.clone(),
+            name: r.name.clone(),
+            value_type: value_type.to_string(),
+            value: r.value.display(),
+            values,
+            entries,
+        }
+    }
+}
+
 /// Python wrapper for ResourceResolver
 #[pyclass]
 pub struct PyResourceResolver {
@@ -235,23 +477,22 @@ pub struct PyResourceResolver {
 impl PyResourceResolver {
     /// Resolve a resource ID to its value
     pub fn resolve(&self, resource_id: u32) -> Option<PyResolvedResource> {
-        self.resolver.resolve(resource_id).map(|r| {
-            let (value_type, value) = match &r.value {
-                ResourceData::String(s) => ("string", s.clone()),
-                ResourceData::Integer(i) => ("integer", i.to_string()),
-                ResourceData::Boolean(b) => ("boolean", b.to_string()),
-                ResourceData::Reference(ref_id) => ("reference", format!("0x{:08x}", ref_id)),
-                ResourceData::Unknown => ("unknown", "?".to_string()),
-            };
-
-            PyResolvedResource {
-                id: r.id,
-                type_name: r.type_name.clone(),
-                name: r.name.clone(),
-                value_type: value_type.to_string(),
-                value,
-            }
-        })
+        self.resolver.resolve(resource_id).map(PyResolvedResource::from)
+    }
+
+    /// Resolve a resource ID, following references to the final value
+    ///
+    /// Returns None for dangling or cyclic references.
+    #[pyo3(signature = (resource_id, max_depth=MAX_REFERENCE_DEPTH))]
+    pub fn resolve_deref(&self, resource_id: u32, max_depth: usize) -> Option<PyResolvedResource> {
+        self.resolver.resolve_deref(resource_id, max_depth).as_ref().map(PyResolvedResource::from)
+    }
+
+    /// Look up a resource by type and name
+    ///
+    /// Example: `resolver.get_by_name("string", "app_name")`
+    pub fn get_by_name(&self, type_name: &str, name: &str) -> Option<PyResolvedResource> {
+        self.resolver.get_by_name(type_name, name).map(PyResolvedResource::from)
     }
 
     /// Check if a value looks like a resource ID
@@ -289,8 +530,14 @@ impl PyResourceResolver {
 }
 
 /// Parse resources.arsc from APK
+///
+/// Args:
+///     apk_path: Path to the APK file
+///     locale: Prefer values from this locale's configuration, e.g. "en"
+///         (default: the default configuration)
 #[pyfunction]
-pub fn parse_resources_from_apk(apk_path: String) -> PyResult<PyResourceResolver> {
+#[pyo3(signature = (apk_path, locale=None))]
+pub fn parse_resources_from_apk(apk_path: String, locale: Option<String>) -> PyResult<PyResourceResolver> {
     use crate::apk::ApkExtractor;
 
     let extractor = ApkExtractor::new(&apk_path)
@@ -306,8 +553,137 @@ pub fn parse_resources_from_apk(apk_path: String) -> PyResult<PyResourceResolver
         .extract_resources()
         .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
 
-    let resolver = ResourceResolver::from_bytes(arsc_bytes)
+    let resolver = ResourceResolver::from_bytes_with_locale(arsc_bytes, locale.as_deref())
         .map_err(|e| pyo3::exceptions::PyValueError::new_err(e))?;
 
     Ok(PyResourceResolver { resolver })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(id: u32, name: &str, value: ResourceData) -> ResolvedResource {
+        ResolvedResource { id, type_name: "string".to_string(), name: name.to_string(), value }
+    }
+
+    #[test]
+    fn test_resolve_deref() {
+        let resolver = ResourceResolver::from_resources(vec![
+            resource(0x7f0b0001, "app_name", ResourceData::String("Playfast".to_string())),
+            resource(0x7f0b0002, "title", ResourceData::Reference(0x7f0b0001)),
+            resource(0x7f0b0003, "launcher_title", ResourceData::Reference(0x7f0b0002)),
+            resource(0x7f0b0004, "self", ResourceData::Reference(0x7f0b0004)),
+            resource(0x7f0b0005, "dangling", ResourceData::Reference(0x7f0b00ff)),
+        ]);
+
+        // Two hops: launcher_title -> title -> app_name
+        let resolved = resolver.resolve_deref(0x7f0b0003, MAX_REFERENCE_DEPTH).unwrap();
+        assert_eq!(resolved.name, "launcher_title");
+        assert!(matches!(resolved.value, ResourceData::String(ref s) if s == "Playfast"));
+        assert!(resolver.resolve_deref(0x7f0b0003, 1).is_none());
+        assert_eq!(resolver.resolve_string(0x7f0b0003).as_deref(), Some("Playfast"));
+
+        // Concrete values resolve to themselves
+        assert!(resolver.resolve_deref(0x7f0b0001, 0).is_some());
+
+        assert!(resolver.resolve_deref(0x7f0b0004, MAX_REFERENCE_DEPTH).is_none());
+        assert!(resolver.resolve_deref(0x7f0b0005, MAX_REFERENCE_DEPTH).is_none());
+    }
+
+    #[test]
+    fn test_get_by_name() {
+        let resolver = ResourceResolver::from_resources(vec![
+            resource(0x7f0b0001, "app_name", ResourceData::String("Playfast".to_string())),
+            resource(0x7f0b0002, "title", ResourceData::Reference(0x7f0b0001)),
+            // Framework resource with the same type and name
+            resource(0x01040001, "app_name", ResourceData::String("Android".to_string())),
+        ]);
+
+        let by_name = resolver.get_by_name("string", "app_name").unwrap();
+        let by_id = resolver.resolve(0x7f0b0001).unwrap();
+        assert_eq!(by_name.id, by_id.id);
+        assert!(matches!(by_name.value, ResourceData::String(ref s) if s == "Playfast"));
+
+        assert_eq!(resolver.get_by_name("string", "title").map(|r| r.id), Some(0x7f0b0002));
+        assert!(resolver.get_by_name("drawable", "app_name").is_none());
+        assert!(resolver.get_by_name("string", "missing").is_none());
+    }
+
+    #[test]
+    fn test_string_array_and_plurals() {
+        use arsc::components::StringPool;
+
+        const TYPE_STRING: u8 = 0x03;
+        const TYPE_INT_DEC: u8 = 0x10;
+        let value = |r#type: u8, data_index: usize| Value { size: 8, zero: 0, r#type, data_index };
+        let pool = StringPool {
+            flags: 0,
+            strings: vec!["https://api.example.com".to_string(), "https://cdn.example.com".to_string(), "%d item".to_string(), "%d items".to_string()],
+            styles: Vec::new(),
+        };
+
+        // <string-array name="endpoints">, items stored out of order
+        let array = ResourceValue::Bag {
+            parent: 0,
+            values: vec![(ARRAY_KEY_BASE + 1, value(TYPE_STRING, 1)), (ARRAY_KEY_BASE, value(TYPE_STRING, 0))],
+        };
+        let endpoints = ResolvedResource {
+            id: 0x7f030000,
+            type_name: "array".to_string(),
+            name: "endpoints".to_string(),
+            value: ResourceResolver::extract_value(&array, &pool),
+        };
+        let py = PyResolvedResource::from(&endpoints);
+        assert!(py.is_array());
+        assert_eq!(py.values, vec!["https://api.example.com", "https://cdn.example.com"]);
+        assert_eq!(py.value, "[https://api.example.com, https://cdn.example.com]");
+
+        let numbers = ResourceValue::Bag { parent: 0, values: vec![(ARRAY_KEY_BASE, value(TYPE_INT_DEC, 42))] };
+        assert!(matches!(
+            ResourceResolver::extract_value(&numbers, &pool),
+            ResourceData::Array(ref items) if matches!(items[..], [ResourceData::Integer(42)])
+        ));
+
+        // <plurals name="items">
+        let plurals = ResourceValue::Bag {
+            parent: 0,
+            values: vec![(0x0100_0006, value(TYPE_STRING, 2)), (0x0100_0004, value(TYPE_STRING, 3))],
+        };
+        let items = ResolvedResource {
+            id: 0x7f100000,
+            type_name: "plurals".to_string(),
+            name: "items".to_string(),
+            value: ResourceResolver::extract_value(&plurals, &pool),
+        };
+        let py = PyResolvedResource::from(&items);
+        assert_eq!(py.value_type, "bag");
+        assert_eq!(py.entries["one"], "%d item");
+        assert_eq!(py.entries["other"], "%d items");
+    }
+
+    #[test]
+    fn test_config_rank() {
+        let config = |language: &[u8; 2], country: &[u8; 2]| {
+            let mut id = vec![0u8; 28];
+            id[4..6].copy_from_slice(language);
+            id[6..8].copy_from_slice(country);
+            id
+        };
+        let default = vec![0u8; 28];
+        let en = config(b"en", &[0, 0]);
+        let en_us = config(b"en", b"US");
+        let ko = config(b"ko", &[0, 0]);
+
+        assert_eq!(config_locale(&en_us).as_deref(), Some("en-US"));
+        assert_eq!(config_locale(&default), None);
+
+        assert_eq!(config_rank(&default, None), 1);
+        assert_eq!(config_rank(&ko, None), 0);
+        assert_eq!(config_rank(&en, Some("en")), 3);
+        assert_eq!(config_rank(&en_us, Some("en-rUS")), 3);
+        assert_eq!(config_rank(&en_us, Some("en")), 2);
+        assert_eq!(config_rank(&ko, Some("en")), 0);
+        assert_eq!(config_rank(&default, Some("en")), 1);
+    }
+}
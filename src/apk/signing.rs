@@ -0,0 +1,578 @@
+//! APK signing certificates
+//!
+//! Reads signer certificates from the APK Signing Block (v2/v3) and from v1
+//! JAR signatures (`META-INF/*.RSA|.DSA|.EC` PKCS#7 blocks), decoding the
+//! X.509 fields needed to tell who an APK claims to be signed by.
+//!
+//! Nothing here verifies signatures or digests: a re-signed or tampered APK
+//! reports whatever certificates it carries.
+
+use std::io::{Read, Seek, SeekFrom};
+
+use pyo3::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::apk::ApkExtractor;
+
+/// Magic closing an APK Signing Block
+pub(crate) const SIGNING_BLOCK_MAGIC: &[u8; 16] = b"APK Sig Block 42";
+/// End of central directory record signature
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+/// Bytes from the end that can hold the EOCD record: 22 plus a comment of
+/// up to 64 KiB
+const EOCD_SEARCH_LEN: usize = 22 + 0xFFFF;
+/// Signing block pair IDs of the v2, v3 and v3.1 schemes
+pub(crate) const SIGNATURE_SCHEME_IDS: &[(u32, &str)] = &[(0x7109_871a, "v2"), (0xf053_68c0, "v3"), (0x1b93_ad61, "v3.1")];
+
+/// DER tags used by certificates and PKCS#7
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OID: u8 = 0x06;
+const TAG_UTC_TIME: u8 = 0x17;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+const TAG_BMP_STRING: u8 = 0x1e;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_CONTEXT_0: u8 = 0xa0;
+
+/// Short names of distinguished name attributes, by encoded OID
+const NAME_ATTRIBUTES: &[(&[u8], &str)] = &[
+    (&[0x55, 0x04, 0x03], "CN"),
+    (&[0x55, 0x04, 0x06], "C"),
+    (&[0x55, 0x04, 0x07], "L"),
+    (&[0x55, 0x04, 0x08], "ST"),
+    (&[0x55, 0x04, 0x0a], "O"),
+    (&[0x55, 0x04, 0x0b], "OU"),
+    (&[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x01], "emailAddress"),
+];
+
+/// An APK signer's X.509 certificate
+#[pyclass]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertInfo {
+    /// Distinguished name in encoding order, e.g. "C=US, O=Example, CN=Example"
+    #[pyo3(get)]
+    pub subject: String,
+    #[pyo3(get)]
+    pub issuer: String,
+    /// Serial number as lowercase hex
+    #[pyo3(get)]
+    pub serial_number: String,
+    /// Start of validity as "YYYY-MM-DDTHH:MM:SSZ"
+    #[pyo3(get)]
+    pub not_before: String,
+    /// End of validity as "YYYY-MM-DDTHH:MM:SSZ"
+    #[pyo3(get)]
+    pub not_after: String,
+    /// SHA-256 of the DER-encoded certificate as lowercase hex
+    #[pyo3(get)]
+    pub sha256_fingerprint: String,
+    /// Signing schemes that carry this certificate: "v1", "v2", "v3", "v3.1"
+    #[pyo3(get)]
+    pub schemes: Vec<String>,
+}
+
+#[pymethods]
+impl CertInfo {
+    pub fn is_self_signed(&self) -> bool {
+        self.subject == self.issuer
+    }
+
+    /// Convert to Python dictionary
+    pub fn to_dict(&self, py: Python) -> PyResult<Py<pyo3::types::PyAny>> {
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("subject", &self.subject)?;
+        dict.set_item("issuer", &self.issuer)?;
+        dict.set_item("serial_number", &self.serial_number)?;
+        dict.set_item("not_before", &self.not_before)?;
+        dict.set_item("not_after", &self.not_after)?;
+        dict.set_item("sha256_fingerprint", &self.sha256_fingerprint)?;
+        dict.set_item("schemes", &self.schemes)?;
+        Ok(dict.into())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CertInfo(subject='{}', sha256='{}', schemes={:?})",
+            self.subject, self.sha256_fingerprint, self.schemes
+        )
+    }
+}
+
+impl CertInfo {
+    /// Decode a DER-encoded X.509 certificate
+    pub fn from_der(der: &[u8]) -> Option<Self> {
+        let (certificate, _) = read_tlv(der)?;
+        let (tbs, _) = read_tlv(certificate.content)?;
+
+        let mut fields = tbs.content;
+        let mut next = || {
+            let (tlv, rest) = read_tlv(fields)?;
+            fields = rest;
+            Some(tlv)
+        };
+        let mut serial = next()?;
+        if serial.tag == TAG_CONTEXT_0 {
+            serial = next()?;
+        }
+        if serial.tag != TAG_INTEGER {
+            return None;
+        }
+        let _signature_algorithm = next()?;
+        let issuer = next()?;
+        let validity = next()?;
+        let subject = next()?;
+
+        let (not_before, rest) = read_tlv(validity.content)?;
+        let (not_after, _) = read_tlv(rest)?;
+
+        // INTEGER encoding pads positive values with a leading zero byte
+        let serial_bytes = match serial.content {
+            [0, rest @ ..] if !rest.is_empty() => rest,
+            bytes => bytes,
+        };
+
+        Some(Self {
+            subject: distinguished_name(subject.content),
+            issuer: distinguished_name(issuer.content),
+            serial_number: hex(serial_bytes),
+            not_before: format_time(not_before),
+            not_after: format_time(not_after),
+            sha256_fingerprint: hex(&Sha256::digest(certificate.raw)),
+            schemes: Vec::new(),
+        })
+    }
+}
+
+/// One DER element
+struct Tlv<'a> {
+    tag: u8,
+    content: &'a [u8],
+    /// The whole element, header included
+    raw: &'a [u8],
+}
+
+/// Read one DER element, returning it and the bytes after it
+fn read_tlv(data: &[u8]) -> Option<(Tlv<'_>, &[u8])> {
+    let tag = *data.first()?;
+    let first = *data.get(1)? as usize;
+    let (length, header) = if first < 0x80 {
+        (first, 2)
+    } else {
+        let count = first & 0x7f;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let length = data.get(2..2 + count)?.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (length, 2 + count)
+    };
+    let end = header.checked_add(length)?;
+    let raw = data.get(..end)?;
+    Some((Tlv { tag, content: &raw[header..], raw }, &data[end..]))
+}
+
+/// All DER elements in `data`, in order
+fn read_all(mut data: &[u8]) -> Vec<Tlv<'_>> {
+    let mut elements = Vec::new();
+    while let Some((tlv, rest)) = read_tlv(data) {
+        elements.push(tlv);
+        data = rest;
+    }
+    elements
+}
+
+/// Render a `Name` (SEQUENCE of SETs of type/value pairs) as "C=US, CN=Example"
+fn distinguished_name(name: &[u8]) -> String {
+    read_all(name)
+        .iter()
+        .flat_map(|set| read_all(set.content))
+        .filter_map(|attribute| {
+            let (oid, rest) = read_tlv(attribute.content)?;
+            let (value, _) = read_tlv(rest)?;
+            if oid.tag != TAG_OID {
+                return None;
+            }
+            let key = NAME_ATTRIBUTES
+                .iter()
+                .find(|(encoded, _)| *encoded == oid.content)
+                .map_or_else(|| oid_string(oid.content), |(_, short)| short.to_string());
+            Some(format!("{}={}", key, string_value(&value)))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Decode a directory string (UTF8String, PrintableString, BMPString, ...)
+fn string_value(value: &Tlv) -> String {
+    if value.tag == TAG_BMP_STRING {
+        let units: Vec<u16> = value.content.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+        return String::from_utf16_lossy(&units);
+    }
+    String::from_utf8_lossy(value.content).into_owned()
+}
+
+/// Dotted form of an encoded OID, e.g. "2.5.4.3"
+fn oid_string(encoded: &[u8]) -> String {
+    let mut arcs = Vec::new();
+    let mut value: u64 = 0;
+    for byte in encoded {
+        value = (value << 7) | u64::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            if arcs.is_empty() {
+                let first = (value / 40).min(2);
+                arcs.push(first);
+                arcs.push(value - first * 40);
+            } else {
+                arcs.push(value);
+            }
+            value = 0;
+        }
+    }
+    arcs.iter().map(u64::to_string).collect::<Vec<_>>().join(".")
+}
+
+/// Render a UTCTime or GeneralizedTime as "YYYY-MM-DDTHH:MM:SSZ"
+///
+/// Values in other forms (offsets, fractional seconds) are returned as
+/// encoded.
+fn format_time(time: Tlv) -> String {
+    let text = String::from_utf8_lossy(time.content).into_owned();
+    let digits = text.trim_end_matches('Z');
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return text;
+    }
+    let full = match (time.tag, digits.len()) {
+        // UTCTime years 50-99 are 1950-1999
+        (TAG_UTC_TIME, 12) => {
+            let century = if digits[..2] < *"50" { "20" } else { "19" };
+            format!("{}{}", century, digits)
+        }
+        (TAG_GENERALIZED_TIME, 14) => digits.to_string(),
+        _ => return text,
+    };
+    format!("{}-{}-{}T{}:{}:{}Z", &full[..4], &full[4..6], &full[6..8], &full[8..10], &full[10..12], &full[12..14])
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Whether a ZIP entry holds a v1 (JAR) signature block
+pub(crate) fn is_v1_signature_file(name: &str) -> bool {
+    name.starts_with("META-INF/") && [".RSA", ".DSA", ".EC"].iter().any(|ext| name.to_uppercase().ends_with(ext))
+}
+
+/// Offset of the central directory, from the EOCD record in `tail` (the
+/// last bytes of the archive, or all of it)
+fn central_directory_offset(tail: &[u8]) -> Option<u64> {
+    let u32_at = |offset: usize| tail.get(offset..offset + 4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+
+    let search_start = tail.len().saturating_sub(EOCD_SEARCH_LEN);
+    let eocd = (search_start..tail.len().saturating_sub(21)).rev().find(|&i| u32_at(i) == Some(EOCD_SIGNATURE))?;
+    u32_at(eocd + 16).map(u64::from)
+}
+
+/// Size of a signing block from its 24-byte footer (size, magic),
+/// counting everything but the leading size field
+fn signing_block_size(footer: &[u8]) -> Option<u64> {
+    if footer.get(8..24) != Some(SIGNING_BLOCK_MAGIC.as_slice()) {
+        return None;
+    }
+    Some(u64::from_le_bytes(footer.get(..8)?.try_into().ok()?))
+}
+
+/// ID-value pairs of a signing block, starting at its leading size field
+///
+/// Block layout: size (u64), ID-value pairs, size (u64), magic
+fn block_pairs(block: &[u8]) -> Vec<(u32, &[u8])> {
+    let u32_at = |offset: usize| block.get(offset..offset + 4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+    let u64_at = |offset: usize| block.get(offset..offset + 8).map(|b| u64::from_le_bytes(b.try_into().unwrap()));
+    let end = block.len().saturating_sub(24);
+
+    let mut pairs = Vec::new();
+    let mut offset = 8;
+    while offset + 12 <= end {
+        let (Some(pair_len), Some(id)) = (u64_at(offset), u32_at(offset + 8)) else { break };
+        let Some(next) = usize::try_from(pair_len).ok().and_then(|len| (offset + 8).checked_add(len)) else { break };
+        if let Some(value) = block.get(offset + 12..next.min(end)) {
+            pairs.push((id, value));
+        }
+        offset = next;
+    }
+    pairs
+}
+
+/// ID-value pairs of the APK Signing Block preceding the central directory
+pub(crate) fn signing_block_pairs(data: &[u8]) -> Vec<(u32, &[u8])> {
+    let block = central_directory_offset(data).and_then(|cd_offset| {
+        let cd_offset = usize::try_from(cd_offset).ok()?;
+        let size = usize::try_from(signing_block_size(data.get(cd_offset.checked_sub(24)?..cd_offset)?)?).ok()?;
+        data.get((cd_offset - 8).checked_sub(size)?..cd_offset)
+    });
+    block.map(block_pairs).unwrap_or_default()
+}
+
+/// Read the APK Signing Block from `source`, or nothing if there is none
+///
+/// Only the archive tail (for the EOCD record) and the block in front of
+/// the central directory are read, never the entries.
+fn read_signing_block<R: Read + Seek>(source: &mut R) -> std::io::Result<Vec<u8>> {
+    let len = source.seek(SeekFrom::End(0))?;
+    let mut tail = vec![0; len.min(EOCD_SEARCH_LEN as u64) as usize];
+    source.seek(SeekFrom::Start(len - tail.len() as u64))?;
+    source.read_exact(&mut tail)?;
+
+    let Some(cd_offset) = central_directory_offset(&tail).filter(|&offset| (24..=len).contains(&offset)) else {
+        return Ok(Vec::new());
+    };
+    let mut footer = [0u8; 24];
+    source.seek(SeekFrom::Start(cd_offset - 24))?;
+    source.read_exact(&mut footer)?;
+    let Some(size) = signing_block_size(&footer).filter(|&size| size.checked_add(8).is_some_and(|total| total <= cd_offset)) else {
+        return Ok(Vec::new());
+    };
+
+    let mut block = vec![0; (size + 8) as usize];
+    source.seek(SeekFrom::Start(cd_offset - 8 - size))?;
+    source.read_exact(&mut block)?;
+    Ok(block)
+}
+
+/// Items of a sequence of u32-length-prefixed values
+fn length_prefixed(mut data: &[u8]) -> Vec<&[u8]> {
+    let mut items = Vec::new();
+    while let Some((item, rest)) = take_prefixed(data) {
+        items.push(item);
+        data = rest;
+    }
+    items
+}
+
+/// Split one u32-length-prefixed value off the front of `data`
+fn take_prefixed(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let item = data.get(4..4 + len)?;
+    Some((item, &data[4 + len..]))
+}
+
+/// Certificates of every signer in a v2/v3 scheme block
+///
+/// Each signer starts with its length-prefixed signed data, which holds
+/// length-prefixed digests followed by length-prefixed certificates.
+fn scheme_certificates(value: &[u8]) -> Vec<&[u8]> {
+    let Some((signers, _)) = take_prefixed(value) else { return Vec::new() };
+    length_prefixed(signers)
+        .into_iter()
+        .filter_map(|signer| {
+            let (signed_data, _) = take_prefixed(signer)?;
+            let (_digests, rest) = take_prefixed(signed_data)?;
+            let (certificates, _) = take_prefixed(rest)?;
+            Some(length_prefixed(certificates))
+        })
+        .flatten()
+        .collect()
+}
+
+/// Certificates embedded in a PKCS#7 SignedData block (v1 signature file)
+fn pkcs7_certificates(block: &[u8]) -> Vec<&[u8]> {
+    // ContentInfo { contentType, [0] SignedData { version, digestAlgorithms,
+    // contentInfo, [0] certificates, ... } }
+    let signed_data = read_tlv(block)
+        .and_then(|(content_info, _)| read_all(content_info.content).into_iter().find(|tlv| tlv.tag == TAG_CONTEXT_0))
+        .and_then(|explicit| read_tlv(explicit.content))
+        .filter(|(signed_data, _)| signed_data.tag == TAG_SEQUENCE);
+    let Some((signed_data, _)) = signed_data else { return Vec::new() };
+
+    read_all(signed_data.content)
+        .into_iter()
+        .skip(3)
+        .find(|tlv| tlv.tag == TAG_CONTEXT_0)
+        .map(|certificates| read_all(certificates.content).into_iter().map(|cert| cert.raw).collect())
+        .unwrap_or_default()
+}
+
+/// Add a certificate found under `scheme`, merging repeats of the same
+/// certificate across schemes
+fn add_certificate(certificates: &mut Vec<CertInfo>, scheme: &str, der: &[u8]) {
+    let Some(cert) = CertInfo::from_der(der) else { return };
+    match certificates.iter_mut().find(|known| known.sha256_fingerprint == cert.sha256_fingerprint) {
+        Some(known) if known.schemes.iter().any(|s| s == scheme) => {}
+        Some(known) => known.schemes.push(scheme.to_string()),
+        None => certificates.push(CertInfo { schemes: vec![scheme.to_string()], ..cert }),
+    }
+}
+
+impl ApkExtractor {
+    /// Signer certificates from the APK Signing Block (v2/v3), unverified
+    ///
+    /// Falls back to the PKCS#7 blocks of a v1 signature when the APK has
+    /// no signing block. A certificate present under several schemes is
+    /// reported once. Unsigned APKs give an empty list.
+    ///
+    /// Neither the signatures nor the APK digests are checked, so these are
+    /// the certificates the APK claims, not proof of who signed it.
+    pub fn unverified_signing_certificates(&self) -> crate::apk::error::Result<Vec<CertInfo>> {
+        let block = read_signing_block(&mut self.archive_source()?)?;
+        let mut certificates = Vec::new();
+
+        for (id, value) in block_pairs(&block) {
+            let Some((_, scheme)) = SIGNATURE_SCHEME_IDS.iter().find(|(scheme_id, _)| *scheme_id == id) else { continue };
+            for der in scheme_certificates(value) {
+                add_certificate(&mut certificates, scheme, der);
+            }
+        }
+
+        if certificates.is_empty() {
+            let v1_files: Vec<String> =
+                self.list_entries().iter().map(|entry| entry.name.clone()).filter(|name| is_v1_signature_file(name)).collect();
+            for name in v1_files {
+                let block = self.extract_file(&name)?;
+                for der in pkcs7_certificates(&block) {
+                    add_certificate(&mut certificates, "v1", der);
+                }
+            }
+        }
+
+        Ok(certificates)
+    }
+}
+
+/// Extract the certificates an APK claims to be signed with
+///
+/// Reads the v2/v3 APK Signing Block, falling back to the v1 `META-INF`
+/// signature when there is none. Signatures are not verified, so a
+/// tampered or re-signed APK is not detected.
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     list[dict]: One dict per certificate with subject, issuer,
+///     serial_number, not_before, not_after, sha256_fingerprint and schemes
+///
+/// Raises:
+///     IOError: If the APK cannot be read
+#[pyfunction]
+pub fn extract_signing_info(py: Python, apk_path: &str) -> PyResult<Vec<Py<pyo3::types::PyAny>>> {
+    let extractor = ApkExtractor::new(apk_path).map_err(crate::error::PlayfastError::from)?;
+    let certificates = extractor.unverified_signing_certificates().map_err(crate::error::PlayfastError::from)?;
+    certificates.iter().map(|cert| cert.to_dict(py)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apk::test_support::with_signing_block;
+    use crate::dex::test_support::{sample_dex, write_test_apk_with_entries};
+
+    const CERT_DER: &[u8] = include_bytes!("../../tests/fixtures/signing_cert.der");
+    const CERT_PKCS7: &[u8] = include_bytes!("../../tests/fixtures/signing_cert.p7b");
+    const CERT_SHA256: &str = "145e2c64dc5e7465b4ffa61b61d982e4615bc81b8a3e5be74d54b499c99c3e6a";
+
+    /// v2/v3 scheme value with one signer carrying `cert`
+    fn scheme_value(cert: &[u8]) -> Vec<u8> {
+        let prefixed = |data: &[u8]| [&(data.len() as u32).to_le_bytes()[..], data].concat();
+        let signed_data = [prefixed(&[]), prefixed(&prefixed(cert))].concat();
+        let signer = [prefixed(&signed_data), prefixed(&[]), prefixed(&[])].concat();
+        prefixed(&prefixed(&signer))
+    }
+
+    #[test]
+    fn test_decode_certificate() {
+        let cert = CertInfo::from_der(CERT_DER).unwrap();
+        assert_eq!(cert.subject, "C=KR, O=Playfast, CN=Playfast Test");
+        assert!(cert.is_self_signed());
+        assert_eq!(cert.serial_number, "1a2b3c4d");
+        // UTCTime and, past 2049, GeneralizedTime
+        assert_eq!(cert.not_before, "2026-10-16T12:16:17Z");
+        assert_eq!(cert.not_after, "2056-10-08T12:16:17Z");
+        assert_eq!(cert.sha256_fingerprint, CERT_SHA256);
+
+        assert_eq!(oid_string(&[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b]), "1.2.840.113549.1.1.11");
+        assert!(CertInfo::from_der(&CERT_DER[..100]).is_none());
+
+        // Malformed times come back as encoded, even when a slice of the
+        // expected length would split a character
+        let time = |tag: u8, content: &'static str| format_time(Tlv { tag, content: content.as_bytes(), raw: &[] });
+        assert_eq!(time(TAG_UTC_TIME, "a\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}aZ"), "a\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}aZ");
+        assert_eq!(time(TAG_UTC_TIME, "26101612161Z"), "26101612161Z");
+        assert_eq!(time(TAG_UTC_TIME, "261016121617Z"), "2026-10-16T12:16:17Z");
+    }
+
+    #[test]
+    fn test_v1_signing_certificates() {
+        let apk = write_test_apk_with_entries(
+            "signing-v1",
+            &[sample_dex()],
+            &[("AndroidManifest.xml", b"".as_slice()), ("META-INF/CERT.RSA", CERT_PKCS7)],
+        );
+        let certificates = ApkExtractor::new(&apk).unwrap().unverified_signing_certificates().unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].sha256_fingerprint, CERT_SHA256);
+        assert_eq!(certificates[0].schemes, vec!["v1"]);
+    }
+
+    #[test]
+    fn test_signing_block_certificates() {
+        let apk = write_test_apk_with_entries(
+            "signing-v2",
+            &[sample_dex()],
+            &[("AndroidManifest.xml", b"".as_slice()), ("META-INF/CERT.RSA", CERT_PKCS7)],
+        );
+        let zip = std::fs::read(&apk).unwrap();
+        let signed = with_signing_block(&zip, &[(0x7109_871a, scheme_value(CERT_DER)), (0xf053_68c0, scheme_value(CERT_DER))]);
+        std::fs::write(&apk, signed).unwrap();
+        let certificates = ApkExtractor::new(&apk).unwrap().unverified_signing_certificates().unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        // The signing block wins over the v1 signature
+        assert_eq!(certificates.len(), 1);
+        assert_eq!(certificates[0].sha256_fingerprint, CERT_SHA256);
+        assert_eq!(certificates[0].schemes, vec!["v2", "v3"]);
+    }
+
+    #[test]
+    fn test_signing_block_read_skips_entries() {
+        /// Cursor that counts the bytes read through it
+        struct Counting {
+            inner: std::io::Cursor<Vec<u8>>,
+            read: usize,
+        }
+        impl Read for Counting {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let n = self.inner.read(buf)?;
+                self.read += n;
+                Ok(n)
+            }
+        }
+        impl Seek for Counting {
+            fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+                self.inner.seek(pos)
+            }
+        }
+
+        // Incompressible, so the archive really is over a megabyte
+        let mut state = 0x2545_f491_u32;
+        let payload: Vec<u8> = (0..1 << 20)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        let apk = write_test_apk_with_entries("signing-read", &[sample_dex()], &[("assets/payload.bin", payload.as_slice())]);
+        let zip = std::fs::read(&apk).unwrap();
+        std::fs::remove_file(&apk).ok();
+        assert!(zip.len() > 1 << 20);
+        let signed = with_signing_block(&zip, &[(0x7109_871a, scheme_value(CERT_DER))]);
+
+        let mut source = Counting { inner: std::io::Cursor::new(signed.clone()), read: 0 };
+        let block = read_signing_block(&mut source).unwrap();
+        assert_eq!(block_pairs(&block), signing_block_pairs(&signed));
+        assert!(source.read <= EOCD_SEARCH_LEN + 24 + block.len());
+
+        let mut unsigned = Counting { inner: std::io::Cursor::new(zip), read: 0 };
+        assert!(read_signing_block(&mut unsigned).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+//! APK size breakdown by content category
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::BTreeMap;
+
+use crate::apk::extractor::{read_zip_entries, ApkEntry};
+
+/// Compressed and uncompressed byte counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EntrySize {
+    pub compressed: u64,
+    pub uncompressed: u64,
+}
+
+impl EntrySize {
+    fn add(&mut self, entry: &ApkEntry) {
+        self.compressed += entry.compressed_size;
+        self.uncompressed += entry.size;
+    }
+
+    fn to_dict<'py>(self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("compressed", self.compressed)?;
+        dict.set_item("uncompressed", self.uncompressed)?;
+        Ok(dict)
+    }
+}
+
+/// Where the bytes of an APK go
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SizeBreakdown {
+    pub total: EntrySize,
+    /// `*.dex` files
+    pub dex: EntrySize,
+    /// `resources.arsc`
+    pub resources: EntrySize,
+    /// `lib/<abi>/...`, keyed by ABI
+    pub native_libs: BTreeMap<String, EntrySize>,
+    /// `assets/...`
+    pub assets: EntrySize,
+    /// `res/...`
+    pub res: EntrySize,
+    /// Everything else (manifest, META-INF, ...)
+    pub other: EntrySize,
+}
+
+impl SizeBreakdown {
+    /// Sort every entry into exactly one category
+    pub fn from_entries(entries: &[ApkEntry]) -> Self {
+        let mut breakdown = Self::default();
+
+        for entry in entries {
+            breakdown.total.add(entry);
+
+            let name = entry.name.as_str();
+            let category = if name.ends_with(".dex") {
+                &mut breakdown.dex
+            } else if name == "resources.arsc" {
+                &mut breakdown.resources
+            } else if let Some(abi) = name.strip_prefix("lib/").and_then(|rest| rest.split_once('/')).map(|(abi, _)| abi) {
+                breakdown.native_libs.entry(abi.to_string()).or_default()
+            } else if name.starts_with("assets/") {
+                &mut breakdown.assets
+            } else if name.starts_with("res/") {
+                &mut breakdown.res
+            } else {
+                &mut breakdown.other
+            };
+            category.add(entry);
+        }
+
+        breakdown
+    }
+
+    /// Convert to Python dictionary
+    pub fn to_dict(&self, py: Python) -> PyResult<Py<pyo3::types::PyAny>> {
+        let native_libs = PyDict::new(py);
+        for (abi, size) in &self.native_libs {
+            native_libs.set_item(abi, size.to_dict(py)?)?;
+        }
+
+        let dict = PyDict::new(py);
+        dict.set_item("total", self.total.to_dict(py)?)?;
+        dict.set_item("dex", self.dex.to_dict(py)?)?;
+        dict.set_item("resources", self.resources.to_dict(py)?)?;
+        dict.set_item("native_libs", native_libs)?;
+        dict.set_item("assets", self.assets.to_dict(py)?)?;
+        dict.set_item("res", self.res.to_dict(py)?)?;
+        dict.set_item("other", self.other.to_dict(py)?)?;
+        Ok(dict.into())
+    }
+}
+
+/// Break down an APK's size by content category
+///
+/// Sizes come from the ZIP central directory, so no entry is decompressed.
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     dict: "total", "dex", "resources", "assets", "res" and "other", each a
+///     dict of "compressed" and "uncompressed" byte counts, plus
+///     "native_libs" mapping each ABI to the same kind of dict
+///
+/// Raises:
+///     IOError: If the APK cannot be opened
+#[pyfunction]
+pub fn apk_size_breakdown(py: Python, apk_path: &str) -> PyResult<Py<PyAny>> {
+    let entries = read_zip_entries(apk_path)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+
+    SizeBreakdown::from_entries(&entries).to_dict(py)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dex::test_support::{sample_dex, write_test_apk_with_entries};
+
+    #[test]
+    fn test_size_breakdown_categories_sum_to_total() {
+        let apk = write_test_apk_with_entries(
+            "size-breakdown",
+            &[sample_dex(), sample_dex()],
+            &[
+                ("AndroidManifest.xml", b"manifest".as_slice()),
+                ("resources.arsc", &[0u8; 64]),
+                ("lib/arm64-v8a/libnative.so", &[1u8; 300]),
+                ("lib/armeabi-v7a/libnative.so", &[2u8; 200]),
+                ("assets/data.bin", &[3u8; 1000]),
+                ("res/layout/main.xml", b"<layout/>".as_slice()),
+                ("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0".as_slice()),
+            ],
+        );
+        let entries = read_zip_entries(&apk).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        let breakdown = SizeBreakdown::from_entries(&entries);
+        let categories = [breakdown.dex, breakdown.resources, breakdown.assets, breakdown.res, breakdown.other]
+            .into_iter()
+            .chain(breakdown.native_libs.values().copied());
+        let (compressed, uncompressed) = categories.fold((0, 0), |(c, u), size| (c + size.compressed, u + size.uncompressed));
+
+        assert_eq!(compressed, breakdown.total.compressed);
+        assert_eq!(uncompressed, breakdown.total.uncompressed);
+        assert_eq!(breakdown.dex.uncompressed, 2 * sample_dex().len() as u64);
+        assert_eq!(breakdown.resources.uncompressed, 64);
+        assert_eq!(breakdown.assets.uncompressed, 1000);
+        assert_eq!(breakdown.native_libs.keys().collect::<Vec<_>>(), vec!["arm64-v8a", "armeabi-v7a"]);
+        assert_eq!(breakdown.native_libs["arm64-v8a"].uncompressed, 300);
+        assert_eq!(breakdown.other.uncompressed, 8 + 21);
+    }
+}
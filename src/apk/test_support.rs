@@ -0,0 +1,208 @@
+//! Helpers for building binary XML documents and signed APKs in tests
+
+#![allow(dead_code)]
+
+use crate::apk::binary_xml::{XmlAttribute, XmlElement, XmlValue};
+use crate::apk::signing::SIGNING_BLOCK_MAGIC;
+
+const ANDROID_NS: &str = "http://schemas.android.com/apk/res/android";
+const NO_INDEX: u32 = 0xFFFF_FFFF;
+const UNQUALIFIED_ATTRS: [&str; 2] = ["package", "split"];
+
+/// Start building an element with the given name
+pub fn element(name: &str) -> XmlElement {
+    XmlElement {
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+impl XmlElement {
+    pub fn with_attr(mut self, name: &str, value: XmlValue) -> Self {
+        self.attributes.push(XmlAttribute {
+            name: name.to_string(),
+            value,
+        });
+        self
+    }
+
+    pub fn with_child(mut self, child: XmlElement) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = text.to_string();
+        self
+    }
+}
+
+/// Shorthand for a string attribute value
+pub fn s(value: &str) -> XmlValue {
+    XmlValue::String(value.to_string())
+}
+
+/// Encode an element tree as compiled binary XML (UTF-16 string pool)
+///
+/// Attributes are placed in the android namespace, as aapt does for manifest
+/// and resource XML attributes, except the `<manifest>` attributes aapt
+/// leaves unqualified (`package`, `split`).
+pub fn encode_binary_xml(root: &XmlElement) -> Vec<u8> {
+    let mut pool = StringPool::default();
+    let prefix = pool.index("android");
+    let uri = pool.index(ANDROID_NS);
+
+    let mut body = Vec::new();
+    chunk(&mut body, 0x0100, &[prefix.to_le_bytes(), uri.to_le_bytes()].concat());
+    encode_element(root, &mut pool, uri, &mut body);
+    chunk(&mut body, 0x0101, &[prefix.to_le_bytes(), uri.to_le_bytes()].concat());
+
+    let pool_chunk = pool.encode();
+    let mut out = Vec::new();
+    out.extend_from_slice(&0x0003u16.to_le_bytes());
+    out.extend_from_slice(&8u16.to_le_bytes());
+    out.extend_from_slice(&((8 + pool_chunk.len() + body.len()) as u32).to_le_bytes());
+    out.extend(pool_chunk);
+    out.extend(body);
+    out
+}
+
+/// Append a node chunk: 16-byte header (line number 1, no comment) then `ext`
+fn chunk(out: &mut Vec<u8>, chunk_type: u16, ext: &[u8]) {
+    out.extend_from_slice(&chunk_type.to_le_bytes());
+    out.extend_from_slice(&16u16.to_le_bytes());
+    out.extend_from_slice(&((16 + ext.len()) as u32).to_le_bytes());
+    out.extend_from_slice(&1u32.to_le_bytes());
+    out.extend_from_slice(&NO_INDEX.to_le_bytes());
+    out.extend_from_slice(ext);
+}
+
+fn encode_element(element: &XmlElement, pool: &mut StringPool, ns: u32, out: &mut Vec<u8>) {
+    let name = pool.index(&element.name);
+
+    let mut ext = Vec::new();
+    ext.extend_from_slice(&NO_INDEX.to_le_bytes());
+    ext.extend_from_slice(&name.to_le_bytes());
+    ext.extend_from_slice(&20u16.to_le_bytes());
+    ext.extend_from_slice(&20u16.to_le_bytes());
+    ext.extend_from_slice(&(element.attributes.len() as u16).to_le_bytes());
+    ext.extend_from_slice(&[0u8; 6]);
+
+    for attr in &element.attributes {
+        let attr_name = pool.index(&attr.name);
+        let attr_ns = if UNQUALIFIED_ATTRS.contains(&attr.name.as_str()) { NO_INDEX } else { ns };
+        let (raw, data_type, data) = match &attr.value {
+            XmlValue::String(v) => {
+                let idx = pool.index(v);
+                (idx, 0x03u8, idx)
+            }
+            XmlValue::Bool(b) => (NO_INDEX, 0x12, if *b { NO_INDEX } else { 0 }),
+            XmlValue::Int(i) => (NO_INDEX, 0x10, *i as u32),
+            XmlValue::Reference(id) => (NO_INDEX, 0x01, *id),
+            XmlValue::Other(t, d) => (NO_INDEX, *t, *d),
+        };
+        ext.extend_from_slice(&attr_ns.to_le_bytes());
+        ext.extend_from_slice(&attr_name.to_le_bytes());
+        ext.extend_from_slice(&raw.to_le_bytes());
+        ext.extend_from_slice(&8u16.to_le_bytes());
+        ext.push(0);
+        ext.push(data_type);
+        ext.extend_from_slice(&data.to_le_bytes());
+    }
+    chunk(out, 0x0102, &ext);
+
+    if !element.text.is_empty() {
+        let text = pool.index(&element.text);
+        let mut cdata = Vec::new();
+        cdata.extend_from_slice(&text.to_le_bytes());
+        cdata.extend_from_slice(&8u16.to_le_bytes());
+        cdata.push(0);
+        cdata.push(0x03);
+        cdata.extend_from_slice(&text.to_le_bytes());
+        chunk(out, 0x0104, &cdata);
+    }
+
+    for child in &element.children {
+        encode_element(child, pool, ns, out);
+    }
+
+    chunk(out, 0x0103, &[NO_INDEX.to_le_bytes(), name.to_le_bytes()].concat());
+}
+
+#[derive(Default)]
+struct StringPool {
+    strings: Vec<String>,
+}
+
+impl StringPool {
+    fn index(&mut self, value: &str) -> u32 {
+        match self.strings.iter().position(|s| s == value) {
+            Some(i) => i as u32,
+            None => {
+                self.strings.push(value.to_string());
+                (self.strings.len() - 1) as u32
+            }
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut offsets = Vec::new();
+        let mut data = Vec::new();
+        for string in &self.strings {
+            offsets.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            let units: Vec<u16> = string.encode_utf16().collect();
+            data.extend_from_slice(&(units.len() as u16).to_le_bytes());
+            for unit in units {
+                data.extend_from_slice(&unit.to_le_bytes());
+            }
+            data.extend_from_slice(&0u16.to_le_bytes());
+        }
+        while data.len() % 4 != 0 {
+            data.push(0);
+        }
+
+        let header_size = 28u32;
+        let strings_start = header_size + offsets.len() as u32;
+        let mut out = Vec::new();
+        out.extend_from_slice(&0x0001u16.to_le_bytes());
+        out.extend_from_slice(&(header_size as u16).to_le_bytes());
+        out.extend_from_slice(&(strings_start + data.len() as u32).to_le_bytes());
+        out.extend_from_slice(&(self.strings.len() as u32).to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend_from_slice(&strings_start.to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend(offsets);
+        out.extend(data);
+        out
+    }
+}
+
+/// Insert an APK Signing Block with the given ID-value pairs before the
+/// central directory of `zip`, shifting the EOCD's central directory offset
+/// past it
+///
+/// `zip` must not have an archive comment.
+pub fn with_signing_block(zip: &[u8], pairs: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let eocd = zip.len() - 22;
+    let cd_offset = u32::from_le_bytes(zip[eocd + 16..eocd + 20].try_into().unwrap()) as usize;
+
+    let mut encoded = Vec::new();
+    for (id, value) in pairs {
+        encoded.extend_from_slice(&((value.len() + 4) as u64).to_le_bytes());
+        encoded.extend_from_slice(&id.to_le_bytes());
+        encoded.extend_from_slice(value);
+    }
+    let block_size = (encoded.len() + 8 + 16) as u64;
+    let mut block = block_size.to_le_bytes().to_vec();
+    block.extend_from_slice(&encoded);
+    block.extend_from_slice(&block_size.to_le_bytes());
+    block.extend_from_slice(SIGNING_BLOCK_MAGIC);
+
+    let mut signed = zip[..cd_offset].to_vec();
+    signed.extend_from_slice(&block);
+    signed.extend_from_slice(&zip[cd_offset..]);
+    let new_eocd = signed.len() - 22;
+    signed[new_eocd + 16..new_eocd + 20].copy_from_slice(&((cd_offset + block.len()) as u32).to_le_bytes());
+    signed
+}
@@ -0,0 +1,383 @@
+//! API Level Checker
+//!
+//! Flags calls to framework APIs that were introduced after the app's
+//! `minSdkVersion`. Introduction levels come from a small built-in table of
+//! commonly misused classes and methods, which callers can extend.
+
+use pyo3::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use crate::apk::manifest::parse_manifest;
+use crate::apk::ApkExtractor;
+use super::instruction::{Instruction, InstructionDecoder, Opcode};
+use super::parser::DexParser;
+
+/// Class/method introduction levels shipped with the checker
+///
+/// A bare class name covers every method of that class; "Class.method"
+/// covers a single method.
+const BUILTIN_API_LEVELS: &[(&str, u32)] = &[
+    ("android.content.Context.checkSelfPermission", 23),
+    ("android.app.Activity.requestPermissions", 23),
+    ("android.security.keystore.KeyGenParameterSpec", 23),
+    ("android.security.keystore.KeyGenParameterSpec$Builder", 23),
+    ("android.content.pm.ShortcutManager", 25),
+    ("android.app.NotificationChannel", 26),
+    ("android.app.NotificationManager.createNotificationChannel", 26),
+    ("android.content.Context.startForegroundService", 26),
+    ("java.util.Base64", 26),
+    ("java.time.LocalDate", 26),
+    ("java.time.Instant", 26),
+    ("android.webkit.WebView.startSafeBrowsing", 27),
+    ("android.hardware.biometrics.BiometricPrompt", 28),
+    ("android.hardware.biometrics.BiometricPrompt$Builder", 28),
+    ("android.graphics.ImageDecoder", 28),
+    ("android.app.Activity.setShowWhenLocked", 27),
+];
+
+/// The field read by `if (Build.VERSION.SDK_INT >= ...)` guards
+const SDK_INT_FIELD: (&str, &str) = ("android.os.Build$VERSION", "SDK_INT");
+
+/// Lookup table of API introduction levels
+#[derive(Debug, Clone)]
+pub struct ApiLevelTable {
+    levels: HashMap<String, u32>,
+}
+
+impl Default for ApiLevelTable {
+    fn default() -> Self {
+        let mut table = Self { levels: HashMap::new() };
+        for (api, level) in BUILTIN_API_LEVELS {
+            table.add(api, *level);
+        }
+        table
+    }
+}
+
+impl ApiLevelTable {
+    /// Add or override an entry ("pkg.Class" or "pkg.Class.method")
+    pub fn add(&mut self, api: &str, level: u32) {
+        self.levels.insert(api.to_string(), level);
+    }
+
+    /// Level at which `class_name.method_name` became available
+    ///
+    /// A method entry takes precedence over its class entry. Returns the
+    /// matched table key along with the level.
+    pub fn lookup(&self, class_name: &str, method_name: &str) -> Option<(String, u32)> {
+        let method_key = format!("{}.{}", class_name, method_name);
+        if let Some(&level) = self.levels.get(&method_key) {
+            return Some((method_key, level));
+        }
+        self.levels.get(class_name).map(|&level| (class_name.to_string(), level))
+    }
+}
+
+/// A call to an API newer than the app's minSdkVersion
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiLevelViolation {
+    /// Calling method as "pkg.Class.method"
+    #[pyo3(get)]
+    pub caller: String,
+
+    /// Table entry that matched the call target
+    #[pyo3(get)]
+    pub api: String,
+
+    /// API level that introduced `api`
+    #[pyo3(get)]
+    pub api_level: u32,
+
+    /// The app's minSdkVersion
+    #[pyo3(get)]
+    pub min_sdk: u32,
+}
+
+#[pymethods]
+impl ApiLevelViolation {
+    fn __repr__(&self) -> String {
+        format!(
+            "ApiLevelViolation(caller='{}', api='{}', api_level={}, min_sdk={})",
+            self.caller, self.api, self.api_level, self.min_sdk
+        )
+    }
+}
+
+/// Read minSdkVersion from the APK's binary manifest
+///
+/// Returns 1 when the manifest has no `<uses-sdk>` entry, matching the
+/// platform default.
+pub fn manifest_min_sdk(extractor: &ApkExtractor) -> crate::apk::error::Result<u32> {
+    let manifest = parse_manifest(&extractor.extract_manifest()?)?;
+
+    Ok(manifest
+        .min_sdk_version
+        .and_then(|level| level.parse().ok())
+        .unwrap_or(1))
+}
+
+/// Check every method of one DEX file against `table`
+///
+/// A call is treated as guarded when it sits inside the fall-through block
+/// of an `if (Build.VERSION.SDK_INT >= N)` check with `N` at least the
+/// API's level. See `guarded_calls` for the shapes that are recognized.
+pub fn dex_api_level_violations(parser: &DexParser, min_sdk: u32, table: &ApiLevelTable) -> Vec<ApiLevelViolation> {
+    let mut violations = Vec::new();
+
+    for class_idx in 0..parser.class_count() {
+        let Ok(class_def) = parser.get_class_def(class_idx) else { continue };
+        let Ok(class_data) = parser.parse_class_data(class_def.class_data_off) else { continue };
+        let Ok(class_name) = parser.get_type_name(class_def.class_idx) else { continue };
+
+        for encoded_method in class_data.direct_methods.iter().chain(class_data.virtual_methods.iter()) {
+            if encoded_method.code_off == 0 {
+                continue;
+            }
+            let Ok(bytecode) = parser.get_method_bytecode(encoded_method.code_off) else { continue };
+            let Some(caller_name) = method_name(parser, encoded_method.method_idx) else { continue };
+            let caller = format!("{}.{}", class_name, caller_name);
+
+            for (method_idx, guard_level) in guarded_calls(parser, &bytecode) {
+                let Ok(target) = parser.get_method_info(method_idx) else { continue };
+                let Ok(target_class) = parser.get_type_name(target.class_idx) else { continue };
+                let Some(target_name) = method_name(parser, method_idx) else { continue };
+
+                if let Some((api, api_level)) = table.lookup(&target_class, &target_name) {
+                    if api_level > min_sdk.max(guard_level) {
+                        violations.push(ApiLevelViolation { caller: caller.clone(), api, api_level, min_sdk });
+                    }
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+fn method_name(parser: &DexParser, method_idx: u32) -> Option<String> {
+    let info = parser.get_method_info(method_idx).ok()?;
+    parser.get_string(info.name_idx).ok()
+}
+
+/// Every call in `bytecode`, with the SDK level its call site is known to run at
+///
+/// Recognizes the shape compilers emit for `if (SDK_INT >= N) { ... }`:
+/// `sget vA, Build$VERSION.SDK_INT`, a constant `N` in vB, then
+/// `if-lt vA, vB, :skip` (or `if-le` against `N - 1`). Calls between the
+/// branch and `:skip` get level `N`; every other call gets 0. Register
+/// values are tracked linearly, so a guard only counts when the SDK_INT
+/// read and the constant are loaded before the branch in code order.
+fn guarded_calls(parser: &DexParser, bytecode: &[u16]) -> Vec<(u32, u32)> {
+    let mut sdk_registers: HashSet<u16> = HashSet::new();
+    let mut constants: HashMap<u16, i32> = HashMap::new();
+    // (start, end, level) word ranges where SDK_INT >= level
+    let mut guards: Vec<(usize, usize, u32)> = Vec::new();
+    let mut calls = Vec::new();
+
+    let mut offset = 0;
+    while offset < bytecode.len() {
+        let (instruction, width) = InstructionDecoder::decode_one(bytecode, offset);
+        let guard_level = guards
+            .iter()
+            .filter(|(start, end, _)| (*start..*end).contains(&offset))
+            .map(|(_, _, level)| *level)
+            .max()
+            .unwrap_or(0);
+
+        match &instruction {
+            Instruction::InvokeVirtual { method_idx, .. }
+            | Instruction::InvokeStatic { method_idx, .. }
+            | Instruction::InvokeDirect { method_idx, .. }
+            | Instruction::InvokeSuper { method_idx, .. }
+            | Instruction::InvokeInterface { method_idx, .. }
+            | Instruction::InvokeVirtualRange { method_idx, .. }
+            | Instruction::InvokeStaticRange { method_idx, .. } => calls.push((*method_idx, guard_level)),
+            Instruction::Const4 { dest, value } => {
+                sdk_registers.remove(&u16::from(*dest));
+                constants.insert(u16::from(*dest), i32::from(*value));
+            }
+            Instruction::Const16 { dest, value } => {
+                sdk_registers.remove(&u16::from(*dest));
+                constants.insert(u16::from(*dest), i32::from(*value));
+            }
+            Instruction::Const { dest, value } => {
+                sdk_registers.remove(&u16::from(*dest));
+                constants.insert(u16::from(*dest), *value);
+            }
+            Instruction::Unknown { opcode, data } if *opcode == Opcode::Sget as u8 => {
+                let dest = data[0] >> 8;
+                constants.remove(&dest);
+                let field_idx = data.get(1).copied().unwrap_or(0) as u32;
+                if is_sdk_int(parser, field_idx) {
+                    sdk_registers.insert(dest);
+                } else {
+                    sdk_registers.remove(&dest);
+                }
+            }
+            Instruction::Unknown { opcode, data } if *opcode == Opcode::IfLt as u8 || *opcode == Opcode::IfLe as u8 => {
+                let (a, b) = ((data[0] >> 8) & 0xF, data[0] >> 12);
+                let branch = data.get(1).copied().unwrap_or(0) as i16;
+                if branch > 0 && sdk_registers.contains(&a) {
+                    if let Some(&bound) = constants.get(&b) {
+                        // if-lt skips when SDK_INT < N; if-le when SDK_INT <= N
+                        let level = if *opcode == Opcode::IfLe as u8 { bound + 1 } else { bound };
+                        let target = offset + branch as usize;
+                        guards.push((offset + width, target, u32::try_from(level).unwrap_or(0)));
+                    }
+                }
+            }
+            _ => {}
+        }
+        offset += width;
+    }
+
+    calls
+}
+
+/// Whether `field_idx` is `Build$VERSION.SDK_INT`
+fn is_sdk_int(parser: &DexParser, field_idx: u32) -> bool {
+    parser.get_field_info(field_idx).ok().is_some_and(|field| {
+        parser.get_type_name(field.class_idx).ok().as_deref() == Some(SDK_INT_FIELD.0)
+            && parser.get_string(field.name_idx).ok().as_deref() == Some(SDK_INT_FIELD.1)
+    })
+}
+
+/// Find calls to APIs introduced after the app's minSdkVersion
+///
+/// Uses a built-in table of introduction levels for commonly misused
+/// framework classes and methods. Calls inside an
+/// `if (Build.VERSION.SDK_INT >= N)` block are not reported when `N` covers
+/// the API's level.
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///     extra_levels (dict[str, int] | None): Additional or overriding table
+///         entries, keyed by "pkg.Class" or "pkg.Class.method"
+///
+/// Returns:
+///     list[ApiLevelViolation]: One entry per unguarded call site
+///
+/// Raises:
+///     IOError: If the APK cannot be opened
+///     ValueError: If the manifest cannot be parsed
+#[pyfunction]
+#[pyo3(signature = (apk_path, extra_levels=None))]
+pub fn find_api_level_violations(
+    apk_path: &str,
+    extra_levels: Option<HashMap<String, u32>>,
+) -> PyResult<Vec<ApiLevelViolation>> {
+    let extractor = ApkExtractor::new(apk_path)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+    let min_sdk = manifest_min_sdk(&extractor)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+
+    let mut table = ApiLevelTable::default();
+    for (api, level) in extra_levels.unwrap_or_default() {
+        table.add(&api, level);
+    }
+
+    Ok(extractor
+        .dex_entries()
+        .iter()
+        .filter_map(|dex_entry| DexParser::from_arc(dex_entry.data.clone()).ok())
+        .flat_map(|parser| dex_api_level_violations(&parser, min_sdk, &table))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apk::binary_xml::XmlValue;
+    use crate::apk::test_support::{element, encode_binary_xml, s};
+    use crate::dex::test_support::{write_test_apk_with_entries, DexBuilder};
+
+    #[test]
+    fn test_unguarded_newer_api_is_flagged() {
+        let manifest = encode_binary_xml(
+            &element("manifest")
+                .with_attr("package", s("com.example"))
+                .with_child(element("uses-sdk").with_attr("minSdkVersion", XmlValue::Int(21))),
+        );
+
+        let mut builder = DexBuilder::new();
+        let create_channel = builder.method(
+            "Landroid/app/NotificationManager;",
+            "createNotificationChannel",
+            "V",
+            &["Landroid/app/NotificationChannel;"],
+        );
+        let check_permission = builder.method("Landroid/content/Context;", "checkSelfPermission", "I", &["Ljava/lang/String;"]);
+        let setup = builder.method("Lcom/example/Notifier;", "setup", "V", &[]);
+        let guarded = builder.method("Lcom/example/Notifier;", "guardedSetup", "V", &[]);
+        let weak_guard = builder.method("Lcom/example/Notifier;", "weakGuard", "V", &[]);
+        let sdk_int = builder.field("Landroid/os/Build$VERSION;", "I", "SDK_INT");
+
+        let class = builder.class("Lcom/example/Notifier;", Some("Ljava/lang/Object;"), 0x0001);
+        builder.virtual_method(class, setup, 0x0001, Some(vec![0x106e, create_channel as u16, 0x0001, 0x000e]));
+        // if (SDK_INT >= 26) createNotificationChannel(); checkSelfPermission();
+        builder.virtual_method(
+            class,
+            guarded,
+            0x0001,
+            Some(vec![
+                0x0060, sdk_int as u16, // sget v0, SDK_INT
+                0x0113, 26,             // const/16 v1, #26
+                0x1034, 0x0005,         // if-lt v0, v1, +5
+                0x106e, create_channel as u16, 0x0001,
+                0x106e, check_permission as u16, 0x0001,
+                0x000e,
+            ]),
+        );
+        // if (SDK_INT > 22) createNotificationChannel(); -- too low for a level-26 API
+        builder.virtual_method(
+            class,
+            weak_guard,
+            0x0001,
+            Some(vec![
+                0x0060, sdk_int as u16, // sget v0, SDK_INT
+                0x0113, 22,             // const/16 v1, #22
+                0x1037, 0x0005,         // if-le v0, v1, +5
+                0x106e, create_channel as u16, 0x0001,
+                0x000e,
+            ]),
+        );
+        let dex = builder.build();
+
+        let apk = write_test_apk_with_entries("api-level", &[dex], &[("AndroidManifest.xml", &manifest)]);
+        let violations = find_api_level_violations(apk.to_str().unwrap(), None).unwrap();
+        let with_extra = find_api_level_violations(
+            apk.to_str().unwrap(),
+            Some(HashMap::from([("android.app.NotificationManager".to_string(), 19)])),
+        )
+        .unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        let violation = |caller: &str, api: &str, api_level| ApiLevelViolation {
+            caller: format!("com.example.Notifier.{}", caller),
+            api: api.to_string(),
+            api_level,
+            min_sdk: 21,
+        };
+        assert_eq!(
+            violations,
+            vec![
+                violation("setup", "android.app.NotificationManager.createNotificationChannel", 26),
+                violation("guardedSetup", "android.content.Context.checkSelfPermission", 23),
+                violation("weakGuard", "android.app.NotificationManager.createNotificationChannel", 26),
+            ]
+        );
+        // The method entry is more specific than the added class entry
+        assert_eq!(with_extra, violations);
+    }
+
+    #[test]
+    fn test_table_lookup_prefers_method_entry() {
+        let mut table = ApiLevelTable::default();
+        table.add("java.util.Base64.getEncoder", 24);
+
+        assert_eq!(table.lookup("java.util.Base64", "getEncoder"), Some(("java.util.Base64.getEncoder".to_string(), 24)));
+        assert_eq!(table.lookup("java.util.Base64", "getDecoder"), Some(("java.util.Base64".to_string(), 26)));
+        assert_eq!(table.lookup("java.lang.String", "length"), None);
+    }
+}
@@ -4,8 +4,10 @@
 
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use super::instruction::{Instruction, InstructionDecoder};
+use super::instruction::{format_register_list, format_register_range, DecodeMode, Instruction, InstructionDecoder};
+use super::parser::DexParser;
 
 /// Python-friendly instruction representation
 #[pyclass]
@@ -17,7 +19,7 @@ pub struct RustInstruction {
 
     /// Destination register (if applicable)
     #[pyo3(get)]
-    pub dest: Option<u8>,
+    pub dest: Option<u16>,
 
     /// Constant value (for const instructions)
     #[pyo3(get)]
@@ -31,9 +33,10 @@ pub struct RustInstruction {
     #[pyo3(get)]
     pub method_idx: Option<u32>,
 
-    /// Argument registers (for invoke instructions)
+    /// Argument registers (for invoke instructions); range invokes can
+    /// name registers above v255
     #[pyo3(get)]
-    pub args: Vec<u8>,
+    pub args: Vec<u16>,
 
     /// Raw instruction for debugging
     #[pyo3(get)]
@@ -83,9 +86,45 @@ impl RustInstruction {
 impl From<&Instruction> for RustInstruction {
     fn from(insn: &Instruction) -> Self {
         match insn {
+            Instruction::Move { dest, src, .. } => RustInstruction {
+                opcode: insn.mnemonic(),
+                dest: Some((*dest).into()),
+                value: None,
+                string_idx: None,
+                method_idx: None,
+                args: vec![(*src).into()],
+                raw: insn.to_string(),
+            },
+            Instruction::MoveFrom16 { dest, src, .. } => RustInstruction {
+                opcode: insn.mnemonic(),
+                dest: Some((*dest).into()),
+                value: None,
+                string_idx: None,
+                method_idx: None,
+                args: vec![*src],
+                raw: insn.to_string(),
+            },
+            Instruction::Move16 { dest, src, .. } => RustInstruction {
+                opcode: insn.mnemonic(),
+                dest: Some(*dest),
+                value: None,
+                string_idx: None,
+                method_idx: None,
+                args: vec![*src],
+                raw: insn.to_string(),
+            },
+            Instruction::MoveResult { dest, .. } => RustInstruction {
+                opcode: insn.mnemonic(),
+                dest: Some((*dest).into()),
+                value: None,
+                string_idx: None,
+                method_idx: None,
+                args: Vec::new(),
+                raw: insn.to_string(),
+            },
             Instruction::Const4 { dest, value } => RustInstruction {
                 opcode: "const/4".to_string(),
-                dest: Some(*dest),
+                dest: Some((*dest).into()),
                 value: Some(*value as i64),
                 string_idx: None,
                 method_idx: None,
@@ -94,7 +133,7 @@ impl From<&Instruction> for RustInstruction {
             },
             Instruction::Const16 { dest, value } => RustInstruction {
                 opcode: "const/16".to_string(),
-                dest: Some(*dest),
+                dest: Some((*dest).into()),
                 value: Some(*value as i64),
                 string_idx: None,
                 method_idx: None,
@@ -103,7 +142,7 @@ impl From<&Instruction> for RustInstruction {
             },
             Instruction::Const { dest, value } => RustInstruction {
                 opcode: "const".to_string(),
-                dest: Some(*dest),
+                dest: Some((*dest).into()),
                 value: Some(*value as i64),
                 string_idx: None,
                 method_idx: None,
@@ -112,28 +151,48 @@ impl From<&Instruction> for RustInstruction {
             },
             Instruction::ConstString { dest, string_idx } => RustInstruction {
                 opcode: "const-string".to_string(),
-                dest: Some(*dest),
+                dest: Some((*dest).into()),
                 value: None,
                 string_idx: Some(*string_idx),
                 method_idx: None,
                 args: Vec::new(),
                 raw: format!("const-string v{}, string@{}", dest, string_idx),
             },
+            Instruction::ConstStringJumbo { dest, string_idx } => RustInstruction {
+                opcode: "const-string/jumbo".to_string(),
+                dest: Some((*dest).into()),
+                value: None,
+                string_idx: Some(*string_idx),
+                method_idx: None,
+                args: Vec::new(),
+                raw: format!("const-string/jumbo v{}, string@{}", dest, string_idx),
+            },
+            Instruction::ConstClass { dest, type_idx } => RustInstruction {
+                opcode: "const-class".to_string(),
+                dest: Some((*dest).into()),
+                value: None,
+                string_idx: None,
+                method_idx: None,
+                args: Vec::new(),
+                raw: format!("const-class v{}, type@{}", dest, type_idx),
+            },
+            Instruction::NewInstance { dest, type_idx } => RustInstruction {
+                opcode: "new-instance".to_string(),
+                dest: Some((*dest).into()),
+                value: None,
+                string_idx: None,
+                method_idx: None,
+                args: Vec::new(),
+                raw: format!("new-instance v{}, type@{}", dest, type_idx),
+            },
             Instruction::InvokeVirtual { args, method_idx } => RustInstruction {
                 opcode: "invoke-virtual".to_string(),
                 dest: None,
                 value: None,
                 string_idx: None,
                 method_idx: Some(*method_idx),
-                args: args.clone(),
-                raw: format!(
-                    "invoke-virtual {{v{}}}, method@{}",
-                    args.iter()
-                        .map(|a| a.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", v"),
-                    method_idx
-                ),
+                args: args.iter().map(|&arg| arg.into()).collect(),
+                raw: format!("invoke-virtual {}, method@{}", format_register_list(args), method_idx),
             },
             Instruction::InvokeStatic { args, method_idx } => RustInstruction {
                 opcode: "invoke-static".to_string(),
@@ -141,15 +200,8 @@ impl From<&Instruction> for RustInstruction {
                 value: None,
                 string_idx: None,
                 method_idx: Some(*method_idx),
-                args: args.clone(),
-                raw: format!(
-                    "invoke-static {{v{}}}, method@{}",
-                    args.iter()
-                        .map(|a| a.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", v"),
-                    method_idx
-                ),
+                args: args.iter().map(|&arg| arg.into()).collect(),
+                raw: format!("invoke-static {}, method@{}", format_register_list(args), method_idx),
             },
             Instruction::InvokeDirect { args, method_idx } => RustInstruction {
                 opcode: "invoke-direct".to_string(),
@@ -157,15 +209,8 @@ impl From<&Instruction> for RustInstruction {
                 value: None,
                 string_idx: None,
                 method_idx: Some(*method_idx),
-                args: args.clone(),
-                raw: format!(
-                    "invoke-direct {{v{}}}, method@{}",
-                    args.iter()
-                        .map(|a| a.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", v"),
-                    method_idx
-                ),
+                args: args.iter().map(|&arg| arg.into()).collect(),
+                raw: format!("invoke-direct {}, method@{}", format_register_list(args), method_idx),
             },
             Instruction::InvokeSuper { args, method_idx } => RustInstruction {
                 opcode: "invoke-super".to_string(),
@@ -173,15 +218,8 @@ impl From<&Instruction> for RustInstruction {
                 value: None,
                 string_idx: None,
                 method_idx: Some(*method_idx),
-                args: args.clone(),
-                raw: format!(
-                    "invoke-super {{v{}}}, method@{}",
-                    args.iter()
-                        .map(|a| a.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", v"),
-                    method_idx
-                ),
+                args: args.iter().map(|&arg| arg.into()).collect(),
+                raw: format!("invoke-super {}, method@{}", format_register_list(args), method_idx),
             },
             Instruction::InvokeInterface { args, method_idx } => RustInstruction {
                 opcode: "invoke-interface".to_string(),
@@ -189,15 +227,8 @@ impl From<&Instruction> for RustInstruction {
                 value: None,
                 string_idx: None,
                 method_idx: Some(*method_idx),
-                args: args.clone(),
-                raw: format!(
-                    "invoke-interface {{v{}}}, method@{}",
-                    args.iter()
-                        .map(|a| a.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", v"),
-                    method_idx
-                ),
+                args: args.iter().map(|&arg| arg.into()).collect(),
+                raw: format!("invoke-interface {}, method@{}", format_register_list(args), method_idx),
             },
             Instruction::InvokeVirtualRange {
                 first_arg,
@@ -209,13 +240,12 @@ impl From<&Instruction> for RustInstruction {
                 value: None,
                 string_idx: None,
                 method_idx: Some(*method_idx),
-                args: (*first_arg..(*first_arg + *arg_count as u16))
-                    .map(|r| r as u8)
+                args: (*first_arg as u32..*first_arg as u32 + *arg_count as u32)
+                    .filter_map(|r| u16::try_from(r).ok())
                     .collect(),
                 raw: format!(
-                    "invoke-virtual/range {{v{} .. v{}}}, method@{}",
-                    first_arg,
-                    first_arg + *arg_count as u16 - 1,
+                    "invoke-virtual/range {}, method@{}",
+                    format_register_range(*first_arg, *arg_count),
                     method_idx
                 ),
             },
@@ -229,16 +259,26 @@ impl From<&Instruction> for RustInstruction {
                 value: None,
                 string_idx: None,
                 method_idx: Some(*method_idx),
-                args: (*first_arg..(*first_arg + *arg_count as u16))
-                    .map(|r| r as u8)
+                args: (*first_arg as u32..*first_arg as u32 + *arg_count as u32)
+                    .filter_map(|r| u16::try_from(r).ok())
                     .collect(),
                 raw: format!(
-                    "invoke-static/range {{v{} .. v{}}}, method@{}",
-                    first_arg,
-                    first_arg + *arg_count as u16 - 1,
+                    "invoke-static/range {}, method@{}",
+                    format_register_range(*first_arg, *arg_count),
                     method_idx
                 ),
             },
+            Instruction::PackedSwitchPayload { .. }
+            | Instruction::SparseSwitchPayload { .. }
+            | Instruction::FillArrayDataPayload { .. } => RustInstruction {
+                opcode: insn.mnemonic(),
+                dest: None,
+                value: None,
+                string_idx: None,
+                method_idx: None,
+                args: Vec::new(),
+                raw: insn.to_string(),
+            },
             Instruction::Unknown { opcode, .. } => RustInstruction {
                 opcode: format!("unknown(0x{:02x})", opcode),
                 dest: None,
@@ -253,10 +293,36 @@ impl From<&Instruction> for RustInstruction {
 }
 
 /// Decode bytecode into instructions
+///
+/// With `strict=True`, raises ValueError (naming the word offset) on an
+/// unrecognized opcode instead of skipping a single word, since the rest of
+/// the disassembly would be unreliable.
 #[pyfunction]
-pub fn decode_bytecode(bytecode: Vec<u16>) -> Vec<RustInstruction> {
-    let instructions = InstructionDecoder::decode(&bytecode);
-    instructions.iter().map(RustInstruction::from).collect()
+#[pyo3(signature = (bytecode, strict=false))]
+pub fn decode_bytecode(bytecode: Vec<u16>, strict: bool) -> PyResult<Vec<RustInstruction>> {
+    let mode = if strict { DecodeMode::Strict } else { DecodeMode::Lenient };
+    let instructions = InstructionDecoder::decode_with_mode(&bytecode, mode)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    Ok(instructions.iter().map(RustInstruction::from).collect())
+}
+
+/// Decode exactly one instruction starting at `word_offset`
+///
+/// Returns the instruction and the offset of the following one, so callers
+/// can step through a method (or resync after a branch) without decoding it
+/// all. Raises ValueError if `word_offset` is past the end of `bytecode`.
+#[pyfunction]
+pub fn decode_instruction_at(bytecode: Vec<u16>, word_offset: usize) -> PyResult<(RustInstruction, usize)> {
+    if word_offset >= bytecode.len() {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Offset {} out of bounds for bytecode of {} words",
+            word_offset,
+            bytecode.len()
+        )));
+    }
+
+    let (instruction, consumed) = InstructionDecoder::decode_one(&bytecode, word_offset);
+    Ok((RustInstruction::from(&instruction), word_offset + consumed))
 }
 
 /// Extract constant values from bytecode
@@ -277,6 +343,41 @@ pub fn extract_constants(bytecode: Vec<u16>) -> Vec<i64> {
     constants
 }
 
+/// Resolve the strings loaded by `const-string` and `const-string/jumbo`
+///
+/// Indices the parser cannot resolve are skipped.
+pub fn string_constants(bytecode: &[u16], parser: &DexParser) -> Vec<String> {
+    InstructionDecoder::decode(bytecode)
+        .into_iter()
+        .filter_map(|insn| match insn {
+            Instruction::ConstString { string_idx, .. } | Instruction::ConstStringJumbo { string_idx, .. } => {
+                parser.get_string(string_idx).ok()
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Extract string constants from bytecode
+///
+/// Args:
+///     bytecode (list[int]): Method bytecode as 16-bit code units
+///     dex_data (bytes): The DEX file the method belongs to, used to
+///         resolve string indices
+///
+/// Returns:
+///     list[str]: Strings loaded by `const-string`/`const-string/jumbo`,
+///     in bytecode order
+///
+/// Raises:
+///     ValueError: If `dex_data` is not a valid DEX file
+#[pyfunction]
+pub fn extract_string_constants(bytecode: Vec<u16>, dex_data: Vec<u8>) -> PyResult<Vec<String>> {
+    let parser = DexParser::new(dex_data)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    Ok(string_constants(&bytecode, &parser))
+}
+
 /// Extract method calls from bytecode
 #[pyfunction]
 pub fn extract_method_calls(bytecode: Vec<u16>) -> Vec<u32> {
@@ -300,3 +401,130 @@ pub fn extract_method_calls(bytecode: Vec<u16>) -> Vec<u32> {
 
     method_calls
 }
+
+/// Add opcode counts of `bytecode` to an existing histogram
+pub fn accumulate_opcodes(bytecode: &[u16], histogram: &mut HashMap<String, u64>) {
+    for insn in InstructionDecoder::decode(bytecode) {
+        *histogram.entry(insn.mnemonic()).or_insert(0) += 1;
+    }
+}
+
+/// Merge opcode counts from `other` into `histogram`
+pub fn merge_histograms(mut histogram: HashMap<String, u64>, other: HashMap<String, u64>) -> HashMap<String, u64> {
+    for (opcode, count) in other {
+        *histogram.entry(opcode).or_insert(0) += count;
+    }
+    histogram
+}
+
+/// Count instructions by opcode mnemonic
+#[pyfunction]
+pub fn opcode_histogram(bytecode: Vec<u16>) -> HashMap<String, u64> {
+    let mut histogram = HashMap::new();
+    accumulate_opcodes(&bytecode, &mut histogram);
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opcode_histogram() {
+        // const/16 v0, #1; const/16 v1, #2; const-string v2, string@5
+        let bytecode = vec![0x0013, 0x0001, 0x0113, 0x0002, 0x021a, 0x0005];
+        let histogram = opcode_histogram(bytecode);
+
+        assert_eq!(histogram.get("const/16"), Some(&2));
+        assert_eq!(histogram.get("const-string"), Some(&1));
+        assert_eq!(histogram.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_histograms_matches_sum() {
+        let methods = vec![
+            vec![0x0013, 0x0001, 0x021a, 0x0005],
+            vec![0x0013, 0x0002],
+            vec![0x1071, 0x0003, 0x0000],
+        ];
+
+        let profile = methods
+            .iter()
+            .map(|m| opcode_histogram(m.clone()))
+            .fold(HashMap::new(), merge_histograms);
+
+        let mut combined = HashMap::new();
+        for method in &methods {
+            accumulate_opcodes(method, &mut combined);
+        }
+
+        assert_eq!(profile, combined);
+        assert_eq!(profile.get("const/16"), Some(&2));
+        assert_eq!(profile.get("const-string"), Some(&1));
+        assert_eq!(profile.get("invoke-static"), Some(&1));
+    }
+
+    #[test]
+    fn test_decode_instruction_at_steps_like_decode() {
+        // const/16 v0, #1; move v1, v0; invoke-static {v1}, method@3; const/4 v2, #1; return-void
+        let bytecode = vec![0x0013, 0x0001, 0x0101, 0x1071, 0x0003, 0x0001, 0x1212, 0x000e];
+        let full: Vec<String> = decode_bytecode(bytecode.clone(), false)
+            .unwrap()
+            .into_iter()
+            .map(|insn| insn.raw)
+            .collect();
+
+        let mut stepped = Vec::new();
+        let mut offsets = Vec::new();
+        let mut offset = 0;
+        while offset < bytecode.len() {
+            offsets.push(offset);
+            let (insn, next) = decode_instruction_at(bytecode.clone(), offset).unwrap();
+            stepped.push(insn.raw);
+            offset = next;
+        }
+
+        assert_eq!(stepped, full);
+        assert_eq!(offsets, vec![0, 2, 3, 6, 7]);
+
+        // Resync directly at the invoke
+        let (insn, next) = decode_instruction_at(bytecode.clone(), 3).unwrap();
+        assert_eq!(insn.opcode, "invoke-static");
+        assert_eq!(next, 6);
+    }
+
+    #[test]
+    fn test_extract_string_constants() {
+        use crate::dex::test_support::DexBuilder;
+
+        let mut builder = DexBuilder::new();
+        let hello = builder.string("hello");
+        let url = builder.string("https://example.com");
+        let dex = builder.build();
+
+        // const-string v0, "hello"; const-string/jumbo v1, "https://example.com"; const/4 v2, #1; return-void
+        let bytecode = vec![0x001a, hello as u16, 0x011b, url as u16, (url >> 16) as u16, 0x1212, 0x000e];
+        let decoded = decode_bytecode(bytecode.clone(), true).unwrap();
+        assert_eq!(decoded[1].opcode, "const-string/jumbo");
+        assert_eq!(decoded[1].string_idx, Some(url));
+        assert_eq!(decoded[2].opcode, "const/4");
+
+        assert_eq!(extract_string_constants(bytecode, dex).unwrap(), vec!["hello", "https://example.com"]);
+    }
+
+    #[test]
+    fn test_range_invoke_registers_above_255() {
+        // invoke-static/range {v300 .. v302}, method@7; move-object/16 v300, v1000
+        let insn = RustInstruction::from(&InstructionDecoder::decode(&[0x0377, 0x0007, 0x012c])[0]);
+        assert_eq!(insn.args, vec![300, 301, 302]);
+
+        let insn = RustInstruction::from(&InstructionDecoder::decode(&[0x0009, 0x012c, 0x03e8])[0]);
+        assert_eq!(insn.dest, Some(300));
+        assert_eq!(insn.args, vec![1000]);
+    }
+
+    #[test]
+    fn test_decode_instruction_at_out_of_bounds() {
+        assert!(decode_instruction_at(vec![0x000e], 1).is_err());
+    }
+}
@@ -4,14 +4,16 @@
 //! enabling analysis of call paths from entry points to specific APIs (e.g., WebView.loadUrl).
 
 use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use crate::dex::class_decompiler::{DecompiledClass, DecompiledMethod};
+use crate::dex::data_flow_analyzer::flow_endpoint_patterns;
 use crate::dex::expression_builder::ReconstructedExpression;
 
 /// Represents a method call edge in the call graph
 #[pyclass]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MethodCall {
     /// Caller method signature
     #[pyo3(get)]
@@ -89,6 +91,13 @@ pub struct CallGraph {
     methods: HashSet<String>,
 }
 
+/// Serialized form of a call graph: the reverse map is rebuilt on load
+#[derive(Serialize, Deserialize)]
+struct CallGraphJson {
+    methods: Vec<String>,
+    edges: Vec<MethodCall>,
+}
+
 impl CallGraph {
     /// Create a new empty call graph
     pub fn new() -> Self {
@@ -123,6 +132,92 @@ impl CallGraph {
             .push(caller);
     }
 
+    /// Add many call edges at once
+    pub fn add_calls(&mut self, calls: Vec<MethodCall>) {
+        for call in calls {
+            self.add_call(call.caller, call.callee, call.call_site);
+        }
+    }
+
+    /// Union another graph into this one
+    ///
+    /// Edges already present (same caller, callee and call site) are not
+    /// duplicated; forward and reverse maps are updated together through
+    /// `add_call`, so caller/callee queries stay consistent.
+    pub fn merge(&mut self, other: CallGraph) {
+        let CallGraph { graph, methods, .. } = other;
+
+        for call in graph.into_values().flatten() {
+            let exists = self.graph.get(&call.caller).is_some_and(|calls| {
+                calls
+                    .iter()
+                    .any(|c| c.callee == call.callee && c.call_site == call.call_site)
+            });
+            if !exists {
+                self.add_call(call.caller, call.callee, call.call_site);
+            }
+        }
+
+        self.methods.extend(methods);
+    }
+
+    /// Serialize the method set and forward edges (with call sites) to JSON
+    ///
+    /// Callers are written in sorted order and each caller's edges in
+    /// insertion order, so `find_paths` results survive a round trip.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        let mut methods: Vec<String> = self.methods.iter().cloned().collect();
+        methods.sort();
+
+        let mut callers: Vec<&String> = self.graph.keys().collect();
+        callers.sort();
+        let edges = callers
+            .into_iter()
+            .flat_map(|caller| self.graph[caller].iter().cloned())
+            .collect();
+
+        serde_json::to_string(&CallGraphJson { methods, edges })
+    }
+
+    /// Load a graph written by `to_json`, rebuilding the reverse map
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let data: CallGraphJson = serde_json::from_str(json)?;
+
+        let mut graph = CallGraph::new();
+        graph.add_calls(data.edges);
+        graph.methods.extend(data.methods);
+        Ok(graph)
+    }
+
+    /// Render the graph in Graphviz DOT format
+    ///
+    /// One node per method and one edge per call, in sorted order so the
+    /// output is stable. Methods containing any of the `highlight`
+    /// substrings (e.g. sink names) are filled red.
+    pub fn to_dot(&self, highlight: Option<Vec<String>>) -> String {
+        let highlight = highlight.unwrap_or_default();
+        let mut out = String::from("digraph CallGraph {\n    rankdir=LR;\n    node [shape=box, fontsize=10];\n");
+
+        let mut methods: Vec<&String> = self.methods.iter().collect();
+        methods.sort();
+        for method in methods {
+            if highlight.iter().any(|pattern| method.contains(pattern.as_str())) {
+                out.push_str(&format!("    {} [style=filled, fillcolor=\"#f4a6a6\"];\n", dot_id(method)));
+            } else {
+                out.push_str(&format!("    {};\n", dot_id(method)));
+            }
+        }
+
+        let mut callers: Vec<&String> = self.graph.keys().collect();
+        callers.sort();
+        for call in callers.into_iter().flat_map(|caller| &self.graph[caller]) {
+            out.push_str(&format!("    {} -> {};\n", dot_id(&call.caller), dot_id(&call.callee)));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
     /// Get all methods called by a given method
     pub fn get_callees(&self, method: &str) -> Vec<String> {
         self.graph
@@ -140,7 +235,15 @@ impl CallGraph {
     }
 
     /// Find all paths from source to target method (BFS with depth limit)
-    pub fn find_paths(&self, source: &str, target: &str, max_depth: usize) -> Vec<CallPath> {
+    ///
+    /// Paths come out shortest first: the BFS dequeues partial paths in
+    /// order of length. With `max_paths` the search stops as soon as that
+    /// many paths are found, which bounds the work on dense graphs.
+    pub fn find_paths(&self, source: &str, target: &str, max_depth: usize, max_paths: Option<usize>) -> Vec<CallPath> {
+        if max_paths == Some(0) {
+            return Vec::new();
+        }
+
         let mut paths = Vec::new();
         let mut queue: VecDeque<(String, Vec<String>, Vec<MethodCall>)> = VecDeque::new();
 
@@ -160,6 +263,9 @@ impl CallGraph {
                     calls: calls.clone(),
                     length: path.len() - 1,
                 });
+                if max_paths.is_some_and(|cap| paths.len() >= cap) {
+                    break;
+                }
                 continue;
             }
 
@@ -185,6 +291,127 @@ impl CallGraph {
         paths
     }
 
+    /// Whether any method containing `target` is reachable from `source`
+    ///
+    /// A BFS over the forward graph that stops at the first match, without
+    /// building paths; `target` matches by substring, as in `find_paths`.
+    pub fn is_reachable(&self, source: &str, target: &str) -> bool {
+        let mut visited = HashSet::from([source]);
+        let mut queue = VecDeque::from([source]);
+        while let Some(current) = queue.pop_front() {
+            for call in self.graph.get(current).into_iter().flatten() {
+                if call.callee.contains(target) {
+                    return true;
+                }
+                if visited.insert(call.callee.as_str()) {
+                    queue.push_back(call.callee.as_str());
+                }
+            }
+        }
+        false
+    }
+
+    /// Every method reachable from `source` through one or more calls
+    ///
+    /// `source` itself is included only if it is on a cycle.
+    pub fn reachable_set(&self, source: &str) -> HashSet<String> {
+        self.transitive(source, |method| {
+            self.graph.get(method).into_iter().flatten().map(|call| call.callee.as_str()).collect()
+        })
+    }
+
+    /// Every method that reaches `target` through one or more calls
+    ///
+    /// The reverse counterpart of `reachable_set`; `target` is an exact
+    /// method name and is included only if it is on a cycle.
+    pub fn callers_transitive(&self, target: &str) -> HashSet<String> {
+        self.transitive(target, |method| {
+            self.reverse_graph.get(method).into_iter().flatten().map(String::as_str).collect()
+        })
+    }
+
+    /// Methods not reachable from any of `roots` (e.g. entry point
+    /// lifecycle methods), sorted
+    ///
+    /// Roots count as live. The graph has no notion of which methods are
+    /// defined in the app, so library methods called only from dead code
+    /// are reported too; filter by package if only app code matters.
+    pub fn dead_methods(&self, roots: Vec<String>) -> Vec<String> {
+        let mut live: HashSet<String> = HashSet::new();
+        for root in roots {
+            if !live.contains(&root) {
+                live.extend(self.reachable_set(&root));
+            }
+            live.insert(root);
+        }
+
+        let mut dead: Vec<String> = self.methods.iter().filter(|m| !live.contains(*m)).cloned().collect();
+        dead.sort();
+        dead
+    }
+
+    /// BFS closure of `start` under `next`, excluding `start` unless revisited
+    fn transitive<'a>(&'a self, start: &str, next: impl Fn(&str) -> Vec<&'a str>) -> HashSet<String> {
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = next(start).into();
+        while let Some(current) = queue.pop_front() {
+            if visited.insert(current) {
+                queue.extend(next(current));
+            }
+        }
+        visited.into_iter().map(str::to_string).collect()
+    }
+
+    /// Shortest path from source to target, stopping at the first hit
+    pub fn find_shortest_path(&self, source: &str, target: &str, max_depth: usize) -> Option<CallPath> {
+        self.find_paths(source, target, max_depth, Some(1)).pop()
+    }
+
+    /// Find call cycles (recursion) of at most `max_len` methods
+    ///
+    /// Each cycle is reported once as the ordered list of methods along it,
+    /// starting from its lexicographically smallest method, without repeating
+    /// the start at the end. A self-recursive method is a one-element cycle.
+    pub fn find_cycles(&self, max_len: usize) -> Vec<Vec<String>> {
+        let mut starts: Vec<&String> = self.graph.keys().collect();
+        starts.sort();
+
+        let mut cycles = Vec::new();
+        for start in starts {
+            let mut path = vec![start.clone()];
+            self.collect_cycles(start, start, max_len, &mut path, &mut cycles);
+        }
+        cycles
+    }
+
+    /// DFS for paths returning to `start` that only visit methods ordered
+    /// after it, so every cycle is found from exactly one rotation
+    fn collect_cycles(
+        &self,
+        start: &str,
+        current: &str,
+        max_len: usize,
+        path: &mut Vec<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        let mut callees: Vec<&String> = match self.graph.get(current) {
+            Some(calls) => calls.iter().map(|c| &c.callee).collect(),
+            None => return,
+        };
+        callees.sort();
+        callees.dedup();
+
+        for callee in callees {
+            if callee == start {
+                cycles.push(path.clone());
+            } else if callee.as_str() > start && path.len() < max_len && !path.contains(callee) {
+                path.push(callee.clone());
+                self.collect_cycles(start, callee, max_len, path, cycles);
+                path.pop();
+            }
+        }
+    }
+
     /// Find all methods that match a pattern (e.g., "WebView.loadUrl")
     pub fn find_methods_matching(&self, pattern: &str) -> Vec<String> {
         self.methods
@@ -193,6 +420,19 @@ impl CallGraph {
             .cloned()
             .collect()
     }
+
+    /// All methods declared on exactly `class_name` (e.g. "com.example.Pay"),
+    /// excluding nested classes, sorted by name
+    pub fn methods_of_class(&self, class_name: &str) -> Vec<String> {
+        let mut methods: Vec<String> = self
+            .methods
+            .iter()
+            .filter(|m| m.rsplit_once('.').is_some_and(|(class, _)| class == class_name))
+            .cloned()
+            .collect();
+        methods.sort();
+        methods
+    }
 }
 
 #[pymethods]
@@ -215,10 +455,86 @@ impl CallGraph {
         self.get_callers(method)
     }
 
-    /// Find paths between two methods
-    #[pyo3(name = "find_paths")]
-    pub fn find_paths_py(&self, source: &str, target: &str, max_depth: Option<usize>) -> Vec<CallPath> {
-        self.find_paths(source, target, max_depth.unwrap_or(10))
+    /// Find paths between two methods, shortest first, at most `max_paths`
+    #[pyo3(name = "find_paths", signature = (source, target, max_depth=None, max_paths=None))]
+    pub fn find_paths_py(
+        &self,
+        source: &str,
+        target: &str,
+        max_depth: Option<usize>,
+        max_paths: Option<usize>,
+    ) -> Vec<CallPath> {
+        self.find_paths(source, target, max_depth.unwrap_or(10), max_paths)
+    }
+
+    /// Whether any method containing `target` is reachable from `source`
+    #[pyo3(name = "is_reachable")]
+    pub fn is_reachable_py(&self, source: &str, target: &str) -> bool {
+        self.is_reachable(source, target)
+    }
+
+    /// All methods transitively called from `source`
+    #[pyo3(name = "reachable_set")]
+    pub fn reachable_set_py(&self, source: &str) -> HashSet<String> {
+        self.reachable_set(source)
+    }
+
+    /// All methods that transitively call `target`
+    #[pyo3(name = "callers_transitive")]
+    pub fn callers_transitive_py(&self, target: &str) -> HashSet<String> {
+        self.callers_transitive(target)
+    }
+
+    /// Methods not reachable from any of `roots`, sorted
+    #[pyo3(name = "dead_methods")]
+    pub fn dead_methods_py(&self, roots: Vec<String>) -> Vec<String> {
+        self.dead_methods(roots)
+    }
+
+    /// Shortest path between two methods, or None
+    #[pyo3(name = "find_shortest_path", signature = (source, target, max_depth=None))]
+    pub fn find_shortest_path_py(&self, source: &str, target: &str, max_depth: Option<usize>) -> Option<CallPath> {
+        self.find_shortest_path(source, target, max_depth.unwrap_or(10))
+    }
+
+    /// Add many call edges at once
+    #[pyo3(name = "add_calls")]
+    pub fn add_calls_py(&mut self, calls: Vec<MethodCall>) {
+        self.add_calls(calls);
+    }
+
+    /// Union another graph into this one
+    #[pyo3(name = "merge")]
+    pub fn merge_py(&mut self, other: &CallGraph) {
+        self.merge(other.clone());
+    }
+
+    /// Find recursive call cycles of at most `max_len` methods
+    #[pyo3(name = "find_cycles", signature = (max_len=10))]
+    pub fn find_cycles_py(&self, max_len: usize) -> Vec<Vec<String>> {
+        self.find_cycles(max_len)
+    }
+
+    /// Serialize the graph to JSON (see `CallGraph.from_json`)
+    #[pyo3(name = "to_json")]
+    pub fn to_json_py(&self) -> PyResult<String> {
+        self.to_json()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Load a graph previously saved with `to_json`
+    #[staticmethod]
+    #[pyo3(name = "from_json")]
+    pub fn from_json_py(json: &str) -> PyResult<CallGraph> {
+        CallGraph::from_json(json)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Render the graph in Graphviz DOT format, filling methods that
+    /// contain any `highlight` substring
+    #[pyo3(name = "to_dot", signature = (highlight=None))]
+    pub fn to_dot_py(&self, highlight: Option<Vec<String>>) -> String {
+        self.to_dot(highlight)
     }
 
     /// Find methods matching a pattern
@@ -246,9 +562,55 @@ impl CallGraph {
     }
 }
 
+/// Package prefixes treated as framework code
+pub const DEFAULT_FRAMEWORK_PREFIXES: &[&str] = &[
+    "android.",
+    "androidx.",
+    "java.",
+    "javax.",
+    "kotlin.",
+    "kotlinx.",
+    "dalvik.",
+];
+
+/// Drops edges into framework packages, except for allowlisted callees
+///
+/// The default allowlist is every sink and Intent source pattern of the
+/// data flow analyzer, matched as substrings of the callee, so excluding
+/// framework edges never hides a flow endpoint.
+#[derive(Clone, Debug)]
+pub struct FrameworkFilter {
+    prefixes: Vec<String>,
+    keep: Vec<String>,
+}
+
+impl Default for FrameworkFilter {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl FrameworkFilter {
+    /// Create a filter, falling back to the defaults for missing lists
+    pub fn new(prefixes: Option<Vec<String>>, keep: Option<Vec<String>>) -> Self {
+        let to_owned = |defaults: &[&str]| defaults.iter().map(|s| s.to_string()).collect();
+        Self {
+            prefixes: prefixes.unwrap_or_else(|| to_owned(DEFAULT_FRAMEWORK_PREFIXES)),
+            keep: keep.unwrap_or_else(flow_endpoint_patterns),
+        }
+    }
+
+    /// Whether an edge into `callee` should be dropped
+    pub fn excludes(&self, callee: &str) -> bool {
+        self.prefixes.iter().any(|p| callee.starts_with(p.as_str()))
+            && !self.keep.iter().any(|k| callee.contains(k.as_str()))
+    }
+}
+
 /// Call Graph Builder - constructs call graphs from decompiled classes
 pub struct CallGraphBuilder {
     graph: CallGraph,
+    framework_filter: Option<FrameworkFilter>,
 }
 
 impl CallGraphBuilder {
@@ -256,9 +618,16 @@ impl CallGraphBuilder {
     pub fn new() -> Self {
         Self {
             graph: CallGraph::new(),
+            framework_filter: None,
         }
     }
 
+    /// Drop edges whose callee is matched by `filter`
+    pub fn with_framework_filter(mut self, filter: FrameworkFilter) -> Self {
+        self.framework_filter = Some(filter);
+        self
+    }
+
     /// Add a decompiled class to the call graph
     pub fn add_class(&mut self, class: &DecompiledClass) {
         for method in &class.methods {
@@ -273,6 +642,9 @@ impl CallGraphBuilder {
         // Extract method calls from expressions
         for expr in &method.expressions {
             if let Some(call_info) = self.extract_method_call(expr) {
+                if self.framework_filter.as_ref().is_some_and(|f| f.excludes(&call_info)) {
+                    continue;
+                }
                 let call_site = format!("{}:{}", method.name, expr.value_type);
                 self.graph.add_call(method_sig.clone(), call_info, call_site);
             }
@@ -355,10 +727,19 @@ impl PyCallGraphBuilder {
 }
 
 /// Build a call graph from an APK file
+///
+/// With `exclude_framework`, edges into `framework_prefixes` (default:
+/// android., androidx., java., javax., kotlin., kotlinx., dalvik.) are
+/// dropped unless the callee contains one of `keep_callees` (default: the
+/// data flow analyzer's sink patterns and Intent source getters).
 #[pyfunction]
+#[pyo3(signature = (apk_path, class_filter=None, exclude_framework=false, framework_prefixes=None, keep_callees=None))]
 pub fn build_call_graph_from_apk(
     apk_path: String,
     class_filter: Option<Vec<String>>,
+    exclude_framework: bool,
+    framework_prefixes: Option<Vec<String>>,
+    keep_callees: Option<Vec<String>>,
 ) -> PyResult<CallGraph> {
     use crate::apk::ApkExtractor;
     use crate::dex::parser::DexParser;
@@ -368,10 +749,13 @@ pub fn build_call_graph_from_apk(
         .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
 
     let mut builder = CallGraphBuilder::new();
+    if exclude_framework {
+        builder = builder.with_framework_filter(FrameworkFilter::new(framework_prefixes, keep_callees));
+    }
 
     // Process each DEX file
     for dex_entry in extractor.dex_entries() {
-        let parser = match DexParser::new(dex_entry.data.clone()) {
+        let parser = match DexParser::from_arc(dex_entry.data.clone()) {
             Ok(p) => p,
             Err(_) => continue,
         };
@@ -396,7 +780,7 @@ pub fn build_call_graph_from_apk(
             }
 
             // Decompile and add to graph
-            if let Ok(decompiled) = decompile_class(&parser, class_def, &dex_entry.data) {
+            if let Ok(decompiled) = decompile_class(&parser, class_def) {
                 builder.add_class(&decompiled);
             }
         }
@@ -405,12 +789,68 @@ pub fn build_call_graph_from_apk(
     Ok(builder.build())
 }
 
+/// Counters collected while building a call graph
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildStats {
+    /// Classes decompiled and added to the graph
+    #[pyo3(get)]
+    pub classes_processed: usize,
+
+    /// Classes that could not be read or decompiled
+    #[pyo3(get)]
+    pub classes_skipped: usize,
+
+    /// Edges in the resulting graph
+    #[pyo3(get)]
+    pub edges_added: usize,
+
+    /// DEX files that parsed successfully
+    #[pyo3(get)]
+    pub dex_files: usize,
+}
+
+#[pymethods]
+impl BuildStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "BuildStats(classes_processed={}, classes_skipped={}, edges_added={}, dex_files={})",
+            self.classes_processed, self.classes_skipped, self.edges_added, self.dex_files
+        )
+    }
+}
+
 /// Build a call graph from an APK file (parallel version - optimized)
+///
+/// Accepts the same framework-edge options as `build_call_graph_from_apk`.
 #[pyfunction]
+#[pyo3(signature = (apk_path, class_filter=None, exclude_framework=false, framework_prefixes=None, keep_callees=None))]
 pub fn build_call_graph_from_apk_parallel(
     apk_path: String,
     class_filter: Option<Vec<String>>,
+    exclude_framework: bool,
+    framework_prefixes: Option<Vec<String>>,
+    keep_callees: Option<Vec<String>>,
 ) -> PyResult<CallGraph> {
+    build_call_graph_with_stats(apk_path, class_filter, exclude_framework, framework_prefixes, keep_callees)
+        .map(|(graph, _)| graph)
+}
+
+/// Build a call graph in parallel and report how the build went
+///
+/// Same as `build_call_graph_from_apk_parallel`, but also returns
+/// `BuildStats`. A high `classes_skipped` relative to `classes_processed`
+/// means many classes failed to decompile and the graph is incomplete.
+/// Classes excluded by `class_filter` are counted in neither.
+#[pyfunction]
+#[pyo3(signature = (apk_path, class_filter=None, exclude_framework=false, framework_prefixes=None, keep_callees=None))]
+pub fn build_call_graph_with_stats(
+    apk_path: String,
+    class_filter: Option<Vec<String>>,
+    exclude_framework: bool,
+    framework_prefixes: Option<Vec<String>>,
+    keep_callees: Option<Vec<String>>,
+) -> PyResult<(CallGraph, BuildStats)> {
     use crate::apk::ApkExtractor;
     use crate::dex::parser::DexParser;
     use crate::dex::class_decompiler::decompile_class;
@@ -420,28 +860,34 @@ pub fn build_call_graph_from_apk_parallel(
     let extractor = ApkExtractor::new(&apk_path)
         .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
 
-    // Collect all classes to process with shared Arc-wrapped data
+    let mut stats = BuildStats::default();
+
+    // Collect all classes to process with a shared parser per DEX
     let mut tasks = Vec::new();
 
     for dex_entry in extractor.dex_entries() {
-        // Wrap DEX data in Arc - shared across all threads, no cloning!
-        let dex_data = Arc::new(dex_entry.data.clone());
-
-        // Create parser once with cloned data (one-time cost per DEX file)
-        let parser = match DexParser::new((*dex_data).clone()) {
-            Ok(p) => Arc::new(p),  // Wrap parser in Arc too
+        // The parser shares the entry's buffer, so no DEX bytes are copied
+        let parser = match DexParser::from_arc(dex_entry.data.clone()) {
+            Ok(p) => Arc::new(p),
             Err(_) => continue,
         };
+        stats.dex_files += 1;
 
         for class_idx in 0..parser.class_count() {
             let class_def = match parser.get_class_def(class_idx) {
                 Ok(c) => c,
-                Err(_) => continue,
+                Err(_) => {
+                    stats.classes_skipped += 1;
+                    continue;
+                }
             };
 
             let class_name = match parser.get_type_name(class_def.class_idx) {
                 Ok(n) => n,
-                Err(_) => continue,
+                Err(_) => {
+                    stats.classes_skipped += 1;
+                    continue;
+                }
             };
 
             // Apply filter if provided
@@ -451,26 +897,393 @@ pub fn build_call_graph_from_apk_parallel(
                 }
             }
 
-            // Store: (Arc<parser>, Arc<dex_data>, class_def)
             // Arc cloning is cheap (just atomic reference count increment)
-            tasks.push((Arc::clone(&parser), Arc::clone(&dex_data), class_def));
+            tasks.push((Arc::clone(&parser), class_def));
         }
     }
 
     // Process classes in parallel - each thread uses shared parser and data
     let decompiled_classes: Vec<_> = tasks
         .par_iter()
-        .filter_map(|(parser, dex_data, class_def)| {
-            // Use shared parser and data - NO CLONING!
-            decompile_class(parser.as_ref(), class_def.clone(), dex_data.as_ref()).ok()
-        })
+        .filter_map(|(parser, class_def)| decompile_class(parser.as_ref(), class_def.clone()).ok())
         .collect();
+    stats.classes_processed = decompiled_classes.len();
+    stats.classes_skipped += tasks.len() - decompiled_classes.len();
 
     // Build graph from decompiled classes (sequential - fast)
     let mut builder = CallGraphBuilder::new();
+    if exclude_framework {
+        builder = builder.with_framework_filter(FrameworkFilter::new(framework_prefixes, keep_callees));
+    }
     for decompiled in decompiled_classes {
         builder.add_class(&decompiled);
     }
 
-    Ok(builder.build())
+    let graph = builder.build();
+    stats.edges_added = graph.graph.values().map(|v| v.len()).sum();
+    Ok((graph, stats))
+}
+
+/// Quote a method signature as a DOT identifier
+///
+/// Signatures contain `.`, `<init>`, `$` and spaces, so every ID is a
+/// quoted string with backslashes, quotes and line breaks escaped.
+fn dot_id(name: &str) -> String {
+    let mut id = String::with_capacity(name.len() + 2);
+    id.push('"');
+    for c in name.chars() {
+        match c {
+            '"' => id.push_str("\\\""),
+            '\\' => id.push_str("\\\\"),
+            '\n' => id.push_str("\\n"),
+            '\r' => {}
+            c => id.push(c),
+        }
+    }
+    id.push('"');
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(caller: &str, callee: &str) -> MethodCall {
+        MethodCall {
+            caller: caller.to_string(),
+            callee: callee.to_string(),
+            call_site: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_merge_graphs() {
+        let mut base = CallGraph::new();
+        base.add_calls(vec![
+            call("com.example.MainActivity.onCreate", "com.example.Router.open"),
+            call("com.example.Router.open", "com.example.feature.ChatActivity.start"),
+        ]);
+
+        let mut feature = CallGraph::new();
+        feature.add_calls(vec![
+            call("com.example.Router.open", "com.example.feature.ChatActivity.start"),
+            call("com.example.feature.ChatActivity.start", "android.webkit.WebView.loadUrl"),
+        ]);
+
+        base.merge(feature);
+
+        let paths = base.find_paths("com.example.MainActivity.onCreate", "WebView.loadUrl", 10, None);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].length, 3);
+
+        // The shared edge is not duplicated in either direction
+        assert_eq!(base.get_callees("com.example.Router.open").len(), 1);
+        assert_eq!(base.get_callers("com.example.feature.ChatActivity.start"), vec!["com.example.Router.open"]);
+        assert_eq!(base.get_callers("android.webkit.WebView.loadUrl"), vec!["com.example.feature.ChatActivity.start"]);
+        assert_eq!(base.get_stats()["total_methods"], 4);
+        assert_eq!(base.get_stats()["total_edges"], 3);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut graph = CallGraph::new();
+        graph.add_calls(vec![
+            call("com.example.MainActivity.onCreate", "com.example.Web.<init>"),
+            call("com.example.MainActivity.onCreate", "com.example.Web.open"),
+            call("com.example.Web.open", "android.webkit.WebView.loadUrl"),
+            call("com.example.Odd\"Name\\.run", "android.webkit.WebView.loadUrl"),
+        ]);
+
+        let dot = graph.to_dot(Some(vec!["WebView.loadUrl".to_string()]));
+        assert!(dot.starts_with("digraph CallGraph {\n"));
+        assert!(dot.ends_with("}\n"));
+
+        let edges: Vec<(&str, &str)> = dot
+            .lines()
+            .filter_map(|line| line.trim().strip_suffix(';')?.split_once(" -> "))
+            .collect();
+        assert_eq!(edges.len(), graph.get_stats()["total_edges"]);
+        assert!(edges.contains(&("\"com.example.MainActivity.onCreate\"", "\"com.example.Web.<init>\"")));
+        assert!(edges.contains(&("\"com.example.Odd\\\"Name\\\\.run\"", "\"android.webkit.WebView.loadUrl\"")));
+
+        let highlighted: Vec<&str> = dot.lines().filter(|line| line.contains("fillcolor")).collect();
+        assert_eq!(highlighted, vec!["    \"android.webkit.WebView.loadUrl\" [style=filled, fillcolor=\"#f4a6a6\"];"]);
+        assert_eq!(graph.to_dot(None).matches("fillcolor").count(), 0);
+    }
+
+    #[test]
+    fn test_find_paths_shortest_first_with_cap() {
+        // Diamond: Main -> A -> Sink, Main -> B -> C -> Sink, and Main -> Sink
+        let mut graph = CallGraph::new();
+        graph.add_calls(vec![
+            call("com.example.Main.run", "com.example.B.go"),
+            call("com.example.Main.run", "com.example.A.go"),
+            call("com.example.B.go", "com.example.C.go"),
+            call("com.example.A.go", "android.webkit.WebView.loadUrl"),
+            call("com.example.C.go", "android.webkit.WebView.loadUrl"),
+            call("com.example.Main.run", "android.webkit.WebView.loadUrl"),
+        ]);
+
+        let paths = graph.find_paths("com.example.Main.run", "WebView.loadUrl", 10, None);
+        let lengths: Vec<usize> = paths.iter().map(|p| p.length).collect();
+        assert_eq!(lengths, vec![1, 2, 3]);
+
+        let capped = graph.find_paths("com.example.Main.run", "WebView.loadUrl", 10, Some(2));
+        assert_eq!(capped.iter().map(|p| p.length).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(graph.find_paths("com.example.Main.run", "WebView.loadUrl", 10, Some(0)).is_empty());
+
+        let shortest = graph.find_shortest_path("com.example.Main.run", "WebView.loadUrl", 10).unwrap();
+        assert_eq!(shortest.methods, vec!["com.example.Main.run", "android.webkit.WebView.loadUrl"]);
+        assert!(graph.find_shortest_path("com.example.A.go", "com.example.C.go", 10).is_none());
+    }
+
+    #[test]
+    fn test_reachability_with_cycles() {
+        let mut graph = CallGraph::new();
+        graph.add_calls(vec![
+            call("com.example.Main.run", "com.example.Parser.parseList"),
+            call("com.example.Parser.parseList", "com.example.Parser.parseValue"),
+            call("com.example.Parser.parseValue", "com.example.Parser.parseList"),
+            call("com.example.Parser.parseValue", "com.example.Web.show"),
+            call("com.example.Web.show", "android.webkit.WebView.loadUrl"),
+            call("com.example.Other.idle", "com.example.Other.idle"),
+        ]);
+
+        assert!(graph.is_reachable("com.example.Main.run", "WebView.loadUrl"));
+        assert!(graph.is_reachable("com.example.Parser.parseValue", "Parser.parseValue"));
+        assert!(!graph.is_reachable("com.example.Other.idle", "WebView.loadUrl"));
+        assert!(!graph.is_reachable("com.example.Unknown.run", "WebView.loadUrl"));
+
+        let reachable = graph.reachable_set("com.example.Main.run");
+        assert_eq!(reachable.len(), 4);
+        assert!(!reachable.contains("com.example.Main.run"));
+        assert!(graph.reachable_set("com.example.Parser.parseList").contains("com.example.Parser.parseList"));
+        assert_eq!(graph.reachable_set("com.example.Other.idle"), HashSet::from(["com.example.Other.idle".to_string()]));
+
+        let callers = graph.callers_transitive("android.webkit.WebView.loadUrl");
+        let expected: HashSet<String> = [
+            "com.example.Web.show",
+            "com.example.Parser.parseValue",
+            "com.example.Parser.parseList",
+            "com.example.Main.run",
+        ]
+        .iter()
+        .map(|m| m.to_string())
+        .collect();
+        assert_eq!(callers, expected);
+        assert!(graph.callers_transitive("com.example.Main.run").is_empty());
+    }
+
+    #[test]
+    fn test_dead_methods() {
+        let mut graph = CallGraph::new();
+        graph.add_calls(vec![
+            call("com.example.MainActivity.onCreate", "com.example.Web.show"),
+            call("com.example.Web.show", "android.webkit.WebView.loadUrl"),
+            call("com.example.Legacy.sync", "com.example.Legacy.retry"),
+            call("com.example.Legacy.retry", "com.example.Legacy.sync"),
+            call("com.example.Legacy.retry", "okhttp3.OkHttpClient.newCall"),
+        ]);
+        graph.methods.insert("com.example.Util.unused".to_string());
+
+        assert_eq!(
+            graph.dead_methods(vec!["com.example.MainActivity.onCreate".to_string()]),
+            vec![
+                "com.example.Legacy.retry",
+                "com.example.Legacy.sync",
+                "com.example.Util.unused",
+                "okhttp3.OkHttpClient.newCall",
+            ]
+        );
+        assert_eq!(
+            graph.dead_methods(vec![
+                "com.example.MainActivity.onCreate".to_string(),
+                "com.example.Legacy.sync".to_string(),
+            ]),
+            vec!["com.example.Util.unused"]
+        );
+        assert_eq!(graph.dead_methods(Vec::new()).len(), graph.get_stats()["total_methods"]);
+    }
+
+    #[test]
+    fn test_find_cycles() {
+        let mut graph = CallGraph::new();
+        graph.add_calls(vec![
+            call("com.example.Parser.parseList", "com.example.Parser.parseValue"),
+            call("com.example.Parser.parseValue", "com.example.Parser.parseList"),
+            call("com.example.Parser.parseValue", "com.example.Parser.parseValue"),
+            call("com.example.Main.run", "com.example.Parser.parseList"),
+            call("com.example.Math.fact", "com.example.Math.fact"),
+        ]);
+
+        let cycles = graph.find_cycles(10);
+        assert_eq!(
+            cycles,
+            vec![
+                vec!["com.example.Math.fact".to_string()],
+                vec!["com.example.Parser.parseList".to_string(), "com.example.Parser.parseValue".to_string()],
+                vec!["com.example.Parser.parseValue".to_string()],
+            ]
+        );
+
+        // Only self-loops fit in a length-1 bound
+        assert_eq!(graph.find_cycles(1).len(), 2);
+
+        // find_paths never revisits a method on a cycle
+        let paths = graph.find_paths("com.example.Main.run", "com.example.Parser.parseValue", 10, None);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].length, 2);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut graph = CallGraph::new();
+        graph.add_call(
+            "com.example.MainActivity.onCreate".to_string(),
+            "com.example.Web.open".to_string(),
+            "onCreate:void".to_string(),
+        );
+        graph.add_calls(vec![
+            call("com.example.MainActivity.onCreate", "com.example.Web.prefetch"),
+            call("com.example.Web.open", "android.webkit.WebView.loadUrl"),
+            call("com.example.Web.prefetch", "android.webkit.WebView.loadUrl"),
+            call("com.example.DeepLink.onNewIntent", "com.example.Web.open"),
+        ]);
+        // A method with no edges survives through the method list
+        graph.methods.insert("com.example.Unused.run".to_string());
+
+        let json = graph.to_json().unwrap();
+        let loaded = CallGraph::from_json(&json).unwrap();
+
+        let path_methods = |g: &CallGraph| -> Vec<Vec<String>> {
+            g.find_paths("com.example.MainActivity.onCreate", "WebView.loadUrl", 10, None)
+                .into_iter()
+                .map(|p| p.methods)
+                .collect()
+        };
+        assert_eq!(path_methods(&loaded), path_methods(&graph));
+        assert_eq!(path_methods(&loaded).len(), 2);
+
+        let sorted_callers = |g: &CallGraph, m: &str| {
+            let mut callers = g.get_callers(m);
+            callers.sort();
+            callers
+        };
+        for method in ["com.example.Web.open", "android.webkit.WebView.loadUrl"] {
+            assert_eq!(sorted_callers(&loaded, method), sorted_callers(&graph, method));
+        }
+
+        let paths = loaded.find_paths("com.example.MainActivity.onCreate", "com.example.Web.open", 10, None);
+        assert_eq!(paths[0].calls[0].call_site, "onCreate:void");
+        assert_eq!(loaded.get_stats(), graph.get_stats());
+        assert!(loaded.get_all_methods_py().contains(&"com.example.Unused.run".to_string()));
+        assert_eq!(loaded.to_json().unwrap(), json);
+
+        assert!(CallGraph::from_json("{\"edges\": 3}").is_err());
+    }
+
+    fn invoking(name: &str, callees: &[&str]) -> DecompiledMethod {
+        DecompiledMethod {
+            name: name.to_string(),
+            signature: "()V".to_string(),
+            access_flags: 0x0001,
+            is_public: true,
+            is_private: false,
+            is_static: false,
+            parameters: Vec::new(),
+            return_type: "void".to_string(),
+            expressions: callees
+                .iter()
+                .map(|callee| ReconstructedExpression {
+                    expression: format!("{}()", callee),
+                    value_type: "void".to_string(),
+                    is_method_call: true,
+                    method_signature: Some(callee.to_string()),
+                })
+                .collect(),
+            bytecode_size: 0,
+            source_file: None,
+            line_numbers: Vec::new(),
+            caught_exceptions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_exclude_framework_edges() {
+        let class = DecompiledClass {
+            class_name: "com.example.BrowserActivity".to_string(),
+            package: "com.example".to_string(),
+            simple_name: "BrowserActivity".to_string(),
+            superclass: Some("android.app.Activity".to_string()),
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+            methods: vec![invoking(
+                "onCreate",
+                &[
+                    "android.app.Activity.onCreate",
+                    "java.lang.StringBuilder.append",
+                    "androidx.core.content.ContextCompat.getColor",
+                    "com.example.Router.open",
+                    "android.webkit.WebView.loadUrl",
+                    "android.content.Intent.getStringExtra",
+                ],
+            )],
+            access_flags: 0x0001,
+            static_field_values: Default::default(),
+        };
+        let callees = |graph: &CallGraph| {
+            let mut callees = graph.get_callees("com.example.BrowserActivity.onCreate");
+            callees.sort();
+            callees
+        };
+
+        let mut builder = CallGraphBuilder::new().with_framework_filter(FrameworkFilter::default());
+        builder.add_class(&class);
+        let graph = builder.build();
+        assert_eq!(
+            callees(&graph),
+            vec!["android.content.Intent.getStringExtra", "android.webkit.WebView.loadUrl", "com.example.Router.open"]
+        );
+        assert!(!graph.get_all_methods_py().contains(&"java.lang.StringBuilder.append".to_string()));
+
+        // A custom allowlist replaces the default sinks
+        let filter = FrameworkFilter::new(None, Some(vec!["StringBuilder.append".to_string()]));
+        let mut builder = CallGraphBuilder::new().with_framework_filter(filter);
+        builder.add_class(&class);
+        assert_eq!(callees(&builder.build()), vec!["com.example.Router.open", "java.lang.StringBuilder.append"]);
+
+        let mut builder = CallGraphBuilder::new();
+        builder.add_class(&class);
+        assert_eq!(callees(&builder.build()).len(), 6);
+    }
+
+    #[test]
+    fn test_build_stats() {
+        use crate::dex::test_support::{sample_dex, write_test_apk, DexBuilder};
+
+        let mut builder = DexBuilder::new();
+        let load_url = builder.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let open = builder.method("Lcom/example/Browser;", "open", "V", &[]);
+        let class = builder.class("Lcom/example/Browser;", Some("Ljava/lang/Object;"), 0x0001);
+        builder.virtual_method(class, open, 0x0001, Some(vec![0x106e, load_url as u16, 0x0000, 0x000e]));
+
+        let apk = write_test_apk("call-graph-stats", &[sample_dex(), builder.build()]);
+        let apk_path = apk.to_str().unwrap().to_string();
+        // A local pool keeps the global one untouched for the other tests
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let (graph, stats) = pool.install(|| build_call_graph_with_stats(apk_path.clone(), None, false, None, None)).unwrap();
+        let (_, filtered) = pool
+            .install(|| build_call_graph_with_stats(apk_path.clone(), Some(vec!["Browser".to_string()]), false, None, None))
+            .unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        assert_eq!(stats.dex_files, 2);
+        assert_eq!(stats.classes_processed, 3);
+        assert_eq!(stats.classes_skipped, 0);
+        assert_eq!(stats.edges_added, graph.get_stats()["total_edges"]);
+        assert_eq!(graph.get_callees("com.example.Browser.open").len(), 1);
+
+        assert_eq!(filtered.classes_processed, 1);
+        assert_eq!(filtered.classes_skipped, 0);
+    }
 }
@@ -6,6 +6,7 @@ use crate::dex::expression_builder::{ExpressionBuilder, ReconstructedExpression}
 use crate::dex::parser::{DexParser, ClassDef, EncodedMethod};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Decompiled method with metadata
 #[pyclass]
@@ -31,6 +32,16 @@ pub struct DecompiledMethod {
     pub expressions: Vec<ReconstructedExpression>,
     #[pyo3(get)]
     pub bytecode_size: usize,
+    /// Source file of the declaring class, e.g. "MainActivity.java"
+    #[pyo3(get)]
+    pub source_file: Option<String>,
+    /// (address, line) pairs from debug_info, addresses in code units
+    #[pyo3(get)]
+    pub line_numbers: Vec<(u32, u32)>,
+    /// Exception types the method's catch clauses handle, in code order,
+    /// e.g. "java.io.IOException"
+    #[pyo3(get)]
+    pub caught_exceptions: Vec<String>,
 }
 
 #[pymethods]
@@ -44,6 +55,21 @@ impl DecompiledMethod {
         })
     }
 
+    /// Source line of the instruction at `address` (in code units)
+    pub fn line_at(&self, address: u32) -> Option<u32> {
+        self.line_numbers
+            .iter()
+            .take_while(|(start, _)| *start <= address)
+            .last()
+            .map(|(_, line)| *line)
+    }
+
+    /// Stack-trace style location of `address`, e.g. "MainActivity.java:143"
+    pub fn location(&self, address: u32) -> Option<String> {
+        let line = self.line_at(address)?;
+        Some(format!("{}:{}", self.source_file.as_deref().unwrap_or("Unknown Source"), line))
+    }
+
     /// Get all WebView-related expressions
     pub fn get_webview_expressions(&self) -> Vec<ReconstructedExpression> {
         self.expressions
@@ -78,6 +104,10 @@ pub struct DecompiledClass {
     pub methods: Vec<DecompiledMethod>,
     #[pyo3(get)]
     pub access_flags: u32,
+    /// Static field initializers from static_values, as Java literals keyed
+    /// by field name (e.g. "API_KEY" -> "\"abc123\"")
+    #[pyo3(get)]
+    pub static_field_values: HashMap<String, String>,
 }
 
 #[pymethods]
@@ -128,6 +158,83 @@ impl DecompiledClass {
         )
     }
 
+    /// Render the class as a pseudo-Java listing
+    ///
+    /// Not compilable: method bodies are the reconstructed expressions in
+    /// bytecode order, and parameters are named `p0`, `p1`, ...
+    pub fn to_pseudo_source(&self) -> String {
+        let mut out = String::new();
+        if !self.package.is_empty() {
+            out.push_str(&format!("package {};\n\n", self.package));
+        }
+
+        let mut modifiers = Vec::new();
+        if self.is_public() {
+            modifiers.push("public");
+        }
+        if self.is_abstract() {
+            modifiers.push("abstract");
+        }
+        if self.is_final() {
+            modifiers.push("final");
+        }
+        modifiers.push(if self.access_flags & 0x0200 != 0 { "interface" } else { "class" });
+        out.push_str(&format!("{} {}", modifiers.join(" "), self.simple_name));
+        if let Some(ref superclass) = self.superclass {
+            if superclass != "java.lang.Object" {
+                out.push_str(&format!(" extends {}", superclass));
+            }
+        }
+        if !self.interfaces.is_empty() {
+            out.push_str(&format!(" implements {}", self.interfaces.join(", ")));
+        }
+        out.push_str(" {\n");
+
+        // Fields are stored as "name: type"
+        for field in &self.fields {
+            match field.split_once(": ") {
+                Some((name, field_type)) => match self.static_field_values.get(name) {
+                    Some(value) => out.push_str(&format!("    static {} {} = {};\n", field_type, name, value)),
+                    None => out.push_str(&format!("    {} {};\n", field_type, name)),
+                },
+                None => out.push_str(&format!("    {};\n", field)),
+            }
+        }
+
+        for method in &self.methods {
+            out.push('\n');
+            let mut modifiers = Vec::new();
+            if method.is_public {
+                modifiers.push("public ");
+            } else if method.is_private {
+                modifiers.push("private ");
+            }
+            if method.is_static {
+                modifiers.push("static ");
+            }
+            let params: Vec<String> = method
+                .parameters
+                .iter()
+                .enumerate()
+                .map(|(i, param)| format!("{} p{}", param, i))
+                .collect();
+            out.push_str(&format!(
+                "    {}{} {}({}) {{\n",
+                modifiers.concat(),
+                method.return_type,
+                method.name,
+                params.join(", ")
+            ));
+            for expr in &method.expressions {
+                out.push_str(&format!("        {};\n", expr.expression));
+            }
+            out.push_str("    }\n");
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "DecompiledClass(name='{}', methods={})",
@@ -147,7 +254,7 @@ pub fn decompile_class_from_apk(apk_path: String, class_name: String) -> PyResul
 
     // Search through DEX files
     for dex_entry in extractor.dex_entries() {
-        if let Ok(parser) = DexParser::new(dex_entry.data.clone()) {
+        if let Ok(parser) = DexParser::from_arc(dex_entry.data.clone()) {
             // Find the class
             for class_idx in 0..parser.class_count() {
                 let class_def = match parser.get_class_def(class_idx) {
@@ -162,7 +269,7 @@ pub fn decompile_class_from_apk(apk_path: String, class_name: String) -> PyResul
 
                 if found_class_name == class_name {
                     // Found the class - decompile it
-                    return decompile_class(&parser, class_def, &dex_entry.data);
+                    return decompile_class(&parser, class_def);
                 }
             }
         }
@@ -178,7 +285,6 @@ pub fn decompile_class_from_apk(apk_path: String, class_name: String) -> PyResul
 pub fn decompile_class(
     parser: &DexParser,
     class_def: ClassDef,
-    dex_data: &[u8],
 ) -> PyResult<DecompiledClass> {
     // Get class metadata
     let class_name = parser
@@ -200,7 +306,17 @@ pub fn decompile_class(
 
     // Get fields
     let mut fields = Vec::new();
+    let mut static_field_values = HashMap::new();
     if let Ok(class_data) = parser.parse_class_data(class_def.class_data_off) {
+        // static_values lines up with static_fields; trailing fields may be omitted
+        let static_values = parser.parse_static_values(class_def.static_values_off).unwrap_or_default();
+        for (field, value) in class_data.static_fields.iter().zip(static_values.iter()) {
+            let name = parser.get_field_info(field.field_idx).and_then(|info| parser.get_string(info.name_idx));
+            if let Ok(name) = name {
+                static_field_values.insert(name, value.to_java_literal());
+            }
+        }
+
         for field in class_data.static_fields.iter().chain(class_data.instance_fields.iter()) {
             if let Ok(field_info) = parser.get_field_info(field.field_idx) {
                 if let Ok(field_name) = parser.get_string(field_info.name_idx) {
@@ -212,11 +328,17 @@ pub fn decompile_class(
         }
     }
 
+    let source_file = if class_def.source_file_idx != 0xFFFFFFFF {
+        parser.get_string(class_def.source_file_idx).ok()
+    } else {
+        None
+    };
+
     // Decompile all methods
     let mut methods = Vec::new();
     if let Ok(class_data) = parser.parse_class_data(class_def.class_data_off) {
         for encoded_method in class_data.direct_methods.iter().chain(class_data.virtual_methods.iter()) {
-            if let Ok(method) = decompile_method(parser, encoded_method, &dex_data) {
+            if let Ok(method) = decompile_method(parser, encoded_method, source_file.as_deref()) {
                 methods.push(method);
             }
         }
@@ -231,6 +353,7 @@ pub fn decompile_class(
         fields,
         methods,
         access_flags: class_def.access_flags,
+        static_field_values,
     })
 }
 
@@ -238,7 +361,7 @@ pub fn decompile_class(
 fn decompile_method(
     parser: &DexParser,
     encoded_method: &EncodedMethod,
-    dex_data: &[u8],
+    source_file: Option<&str>,
 ) -> Result<DecompiledMethod, String> {
     // Get method info
     let method_info = parser.get_method_info(encoded_method.method_idx).map_err(|e| e.to_string())?;
@@ -260,25 +383,25 @@ fn decompile_method(
     let is_private = encoded_method.access_flags & 0x0002 != 0;
     let is_static = encoded_method.access_flags & 0x0008 != 0;
 
-    // Get bytecode and decompile
-    let (expressions, bytecode_size) = if encoded_method.code_off > 0 {
-        if let Ok(bytecode) = parser.get_method_bytecode(encoded_method.code_off) {
-            let size = bytecode.len();
-
-            // Create expression builder
-            if let Ok(parser2) = DexParser::new(dex_data.to_vec()) {
-                let mut builder = ExpressionBuilder::new(parser2);
-                let exprs = builder.process_bytecode(&bytecode).unwrap_or_default();
-                (exprs, size)
-            } else {
-                (Vec::new(), size)
-            }
-        } else {
-            (Vec::new(), 0)
-        }
+    // Get bytecode and decompile; methods without code get an empty code_item
+    let code = parser.get_code_item(encoded_method.code_off).unwrap_or_default();
+    let expressions = if code.insns.is_empty() {
+        Vec::new()
     } else {
-        (Vec::new(), 0)
+        // The builder's parser shares the DEX buffer; nothing is copied
+        let mut builder = ExpressionBuilder::new(parser.clone());
+        builder.process_bytecode(&code.insns).unwrap_or_default()
     };
+    let bytecode_size = code.insns.len();
+
+    let line_numbers = parser.parse_debug_info(code.debug_info_off).unwrap_or_default();
+
+    let mut caught_exceptions: Vec<String> = Vec::new();
+    for clause in code.handlers.iter().flat_map(|handler| handler.catches.iter()) {
+        if !caught_exceptions.contains(&clause.exception_type) {
+            caught_exceptions.push(clause.exception_type.clone());
+        }
+    }
 
     Ok(DecompiledMethod {
         name: method_name,
@@ -291,6 +414,9 @@ fn decompile_method(
         return_type,
         expressions,
         bytecode_size,
+        source_file: source_file.map(str::to_string),
+        line_numbers,
+        caught_exceptions,
     })
 }
 
@@ -306,3 +432,134 @@ fn split_class_name(class_name: &str) -> (String, String) {
         (String::new(), name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_pseudo_source() {
+        let class = DecompiledClass {
+            class_name: "com.example.BrowserActivity".to_string(),
+            package: "com.example".to_string(),
+            simple_name: "BrowserActivity".to_string(),
+            superclass: Some("android.app.Activity".to_string()),
+            interfaces: Vec::new(),
+            fields: vec![
+                "webView: android.webkit.WebView".to_string(),
+                "START_URL: java.lang.String".to_string(),
+            ],
+            methods: vec![DecompiledMethod {
+                name: "onCreate".to_string(),
+                signature: "onCreate(android.os.Bundle): void".to_string(),
+                access_flags: 0x0001,
+                is_public: true,
+                is_private: false,
+                is_static: false,
+                parameters: vec!["android.os.Bundle".to_string()],
+                return_type: "void".to_string(),
+                expressions: vec![ReconstructedExpression {
+                    expression: "webView.loadUrl(\"https://example.com\")".to_string(),
+                    value_type: "void".to_string(),
+                    is_method_call: true,
+                    method_signature: Some("android.webkit.WebView.loadUrl".to_string()),
+                }],
+                bytecode_size: 6,
+                source_file: Some("BrowserActivity.java".to_string()),
+                line_numbers: vec![(0, 20), (4, 22)],
+                caught_exceptions: Vec::new(),
+            }],
+            access_flags: 0x0001,
+            static_field_values: HashMap::from([("START_URL".to_string(), "\"https://example.com\"".to_string())]),
+        };
+
+        let source = class.to_pseudo_source();
+        assert!(source.starts_with("package com.example;\n"));
+        assert!(source.contains("public class BrowserActivity extends android.app.Activity {"));
+        assert!(source.contains("    android.webkit.WebView webView;\n"));
+        assert!(source.contains("    static java.lang.String START_URL = \"https://example.com\";\n"));
+        assert!(source.contains("    public void onCreate(android.os.Bundle p0) {\n"));
+        assert!(source.contains("        webView.loadUrl(\"https://example.com\");\n"));
+        assert!(source.ends_with("    }\n}\n"));
+
+        let method = &class.methods[0];
+        assert_eq!(method.line_at(0), Some(20));
+        assert_eq!(method.line_at(3), Some(20));
+        assert_eq!(method.location(5).as_deref(), Some("BrowserActivity.java:22"));
+    }
+
+    #[test]
+    fn test_decompile_class_emits_initializers() {
+        use crate::dex::test_support::DexBuilder;
+
+        let mut builder = DexBuilder::new();
+        let class = builder.class("Lcom/example/SyncService;", Some("Landroid/app/Service;"), 0x0001);
+        let clinit = builder.method("Lcom/example/SyncService;", "<clinit>", "V", &[]);
+        let init = builder.method("Lcom/example/SyncService;", "<init>", "V", &[]);
+        builder.direct_method(class, clinit, 0x10008, Some(vec![0x000e]));
+        builder.direct_method(class, init, 0x10001, Some(vec![0x000e]));
+        let dex = builder.build();
+
+        let parser = DexParser::new(dex.clone()).unwrap();
+        let decompiled = decompile_class(&parser, parser.get_class_def(0).unwrap()).unwrap();
+        let names: Vec<&str> = decompiled.methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["<clinit>", "<init>"]);
+        assert!(decompiled.methods[0].is_static);
+    }
+
+    #[test]
+    fn test_decompile_class_caught_exceptions() {
+        use crate::dex::test_support::DexBuilder;
+
+        let mut builder = DexBuilder::new();
+        let class_name = "Lcom/example/Loader;";
+        let load = builder.method(class_name, "load", "V", &[]);
+        let io_exception = builder.type_id("Ljava/io/IOException;");
+        let class = builder.class(class_name, Some("Ljava/lang/Object;"), 0x0001);
+        builder.virtual_method(class, load, 0x0001, Some(vec![0x0000, 0x000e, 0x010d, 0x000e]));
+        builder.try_catch(class, load, 0, 1, io_exception, 2);
+
+        let parser = DexParser::new(builder.build()).unwrap();
+        let decompiled = decompile_class(&parser, parser.get_class_def(0).unwrap()).unwrap();
+        assert_eq!(decompiled.methods[0].caught_exceptions, vec!["java.io.IOException"]);
+        assert_eq!(decompiled.methods[0].bytecode_size, 4);
+    }
+
+    #[test]
+    fn test_decompile_class_static_field_values() {
+        use crate::dex::test_support::DexBuilder;
+
+        let mut builder = DexBuilder::new();
+        let class_name = "Lcom/example/Config;";
+        let endpoint = builder.field(class_name, "Ljava/lang/String;", "ENDPOINT");
+        let retries = builder.field(class_name, "I", "RETRIES");
+        let separator = builder.field(class_name, "C", "SEPARATOR");
+        let ratio = builder.field(class_name, "F", "RATIO");
+        let url = builder.string("https://example.com/\"v1\"");
+
+        let class = builder.class(class_name, Some("Ljava/lang/Object;"), 0x0001);
+        for field in [endpoint, retries, separator, ratio] {
+            builder.static_field(class, field, 0x0019);
+        }
+        builder.static_values(
+            class,
+            &[
+                0x04, // 4 values
+                0x17, url as u8, // VALUE_STRING
+                0x04, 0x03, // VALUE_INT 3
+                0x03, b'\t', // VALUE_CHAR '\t'
+                0x30, 0xc0, 0x7f, // VALUE_FLOAT, 2 bytes: 0x7fc00000 = NaN
+            ],
+        );
+
+        let parser = DexParser::new(builder.build()).unwrap();
+        let decompiled = decompile_class(&parser, parser.get_class_def(0).unwrap()).unwrap();
+        let values = &decompiled.static_field_values;
+        assert_eq!(values.len(), 4);
+        assert_eq!(values["ENDPOINT"], r#""https://example.com/\"v1\"""#);
+        assert_eq!(values["RETRIES"], "3");
+        assert_eq!(values["SEPARATOR"], r"'\t'");
+        assert_eq!(values["RATIO"], "Float.NaN");
+        assert!(decompiled.to_pseudo_source().contains(r#"ENDPOINT = "https://example.com/\"v1\"""#));
+    }
+}
@@ -1,7 +1,10 @@
 //! Extract method bytecode from DEX files
 
 use pyo3::prelude::*;
+use std::collections::HashMap;
 
+use super::bytecode::{accumulate_opcodes, extract_method_calls, merge_histograms, RustInstruction};
+use super::instruction::InstructionDecoder;
 use super::parser::DexParser;
 
 /// Extract bytecode for methods from classes in an APK
@@ -25,7 +28,7 @@ pub fn extract_methods_bytecode(
     for class in classes {
         // Try each DEX file
         for dex_entry in &dex_entries {
-            let parser = match DexParser::new(dex_entry.data.clone()) {
+            let parser = match DexParser::from_arc(dex_entry.data.clone()) {
                 Ok(p) => p,
                 Err(_) => continue,
             };
@@ -102,7 +105,7 @@ pub fn get_method_bytecode_from_apk(
 
     // Try each DEX file
     for dex_entry in extractor.dex_entries() {
-        let parser = match DexParser::new(dex_entry.data.clone()) {
+        let parser = match DexParser::from_arc(dex_entry.data.clone()) {
             Ok(p) => p,
             Err(_) => continue,
         };
@@ -162,6 +165,155 @@ pub fn get_method_bytecode_from_apk(
     )))
 }
 
+/// Method calls made by a method, addressed as `(dex_index, method_idx)`
+///
+/// Method indices are local to the DEX file that defines the calling method,
+/// so each call site is paired with that DEX's position in the APK. Pass the
+/// pairs to `MethodResolverPy.resolve_refs` to resolve them against the right
+/// DEX.
+#[pyfunction]
+pub fn extract_method_calls_from_apk(
+    apk_path: String,
+    class_name: String,
+    method_name: String,
+) -> PyResult<Vec<(usize, u32)>> {
+    use crate::apk::ApkExtractor;
+
+    let extractor = ApkExtractor::new(&apk_path)
+        .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+
+    method_call_refs(&extractor, &class_name, &method_name).ok_or_else(|| {
+        pyo3::exceptions::PyException::new_err(format!("Method not found: {}.{}", class_name, method_name))
+    })
+}
+
+/// Find a method in the APK and return its call sites with the DEX index
+pub fn method_call_refs(
+    extractor: &crate::apk::ApkExtractor,
+    class_name: &str,
+    method_name: &str,
+) -> Option<Vec<(usize, u32)>> {
+    extractor.dex_entries().iter().enumerate().find_map(|(dex_index, dex_entry)| {
+        let parser = DexParser::from_arc(dex_entry.data.clone()).ok()?;
+        let bytecode = find_method_bytecode(&parser, class_name, method_name).ok()??;
+
+        Some(
+            extract_method_calls(bytecode)
+                .into_iter()
+                .map(|method_idx| (dex_index, method_idx))
+                .collect(),
+        )
+    })
+}
+
+/// Decode only the first `max_instructions` instructions of a method
+///
+/// Intended for fast triage over many methods where only the prologue
+/// matters (e.g. checking the first invoke). Decoding stops as soon as the
+/// limit is reached.
+///
+/// Returns:
+///     (instructions, truncated) - `truncated` is True if the method body
+///     contains more bytecode than was decoded
+#[pyfunction]
+#[pyo3(signature = (apk_path, class_name, method_name, max_instructions=10))]
+pub fn preview_method(
+    apk_path: String,
+    class_name: String,
+    method_name: String,
+    max_instructions: usize,
+) -> PyResult<(Vec<RustInstruction>, bool)> {
+    use crate::apk::ApkExtractor;
+
+    let extractor = ApkExtractor::new(&apk_path)
+        .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+
+    // Try each DEX file
+    for dex_entry in extractor.dex_entries() {
+        let parser = match DexParser::from_arc(dex_entry.data.clone()) {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+
+        if let Ok(Some(bytecode)) = find_method_bytecode(&parser, &class_name, &method_name) {
+            let (instructions, truncated) =
+                InstructionDecoder::decode_limited(&bytecode, Some(max_instructions));
+            return Ok((
+                instructions.iter().map(RustInstruction::from).collect(),
+                truncated,
+            ));
+        }
+    }
+
+    Err(pyo3::exceptions::PyException::new_err(format!(
+        "Method not found: {}.{}",
+        class_name, method_name
+    )))
+}
+
+/// Build an opcode histogram over every method in every DEX of an APK
+///
+/// The result is a fingerprint of the app's instruction distribution, useful
+/// for clustering malware families. This decodes the full bytecode of every
+/// method, so it is expensive; classes are processed in parallel with rayon
+/// and the GIL is released while decoding.
+#[pyfunction]
+pub fn apk_opcode_profile(py: Python<'_>, apk_path: String) -> PyResult<HashMap<String, u64>> {
+    use crate::apk::ApkExtractor;
+
+    let extractor = ApkExtractor::new(&apk_path)
+        .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+
+    Ok(py.detach(|| extractor_opcode_profile(&extractor)))
+}
+
+/// Opcode histogram summed over every DEX file the extractor found
+///
+/// DEX files that fail to parse are skipped.
+pub fn extractor_opcode_profile(extractor: &crate::apk::ApkExtractor) -> HashMap<String, u64> {
+    extractor
+        .dex_entries()
+        .iter()
+        .filter_map(|dex_entry| DexParser::from_arc(dex_entry.data.clone()).ok())
+        .map(|parser| dex_opcode_profile(&parser))
+        .fold(HashMap::new(), merge_histograms)
+}
+
+/// Build an opcode histogram over every method of a single DEX file (parallel per class)
+pub fn dex_opcode_profile(parser: &DexParser) -> HashMap<String, u64> {
+    use rayon::prelude::*;
+
+    (0..parser.class_count())
+        .into_par_iter()
+        .map(|class_idx| {
+            let mut histogram = HashMap::new();
+
+            let class_data = match parser
+                .get_class_def(class_idx)
+                .and_then(|class_def| parser.parse_class_data(class_def.class_data_off))
+            {
+                Ok(d) => d,
+                Err(_) => return histogram,
+            };
+
+            for encoded_method in class_data
+                .direct_methods
+                .iter()
+                .chain(class_data.virtual_methods.iter())
+            {
+                if encoded_method.code_off == 0 {
+                    continue;
+                }
+                if let Ok(bytecode) = parser.get_method_bytecode(encoded_method.code_off) {
+                    accumulate_opcodes(&bytecode, &mut histogram);
+                }
+            }
+
+            histogram
+        })
+        .reduce(HashMap::new, merge_histograms)
+}
+
 /// Find method bytecode (non-Python version for internal use)
 pub fn find_method_bytecode(
     parser: &DexParser,
@@ -199,3 +351,33 @@ pub fn find_method_bytecode(
 
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apk::ApkExtractor;
+    use crate::dex::test_support::{sample_dex, write_test_apk};
+
+    #[test]
+    fn test_dex_opcode_profile() {
+        let parser = DexParser::new(sample_dex()).unwrap();
+        let profile = dex_opcode_profile(&parser);
+
+        assert_eq!(profile.get("invoke-direct"), Some(&1));
+        assert_eq!(profile.get("invoke-static"), Some(&1));
+        assert_eq!(profile.get("const-string"), Some(&1));
+        // Plus one return per method: <init>, onCreate and Helper.log
+        assert_eq!(profile.values().sum::<u64>(), 6);
+    }
+
+    #[test]
+    fn test_apk_opcode_profile_sums_dex_files() {
+        let apk = write_test_apk("opcode-profile", &[sample_dex(), sample_dex(), b"not a dex".to_vec()]);
+        let extractor = ApkExtractor::new(&apk).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        let profile = extractor_opcode_profile(&extractor);
+        assert_eq!(profile.get("invoke-static"), Some(&2));
+        assert_eq!(profile.values().sum::<u64>(), 12);
+    }
+}
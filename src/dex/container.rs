@@ -1,9 +1,13 @@
 use crate::apk::DexEntry;
 use crate::dex::constants::structure;
+use crate::dex::descriptor::MethodDescriptor;
 use crate::dex::error::{DexError, Result};
+use crate::dex::filter::{ClassFilter, MethodFilter};
 use crate::dex::models::{RustDexClass, RustDexMethod, RustDexField};
 use crate::dex::parser::DexParser;
+use pyo3::prelude::*;
 use rayon::prelude::*;
+use std::collections::{HashMap, VecDeque};
 
 /// Container managing multiple DEX files
 pub struct DexContainer {
@@ -49,6 +53,100 @@ impl DexContainer {
         Ok(results?.into_iter().flatten().collect())
     }
 
+    /// Iterate over classes of all DEX files one at a time
+    ///
+    /// Unlike `extract_all_classes`, only the class currently being yielded is
+    /// materialized, and each DEX buffer is released once it has been consumed.
+    pub fn into_class_iter(self) -> ClassIter {
+        ClassIter {
+            cursor: ClassCursor::new(self),
+        }
+    }
+
+    /// Iterate over the methods matching both filters, one at a time
+    ///
+    /// Only class headers (name, flags, superclass) and method metadata are
+    /// parsed: fields are never read and classes are never fully built, so
+    /// method-only queries on large apps stay cheap. The yielded class
+    /// carries header information only; its `methods` and `fields` are
+    /// empty.
+    pub fn into_method_iter(self, class_filter: ClassFilter, method_filter: MethodFilter) -> MethodIter {
+        MethodIter {
+            cursor: ClassCursor::new(self),
+            class_filter,
+            method_filter,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Names of the classes defined in one DEX file
+    ///
+    /// Only the class_defs table is read, so this is much cheaper than
+    /// `extract_classes_from_dex`.
+    pub fn class_names(&self, dex_index: usize) -> Result<Vec<String>> {
+        let entry = self.dex_entries.get(dex_index).ok_or_else(|| {
+            DexError::InvalidDex(format!(
+                "DEX index {} out of bounds (max: {})",
+                dex_index,
+                self.dex_entries.len()
+            ))
+        })?;
+        let parser = DexParser::from_arc(entry.data.clone())?;
+
+        (0..parser.class_count())
+            .map(|class_idx| parser.get_class_def(class_idx).and_then(|def| parser.get_type_name(def.class_idx)))
+            .collect()
+    }
+
+    /// Find the DEX file defining `class_name`
+    ///
+    /// Returns the entry name (e.g. "classes2.dex") and its position in
+    /// `entries()`, or None if no DEX file defines the class.
+    pub fn find_class_dex(&self, class_name: &str) -> Result<Option<(String, usize)>> {
+        for (index, entry) in self.dex_entries.iter().enumerate() {
+            if self.class_names(index)?.iter().any(|name| name == class_name) {
+                return Ok(Some((entry.name.clone(), index)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Locate the defined method a descriptor refers to
+    ///
+    /// When the descriptor has no signature, the first method with a
+    /// matching name is returned.
+    pub fn find_method_by_descriptor(&self, descriptor: &MethodDescriptor) -> Result<Option<(RustDexClass, RustDexMethod)>> {
+        let Some((_, dex_index)) = self.find_class_dex(&descriptor.class_name)? else {
+            return Ok(None);
+        };
+
+        let class = self
+            .extract_classes_from_dex(dex_index)?
+            .into_iter()
+            .find(|class| class.class_name == descriptor.class_name);
+
+        Ok(class.and_then(|class| {
+            let method = class.methods.iter().find(|method| {
+                method.name == descriptor.method_name
+                    && descriptor.parameters.as_ref().is_none_or(|params| *params == method.parameters)
+                    && descriptor.return_type.as_ref().is_none_or(|ret| *ret == method.return_type)
+            })?;
+            let method = method.clone();
+            Some((class, method))
+        }))
+    }
+
+    /// Number of classes defined in each DEX file, keyed by entry name
+    pub fn classes_per_dex(&self) -> Result<HashMap<String, usize>> {
+        self.dex_entries
+            .iter()
+            .map(|entry| {
+                let parser = DexParser::from_arc(entry.data.clone())?;
+                Ok((entry.name.clone(), parser.class_count() as usize))
+            })
+            .collect()
+    }
+
     /// Extract classes from a specific DEX file
     pub fn extract_classes_from_dex(&self, dex_index: usize) -> Result<Vec<RustDexClass>> {
         if dex_index >= self.dex_entries.len() {
@@ -60,7 +158,7 @@ impl DexContainer {
         }
 
         let entry = &self.dex_entries[dex_index];
-        let parser = DexParser::new(entry.data.clone())?;
+        let parser = DexParser::from_arc(entry.data.clone())?;
 
         let mut classes = Vec::new();
         let class_count = parser.class_count();
@@ -68,7 +166,10 @@ impl DexContainer {
         // Iterate through all class definitions
         for class_idx in 0..class_count {
             match self.parse_class(&parser, class_idx) {
-                Ok(class) => classes.push(class),
+                Ok(mut class) => {
+                    class.source_module = entry.module.clone();
+                    classes.push(class);
+                }
                 Err(e) => {
                     // Log error but continue with other classes
                     eprintln!("Warning: Failed to parse class {}: {:?}", class_idx, e);
@@ -80,8 +181,8 @@ impl DexContainer {
         Ok(classes)
     }
 
-    /// Parse a single class
-    fn parse_class(&self, parser: &DexParser, class_idx: u32) -> Result<RustDexClass> {
+    /// Parse a class's name, access flags and superclass
+    fn parse_class_header(&self, parser: &DexParser, class_idx: u32) -> Result<RustDexClass> {
         let class_def = parser.get_class_def(class_idx)?;
 
         // Get class name
@@ -97,6 +198,14 @@ impl DexContainer {
             }
         }
 
+        Ok(rust_class)
+    }
+
+    /// Parse a single class
+    fn parse_class(&self, parser: &DexParser, class_idx: u32) -> Result<RustDexClass> {
+        let class_def = parser.get_class_def(class_idx)?;
+        let mut rust_class = self.parse_class_header(parser, class_idx)?;
+
         // Parse class data (fields and methods)
         if class_def.class_data_off != 0 {
             let class_data = parser.parse_class_data(class_def.class_data_off)?;
@@ -184,9 +293,223 @@ impl DexContainer {
     }
 }
 
+/// Walk over the class definitions of every DEX in a container
+///
+/// Each DEX is parsed only when the walk reaches it, and its buffer is
+/// dropped once the walk moves past it.
+struct ClassCursor {
+    container: DexContainer,
+    dex_index: usize,
+    parser: Option<DexParser>,
+    class_idx: u32,
+}
+
+impl ClassCursor {
+    fn new(container: DexContainer) -> Self {
+        Self {
+            container,
+            dex_index: 0,
+            parser: None,
+            class_idx: 0,
+        }
+    }
+
+    /// Apply `visit` to the next class, skipping classes it fails on
+    ///
+    /// `visit` gets the container, the parser of the current DEX, its entry
+    /// and the class index. A DEX that fails to parse ends the walk with
+    /// its error.
+    fn next_with<T, F>(&mut self, mut visit: F) -> Option<Result<T>>
+    where
+        F: FnMut(&DexContainer, &DexParser, &DexEntry, u32) -> Result<T>,
+    {
+        loop {
+            if self.parser.is_none() {
+                let entry = self.container.dex_entries.get_mut(self.dex_index)?;
+
+                // The DEX buffer moves into the parser and is dropped with it
+                match DexParser::from_arc(std::mem::take(&mut entry.data)) {
+                    Ok(parser) => {
+                        self.parser = Some(parser);
+                        self.class_idx = 0;
+                    }
+                    Err(e) => {
+                        self.dex_index = self.container.dex_entries.len();
+                        return Some(Err(e));
+                    }
+                }
+            }
+
+            let parser = self.parser.as_ref()?;
+            if self.class_idx >= parser.class_count() {
+                self.parser = None;
+                self.dex_index += 1;
+                continue;
+            }
+
+            let class_idx = self.class_idx;
+            self.class_idx += 1;
+
+            let entry = &self.container.dex_entries[self.dex_index];
+            match visit(&self.container, parser, entry, class_idx) {
+                Ok(found) => return Some(Ok(found)),
+                Err(e) => {
+                    // Log error but continue with other classes
+                    eprintln!("Warning: Failed to parse class {}: {:?}", class_idx, e);
+                }
+            }
+        }
+    }
+}
+
+/// Streaming iterator over the classes of a `DexContainer`
+pub struct ClassIter {
+    cursor: ClassCursor,
+}
+
+impl Iterator for ClassIter {
+    type Item = Result<RustDexClass>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next_with(|container, parser, entry, class_idx| {
+            let mut class = container.parse_class(parser, class_idx)?;
+            class.source_module = entry.module.clone();
+            Ok(class)
+        })
+    }
+}
+
+/// Streaming iterator over the methods of a `DexContainer` matching a
+/// class filter and a method filter
+pub struct MethodIter {
+    cursor: ClassCursor,
+    class_filter: ClassFilter,
+    method_filter: MethodFilter,
+    /// Matches of the current class not yet yielded
+    pending: VecDeque<(RustDexClass, RustDexMethod)>,
+}
+
+impl MethodIter {
+    /// Methods of one class matching both filters, with the class header
+    fn class_matches(
+        class_filter: &ClassFilter,
+        method_filter: &MethodFilter,
+        container: &DexContainer,
+        parser: &DexParser,
+        entry: &DexEntry,
+        class_idx: u32,
+    ) -> Result<Vec<(RustDexClass, RustDexMethod)>> {
+        let mut class = container.parse_class_header(parser, class_idx)?;
+        class.source_module = entry.module.clone();
+        if !class_filter.matches(&class) {
+            return Ok(Vec::new());
+        }
+
+        let class_def = parser.get_class_def(class_idx)?;
+        if class_def.class_data_off == 0 {
+            return Ok(Vec::new());
+        }
+        let class_data = parser.parse_class_data(class_def.class_data_off)?;
+
+        Ok(class_data
+            .direct_methods
+            .iter()
+            .chain(class_data.virtual_methods.iter())
+            .filter_map(|encoded| container.parse_method(parser, encoded.method_idx, encoded.access_flags).ok())
+            .filter(|method| method_filter.matches(method))
+            .map(|method| (class.clone(), method))
+            .collect())
+    }
+}
+
+impl Iterator for MethodIter {
+    type Item = Result<(RustDexClass, RustDexMethod)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.pending.pop_front() {
+                return Some(Ok(found));
+            }
+            let (class_filter, method_filter) = (&self.class_filter, &self.method_filter);
+            let matches = self.cursor.next_with(|container, parser, entry, class_idx| {
+                Self::class_matches(class_filter, method_filter, container, parser, entry, class_idx)
+            });
+            match matches? {
+                Ok(matches) => self.pending.extend(matches),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Python iterator yielding classes one at a time across all DEX files
+///
+/// Bounds memory on large APKs: classes are parsed lazily on `__next__`
+/// instead of materializing the full class list.
+#[pyclass]
+pub struct ClassIterator {
+    inner: ClassIter,
+}
+
+impl ClassIterator {
+    pub fn new(container: DexContainer) -> Self {
+        Self {
+            inner: container.into_class_iter(),
+        }
+    }
+}
+
+#[pymethods]
+impl ClassIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> PyResult<Option<RustDexClass>> {
+        match self.inner.next() {
+            Some(Ok(class)) => Ok(Some(class)),
+            Some(Err(e)) => Err(crate::error::PlayfastError::from(e).into()),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Python iterator yielding (class, method) matches one at a time
+///
+/// The classes carry header information only (name, flags, superclass);
+/// their `methods` and `fields` lists are empty.
+#[pyclass]
+pub struct MethodIterator {
+    inner: MethodIter,
+}
+
+impl MethodIterator {
+    pub fn new(container: DexContainer, class_filter: ClassFilter, method_filter: MethodFilter) -> Self {
+        Self {
+            inner: container.into_method_iter(class_filter, method_filter),
+        }
+    }
+}
+
+#[pymethods]
+impl MethodIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> PyResult<Option<(RustDexClass, RustDexMethod)>> {
+        match self.inner.next() {
+            Some(Ok(found)) => Ok(Some(found)),
+            Some(Err(e)) => Err(crate::error::PlayfastError::from(e).into()),
+            None => Ok(None),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dex::test_support::sample_dex;
 
     #[test]
     fn test_dex_container_creation() {
@@ -198,4 +521,168 @@ mod tests {
         let container = DexContainer::new(entries);
         assert_eq!(container.dex_count(), 2);
     }
+
+    #[test]
+    fn test_find_class_dex() {
+        use crate::dex::test_support::DexBuilder;
+
+        let mut builder = DexBuilder::new();
+        builder.class("Lcom/example/feature/Secondary;", Some("Ljava/lang/Object;"), 0x0001);
+        let entries = vec![
+            DexEntry::new("classes.dex".to_string(), 0, sample_dex()),
+            DexEntry::new("classes2.dex".to_string(), 1, builder.build()),
+        ];
+        let container = DexContainer::new(entries);
+
+        assert_eq!(
+            container.find_class_dex("com.example.feature.Secondary").unwrap(),
+            Some(("classes2.dex".to_string(), 1))
+        );
+        assert_eq!(
+            container.find_class_dex("com.example.MainActivity").unwrap(),
+            Some(("classes.dex".to_string(), 0))
+        );
+        assert_eq!(container.find_class_dex("com.example.Missing").unwrap(), None);
+
+        let counts = container.classes_per_dex().unwrap();
+        assert_eq!(counts["classes.dex"], 2);
+        assert_eq!(counts["classes2.dex"], 1);
+    }
+
+    #[test]
+    fn test_find_method_by_descriptor() {
+        use crate::dex::test_support::DexBuilder;
+
+        let mut builder = DexBuilder::new();
+        let class = builder.class("Lcom/example/Crypto;", Some("Ljava/lang/Object;"), 0x0001);
+        let encrypt = builder.method("Lcom/example/Crypto;", "encrypt", "[B", &["[B", "Ljava/lang/String;"]);
+        let encrypt_int = builder.method("Lcom/example/Crypto;", "encrypt", "[B", &["I"]);
+        builder.virtual_method(class, encrypt, 0x0001, Some(vec![0x000e]));
+        builder.virtual_method(class, encrypt_int, 0x0001, Some(vec![0x000e]));
+        let container = DexContainer::new(vec![
+            DexEntry::new("classes.dex".to_string(), 0, sample_dex()),
+            DexEntry::new("classes2.dex".to_string(), 1, builder.build()),
+        ]);
+
+        let find = |input: &str| {
+            container
+                .find_method_by_descriptor(&MethodDescriptor::parse(input).unwrap())
+                .unwrap()
+                .map(|(class, method)| (class.class_name, method.parameters))
+        };
+
+        let expected = Some(("com.example.Crypto".to_string(), vec!["byte[]".to_string(), "java.lang.String".to_string()]));
+        assert_eq!(find("Lcom/example/Crypto;->encrypt([BLjava/lang/String;)[B"), expected);
+        assert_eq!(find("com.example.Crypto.encrypt([BLjava/lang/String;)[B"), expected);
+        assert_eq!(find("Lcom/example/Crypto;->encrypt(I)[B").unwrap().1, vec!["int".to_string()]);
+        assert_eq!(find("Lcom/example/Crypto;->encrypt(J)[B"), None);
+        assert_eq!(find("Lcom/example/Missing;->encrypt()V"), None);
+    }
+
+    #[test]
+    fn test_class_iter_matches_extract_all() {
+        let entries = vec![
+            DexEntry::new("classes.dex".to_string(), 0, sample_dex()),
+            DexEntry::new("classes2.dex".to_string(), 1, sample_dex()),
+        ];
+
+        let expected = DexContainer::new(entries.clone()).extract_all_classes().unwrap();
+        let streamed: Vec<RustDexClass> = DexContainer::new(entries)
+            .into_class_iter()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(expected.len(), 4);
+        assert_eq!(streamed.len(), expected.len());
+        for (a, b) in streamed.iter().zip(expected.iter()) {
+            assert_eq!(a.class_name, b.class_name);
+            assert_eq!(a.superclass, b.superclass);
+            assert_eq!(a.methods.len(), b.methods.len());
+            assert_eq!(a.fields.len(), b.fields.len());
+        }
+    }
+
+    #[test]
+    fn test_method_iter_matches_eager_search() {
+        let entries = vec![
+            DexEntry::new("classes.dex".to_string(), 0, sample_dex()),
+            DexEntry::new("classes2.dex".to_string(), 1, sample_dex()),
+        ];
+        let class_filter = ClassFilter::default();
+        let method_filter = MethodFilter { param_count: Some(0), ..Default::default() };
+
+        let expected: Vec<(String, String)> = DexContainer::new(entries.clone())
+            .extract_all_classes()
+            .unwrap()
+            .into_iter()
+            .filter(|class| class_filter.matches(class))
+            .flat_map(|class| {
+                class
+                    .methods
+                    .iter()
+                    .filter(|method| method_filter.matches(method))
+                    .map(|method| (class.class_name.clone(), method.signature()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let streamed: Vec<(String, String)> = DexContainer::new(entries)
+            .into_method_iter(class_filter, method_filter)
+            .map(|found| found.map(|(class, method)| (class.class_name, method.signature())))
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert!(!expected.is_empty());
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_class_iter_invalid_dex() {
+        let entries = vec![DexEntry::new("classes.dex".to_string(), 0, vec![0; 16])];
+        let mut iter = DexContainer::new(entries).into_class_iter();
+
+        assert!(matches!(iter.next(), Some(Err(_))));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_feature_split_classes_are_tagged() {
+        use crate::apk::test_support::{element, encode_binary_xml, s};
+        use crate::apk::ApkExtractor;
+        use crate::dex::test_support::{write_test_apk_with_entries, DexBuilder};
+
+        let mut feature = DexBuilder::new();
+        feature.class("Lcom/example/chat/ChatActivity;", Some("Landroid/app/Activity;"), 0x0001);
+
+        let manifest = element("manifest").with_attr("package", s("com.example"));
+        let base_manifest = encode_binary_xml(&manifest);
+        let split_manifest = encode_binary_xml(&manifest.with_attr("split", s("chat")));
+        let base = write_test_apk_with_entries("merged-base", &[sample_dex()], &[("AndroidManifest.xml", &base_manifest)]);
+        let split =
+            write_test_apk_with_entries("merged-feature", &[feature.build()], &[("AndroidManifest.xml", &split_manifest)]);
+        let bundle = write_test_apk_with_entries(
+            "merged-bundle",
+            &[],
+            &[
+                ("base.apk", &std::fs::read(&base).unwrap()),
+                ("split_chat.apk", &std::fs::read(&split).unwrap()),
+            ],
+        );
+        let extractor = ApkExtractor::new(&bundle).unwrap();
+        for path in [base, split, bundle] {
+            std::fs::remove_file(path).ok();
+        }
+
+        let classes = DexContainer::new(extractor.dex_entries().to_vec()).extract_all_classes().unwrap();
+        let chat = classes
+            .iter()
+            .find(|c| c.class_name == "com.example.chat.ChatActivity")
+            .expect("class defined only in the feature split");
+        assert_eq!(chat.source_module.as_deref(), Some("split_chat"));
+
+        let main = classes.iter().find(|c| c.class_name == "com.example.MainActivity").unwrap();
+        assert_eq!(main.source_module.as_deref(), Some("base"));
+
+        let base_only: Vec<DexEntry> = extractor.dex_entries().iter().filter(|e| e.is_base_module()).cloned().collect();
+        assert_eq!(base_only.len(), 1);
+    }
 }
@@ -9,6 +9,9 @@ use std::collections::{HashMap, HashSet};
 
 use crate::dex::entry_point_analyzer::EntryPointAnalyzer;
 use crate::dex::call_graph::{CallGraph, CallPath};
+use crate::dex::class_decompiler::decompile_class;
+use crate::dex::field_taint::{find_call_flows, find_field_flows, SinkSpec};
+use crate::dex::parser::DexParser;
 
 /// Represents a complete data flow from an entry point to a sink method
 #[pyclass]
@@ -41,14 +44,19 @@ pub struct Flow {
     /// Number of different paths
     #[pyo3(get)]
     pub path_count: usize,
+
+    /// Sink group: "webview", "file", "network", "sql", "custom" for
+    /// caller-supplied patterns, or "class" for `find_flows_to_class`
+    #[pyo3(get)]
+    pub sink_category: String,
 }
 
 #[pymethods]
 impl Flow {
     fn __repr__(&self) -> String {
         format!(
-            "Flow({} → {} via {} path(s), depth={})",
-            self.entry_point, self.sink_method, self.path_count, self.min_path_length
+            "Flow([{}] {} → {} via {} path(s), depth={})",
+            self.sink_category, self.entry_point, self.sink_method, self.path_count, self.min_path_length
         )
     }
 
@@ -81,6 +89,87 @@ impl Flow {
     }
 }
 
+/// Category of the data a flow starts from
+///
+/// The built-in sources are Intent getters; the network and file kinds are
+/// reported for custom sources set with `with_rules`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[pyclass(eq, eq_int, hash, frozen)]
+pub enum DataSourceKind {
+    /// Intent extras (`getStringExtra`, `getIntExtra`, ...)
+    IntentExtra,
+    /// The intent data as a string (`getDataString`)
+    IntentData,
+    /// The intent data as a Uri (`getData`)
+    IntentUri,
+    /// A Bundle (`getExtras`, `Bundle.getString`, ...)
+    Bundle,
+    /// A network response body or stream
+    NetworkResponse,
+    /// Data read from a file
+    FileRead,
+    /// Anything else
+    Unknown,
+}
+
+impl DataSourceKind {
+    /// Classify a source method ID such as "android.content.Intent.getData"
+    ///
+    /// A full signature ("...getData(): android.net.Uri") is classified by
+    /// the part before its parameter list.
+    pub fn classify(method: &str) -> Self {
+        let method = method.split_once('(').map_or(method, |(name, _)| name);
+        let (class_name, name) = method.rsplit_once('.').unwrap_or(("", method));
+
+        match name {
+            "getData" => Self::IntentUri,
+            "getDataString" => Self::IntentData,
+            "getExtras" => Self::Bundle,
+            _ if name.starts_with("get") && name.ends_with("Extra") => Self::IntentExtra,
+            _ if class_name.ends_with("Bundle") && name.starts_with("get") => Self::Bundle,
+            _ if class_name.ends_with("ResponseBody")
+                || class_name.ends_with("Response") && name == "body"
+                || class_name.ends_with("URLConnection") && name == "getInputStream" =>
+            {
+                Self::NetworkResponse
+            }
+            _ if class_name.ends_with("FileInputStream")
+                || class_name.ends_with("FileReader")
+                || class_name.ends_with("Files") && name.starts_with("read") =>
+            {
+                Self::FileRead
+            }
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[pymethods]
+impl DataSourceKind {
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+/// How `analyze_data_flows_with_mode` decides intent data reaches a sink
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[pyclass]
+pub enum TaintMode {
+    /// A source method name appears anywhere on the call path
+    #[default]
+    Heuristic,
+    /// The source's return value reaches a dangerous sink argument through
+    /// registers, following it into callees passed it as an argument
+    Register,
+}
+
+#[pymethods]
+impl TaintMode {
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
 /// Data flow information from source to sink
 #[pyclass]
 #[derive(Clone, Debug)]
@@ -89,6 +178,10 @@ pub struct DataFlow {
     #[pyo3(get)]
     pub source: String,
 
+    /// Category of `source`
+    #[pyo3(get)]
+    pub source_kind: DataSourceKind,
+
     /// Sink where data is used (e.g., "WebView.loadUrl", "File.write")
     #[pyo3(get)]
     pub sink: String,
@@ -97,9 +190,17 @@ pub struct DataFlow {
     #[pyo3(get)]
     pub flow_path: Vec<String>,
 
-    /// Estimated confidence (0.0 - 1.0)
+    /// Estimated confidence (0.0 - 1.0), derived from `evidence`
     #[pyo3(get)]
     pub confidence: f32,
+
+    /// Reasons behind `confidence`, one per signal
+    #[pyo3(get)]
+    pub evidence: Vec<String>,
+
+    /// Whether the path calls a sanitizer before reaching the sink
+    #[pyo3(get)]
+    pub sanitized: bool,
 }
 
 #[pymethods]
@@ -115,11 +216,315 @@ impl DataFlow {
     }
 }
 
+/// Intent data extraction methods treated as flow sources by default
+const INTENT_SOURCE_METHODS: &[&str] = &[
+    "getStringExtra",
+    "getIntExtra",
+    "getBooleanExtra",
+    "getData",
+    "getDataString",
+    "getExtras",
+];
+
+/// Default sanitizer/validator patterns, matched against method IDs on a path
+const DEFAULT_SANITIZER_PATTERNS: &[&str] = &[
+    "Uri.parse",
+    "URLEncoder.encode",
+    "Pattern.matcher",
+    "Html.escapeHtml",
+    "sanitize",
+    "escape",
+    "validate",
+    "isAllowed",
+    "allowlist",
+    "whitelist",
+];
+
+/// Methods on `flow_path` before the sink that match a sanitizer pattern
+fn sanitizer_calls(flow_path: &[String], patterns: &[String]) -> Vec<String> {
+    let before_sink = &flow_path[..flow_path.len().saturating_sub(1)];
+    before_sink
+        .iter()
+        .filter(|method| patterns.iter().any(|pattern| method.contains(pattern.as_str())))
+        .cloned()
+        .collect()
+}
+
+/// Score a source-to-sink path from concrete signals
+///
+/// Name-only matches keep the path-length tiers `find_data_flows` has always
+/// used (0.9 up to 3 hops, then 0.7, 0.5 and 0.3). Register-proven taint
+/// starts at 0.9 and loses a little per hop past 3. Sanitizer calls on the
+/// path lower either score. Returns the confidence (clamped to 0.0 - 1.0)
+/// and the evidence behind it.
+pub(crate) fn score_flow(flow_path: &[String], register_proven: bool, sanitizers: &[String]) -> (f32, Vec<String>) {
+    let mut evidence = Vec::new();
+    let hops = flow_path.len().saturating_sub(1);
+
+    let mut confidence: f32 = if register_proven {
+        evidence.push("taint reaches the sink through registers".to_string());
+        0.9 - (0.05 * hops.saturating_sub(3) as f32).min(0.3)
+    } else {
+        evidence.push("source and sink matched by method name only".to_string());
+        match hops {
+            0..=3 => 0.9,
+            4..=5 => 0.7,
+            6..=8 => 0.5,
+            _ => 0.3,
+        }
+    };
+
+    if hops > 3 {
+        evidence.push(format!("long call path ({} hops)", hops));
+    }
+
+    for method in sanitizers {
+        confidence -= 0.2;
+        evidence.push(format!("passes through sanitizer {}", method));
+    }
+
+    (confidence.clamp(0.0, 1.0), evidence)
+}
+
+/// Attack-surface summary for one deeplink-handling component
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct DeeplinkFinding {
+    /// Component that receives the deeplink
+    #[pyo3(get)]
+    pub component: String,
+
+    /// URI patterns handled (e.g., "myapp://open/*")
+    #[pyo3(get)]
+    pub deeplink_patterns: Vec<String>,
+
+    #[pyo3(get)]
+    pub reaches_webview: bool,
+
+    #[pyo3(get)]
+    pub reaches_file: bool,
+
+    #[pyo3(get)]
+    pub reaches_network: bool,
+
+    #[pyo3(get)]
+    pub reaches_sql: bool,
+
+    /// Sink methods reachable from the component
+    #[pyo3(get)]
+    pub sinks: Vec<String>,
+
+    /// Shortest path to any sink
+    #[pyo3(get)]
+    pub shortest_path: Option<CallPath>,
+}
+
+#[pymethods]
+impl DeeplinkFinding {
+    /// Whether the handler reaches any sink
+    pub fn is_exploitable(&self) -> bool {
+        !self.sinks.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DeeplinkFinding({} {:?}, sinks={})",
+            self.component,
+            self.deeplink_patterns,
+            self.sinks.len()
+        )
+    }
+}
+
+/// Built-in sinks of one category
+///
+/// Each entry is a substring pattern over "pkg.Class.method" and the
+/// parameter positions through which taint makes the call dangerous,
+/// counted from 0 without the receiver. An empty list means every
+/// argument.
+pub(crate) type SinkTable = &'static [(&'static str, &'static [usize])];
+
+const WEBVIEW_SINK_PATTERNS: SinkTable = &[
+    ("loadUrl", &[0]),
+    ("loadData", &[0]),
+    ("loadDataWithBaseURL", &[0, 1]),
+    ("evaluateJavascript", &[0]),
+    ("postUrl", &[0]),
+    ("addJavascriptInterface", &[]),
+    ("setWebViewClient", &[]),
+    ("setWebChromeClient", &[]),
+];
+
+const FILE_SINK_PATTERNS: SinkTable = &[
+    ("FileOutputStream", &[]),
+    ("FileWriter", &[]),
+    ("RandomAccessFile.write", &[]),
+    ("Files.write", &[]),
+];
+
+const NETWORK_SINK_PATTERNS: SinkTable = &[
+    ("HttpURLConnection", &[]),
+    ("OkHttp", &[]),
+    ("URLConnection.connect", &[]),
+    ("Socket.connect", &[]),
+];
+
+const SQL_SINK_PATTERNS: SinkTable = &[
+    ("execSQL", &[0]),
+    ("rawQuery", &[0]),
+    ("SQLiteDatabase.query", &[]),
+];
+
+/// Crypto APIs whose arguments select an algorithm or key; also used by
+/// `MethodSignature::is_crypto_method`
+pub(crate) const CRYPTO_SINK_PATTERNS: SinkTable = &[
+    ("Cipher.getInstance", &[]),
+    ("MessageDigest.getInstance", &[]),
+    ("Mac.getInstance", &[]),
+    ("KeyGenerator.getInstance", &[]),
+    ("SecretKeyFactory.getInstance", &[]),
+    ("KeyPairGenerator.getInstance", &[]),
+    ("Signature.getInstance", &[]),
+    ("SecretKeySpec", &[]),
+    ("IvParameterSpec", &[]),
+];
+
+/// Reflection and dynamic code loading APIs; also used by
+/// `MethodSignature::is_reflection_method`
+pub(crate) const REFLECTION_SINK_PATTERNS: SinkTable = &[
+    ("Class.forName", &[]),
+    ("Class.getMethod", &[]),
+    ("Class.getField", &[]),
+    ("Method.invoke", &[]),
+    ("getDeclaredMethod", &[]),
+    ("getDeclaredField", &[]),
+    ("DexClassLoader", &[]),
+    ("PathClassLoader", &[]),
+];
+
+const SINK_TABLES: [SinkTable; 6] = [
+    WEBVIEW_SINK_PATTERNS,
+    FILE_SINK_PATTERNS,
+    NETWORK_SINK_PATTERNS,
+    SQL_SINK_PATTERNS,
+    CRYPTO_SINK_PATTERNS,
+    REFLECTION_SINK_PATTERNS,
+];
+
+/// Patterns of a sink table, without their argument positions
+pub(crate) fn sink_patterns(table: SinkTable) -> Vec<&'static str> {
+    table.iter().map(|(pattern, _)| *pattern).collect()
+}
+
+/// Dangerous parameter positions of the built-in sink `target`
+/// ("pkg.Class.method"), None when every argument is dangerous
+///
+/// The method name must match exactly, so `loadData` does not answer for
+/// `loadDataWithBaseURL`.
+pub(crate) fn default_sink_arguments(target: &str) -> Option<&'static [usize]> {
+    let method_name = |id: &str| id.rsplit('.').next().unwrap_or(id).to_string();
+    let name = method_name(target);
+    SINK_TABLES
+        .iter()
+        .flat_map(|table| table.iter())
+        .find(|(pattern, arguments)| !arguments.is_empty() && method_name(pattern) == name && target.contains(pattern))
+        .map(|(_, arguments)| *arguments)
+}
+
+/// Every sink pattern, plus the Intent getters used as sources
+///
+/// These are the framework calls flow analysis has to see, so
+/// `FrameworkFilter` keeps them by default.
+pub(crate) fn flow_endpoint_patterns() -> Vec<String> {
+    SINK_TABLES
+        .iter()
+        .flat_map(|table| sink_patterns(table))
+        .map(|pattern| pattern.to_string())
+        .chain(INTENT_SOURCE_METHODS.iter().map(|getter| format!("android.content.Intent.{}", getter)))
+        .collect()
+}
+
+/// Algorithm and mode names that make a crypto `getInstance` call weak,
+/// compared case-insensitively against each '/'-separated part; "ECB" only
+/// counts after one of `BLOCK_CIPHERS`
+const WEAK_CRYPTO_ALGORITHMS: &[&str] = &[
+    "DES", "DESede", "RC2", "RC4", "ARCFOUR", "Blowfish", "ECB", "MD2", "MD4", "MD5", "SHA1", "SHA-1",
+];
+
+/// A crypto API called with a weak algorithm or mode constant
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct WeakCryptoUse {
+    /// Method making the call ("pkg.Class.method")
+    #[pyo3(get)]
+    pub method: String,
+
+    /// Reconstructed call, e.g. `Cipher.getInstance("DES/ECB/PKCS5Padding")`
+    #[pyo3(get)]
+    pub expression: String,
+
+    /// Weak names found in the call's string constants, e.g. ["DES", "ECB"];
+    /// "ECB" is also reported for a block cipher named without a mode
+    #[pyo3(get)]
+    pub algorithms: Vec<String>,
+
+    /// Whether an entry point reaches `method` on a crypto flow
+    #[pyo3(get)]
+    pub reachable: bool,
+}
+
+#[pymethods]
+impl WeakCryptoUse {
+    fn __repr__(&self) -> String {
+        format!("WeakCryptoUse({} in {}, reachable={})", self.algorithms.join("/"), self.method, self.reachable)
+    }
+}
+
+/// Block ciphers, for which ECB leaks plaintext patterns
+///
+/// `Cipher.getInstance` also runs them in ECB mode when the transformation
+/// names no mode, e.g. `Cipher.getInstance("AES")`. Other algorithms accept
+/// an "ECB" mode only as a placeholder, e.g. `"RSA/ECB/OAEPPadding"`.
+const BLOCK_CIPHERS: &[&str] = &["AES", "DES", "DESede", "Blowfish", "RC2"];
+
+fn is_block_cipher(algorithm: &str) -> bool {
+    BLOCK_CIPHERS.iter().any(|cipher| cipher.eq_ignore_ascii_case(algorithm.trim()))
+}
+
+/// Weak algorithm names in the string literals of a reconstructed call
+fn weak_algorithms(expression: &str) -> Vec<String> {
+    // Literals sit between every other pair of quotes
+    let literals: Vec<&str> = expression.split('"').skip(1).step_by(2).collect();
+    let mut weak: Vec<String> = literals
+        .iter()
+        .flat_map(|literal| {
+            let block_cipher = literal.split('/').next().is_some_and(is_block_cipher);
+            literal.split('/').filter(move |part| block_cipher || !part.trim().eq_ignore_ascii_case("ECB"))
+        })
+        .filter_map(|part| WEAK_CRYPTO_ALGORITHMS.iter().find(|weak| weak.eq_ignore_ascii_case(part.trim())))
+        .map(|weak| weak.to_string())
+        .collect();
+
+    // A bare block cipher name means the provider default, which is ECB
+    let mode_defaults_to_ecb =
+        expression.contains("Cipher.getInstance(") && literals.first().is_some_and(|transformation| is_block_cipher(transformation));
+    if mode_defaults_to_ecb {
+        weak.push("ECB".to_string());
+    }
+
+    weak
+}
+
 /// Generic Data Flow Analyzer - finds flows from entry points to any sink
 #[pyclass]
 pub struct DataFlowAnalyzer {
     entry_analyzer: EntryPointAnalyzer,
     call_graph: CallGraph,
+    sanitizer_patterns: Vec<String>,
+    include_initializers: bool,
+    scan_callees: bool,
+    source_patterns: Vec<String>,
+    sink_patterns: Vec<String>,
 }
 
 impl DataFlowAnalyzer {
@@ -128,9 +533,58 @@ impl DataFlowAnalyzer {
         Self {
             entry_analyzer,
             call_graph,
+            sanitizer_patterns: DEFAULT_SANITIZER_PATTERNS.iter().map(|p| p.to_string()).collect(),
+            include_initializers: false,
+            scan_callees: false,
+            source_patterns: INTENT_SOURCE_METHODS.iter().map(|p| p.to_string()).collect(),
+            sink_patterns: Vec::new(),
         }
     }
 
+    /// Use `sources` and `sinks` instead of the built-in intent sources
+    ///
+    /// Sources are matched as substrings of method IDs on a path (or of the
+    /// called method in `TaintMode::Register`); sinks are the patterns
+    /// searched by `find_custom_flows` when it is given none.
+    pub fn with_rules(mut self, sources: Vec<String>, sinks: Vec<String>) -> Self {
+        self.set_rules(sources, sinks);
+        self
+    }
+
+    /// Replace the source and sink patterns, see `with_rules`
+    pub fn set_rules(&mut self, sources: Vec<String>, sinks: Vec<String>) {
+        self.source_patterns = sources;
+        self.sink_patterns = sinks;
+    }
+
+    /// Also start flows at `<init>` and `<clinit>` of entry-point classes
+    ///
+    /// Off by default; static initializers often run config or network code
+    /// before any lifecycle method is called.
+    pub fn set_include_initializers(&mut self, include: bool) {
+        self.include_initializers = include;
+    }
+
+    /// Also look for heuristic sources among the methods called from a path
+    ///
+    /// Off by default. Sources like `getIntent` are usually called, not
+    /// walked through, on the way to the sink; a called method counts only
+    /// when its name is exactly a source (`getData` or `Intent.getData`,
+    /// never `getDatabasePath`).
+    pub fn set_scan_callees(&mut self, scan: bool) {
+        self.scan_callees = scan;
+    }
+
+    /// Replace the sanitizer patterns used when scoring data flows
+    pub fn set_sanitizer_patterns(&mut self, patterns: Vec<String>) {
+        self.sanitizer_patterns = patterns;
+    }
+
+    /// Add a sanitizer pattern (e.g. an app-specific allowlist check)
+    pub fn add_sanitizer_pattern(&mut self, pattern: &str) {
+        self.sanitizer_patterns.push(pattern.to_string());
+    }
+
     /// Find all sink methods matching the given patterns
     fn find_sink_methods(&self, patterns: &[&str]) -> Vec<String> {
         let mut sink_methods = Vec::new();
@@ -145,10 +599,31 @@ impl DataFlowAnalyzer {
 
     /// Generic method to find flows from entry points to sinks matching patterns
     pub fn find_flows_to(&self, sink_patterns: &[&str], max_depth: usize) -> Vec<Flow> {
+        self.find_categorized_flows(sink_patterns, max_depth, "custom")
+    }
+
+    /// Find flows from entry points to any method of `class_name`
+    ///
+    /// Every method the call graph knows on that class is a sink, which
+    /// answers "can an exported component reach class X" (e.g. a payment or
+    /// crypto wrapper) without listing method patterns. Flows are tagged
+    /// with the "class" sink category.
+    pub fn find_flows_to_class(&self, class_name: &str, max_depth: usize) -> Vec<Flow> {
+        let sink_methods = self.call_graph.methods_of_class(class_name);
+        self.find_flows_to_methods(&sink_methods, max_depth, "class")
+    }
+
+    /// Find flows to sinks matching patterns, tagging each with `category`
+    fn find_categorized_flows(&self, sink_patterns: &[&str], max_depth: usize, category: &str) -> Vec<Flow> {
+        let sink_methods = self.find_sink_methods(sink_patterns);
+        self.find_flows_to_methods(&sink_methods, max_depth, category)
+    }
+
+    /// Find flows from entry points to the given sink methods
+    fn find_flows_to_methods(&self, sink_methods: &[String], max_depth: usize, category: &str) -> Vec<Flow> {
         let mut flows = Vec::new();
 
         let entry_points = self.entry_analyzer.analyze();
-        let sink_methods = self.find_sink_methods(sink_patterns);
 
         if sink_methods.is_empty() {
             return flows;
@@ -157,19 +632,22 @@ impl DataFlowAnalyzer {
         // For each entry point
         for entry_point in &entry_points {
             // Try lifecycle methods as starting points
-            let lifecycle_methods = vec![
+            let mut lifecycle_methods = vec![
                 "onCreate",
                 "onStart",
                 "onResume",
                 "onNewIntent",
             ];
+            if self.include_initializers {
+                lifecycle_methods.extend(["<init>", "<clinit>"]);
+            }
 
             for lifecycle in &lifecycle_methods {
                 let source_method = format!("{}.{}", entry_point.class_name, lifecycle);
 
                 // Find paths to each sink method
-                for sink_method in &sink_methods {
-                    let paths = self.call_graph.find_paths(&source_method, sink_method, max_depth);
+                for sink_method in sink_methods {
+                    let paths = self.call_graph.find_paths(&source_method, sink_method, max_depth, None);
 
                     if !paths.is_empty() {
                         let min_length = paths.iter().map(|p| p.length).min().unwrap_or(0);
@@ -182,6 +660,7 @@ impl DataFlowAnalyzer {
                             is_deeplink_handler: entry_point.is_deeplink_handler,
                             min_path_length: min_length,
                             path_count: paths.len(),
+                            sink_category: category.to_string(),
                         });
                     }
                 }
@@ -193,52 +672,79 @@ impl DataFlowAnalyzer {
 
     /// Convenience method: Find flows to WebView methods
     pub fn find_webview_flows(&self, max_depth: usize) -> Vec<Flow> {
-        let webview_patterns = vec![
-            "loadUrl",
-            "loadData",
-            "loadDataWithBaseURL",
-            "evaluateJavascript",
-            "addJavascriptInterface",
-            "setWebViewClient",
-            "setWebChromeClient",
-        ];
-
-        self.find_flows_to(&webview_patterns, max_depth)
+        self.find_categorized_flows(&sink_patterns(WEBVIEW_SINK_PATTERNS), max_depth, "webview")
     }
 
     /// Convenience method: Find flows to file I/O methods
     pub fn find_file_flows(&self, max_depth: usize) -> Vec<Flow> {
-        let file_patterns = vec![
-            "FileOutputStream",
-            "FileWriter",
-            "RandomAccessFile.write",
-            "Files.write",
-        ];
-
-        self.find_flows_to(&file_patterns, max_depth)
+        self.find_categorized_flows(&sink_patterns(FILE_SINK_PATTERNS), max_depth, "file")
     }
 
     /// Convenience method: Find flows to network methods
     pub fn find_network_flows(&self, max_depth: usize) -> Vec<Flow> {
-        let network_patterns = vec![
-            "HttpURLConnection",
-            "OkHttp",
-            "URLConnection.connect",
-            "Socket.connect",
-        ];
-
-        self.find_flows_to(&network_patterns, max_depth)
+        self.find_categorized_flows(&sink_patterns(NETWORK_SINK_PATTERNS), max_depth, "network")
     }
 
     /// Convenience method: Find flows to SQL methods
     pub fn find_sql_flows(&self, max_depth: usize) -> Vec<Flow> {
-        let sql_patterns = vec![
-            "execSQL",
-            "rawQuery",
-            "SQLiteDatabase.query",
-        ];
+        self.find_categorized_flows(&sink_patterns(SQL_SINK_PATTERNS), max_depth, "sql")
+    }
 
-        self.find_flows_to(&sql_patterns, max_depth)
+    /// Convenience method: Find flows to cipher, digest and key APIs
+    pub fn find_crypto_flows(&self, max_depth: usize) -> Vec<Flow> {
+        self.find_categorized_flows(&sink_patterns(CRYPTO_SINK_PATTERNS), max_depth, "crypto")
+    }
+
+    /// Convenience method: Find flows to reflection and dynamic class loading
+    pub fn find_reflection_flows(&self, max_depth: usize) -> Vec<Flow> {
+        self.find_categorized_flows(&sink_patterns(REFLECTION_SINK_PATTERNS), max_depth, "reflection")
+    }
+
+    /// Find crypto calls whose constant arguments name a weak algorithm or mode
+    ///
+    /// Call graph edges carry no argument values, so the classes are
+    /// decompiled and the string constants of each reconstructed crypto
+    /// call are checked, e.g. `Cipher.getInstance("AES/ECB/PKCS5Padding")`.
+    /// Calls are reported whether or not an entry point reaches them;
+    /// `reachable` tells the two apart.
+    pub fn find_weak_crypto(&self, parsers: &[DexParser], max_depth: usize) -> Vec<WeakCryptoUse> {
+        let reachable: HashSet<String> = self
+            .find_crypto_flows(max_depth)
+            .iter()
+            .flat_map(|flow| flow.paths.iter())
+            .flat_map(|path| path.methods.iter().rev().skip(1).cloned())
+            .collect();
+
+        let mut uses = Vec::new();
+        for parser in parsers {
+            for class_idx in 0..parser.class_count() {
+                let Ok(class_def) = parser.get_class_def(class_idx) else { continue };
+                let Ok(class) = decompile_class(parser, class_def) else { continue };
+
+                for method in &class.methods {
+                    let method_id = format!("{}.{}", class.class_name, method.name);
+                    for expr in &method.expressions {
+                        let is_crypto_call = expr.method_signature.as_deref().is_some_and(|signature| {
+                            CRYPTO_SINK_PATTERNS.iter().any(|(pattern, _)| signature.contains(pattern))
+                        });
+                        if !is_crypto_call {
+                            continue;
+                        }
+                        let algorithms = weak_algorithms(&expr.expression);
+                        if !algorithms.is_empty() {
+                            uses.push(WeakCryptoUse {
+                                reachable: reachable.contains(&method_id),
+                                method: method_id.clone(),
+                                expression: expr.expression.clone(),
+                                algorithms,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        uses
     }
 
     /// Find deeplink handlers that lead to specific sinks
@@ -249,50 +755,186 @@ impl DataFlowAnalyzer {
             .collect()
     }
 
+    /// Summarize every deeplink handler and the sink categories it reaches
+    ///
+    /// Handlers that reach no sink are still reported so the full deeplink
+    /// surface is visible; `is_exploitable()` filters to the risky ones.
+    pub fn deeplink_attack_surface(&self, max_depth: usize) -> Vec<DeeplinkFinding> {
+        let mut findings: Vec<DeeplinkFinding> = self
+            .entry_analyzer
+            .get_deeplink_handlers()
+            .into_iter()
+            .map(|ep| DeeplinkFinding {
+                deeplink_patterns: ep.get_deeplink_patterns(),
+                component: ep.class_name,
+                reaches_webview: false,
+                reaches_file: false,
+                reaches_network: false,
+                reaches_sql: false,
+                sinks: Vec::new(),
+                shortest_path: None,
+            })
+            .collect();
+
+        let flows = [
+            self.find_webview_flows(max_depth),
+            self.find_file_flows(max_depth),
+            self.find_network_flows(max_depth),
+            self.find_sql_flows(max_depth),
+        ];
+
+        for flow in flows.iter().flatten().filter(|flow| flow.is_deeplink_handler) {
+            let Some(finding) = findings.iter_mut().find(|f| f.component == flow.entry_point) else {
+                continue;
+            };
+
+            match flow.sink_category.as_str() {
+                "webview" => finding.reaches_webview = true,
+                "file" => finding.reaches_file = true,
+                "network" => finding.reaches_network = true,
+                "sql" => finding.reaches_sql = true,
+                _ => continue,
+            }
+            if !finding.sinks.contains(&flow.sink_method) {
+                finding.sinks.push(flow.sink_method.clone());
+            }
+            if let Some(path) = flow.get_shortest_path() {
+                let shorter = finding
+                    .shortest_path
+                    .as_ref()
+                    .is_none_or(|current| path.length < current.length);
+                if shorter {
+                    finding.shortest_path = Some(path);
+                }
+            }
+        }
+
+        findings
+    }
+
     /// Analyze data flow from Intent to sink (simplified heuristic-based approach)
     pub fn analyze_data_flows(&self, flows: &[Flow]) -> Vec<DataFlow> {
+        self.analyze_data_flows_with_mode(flows, TaintMode::Heuristic, &[])
+    }
+
+    /// Analyze data flow from Intent to sink with the given `mode`
+    ///
+    /// `parsers` are only read in `TaintMode::Register`, where the methods
+    /// on each path are scanned instruction by instruction. A value read in
+    /// one method and passed as an argument is followed into the callee,
+    /// at most as many calls deep as the longest path.
+    pub fn analyze_data_flows_with_mode(&self, flows: &[Flow], mode: TaintMode, parsers: &[DexParser]) -> Vec<DataFlow> {
+        self.analyze_data_flows_with_sinks(flows, mode, parsers, &[])
+    }
+
+    /// `analyze_data_flows_with_mode` with the dangerous arguments of some
+    /// sinks overridden
+    ///
+    /// A sink method takes the arguments of the first spec whose pattern it
+    /// contains. Name matching never looks at arguments, so `sinks` only
+    /// matter in `TaintMode::Register`.
+    pub fn analyze_data_flows_with_sinks(
+        &self,
+        flows: &[Flow],
+        mode: TaintMode,
+        parsers: &[DexParser],
+        sinks: &[SinkSpec],
+    ) -> Vec<DataFlow> {
+        match mode {
+            TaintMode::Heuristic => self.heuristic_data_flows(flows, &self.source_refs()),
+            TaintMode::Register => self.register_data_flows(flows, parsers, sinks),
+        }
+    }
+
+    /// First source on `path`: a path method containing a source pattern,
+    /// or, with `scan_callees`, a method one of them calls that is exactly a
+    /// source
+    fn source_on_path(&self, path: &[String], sources: &[&str]) -> Option<String> {
+        let is_called_source = |callee: &str| {
+            sources
+                .iter()
+                .any(|source| callee.strip_suffix(source).is_some_and(|rest| rest.is_empty() || rest.ends_with('.')))
+        };
+
+        path.iter().find_map(|method| {
+            if sources.iter().any(|source| method.contains(source)) {
+                return Some(method.clone());
+            }
+            if !self.scan_callees {
+                return None;
+            }
+            let mut callees = self.call_graph.get_callees(method);
+            callees.sort();
+            callees.into_iter().find(|callee| is_called_source(callee))
+        })
+    }
+
+    fn heuristic_data_flows(&self, flows: &[Flow], sources: &[&str]) -> Vec<DataFlow> {
         let mut data_flows = Vec::new();
 
-        // Intent data extraction methods to track
-        let intent_methods = [
-            "getStringExtra",
-            "getIntExtra",
-            "getBooleanExtra",
-            "getData",
-            "getDataString",
-            "getExtras",
-        ];
+        for flow in flows {
+            for path in &flow.paths {
+                if let Some(source_method) = self.source_on_path(&path.methods, sources) {
+                    // Name matching only: nothing proves the value itself reaches the sink
+                    let sanitizers = sanitizer_calls(&path.methods, &self.sanitizer_patterns);
+                    let (confidence, evidence) = score_flow(&path.methods, false, &sanitizers);
+
+                    data_flows.push(DataFlow {
+                        source_kind: DataSourceKind::classify(&source_method),
+                        source: source_method,
+                        sink: flow.sink_method.clone(),
+                        flow_path: path.methods.clone(),
+                        confidence,
+                        evidence,
+                        sanitized: !sanitizers.is_empty(),
+                    });
+                }
+            }
+        }
+
+        data_flows
+    }
 
+    fn register_data_flows(&self, flows: &[Flow], parsers: &[DexParser], overrides: &[SinkSpec]) -> Vec<DataFlow> {
+        let sink_methods: HashSet<&str> = flows.iter().map(|flow| flow.sink_method.as_str()).collect();
+        let sinks: Vec<SinkSpec> = sink_methods
+            .into_iter()
+            .map(|sink_method| {
+                let arguments = overrides
+                    .iter()
+                    .find(|spec| sink_method.contains(spec.pattern.as_str()))
+                    .and_then(|spec| spec.arguments.clone());
+                SinkSpec { pattern: sink_method.to_string(), arguments }
+            })
+            .collect();
+        let max_depth = flows.iter().flat_map(|flow| flow.paths.iter()).map(|path| path.length).max().unwrap_or(0);
+        let register_flows = find_call_flows(parsers, &self.source_refs(), &sinks, max_depth);
+
+        let mut data_flows = Vec::new();
         for flow in flows {
             for path in &flow.paths {
-                // Check if path contains intent data extraction
-                let has_intent_data = path.methods.iter().any(|m| {
-                    intent_methods.iter().any(|intent_method| m.contains(intent_method))
-                });
-
-                if has_intent_data {
-                    // Find which intent method is used
-                    if let Some(source_method) = path.methods.iter().find(|m| {
-                        intent_methods.iter().any(|intent_method| m.contains(intent_method))
-                    }) {
-                        // Calculate confidence based on path length and directness
-                        let confidence = if path.length <= 3 {
-                            0.9
-                        } else if path.length <= 5 {
-                            0.7
-                        } else if path.length <= 8 {
-                            0.5
-                        } else {
-                            0.3
-                        };
-
-                        data_flows.push(DataFlow {
-                            source: source_method.clone(),
-                            sink: flow.sink_method.clone(),
-                            flow_path: path.methods.clone(),
-                            confidence,
-                        });
-                    }
+                // Only the method calling the sink can hand it a tainted register
+                let Some(caller) = path.methods.len().checked_sub(2).and_then(|i| path.methods.get(i)) else {
+                    continue;
+                };
+
+                for register_flow in register_flows.iter().filter(|register_flow| {
+                    register_flow.sink == flow.sink_method
+                        && register_flow.methods.last() == Some(caller)
+                        && register_flow.methods.iter().all(|method| path.methods.contains(method))
+                }) {
+                    let sanitizers = sanitizer_calls(&path.methods, &self.sanitizer_patterns);
+                    let (confidence, evidence) = score_flow(&path.methods, true, &sanitizers);
+
+                    data_flows.push(DataFlow {
+                        source: register_flow.source.clone(),
+                        source_kind: DataSourceKind::classify(&register_flow.source),
+                        sink: flow.sink_method.clone(),
+                        flow_path: path.methods.clone(),
+                        confidence,
+                        evidence,
+                        sanitized: !sanitizers.is_empty(),
+                    });
                 }
             }
         }
@@ -300,6 +942,56 @@ impl DataFlowAnalyzer {
         data_flows
     }
 
+    /// Find flows to `sink_patterns` and correlate them with intent sources
+    ///
+    /// One-call form of `find_flows_to` followed by `analyze_data_flows`.
+    pub fn find_data_flows(&self, sink_patterns: &[&str], max_depth: usize) -> Vec<DataFlow> {
+        self.find_custom_flows(&self.source_refs(), sink_patterns, max_depth)
+    }
+
+    /// Find flows to `sinks` whose path calls one of `sources`
+    ///
+    /// Both are substring patterns over method IDs, so e.g.
+    /// `ClipboardManager.getPrimaryClip` to `OutputStream.write` works
+    /// without touching the built-in rules.
+    pub fn find_custom_flows(&self, sources: &[&str], sinks: &[&str], max_depth: usize) -> Vec<DataFlow> {
+        self.heuristic_data_flows(&self.find_flows_to(sinks, max_depth), sources)
+    }
+
+    fn source_refs(&self) -> Vec<&str> {
+        self.source_patterns.iter().map(String::as_str).collect()
+    }
+
+    /// Find intent data stored in a field by one method and passed to a
+    /// sink by another
+    ///
+    /// Call paths never connect the writer and the reader, so these flows
+    /// are invisible to `find_data_flows`; they are found by following
+    /// `iput`/`sput` and `iget`/`sget` in the bytecode instead.
+    pub fn analyze_field_flows(&self, parsers: &[DexParser], sinks: &[SinkSpec]) -> Vec<DataFlow> {
+        find_field_flows(parsers, &self.source_refs(), sinks)
+            .into_iter()
+            .map(|field_flow| {
+                let mut flow_path = field_flow.methods;
+                flow_path.push(field_flow.sink.clone());
+
+                let sanitizers = sanitizer_calls(&flow_path, &self.sanitizer_patterns);
+                let (confidence, mut evidence) = score_flow(&flow_path, true, &sanitizers);
+                evidence.extend(field_flow.fields.iter().map(|field| format!("value passes through field {}", field)));
+
+                DataFlow {
+                    source_kind: DataSourceKind::classify(&field_flow.source),
+                    source: field_flow.source,
+                    sink: field_flow.sink,
+                    flow_path,
+                    confidence,
+                    evidence,
+                    sanitized: !sanitizers.is_empty(),
+                }
+            })
+            .collect()
+    }
+
     /// Get statistics about flow analysis
     pub fn get_stats(&self) -> HashMap<String, usize> {
         let mut stats = HashMap::new();
@@ -326,6 +1018,12 @@ impl DataFlowAnalyzer {
         self.find_flows_to(&pattern_refs, max_depth.unwrap_or(10))
     }
 
+    /// Find flows to any method of a class
+    #[pyo3(name = "find_flows_to_class")]
+    pub fn find_flows_to_class_py(&self, class_name: &str, max_depth: Option<usize>) -> Vec<Flow> {
+        self.find_flows_to_class(class_name, max_depth.unwrap_or(10))
+    }
+
     /// Find flows to WebView methods
     #[pyo3(name = "find_webview_flows")]
     pub fn find_webview_flows_py(&self, max_depth: Option<usize>) -> Vec<Flow> {
@@ -350,6 +1048,18 @@ impl DataFlowAnalyzer {
         self.find_sql_flows(max_depth.unwrap_or(10))
     }
 
+    /// Find flows to cipher, digest and key APIs
+    #[pyo3(name = "find_crypto_flows")]
+    pub fn find_crypto_flows_py(&self, max_depth: Option<usize>) -> Vec<Flow> {
+        self.find_crypto_flows(max_depth.unwrap_or(10))
+    }
+
+    /// Find flows to reflection and dynamic class loading
+    #[pyo3(name = "find_reflection_flows")]
+    pub fn find_reflection_flows_py(&self, max_depth: Option<usize>) -> Vec<Flow> {
+        self.find_reflection_flows(max_depth.unwrap_or(10))
+    }
+
     /// Find deeplink flows to specific patterns
     #[pyo3(name = "find_deeplink_flows")]
     pub fn find_deeplink_flows_py(&self, patterns: Vec<String>, max_depth: Option<usize>) -> Vec<Flow> {
@@ -357,12 +1067,94 @@ impl DataFlowAnalyzer {
         self.find_deeplink_flows(&pattern_refs, max_depth.unwrap_or(10))
     }
 
+    /// Summarize deeplink handlers and the sinks they reach
+    #[pyo3(name = "deeplink_attack_surface")]
+    pub fn deeplink_attack_surface_py(&self, max_depth: Option<usize>) -> Vec<DeeplinkFinding> {
+        self.deeplink_attack_surface(max_depth.unwrap_or(10))
+    }
+
     /// Analyze data flows for given flows
     #[pyo3(name = "analyze_data_flows")]
     pub fn analyze_data_flows_py(&self, flows: Vec<Flow>) -> Vec<DataFlow> {
         self.analyze_data_flows(&flows)
     }
 
+    /// Replace the source and sink patterns and return the analyzer
+    #[pyo3(name = "with_rules")]
+    pub fn with_rules_py(mut slf: PyRefMut<'_, Self>, sources: Vec<String>, sinks: Vec<String>) -> PyRefMut<'_, Self> {
+        slf.set_rules(sources, sinks);
+        slf
+    }
+
+    /// Current source patterns
+    #[getter]
+    pub fn source_patterns(&self) -> Vec<String> {
+        self.source_patterns.clone()
+    }
+
+    /// Current sink patterns for `find_custom_flows`
+    #[getter]
+    pub fn sink_patterns(&self) -> Vec<String> {
+        self.sink_patterns.clone()
+    }
+
+    /// Find flows from `sources` to `sinks`, defaulting to the configured rules
+    #[pyo3(name = "find_custom_flows", signature = (sources=None, sinks=None, max_depth=None))]
+    pub fn find_custom_flows_py(
+        &self,
+        sources: Option<Vec<String>>,
+        sinks: Option<Vec<String>>,
+        max_depth: Option<usize>,
+    ) -> Vec<DataFlow> {
+        let sources = sources.unwrap_or_else(|| self.source_patterns.clone());
+        let sinks = sinks.unwrap_or_else(|| self.sink_patterns.clone());
+        let source_refs: Vec<&str> = sources.iter().map(String::as_str).collect();
+        let sink_refs: Vec<&str> = sinks.iter().map(String::as_str).collect();
+        self.find_custom_flows(&source_refs, &sink_refs, max_depth.unwrap_or(10))
+    }
+
+    /// Current sanitizer patterns
+    #[getter]
+    pub fn sanitizer_patterns(&self) -> Vec<String> {
+        self.sanitizer_patterns.clone()
+    }
+
+    /// Replace the sanitizer patterns
+    #[pyo3(name = "set_sanitizer_patterns")]
+    pub fn set_sanitizer_patterns_py(&mut self, patterns: Vec<String>) {
+        self.set_sanitizer_patterns(patterns);
+    }
+
+    /// Add a sanitizer pattern
+    #[pyo3(name = "add_sanitizer_pattern")]
+    pub fn add_sanitizer_pattern_py(&mut self, pattern: &str) {
+        self.add_sanitizer_pattern(pattern);
+    }
+
+    /// Whether constructors and static initializers are flow roots
+    #[getter]
+    pub fn include_initializers(&self) -> bool {
+        self.include_initializers
+    }
+
+    /// Also start flows at `<init>` and `<clinit>` of entry-point classes
+    #[pyo3(name = "set_include_initializers")]
+    pub fn set_include_initializers_py(&mut self, include: bool) {
+        self.set_include_initializers(include);
+    }
+
+    /// Whether heuristic sources are also looked for among called methods
+    #[getter]
+    pub fn scan_callees(&self) -> bool {
+        self.scan_callees
+    }
+
+    /// Also look for heuristic sources among the methods called from a path
+    #[pyo3(name = "set_scan_callees")]
+    pub fn set_scan_callees_py(&mut self, scan: bool) {
+        self.set_scan_callees(scan);
+    }
+
     /// Get flow analysis statistics
     #[pyo3(name = "get_stats")]
     pub fn get_stats_py(&self) -> HashMap<String, usize> {
@@ -405,7 +1197,7 @@ pub fn create_data_flow_analyzer(apk_path: String) -> PyResult<DataFlowAnalyzer>
     let entry_analyzer = analyze_entry_points_from_apk(apk_path.clone())?;
 
     // Build call graph (using optimized parallel version)
-    let call_graph = build_call_graph_from_apk_parallel(apk_path, None)?;
+    let call_graph = build_call_graph_from_apk_parallel(apk_path, None, false, None, None)?;
 
     // Create analyzer
     Ok(DataFlowAnalyzer::new(
@@ -426,6 +1218,68 @@ pub fn find_flows_from_apk(
     Ok(analyzer.find_flows_to(&pattern_refs, max_depth.unwrap_or(10)))
 }
 
+/// Find intent-source to sink data flows from APK
+///
+/// Runs flow discovery and intent-source correlation in one call, so
+/// callers don't have to chain `find_flows_to` and `analyze_data_flows`.
+/// With `follow_fields`, values stored in a field by one method and read
+/// by another are followed too (see `DataFlowAnalyzer::analyze_field_flows`).
+/// Those flows are only reported when the value reaches a dangerous
+/// argument of the sink.
+/// `mode=TaintMode.Register` replaces name matching along call paths with
+/// register tracking in the method calling the sink, with the same
+/// argument rule. `sink_arguments` maps a sink pattern to its dangerous
+/// parameter positions, overriding the built-in sink tables; name matching
+/// cannot honor it, so it is rejected unless `follow_fields` or register
+/// mode is on.
+#[pyfunction]
+#[pyo3(signature = (apk_path, sink_patterns, max_depth=None, follow_fields=false, sink_arguments=None, mode=TaintMode::Heuristic))]
+pub fn find_data_flows_from_apk(
+    apk_path: String,
+    sink_patterns: Vec<String>,
+    max_depth: Option<usize>,
+    follow_fields: bool,
+    sink_arguments: Option<HashMap<String, Vec<usize>>>,
+    mode: TaintMode,
+) -> PyResult<Vec<DataFlow>> {
+    if sink_arguments.is_some() && !follow_fields && mode == TaintMode::Heuristic {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "sink_arguments needs follow_fields=True or mode=TaintMode.Register",
+        ));
+    }
+    let mut sink_arguments = sink_arguments.unwrap_or_default();
+    let sinks: Vec<SinkSpec> = sink_patterns
+        .iter()
+        .map(|pattern| match sink_arguments.remove(pattern) {
+            Some(arguments) => SinkSpec::with_arguments(pattern, arguments),
+            None => SinkSpec::new(pattern),
+        })
+        .collect();
+
+    let analyzer = create_data_flow_analyzer(apk_path.clone())?;
+    let pattern_refs: Vec<&str> = sink_patterns.iter().map(|s| s.as_str()).collect();
+    let parsers = if follow_fields || mode == TaintMode::Register {
+        let extractor = crate::apk::ApkExtractor::new(&apk_path)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+        extractor
+            .dex_entries()
+            .iter()
+            .filter_map(|dex_entry| DexParser::from_arc(dex_entry.data.clone()).ok())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let sink_flows = analyzer.find_flows_to(&pattern_refs, max_depth.unwrap_or(10));
+    let mut flows = analyzer.analyze_data_flows_with_sinks(&sink_flows, mode, &parsers, &sinks);
+
+    if follow_fields {
+        flows.extend(analyzer.analyze_field_flows(&parsers, &sinks));
+    }
+
+    Ok(flows)
+}
+
 /// Convenience: Find WebView flows from APK
 #[pyfunction]
 pub fn find_webview_flows_from_apk(
@@ -456,21 +1310,711 @@ pub fn find_network_flows_from_apk(
     Ok(analyzer.find_network_flows(max_depth.unwrap_or(10)))
 }
 
+/// Find crypto calls made with a weak algorithm or mode, e.g. DES or ECB
+///
+/// See `DataFlowAnalyzer::find_weak_crypto`.
+#[pyfunction]
+#[pyo3(signature = (apk_path, max_depth=None))]
+pub fn find_weak_crypto_from_apk(apk_path: String, max_depth: Option<usize>) -> PyResult<Vec<WeakCryptoUse>> {
+    let analyzer = create_data_flow_analyzer(apk_path.clone())?;
+    let extractor = crate::apk::ApkExtractor::new(&apk_path)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+    let parsers: Vec<DexParser> = extractor
+        .dex_entries()
+        .iter()
+        .filter_map(|dex_entry| DexParser::from_arc(dex_entry.data.clone()).ok())
+        .collect();
+    Ok(analyzer.find_weak_crypto(&parsers, max_depth.unwrap_or(10)))
+}
+
+/// Report every deeplink handler and whether it reaches a WebView, file,
+/// network or SQL sink, with the shortest path
+///
+/// Combines manifest deeplinks, entry-point analysis and the call graph so
+/// callers don't have to wire `get_deeplinks`, `analyze_entry_points_from_apk`
+/// and `find_deeplink_flows` together by hand.
+#[pyfunction]
+pub fn deeplink_attack_surface(
+    apk_path: String,
+    max_depth: Option<usize>,
+) -> PyResult<Vec<DeeplinkFinding>> {
+    let analyzer = create_data_flow_analyzer(apk_path)?;
+    Ok(analyzer.deeplink_attack_surface(max_depth.unwrap_or(10)))
+}
+
 // ============================================================================
 // Backward Compatibility Functions
 // ============================================================================
 
+/// Emit a DeprecationWarning pointing at the caller's line
+///
+/// Every call warns; the `warnings` filters decide whether it is shown,
+/// deduplicated or raised.
+fn warn_deprecated(py: Python<'_>, message: &std::ffi::CStr) -> PyResult<()> {
+    let category = py.get_type::<pyo3::exceptions::PyDeprecationWarning>();
+    PyErr::warn(py, &category, message, 1)
+}
+
 /// Backward compatibility: analyze_webview_flows_from_apk
+///
+/// Deprecated: use `find_webview_flows_from_apk`.
 #[pyfunction]
 pub fn analyze_webview_flows_from_apk(
+    py: Python<'_>,
     apk_path: String,
     max_depth: Option<usize>,
 ) -> PyResult<Vec<Flow>> {
+    warn_deprecated(
+        py,
+        c"analyze_webview_flows_from_apk is deprecated; use find_webview_flows_from_apk",
+    )?;
     find_webview_flows_from_apk(apk_path, max_depth)
 }
 
 /// Backward compatibility: create_webview_analyzer_from_apk
+///
+/// Deprecated: use `create_data_flow_analyzer`.
 #[pyfunction]
-pub fn create_webview_analyzer_from_apk(apk_path: String) -> PyResult<DataFlowAnalyzer> {
+pub fn create_webview_analyzer_from_apk(py: Python<'_>, apk_path: String) -> PyResult<DataFlowAnalyzer> {
+    warn_deprecated(
+        py,
+        c"create_webview_analyzer_from_apk is deprecated; use create_data_flow_analyzer",
+    )?;
     create_data_flow_analyzer(apk_path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apk::manifest::{ActivityIntentFilter, IntentFilterData, RustManifestInfo};
+    use crate::dex::test_support::DexBuilder;
+
+    fn deeplink_filter(activity: &str, scheme: &str, host: &str) -> ActivityIntentFilter {
+        ActivityIntentFilter {
+            activity: activity.to_string(),
+            component_type: "activity".to_string(),
+            actions: vec!["android.intent.action.VIEW".to_string()],
+            categories: vec!["android.intent.category.BROWSABLE".to_string()],
+            data: vec![IntentFilterData {
+                scheme: Some(scheme.to_string()),
+                host: Some(host.to_string()),
+                path: None,
+                path_prefix: Some("/open".to_string()),
+                path_pattern: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_deeplink_attack_surface() {
+        let vulnerable = "com.example.LinkActivity";
+        let benign = "com.example.ShareActivity";
+
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec![vulnerable.to_string(), benign.to_string(), "com.example.MainActivity".to_string()];
+        manifest.intent_filters = vec![
+            deeplink_filter(vulnerable, "example", "link"),
+            deeplink_filter(benign, "https", "example.com"),
+        ];
+
+        let mut graph = CallGraph::new();
+        let call = |graph: &mut CallGraph, caller: &str, callee: &str| {
+            graph.add_call(caller.to_string(), callee.to_string(), String::new());
+        };
+        call(&mut graph, "com.example.LinkActivity.onCreate", "android.content.Intent.getDataString");
+        call(&mut graph, "com.example.LinkActivity.onCreate", "com.example.LinkActivity.open");
+        call(&mut graph, "com.example.LinkActivity.open", "android.webkit.WebView.loadUrl");
+        call(&mut graph, "com.example.LinkActivity.onNewIntent", "android.webkit.WebView.loadUrl");
+        call(&mut graph, "com.example.ShareActivity.onCreate", "android.util.Log.d");
+        call(&mut graph, "com.example.MainActivity.onCreate", "android.database.sqlite.SQLiteDatabase.execSQL");
+
+        let analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph);
+        let findings = analyzer.deeplink_attack_surface(10);
+
+        assert_eq!(findings.len(), 2);
+
+        let link = findings.iter().find(|f| f.component == vulnerable).unwrap();
+        assert!(link.is_exploitable());
+        assert_eq!(link.deeplink_patterns, vec!["example://link/open*"]);
+        assert!(link.reaches_webview);
+        assert!(!link.reaches_file && !link.reaches_network && !link.reaches_sql);
+        assert_eq!(link.sinks, vec!["android.webkit.WebView.loadUrl"]);
+        let path = link.shortest_path.as_ref().unwrap();
+        assert_eq!(path.length, 1);
+        assert_eq!(path.methods, vec!["com.example.LinkActivity.onNewIntent", "android.webkit.WebView.loadUrl"]);
+
+        let share = findings.iter().find(|f| f.component == benign).unwrap();
+        assert!(!share.is_exploitable());
+        assert!(share.shortest_path.is_none());
+    }
+
+    #[test]
+    fn test_deeplink_attack_surface_from_apk() {
+        use crate::apk::binary_xml::XmlValue;
+        use crate::apk::test_support::{element, encode_binary_xml, s};
+        use crate::dex::test_support::write_test_apk_with_entries;
+
+        let deeplink_activity = |name: &str, scheme: &str, host: &str| {
+            element("activity").with_attr("name", s(name)).with_attr("exported", XmlValue::Bool(true)).with_child(
+                element("intent-filter")
+                    .with_child(element("action").with_attr("name", s("android.intent.action.VIEW")))
+                    .with_child(element("category").with_attr("name", s("android.intent.category.BROWSABLE")))
+                    .with_child(element("data").with_attr("scheme", s(scheme)).with_attr("host", s(host))),
+            )
+        };
+        let manifest = encode_binary_xml(
+            &element("manifest").with_attr("package", s("com.example")).with_child(
+                element("application")
+                    .with_child(deeplink_activity(".LinkActivity", "example", "link"))
+                    .with_child(deeplink_activity(".ShareActivity", "https", "example.com")),
+            ),
+        );
+
+        // LinkActivity.onCreate hands the link to WebView.loadUrl; ShareActivity only logs
+        let mut builder = DexBuilder::new();
+        let load_url = builder.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let log = builder.method("Landroid/util/Log;", "d", "I", &["Ljava/lang/String;", "Ljava/lang/String;"]);
+        for (class_name, callee, invoke) in [
+            ("Lcom/example/LinkActivity;", load_url, 0x206e),
+            ("Lcom/example/ShareActivity;", log, 0x2071),
+        ] {
+            let on_create = builder.method(class_name, "onCreate", "V", &["Landroid/os/Bundle;"]);
+            let class = builder.class(class_name, Some("Landroid/app/Activity;"), 0x0001);
+            builder.virtual_method(class, on_create, 0x0004, Some(vec![invoke, callee as u16, 0x0021, 0x000e]));
+        }
+
+        let apk = write_test_apk_with_entries("deeplink-surface", &[builder.build()], &[("AndroidManifest.xml", &manifest)]);
+        let findings = deeplink_attack_surface(apk.to_str().unwrap().to_string(), None).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        assert_eq!(findings.len(), 2);
+        let link = findings.iter().find(|f| f.component == "com.example.LinkActivity").unwrap();
+        assert!(link.is_exploitable());
+        assert!(link.reaches_webview);
+        assert_eq!(link.sinks.len(), 1);
+        assert!(link.sinks[0].starts_with("android.webkit.WebView.loadUrl"));
+        assert_eq!(link.shortest_path.as_ref().unwrap().methods.first().map(String::as_str), Some("com.example.LinkActivity.onCreate"));
+
+        let share = findings.iter().find(|f| f.component == "com.example.ShareActivity").unwrap();
+        assert!(!share.is_exploitable());
+    }
+
+    #[test]
+    fn test_find_data_flows() {
+        let activity = "com.example.LinkActivity";
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec![activity.to_string()];
+        manifest.intent_filters = vec![deeplink_filter(activity, "example", "link")];
+
+        // Sources are matched by name along the path, so the link is read
+        // by a helper that sits between the handler and the sink
+        let mut graph = CallGraph::new();
+        graph.add_call("com.example.LinkActivity.onCreate".to_string(), "com.example.LinkRouter.getData".to_string(), String::new());
+        graph.add_call("com.example.LinkRouter.getData".to_string(), "android.webkit.WebView.loadUrl".to_string(), String::new());
+        graph.add_call("com.example.LinkActivity.onCreate".to_string(), "android.util.Log.d".to_string(), String::new());
+        let analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph);
+
+        let flows = analyzer.find_data_flows(&["loadUrl"], 10);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].source, "com.example.LinkRouter.getData");
+        assert_eq!(flows[0].source_kind, DataSourceKind::IntentUri);
+        assert_eq!(flows[0].sink, "android.webkit.WebView.loadUrl");
+        assert_eq!(flows[0].flow_path.first().map(String::as_str), Some("com.example.LinkActivity.onCreate"));
+
+        assert!(analyzer.find_data_flows(&["execSQL"], 10).is_empty());
+    }
+
+    #[test]
+    fn test_static_initializer_flow_root() {
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.services = vec!["com.example.SyncService".to_string()];
+
+        let mut graph = CallGraph::new();
+        graph.add_call("com.example.SyncService.<clinit>".to_string(), "com.example.Config.load".to_string(), String::new());
+        graph.add_call("com.example.Config.load".to_string(), "okhttp3.OkHttpClient.newCall".to_string(), String::new());
+        graph.add_call("com.example.SyncService.onCreate".to_string(), "android.util.Log.d".to_string(), String::new());
+        let mut analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph);
+
+        assert!(analyzer.find_network_flows(5).is_empty());
+
+        analyzer.set_include_initializers(true);
+        let flows = analyzer.find_network_flows(5);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].entry_point, "com.example.SyncService");
+        assert_eq!(flows[0].paths[0].methods[0], "com.example.SyncService.<clinit>");
+        assert_eq!(flows[0].min_path_length, 2);
+    }
+
+    #[test]
+    fn test_convenience_methods_tag_sink_category() {
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec!["com.example.MainActivity".to_string()];
+
+        let mut graph = CallGraph::new();
+        for sink in [
+            "android.webkit.WebView.loadUrl",
+            "java.io.FileOutputStream.write",
+            "okhttp3.OkHttpClient.newCall",
+            "android.database.sqlite.SQLiteDatabase.execSQL",
+        ] {
+            graph.add_call("com.example.MainActivity.onCreate".to_string(), sink.to_string(), String::new());
+        }
+        let analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph);
+
+        let categories = |flows: Vec<Flow>| flows.into_iter().map(|f| f.sink_category).collect::<Vec<_>>();
+        assert_eq!(categories(analyzer.find_webview_flows(5)), vec!["webview"]);
+        assert_eq!(categories(analyzer.find_file_flows(5)), vec!["file"]);
+        assert_eq!(categories(analyzer.find_network_flows(5)), vec!["network"]);
+        assert_eq!(categories(analyzer.find_sql_flows(5)), vec!["sql"]);
+        assert_eq!(categories(analyzer.find_flows_to(&["execSQL"], 5)), vec!["custom"]);
+
+        let flow = analyzer.find_sql_flows(5).remove(0);
+        assert!(flow.__repr__().starts_with("Flow([sql] com.example.MainActivity"));
+    }
+
+    #[test]
+    fn test_find_flows_to_class() {
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec!["com.example.CheckoutActivity".to_string(), "com.example.MainActivity".to_string()];
+
+        let mut graph = CallGraph::new();
+        let call = |graph: &mut CallGraph, caller: &str, callee: &str| {
+            graph.add_call(caller.to_string(), callee.to_string(), String::new());
+        };
+        call(&mut graph, "com.example.CheckoutActivity.onCreate", "com.example.CheckoutActivity.submit");
+        call(&mut graph, "com.example.CheckoutActivity.submit", "com.example.pay.PaymentClient.charge");
+        call(&mut graph, "com.example.pay.PaymentClient.charge", "com.example.pay.PaymentClient.sign");
+        call(&mut graph, "com.example.MainActivity.onCreate", "com.example.pay.PaymentClientFactory.create");
+        call(&mut graph, "com.example.MainActivity.onCreate", "com.example.pay.PaymentClient$Callback.done");
+
+        let analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph);
+        let flows = analyzer.find_flows_to_class("com.example.pay.PaymentClient", 10);
+
+        assert!(flows.iter().all(|f| f.entry_point == "com.example.CheckoutActivity"));
+        assert!(flows.iter().all(|f| f.sink_category == "class"));
+        let sinks: Vec<&str> = flows.iter().map(|f| f.sink_method.as_str()).collect();
+        assert_eq!(sinks, vec!["com.example.pay.PaymentClient.charge", "com.example.pay.PaymentClient.sign"]);
+        assert_eq!(flows[0].min_path_length, 2);
+
+        assert!(analyzer.find_flows_to_class("com.example.Missing", 10).is_empty());
+    }
+
+    #[test]
+    fn test_confidence_calibration() {
+        let path: Vec<String> = ["com.example.LinkActivity.onCreate", "android.content.Intent.getDataString", "android.webkit.WebView.loadUrl"]
+            .iter()
+            .map(|m| m.to_string())
+            .collect();
+
+        let (proven, proven_evidence) = score_flow(&path, true, &[]);
+        let (textual, textual_evidence) = score_flow(&path, false, &[]);
+        assert_eq!(textual, 0.9);
+        assert_eq!(proven_evidence, vec!["taint reaches the sink through registers"]);
+        assert_eq!(textual_evidence, vec!["source and sink matched by method name only"]);
+
+        let (lowered, evidence) = score_flow(&path, true, &["java.net.URLEncoder.encode".to_string()]);
+        assert!(lowered < proven);
+        assert_eq!(evidence.last().unwrap(), "passes through sanitizer java.net.URLEncoder.encode");
+
+        // Name-only flows keep the path-length tiers
+        let hops = |n: usize| -> Vec<String> { (0..=n).map(|i| format!("com.example.A.m{}", i)).collect() };
+        let tiers: Vec<f32> = [3, 5, 8, 19].iter().map(|&n| score_flow(&hops(n), false, &[]).0).collect();
+        assert_eq!(tiers, vec![0.9, 0.7, 0.5, 0.3]);
+
+        let (long_proven, _) = score_flow(&hops(19), true, &[]);
+        assert!(long_proven > tiers[3]);
+        assert!((0.0..=1.0).contains(&long_proven));
+    }
+
+    #[test]
+    fn test_sanitized_flow() {
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec!["com.example.LinkActivity".to_string()];
+        manifest.intent_filters = vec![deeplink_filter("com.example.LinkActivity", "example", "link")];
+
+        let mut analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), CallGraph::new());
+        let path = |methods: &[&str]| CallPath {
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+            calls: Vec::new(),
+            length: methods.len() - 1,
+        };
+        let flow = Flow {
+            entry_point: "com.example.LinkActivity".to_string(),
+            component_type: "Activity".to_string(),
+            sink_method: "android.webkit.WebView.loadUrl".to_string(),
+            paths: vec![
+                path(&["android.content.Intent.getStringExtra", "java.net.URLEncoder.encode", "android.webkit.WebView.loadUrl"]),
+                path(&["android.content.Intent.getStringExtra", "android.webkit.WebView.loadUrl"]),
+            ],
+            is_deeplink_handler: true,
+            min_path_length: 1,
+            path_count: 2,
+            sink_category: "webview".to_string(),
+        };
+
+        let data_flows = analyzer.analyze_data_flows(std::slice::from_ref(&flow));
+        assert_eq!(data_flows.len(), 2);
+        assert!(data_flows[0].sanitized);
+        assert!(!data_flows[1].sanitized);
+        assert!(data_flows[0].confidence < data_flows[1].confidence);
+        assert!(data_flows[0].evidence.contains(&"passes through sanitizer java.net.URLEncoder.encode".to_string()));
+
+        analyzer.set_sanitizer_patterns(Vec::new());
+        assert!(!analyzer.analyze_data_flows(&[flow])[0].sanitized);
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec!["com.example.PasteActivity".to_string()];
+
+        let mut graph = CallGraph::new();
+        graph.add_call("com.example.PasteActivity.onResume".to_string(), "com.example.Clip.read".to_string(), String::new());
+        graph.add_call("com.example.Clip.read".to_string(), "android.content.ClipboardManager.getPrimaryClip".to_string(), String::new());
+        graph.add_call("com.example.Clip.read".to_string(), "com.example.Uploader.send".to_string(), String::new());
+        graph.add_call("com.example.Clip.read".to_string(), "android.content.ContextWrapper.getDatabasePath".to_string(), String::new());
+        graph.add_call("com.example.Uploader.send".to_string(), "java.net.Socket.connect".to_string(), String::new());
+        let mut analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph);
+
+        // Built-in rules know nothing about the clipboard
+        assert!(analyzer.find_data_flows(&["Socket.connect"], 10).is_empty());
+
+        // The source is called from the path, not on it
+        assert!(analyzer.find_custom_flows(&["ClipboardManager.getPrimaryClip"], &["Socket.connect"], 10).is_empty());
+        analyzer.set_scan_callees(true);
+        let flows = analyzer.find_custom_flows(&["ClipboardManager.getPrimaryClip"], &["Socket.connect"], 10);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].source, "android.content.ClipboardManager.getPrimaryClip");
+        assert_eq!(flows[0].sink, "java.net.Socket.connect");
+        assert!(analyzer.find_custom_flows(&["ClipboardManager.getPrimaryClip"], &["loadUrl"], 10).is_empty());
+        assert!(analyzer.find_custom_flows(&["ClipboardManager.setPrimaryClip"], &["Socket.connect"], 10).is_empty());
+        // Called methods must be the source itself, not merely contain it
+        assert!(analyzer.find_custom_flows(&["Clipboard"], &["Socket.connect"], 10).is_empty());
+        assert_eq!(analyzer.find_custom_flows(&["getPrimaryClip"], &["Socket.connect"], 10).len(), 1);
+        assert!(analyzer.find_custom_flows(&["getData"], &["Socket.connect"], 10).is_empty());
+
+        let analyzer = analyzer.with_rules(
+            vec!["ClipboardManager.getPrimaryClip".to_string()],
+            vec!["Socket.connect".to_string()],
+        );
+        let flows = analyzer.find_data_flows(&["Socket.connect"], 10);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].flow_path.first().map(String::as_str), Some("com.example.PasteActivity.onResume"));
+    }
+
+    #[test]
+    fn test_crypto_and_reflection_flows() {
+        let mut builder = DexBuilder::new();
+        let class_name = "Lcom/example/VaultActivity;";
+        let on_create = builder.method(class_name, "onCreate", "V", &[]);
+        let seal = builder.method(class_name, "seal", "V", &[]);
+        let wrap = builder.method(class_name, "wrap", "V", &[]);
+        let get_cipher = builder.method("Ljavax/crypto/Cipher;", "getInstance", "Ljavax/crypto/Cipher;", &["Ljava/lang/String;"]);
+        let ecb = builder.string("AES/ECB/PKCS5Padding");
+        let gcm = builder.string("AES/GCM/NoPadding");
+        let bare = builder.string("AES");
+
+        let class = builder.class(class_name, Some("Landroid/app/Activity;"), 0x0001);
+        for (method, transformation) in [(on_create, ecb), (seal, gcm), (wrap, bare)] {
+            builder.virtual_method(
+                class,
+                method,
+                0x0001,
+                Some(vec![
+                    0x001a, transformation as u16, // const-string v0, transformation
+                    0x1071, get_cipher as u16, 0x0000, // invoke-static {v0}, Cipher.getInstance
+                    0x000c, // move-result-object v0
+                    0x000e,
+                ]),
+            );
+        }
+        let parser = DexParser::new(builder.build()).unwrap();
+
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec!["com.example.VaultActivity".to_string()];
+        let mut graph = CallGraph::new();
+        graph.add_call("com.example.VaultActivity.onCreate".to_string(), "javax.crypto.Cipher.getInstance".to_string(), String::new());
+        graph.add_call("com.example.VaultActivity.onStart".to_string(), "java.lang.Class.forName".to_string(), String::new());
+        let analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph);
+
+        let crypto = analyzer.find_crypto_flows(10);
+        assert_eq!(crypto.len(), 1);
+        assert_eq!(crypto[0].sink_method, "javax.crypto.Cipher.getInstance");
+        assert_eq!(crypto[0].sink_category, "crypto");
+
+        let reflection = analyzer.find_reflection_flows(10);
+        assert_eq!(reflection.len(), 1);
+        assert_eq!(reflection[0].sink_method, "java.lang.Class.forName");
+        assert_eq!(reflection[0].sink_category, "reflection");
+
+        let weak = analyzer.find_weak_crypto(&[parser], 10);
+        assert_eq!(weak.len(), 2);
+        assert_eq!(weak[0].method, "com.example.VaultActivity.onCreate");
+        assert_eq!(weak[0].expression, "Cipher.getInstance(\"AES/ECB/PKCS5Padding\")");
+        assert_eq!(weak[0].algorithms, vec!["ECB"]);
+        assert!(weak[0].reachable);
+        assert_eq!(weak[1].method, "com.example.VaultActivity.wrap");
+        assert_eq!(weak[1].algorithms, vec!["ECB"]);
+        assert!(!weak[1].reachable);
+
+        assert_eq!(weak_algorithms("MessageDigest.getInstance(\"md5\")"), vec!["MD5"]);
+        assert_eq!(weak_algorithms("Cipher.getInstance(\"DES/CBC/PKCS5Padding\")"), vec!["DES"]);
+
+        // No mode means ECB for block ciphers, but not for RSA or digests
+        assert_eq!(weak_algorithms("Cipher.getInstance(\"AES\")"), vec!["ECB"]);
+        assert_eq!(weak_algorithms("Cipher.getInstance(\"des\")"), vec!["DES", "ECB"]);
+        assert!(weak_algorithms("Cipher.getInstance(\"RSA\")").is_empty());
+        assert!(weak_algorithms("KeyGenerator.getInstance(\"AES\")").is_empty());
+
+        // RSA only takes "ECB" as a placeholder mode
+        assert!(weak_algorithms("Cipher.getInstance(\"RSA/ECB/OAEPWithSHA-256AndMGF1Padding\")").is_empty());
+        assert!(weak_algorithms("Cipher.getInstance(\"RSA/ECB/PKCS1Padding\")").is_empty());
+        assert_eq!(weak_algorithms("Cipher.getInstance(\"DESede/ECB/NoPadding\")"), vec!["DESede", "ECB"]);
+    }
+
+    #[test]
+    fn test_register_taint_mode() {
+        let activity = "com.example.LinkActivity";
+        let mut builder = DexBuilder::new();
+        let class_name = "Lcom/example/LinkActivity;";
+        let on_create = builder.method(class_name, "onCreate", "V", &[]);
+        let on_new_intent = builder.method(class_name, "onNewIntent", "V", &[]);
+        let get_extra = builder.method("Landroid/content/Intent;", "getStringExtra", "Ljava/lang/String;", &["Ljava/lang/String;"]);
+        let load_url = builder.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let home = builder.string("https://example.com/");
+
+        let class = builder.class(class_name, Some("Landroid/app/Activity;"), 0x0001);
+        builder.virtual_method(
+            class,
+            on_create,
+            0x0001,
+            Some(vec![
+                0x206e, get_extra as u16, 0x0013, // invoke-virtual {v3, v1}, Intent.getStringExtra
+                0x000c, // move-result-object v0
+                0x0007, // move-object v0, v0
+                0x206e, load_url as u16, 0x0002, // invoke-virtual {v2, v0}, WebView.loadUrl
+                0x000e,
+            ]),
+        );
+        builder.virtual_method(
+            class,
+            on_new_intent,
+            0x0001,
+            Some(vec![
+                0x206e, get_extra as u16, 0x0013, // invoke-virtual {v3, v1}, Intent.getStringExtra
+                0x000c, // move-result-object v0
+                0x001a, home as u16, // const-string v0, "https://example.com/"
+                0x206e, load_url as u16, 0x0002, // invoke-virtual {v2, v0}, WebView.loadUrl
+                0x000e,
+            ]),
+        );
+        let parser = DexParser::new(builder.build()).unwrap();
+
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec![activity.to_string()];
+        let mut graph = CallGraph::new();
+        for caller in ["com.example.LinkActivity.onCreate", "com.example.LinkActivity.onNewIntent"] {
+            graph.add_call(caller.to_string(), "android.content.Intent.getStringExtra".to_string(), String::new());
+            graph.add_call(caller.to_string(), "android.webkit.WebView.loadUrl".to_string(), String::new());
+        }
+        let mut analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph);
+        let flows = analyzer.find_flows_to(&["loadUrl"], 10);
+
+        // The source is called beside the sink, never on the path to it
+        assert!(analyzer.analyze_data_flows(&flows).is_empty());
+
+        // Scanning callees sees it in both methods; only register tracking
+        // tells them apart
+        analyzer.set_scan_callees(true);
+        let heuristic = analyzer.analyze_data_flows(&flows);
+        assert_eq!(heuristic.len(), 2);
+        assert!(heuristic.iter().all(|flow| flow.source == "android.content.Intent.getStringExtra"));
+
+        let data_flows = analyzer.analyze_data_flows_with_mode(&flows, TaintMode::Register, std::slice::from_ref(&parser));
+        assert_eq!(data_flows.len(), 1);
+        assert_eq!(data_flows[0].source, "android.content.Intent.getStringExtra");
+        assert_eq!(data_flows[0].source_kind, DataSourceKind::IntentExtra);
+        assert_eq!(data_flows[0].flow_path, vec!["com.example.LinkActivity.onCreate", "android.webkit.WebView.loadUrl"]);
+        assert_eq!(data_flows[0].evidence[0], "taint reaches the sink through registers");
+
+        // Overridden argument positions apply in register mode too
+        let overrides = [SinkSpec::with_arguments("loadUrl", vec![1])];
+        let data_flows = analyzer.analyze_data_flows_with_sinks(&flows, TaintMode::Register, std::slice::from_ref(&parser), &overrides);
+        assert!(data_flows.is_empty());
+
+        // Name matching cannot honor them, so they are refused up front
+        Python::initialize();
+        Python::attach(|py| {
+            let sink_arguments = HashMap::from([("loadUrl".to_string(), vec![0])]);
+            let result = find_data_flows_from_apk(
+                "missing.apk".to_string(),
+                vec!["loadUrl".to_string()],
+                None,
+                false,
+                Some(sink_arguments),
+                TaintMode::Heuristic,
+            );
+            let err = result.unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_cross_method_register_flow() {
+        use crate::dex::field_taint::find_call_flows;
+        use crate::dex::test_support::write_test_apk;
+
+        let mut builder = DexBuilder::new();
+        let class_name = "Lcom/example/LinkActivity;";
+        let on_create = builder.method(class_name, "onCreate", "V", &[]);
+        let open = builder.method(class_name, "open", "V", &["Ljava/lang/String;"]);
+        let open_home = builder.method(class_name, "openHome", "V", &["Ljava/lang/String;"]);
+        let get_extra = builder.method("Landroid/content/Intent;", "getStringExtra", "Ljava/lang/String;", &["Ljava/lang/String;"]);
+        let load_url = builder.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let home = builder.string("https://example.com/");
+
+        let class = builder.class(class_name, Some("Landroid/app/Activity;"), 0x0001);
+        builder.virtual_method(
+            class,
+            on_create,
+            0x0001,
+            Some(vec![
+                0x206e, get_extra as u16, 0x0013, // invoke-virtual {v3, v1}, Intent.getStringExtra
+                0x000c, // move-result-object v0
+                0x206e, open as u16, 0x0002, // invoke-virtual {v2, v0}, open
+                0x206e, open_home as u16, 0x0002, // invoke-virtual {v2, v0}, openHome
+                0x000e,
+            ]),
+        );
+        // 16 registers with `this` and the url in v14 and v15
+        builder.virtual_method(
+            class,
+            open,
+            0x0001,
+            Some(vec![
+                0x0008, 0x000f, // move-object/from16 v0, v15
+                0x206e, load_url as u16, 0x0001, // invoke-virtual {v1, v0}, WebView.loadUrl
+                0x000e,
+            ]),
+        );
+        builder.virtual_method(
+            class,
+            open_home,
+            0x0001,
+            Some(vec![
+                0x001a, home as u16, // const-string v0, "https://example.com/"
+                0x206e, load_url as u16, 0x0001, // invoke-virtual {v1, v0}, WebView.loadUrl
+                0x000e,
+            ]),
+        );
+
+        let apk = write_test_apk("cross-method-flow", &[builder.build()]);
+        let extractor = crate::apk::ApkExtractor::new(&apk).unwrap();
+        std::fs::remove_file(&apk).ok();
+        let parsers: Vec<DexParser> = extractor
+            .dex_entries()
+            .iter()
+            .map(|dex_entry| DexParser::from_arc(dex_entry.data.clone()).unwrap())
+            .collect();
+
+        let sinks = [SinkSpec::new("loadUrl")];
+        assert!(find_call_flows(&parsers, &["getStringExtra"], &sinks, 0).is_empty());
+        let flows = find_call_flows(&parsers, &["getStringExtra"], &sinks, 1);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].methods, vec!["com.example.LinkActivity.onCreate", "com.example.LinkActivity.open"]);
+
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec!["com.example.LinkActivity".to_string()];
+        let mut graph = CallGraph::new();
+        for helper in ["com.example.LinkActivity.open", "com.example.LinkActivity.openHome"] {
+            graph.add_call("com.example.LinkActivity.onCreate".to_string(), helper.to_string(), String::new());
+            graph.add_call(helper.to_string(), "android.webkit.WebView.loadUrl".to_string(), String::new());
+        }
+        let analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph);
+
+        let data_flows = analyzer.analyze_data_flows_with_mode(&analyzer.find_flows_to(&["loadUrl"], 10), TaintMode::Register, &parsers);
+        assert_eq!(data_flows.len(), 1);
+        assert_eq!(data_flows[0].source, "android.content.Intent.getStringExtra");
+        assert_eq!(
+            data_flows[0].flow_path,
+            vec!["com.example.LinkActivity.onCreate", "com.example.LinkActivity.open", "android.webkit.WebView.loadUrl"]
+        );
+    }
+
+    #[test]
+    fn test_source_kind_classification() {
+        assert_eq!(DataSourceKind::classify("android.content.Intent.getData"), DataSourceKind::IntentUri);
+        assert_eq!(DataSourceKind::classify("android.content.Intent.getStringExtra"), DataSourceKind::IntentExtra);
+        assert_eq!(DataSourceKind::classify("android.content.Intent.getParcelableExtra"), DataSourceKind::IntentExtra);
+        assert_eq!(DataSourceKind::classify("android.content.Intent.getDataString"), DataSourceKind::IntentData);
+        assert_eq!(DataSourceKind::classify("android.content.Intent.getExtras"), DataSourceKind::Bundle);
+        assert_eq!(DataSourceKind::classify("android.os.Bundle.getString"), DataSourceKind::Bundle);
+        assert_eq!(DataSourceKind::classify("okhttp3.ResponseBody.string"), DataSourceKind::NetworkResponse);
+        assert_eq!(DataSourceKind::classify("java.io.FileInputStream.read"), DataSourceKind::FileRead);
+        assert_eq!(DataSourceKind::classify("com.example.Util.helper"), DataSourceKind::Unknown);
+        assert_eq!(
+            DataSourceKind::classify("android.content.Intent.getStringExtra(java.lang.String): java.lang.String"),
+            DataSourceKind::IntentExtra
+        );
+    }
+
+    #[test]
+    fn test_custom_sources_are_classified() {
+        let mut builder = DexBuilder::new();
+        let class_name = "Lcom/example/SyncActivity;";
+        let on_create = builder.method(class_name, "onCreate", "V", &[]);
+        let on_resume = builder.method(class_name, "onResume", "V", &[]);
+        let body = builder.method("Lokhttp3/ResponseBody;", "string", "Ljava/lang/String;", &[]);
+        let read = builder.method("Ljava/nio/file/Files;", "readString", "Ljava/lang/String;", &["Ljava/nio/file/Path;"]);
+        let load_url = builder.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+
+        let class = builder.class(class_name, Some("Landroid/app/Activity;"), 0x0001);
+        builder.virtual_method(
+            class,
+            on_create,
+            0x0001,
+            Some(vec![
+                0x106e, body as u16, 0x0003, // invoke-virtual {v3}, ResponseBody.string
+                0x000c, // move-result-object v0
+                0x206e, load_url as u16, 0x0002, // invoke-virtual {v2, v0}, WebView.loadUrl
+                0x000e,
+            ]),
+        );
+        builder.virtual_method(
+            class,
+            on_resume,
+            0x0001,
+            Some(vec![
+                0x1071, read as u16, 0x0003, // invoke-static {v3}, Files.readString
+                0x000c, // move-result-object v0
+                0x206e, load_url as u16, 0x0002, // invoke-virtual {v2, v0}, WebView.loadUrl
+                0x000e,
+            ]),
+        );
+        let parser = DexParser::new(builder.build()).unwrap();
+
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec!["com.example.SyncActivity".to_string()];
+        let mut graph = CallGraph::new();
+        for caller in ["com.example.SyncActivity.onCreate", "com.example.SyncActivity.onResume"] {
+            graph.add_call(caller.to_string(), "android.webkit.WebView.loadUrl".to_string(), String::new());
+        }
+        let analyzer = DataFlowAnalyzer::new(EntryPointAnalyzer::new(manifest, Vec::new()), graph)
+            .with_rules(vec!["ResponseBody.string".to_string(), "Files.read".to_string()], vec!["loadUrl".to_string()]);
+
+        let flows = analyzer.find_flows_to(&["loadUrl"], 10);
+        let mut kinds: Vec<(String, DataSourceKind)> = analyzer
+            .analyze_data_flows_with_mode(&flows, TaintMode::Register, std::slice::from_ref(&parser))
+            .into_iter()
+            .map(|flow| (flow.source, flow.source_kind))
+            .collect();
+        kinds.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            kinds,
+            vec![
+                ("java.nio.file.Files.readString".to_string(), DataSourceKind::FileRead),
+                ("okhttp3.ResponseBody.string".to_string(), DataSourceKind::NetworkResponse),
+            ]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Method descriptor parsing
+//!
+//! Parses method references as emitted by hooking tools such as Frida,
+//! e.g. `Lcom/example/Foo;->bar(ILjava/lang/String;)V`, into the dotted
+//! Java names used by the rest of the analyzer.
+
+use crate::dex::constants::type_descriptors;
+
+/// A parsed method reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodDescriptor {
+    /// Declaring class, e.g. "com.example.Foo"
+    pub class_name: String,
+    /// Method name, e.g. "bar"
+    pub method_name: String,
+    /// Parameter types, e.g. ["int", "java.lang.String"]; None when the
+    /// input had no signature
+    pub parameters: Option<Vec<String>>,
+    /// Return type, e.g. "void"; None when the input had no signature
+    pub return_type: Option<String>,
+}
+
+impl MethodDescriptor {
+    /// Parse a method reference
+    ///
+    /// Accepts the Dalvik form `Lcom/x/Y;->m(I)V` as well as dotted forms
+    /// `com.x.Y->m(I)V` and `com.x.Y.m(I)V`. The signature is optional;
+    /// without it only the class and method name are known.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let (owner, rest) = match input.split_once("->") {
+            Some(parts) => parts,
+            None => {
+                let name_end = input.find('(').unwrap_or(input.len());
+                let dot = input[..name_end].rfind('.')?;
+                (&input[..dot], &input[dot + 1..])
+            }
+        };
+
+        let class_name = if owner.starts_with(type_descriptors::OBJECT) && owner.ends_with(';') {
+            java_type(owner)?
+        } else {
+            owner.replace('/', ".")
+        };
+
+        let (method_name, parameters, return_type) = match rest.split_once('(') {
+            Some((name, signature)) => {
+                let (params, ret) = signature.split_once(')')?;
+                (name, Some(parse_type_list(params)?), Some(java_type(ret)?))
+            }
+            None => (rest, None, None),
+        };
+
+        if class_name.is_empty() || method_name.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            class_name,
+            method_name: method_name.to_string(),
+            parameters,
+            return_type,
+        })
+    }
+}
+
+/// Split a concatenated list of type descriptors ("ILjava/lang/String;[B")
+fn parse_type_list(mut list: &str) -> Option<Vec<String>> {
+    let mut types = Vec::new();
+    while !list.is_empty() {
+        let dims = list.chars().take_while(|&c| c == type_descriptors::ARRAY).count();
+        let len = match list[dims..].chars().next()? {
+            type_descriptors::OBJECT => list[dims..].find(';')? + 1,
+            _ => 1,
+        };
+        types.push(java_type(&list[..dims + len])?);
+        list = &list[dims + len..];
+    }
+    Some(types)
+}
+
+/// Convert a single type descriptor to its Java name
+fn java_type(descriptor: &str) -> Option<String> {
+    let dims = descriptor.chars().take_while(|&c| c == type_descriptors::ARRAY).count();
+    let base = &descriptor[dims..];
+    let name = match base.chars().next()? {
+        type_descriptors::OBJECT => base.strip_prefix('L')?.strip_suffix(';')?.replace('/', "."),
+        c if base.len() == 1 => match type_descriptors::descriptor_to_java(c) {
+            "unknown" => return None,
+            primitive => primitive.to_string(),
+        },
+        _ => return None,
+    };
+    Some(format!("{}{}", name, "[]".repeat(dims)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_method_descriptor() {
+        let dalvik = MethodDescriptor::parse("Lcom/example/Foo;->bar(I[Ljava/lang/String;J)Landroid/net/Uri;").unwrap();
+        assert_eq!(dalvik.class_name, "com.example.Foo");
+        assert_eq!(dalvik.method_name, "bar");
+        assert_eq!(
+            dalvik.parameters,
+            Some(vec!["int".to_string(), "java.lang.String[]".to_string(), "long".to_string()])
+        );
+        assert_eq!(dalvik.return_type.as_deref(), Some("android.net.Uri"));
+
+        assert_eq!(MethodDescriptor::parse("com.example.Foo->bar(I[Ljava/lang/String;J)Landroid/net/Uri;"), Some(dalvik.clone()));
+        assert_eq!(MethodDescriptor::parse("com.example.Foo.bar(I[Ljava/lang/String;J)Landroid/net/Uri;"), Some(dalvik));
+
+        let name_only = MethodDescriptor::parse("com.example.Foo.<init>").unwrap();
+        assert_eq!(name_only.method_name, "<init>");
+        assert_eq!(name_only.parameters, None);
+
+        assert_eq!(MethodDescriptor::parse("Lcom/example/Foo;->bar(Q)V"), None);
+        assert_eq!(MethodDescriptor::parse("Lcom/example/Foo;->bar(I"), None);
+        assert_eq!(MethodDescriptor::parse("bar"), None);
+    }
+}
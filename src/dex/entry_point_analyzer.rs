@@ -47,6 +47,15 @@ pub struct EntryPoint {
     #[pyo3(get)]
     pub is_deeplink_handler: bool,
 
+    /// Content provider authorities (empty for other components)
+    #[pyo3(get)]
+    pub authorities: Vec<String>,
+
+    /// Whether other apps can start or bind this component, from
+    /// `android:exported` or its intent-filter default
+    #[pyo3(get)]
+    pub is_exported: bool,
+
     /// Whether the corresponding class was found in DEX
     #[pyo3(get)]
     pub class_found: bool,
@@ -106,9 +115,10 @@ impl EntryPoint {
 
     fn __repr__(&self) -> String {
         format!(
-            "EntryPoint({:?}, {}, deeplink={}, found={})",
+            "EntryPoint({:?}, {}, exported={}, deeplink={}, found={})",
             self.component_type,
             self.class_name,
+            self.is_exported,
             self.is_deeplink_handler,
             self.class_found
         )
@@ -140,34 +150,16 @@ impl EntryPointAnalyzer {
     pub fn analyze(&self) -> Vec<EntryPoint> {
         let mut entry_points = Vec::new();
 
-        // Analyze Activities
-        for activity in &self.manifest.activities {
-            entry_points.push(self.analyze_component(
-                activity,
-                ComponentType::Activity,
-            ));
-        }
-
-        // Analyze Services
-        for service in &self.manifest.services {
-            entry_points.push(EntryPoint {
-                component_type: ComponentType::Service,
-                class_name: service.clone(),
-                intent_filters: Vec::new(), // Services typically don't have intent filters in manifest
-                is_deeplink_handler: false,
-                class_found: self.classes.contains_key(service),
-            });
-        }
-
-        // Analyze BroadcastReceivers
-        for receiver in &self.manifest.receivers {
-            entry_points.push(EntryPoint {
-                component_type: ComponentType::BroadcastReceiver,
-                class_name: receiver.clone(),
-                intent_filters: Vec::new(),
-                is_deeplink_handler: false,
-                class_found: self.classes.contains_key(receiver),
-            });
+        let components = [
+            (&self.manifest.activities, ComponentType::Activity),
+            (&self.manifest.services, ComponentType::Service),
+            (&self.manifest.receivers, ComponentType::BroadcastReceiver),
+            (&self.manifest.providers, ComponentType::ContentProvider),
+        ];
+        for (names, component_type) in components {
+            for name in names {
+                entry_points.push(self.analyze_component(name, component_type.clone()));
+            }
         }
 
         entry_points
@@ -195,10 +187,23 @@ impl EntryPointAnalyzer {
             class_name: class_name.to_string(),
             intent_filters,
             is_deeplink_handler,
+            authorities: self
+                .manifest
+                .providers_detail
+                .iter()
+                .find(|provider| provider.name == class_name)
+                .map(|provider| provider.authorities.clone())
+                .unwrap_or_default(),
+            is_exported: self.is_exported(class_name),
             class_found,
         }
     }
 
+    /// Whether the manifest marks `class_name` as exported
+    fn is_exported(&self, class_name: &str) -> bool {
+        self.manifest.exported_components.iter().any(|name| name == class_name)
+    }
+
     /// Get all deeplink handlers
     pub fn get_deeplink_handlers(&self) -> Vec<EntryPoint> {
         self.analyze()
@@ -207,6 +212,18 @@ impl EntryPointAnalyzer {
             .collect()
     }
 
+    /// Get deeplink handlers whose class was found in DEX, with that class
+    ///
+    /// Manifest-only handlers are dropped, so every result can be used as a
+    /// root for flow analysis.
+    pub fn get_analyzable_deeplink_handlers(&self) -> Vec<(EntryPoint, DecompiledClass)> {
+        self.get_deeplink_handlers()
+            .into_iter()
+            .filter(|ep| ep.class_found)
+            .filter_map(|ep| self.get_entry_point_with_class(&ep.class_name))
+            .collect()
+    }
+
     /// Get entry point with its class
     pub fn get_entry_point_with_class(&self, class_name: &str) -> Option<(EntryPoint, DecompiledClass)> {
         let entry_point = self.analyze()
@@ -249,12 +266,19 @@ impl PyEntryPointAnalyzer {
         self.analyzer.get_deeplink_handlers()
     }
 
-    /// Get entry points that have classes found in DEX
-    pub fn get_found_entry_points(&self) -> Vec<EntryPoint> {
+    /// Get deeplink handlers found in DEX, each paired with its decompiled class
+    pub fn get_analyzable_deeplink_handlers(&self) -> Vec<(EntryPoint, DecompiledClass)> {
+        self.analyzer.get_analyzable_deeplink_handlers()
+    }
+
+    /// Get entry points that have classes found in DEX, optionally only
+    /// the exported ones
+    #[pyo3(signature = (exported_only=false))]
+    pub fn get_found_entry_points(&self, exported_only: bool) -> Vec<EntryPoint> {
         self.analyzer
             .analyze()
             .into_iter()
-            .filter(|ep| ep.class_found)
+            .filter(|ep| ep.class_found && (ep.is_exported || !exported_only))
             .collect()
     }
 
@@ -303,6 +327,7 @@ pub fn analyze_entry_points_from_apk(apk_path: String) -> PyResult<PyEntryPointA
         .iter()
         .chain(manifest.services.iter())
         .chain(manifest.receivers.iter())
+        .chain(manifest.providers.iter())
         .cloned()
         .collect();
 
@@ -317,3 +342,131 @@ pub fn analyze_entry_points_from_apk(apk_path: String) -> PyResult<PyEntryPointA
 
     Ok(PyEntryPointAnalyzer { analyzer })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apk::manifest::IntentFilterData;
+
+    fn deeplink_filter(activity: &str) -> ActivityIntentFilter {
+        ActivityIntentFilter {
+            activity: activity.to_string(),
+            component_type: "activity".to_string(),
+            actions: vec!["android.intent.action.VIEW".to_string()],
+            categories: vec!["android.intent.category.BROWSABLE".to_string()],
+            data: vec![IntentFilterData {
+                scheme: Some("example".to_string()),
+                host: Some("open".to_string()),
+                path: None,
+                path_prefix: None,
+                path_pattern: None,
+            }],
+        }
+    }
+
+    fn decompiled(class_name: &str) -> DecompiledClass {
+        let (package, simple_name) = class_name.rsplit_once('.').unwrap();
+        DecompiledClass {
+            class_name: class_name.to_string(),
+            package: package.to_string(),
+            simple_name: simple_name.to_string(),
+            superclass: Some("android.app.Activity".to_string()),
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+            methods: Vec::new(),
+            access_flags: 0x0001,
+            static_field_values: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_analyzable_deeplink_handlers_require_class() {
+        let in_dex = "com.example.LinkActivity";
+        let manifest_only = "com.example.MissingActivity";
+
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec![in_dex.to_string(), manifest_only.to_string(), "com.example.MainActivity".to_string()];
+        manifest.intent_filters = vec![deeplink_filter(in_dex), deeplink_filter(manifest_only)];
+
+        let analyzer = EntryPointAnalyzer::new(
+            manifest,
+            vec![decompiled(in_dex), decompiled("com.example.MainActivity")],
+        );
+        assert_eq!(analyzer.get_deeplink_handlers().len(), 2);
+
+        let handlers = analyzer.get_analyzable_deeplink_handlers();
+        assert_eq!(handlers.len(), 1);
+        let (entry_point, class) = &handlers[0];
+        assert_eq!(entry_point.class_name, in_dex);
+        assert!(entry_point.class_found && entry_point.is_deeplink_handler);
+        assert_eq!(class.class_name, in_dex);
+    }
+
+    #[test]
+    fn test_exported_entry_points() {
+        // Explicit and intent-filter-implied exports are resolved by
+        // parse_manifest into exported_components
+        let mut manifest = RustManifestInfo::new("com.example".to_string());
+        manifest.activities = vec![
+            "com.example.LinkActivity".to_string(),
+            "com.example.AdminActivity".to_string(),
+        ];
+        manifest.services = vec!["com.example.SyncService".to_string()];
+        manifest.intent_filters = vec![deeplink_filter("com.example.LinkActivity"), deeplink_filter("com.example.AdminActivity")];
+        manifest.exported_components = vec!["com.example.LinkActivity".to_string(), "com.example.SyncService".to_string()];
+
+        let analyzer = EntryPointAnalyzer::new(manifest, vec![decompiled("com.example.LinkActivity"), decompiled("com.example.AdminActivity")]);
+        let entry_points = analyzer.analyze();
+        let exported: Vec<&str> = entry_points.iter().filter(|ep| ep.is_exported).map(|ep| ep.class_name.as_str()).collect();
+        assert_eq!(exported, vec!["com.example.LinkActivity", "com.example.SyncService"]);
+
+        let py_analyzer = PyEntryPointAnalyzer { analyzer };
+        assert_eq!(py_analyzer.get_found_entry_points(false).len(), 2);
+        let found_exported = py_analyzer.get_found_entry_points(true);
+        assert_eq!(found_exported.len(), 1);
+        assert_eq!(found_exported[0].class_name, "com.example.LinkActivity");
+        assert!(found_exported[0].__repr__().contains("exported=true"));
+    }
+
+    #[test]
+    fn test_receiver_and_provider_entry_points() {
+        use crate::apk::manifest::parse_manifest;
+        use crate::apk::test_support::{element, encode_binary_xml, s};
+
+        let manifest = encode_binary_xml(
+            &element("manifest").with_attr("package", s("com.example")).with_child(
+                element("application")
+                    .with_child(
+                        element("receiver").with_attr("name", s(".BootReceiver")).with_child(
+                            element("intent-filter")
+                                .with_child(element("action").with_attr("name", s("android.intent.action.BOOT_COMPLETED"))),
+                        ),
+                    )
+                    .with_child(
+                        element("provider")
+                            .with_attr("name", s(".FilesProvider"))
+                            .with_attr("authorities", s("com.example.files")),
+                    ),
+            ),
+        );
+        let manifest = parse_manifest(&manifest).unwrap();
+
+        let entry_points = EntryPointAnalyzer::new(manifest, Vec::new()).analyze();
+        assert_eq!(entry_points.len(), 2);
+
+        let boot = &entry_points[0];
+        assert_eq!(boot.class_name, "com.example.BootReceiver");
+        assert_eq!(boot.component_type, ComponentType::BroadcastReceiver);
+        assert_eq!(boot.get_actions(), vec!["android.intent.action.BOOT_COMPLETED"]);
+        assert!(boot.handles_action("android.intent.action.BOOT_COMPLETED"));
+        assert!(!boot.is_deeplink_handler);
+        // An intent filter exports a receiver unless it says otherwise
+        assert!(boot.is_exported);
+
+        let files = &entry_points[1];
+        assert_eq!(files.class_name, "com.example.FilesProvider");
+        assert_eq!(files.component_type, ComponentType::ContentProvider);
+        assert_eq!(files.authorities, vec!["com.example.files"]);
+        assert!(files.intent_filters.is_empty());
+    }
+}
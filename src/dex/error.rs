@@ -23,6 +23,9 @@ pub enum DexError {
     #[error("DEX file error: {0}")]
     DexFileError(String),
 
+    #[error("Unrecognized opcode 0x{opcode:02x} at offset {offset}")]
+    UnknownOpcode { opcode: u8, offset: usize },
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
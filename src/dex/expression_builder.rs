@@ -31,6 +31,16 @@ pub enum RegisterValue {
         field_name: String,
         field_type: String,
     },
+    /// Object from new-instance (fully qualified type name), with the
+    /// constructor arguments once its `<init>` has run
+    NewObject {
+        type_name: String,
+        args: Vec<RegisterValue>,
+    },
+    /// Class literal from const-class (fully qualified name)
+    ClassLiteral(String),
+    /// Class used as the target of a static call (fully qualified name)
+    Class(String),
     /// This reference
     This,
     /// Parameter reference
@@ -75,6 +85,13 @@ impl RegisterValue {
                 let receiver_str = receiver.format();
                 format!("{}.{}", receiver_str, field_name)
             }
+            RegisterValue::NewObject { type_name, args } => {
+                let simple_name = type_name.rsplit('.').next().unwrap_or(type_name);
+                let args_str: Vec<String> = args.iter().map(|a| a.format()).collect();
+                format!("new {}({})", simple_name, args_str.join(", "))
+            }
+            RegisterValue::ClassLiteral(name) => format!("{}.class", name.rsplit('.').next().unwrap_or(name)),
+            RegisterValue::Class(name) => name.rsplit('.').next().unwrap_or(name).to_string(),
             RegisterValue::This => "this".to_string(),
             RegisterValue::Parameter(idx) => format!("param{}", idx),
         }
@@ -119,6 +136,8 @@ pub struct ExpressionBuilder {
     resolver: MethodResolver,
     /// Track register values
     registers: HashMap<u8, RegisterValue>,
+    /// Value of the last invoke, claimed by an immediately following move-result
+    pending_result: Option<RegisterValue>,
     /// Current instruction index
     pc: usize,
 }
@@ -128,6 +147,7 @@ impl ExpressionBuilder {
         Self {
             resolver: MethodResolver::new(parser),
             registers: HashMap::new(),
+            pending_result: None,
             pc: 0,
         }
     }
@@ -150,7 +170,31 @@ impl ExpressionBuilder {
 
     /// Process a single instruction
     fn process_instruction(&mut self, insn: &Instruction) -> std::result::Result<Option<ReconstructedExpression>, String> {
+        // Only the instruction right after an invoke may claim its result
+        let pending_result = self.pending_result.take();
+
         match insn {
+            // Register copies carry the source value (e.g. a constant moved
+            // into the argument register right before a call)
+            Instruction::Move { dest, src, .. } => {
+                self.copy_register(*dest as u16, *src as u16);
+                Ok(None)
+            }
+            Instruction::MoveFrom16 { dest, src, .. } => {
+                self.copy_register(*dest as u16, *src);
+                Ok(None)
+            }
+            Instruction::Move16 { dest, src, .. } => {
+                self.copy_register(*dest, *src);
+                Ok(None)
+            }
+
+            // Method-call results, so chains like getSettings().setJavaScriptEnabled(true) resolve
+            Instruction::MoveResult { dest, .. } => {
+                self.registers.insert(*dest, pending_result.unwrap_or(RegisterValue::Unknown));
+                Ok(None)
+            }
+
             // Const instructions
             Instruction::Const4 { dest, value } => {
                 self.registers
@@ -169,7 +213,7 @@ impl ExpressionBuilder {
             }
 
             // String constants
-            Instruction::ConstString { dest, string_idx } => {
+            Instruction::ConstString { dest, string_idx } | Instruction::ConstStringJumbo { dest, string_idx } => {
                 // Try to resolve string - parser.get_string expects u32
                 if let Ok(string_value) = self.resolver.resolve_string(*string_idx) {
                     self.registers
@@ -180,25 +224,66 @@ impl ExpressionBuilder {
                 Ok(None)
             }
 
+            // Class literals, e.g. the receiver of reflective getMethod calls
+            Instruction::ConstClass { dest, type_idx } => {
+                let value = match self.resolver.resolve_type(*type_idx) {
+                    Ok(type_name) => RegisterValue::ClassLiteral(type_name),
+                    Err(_) => RegisterValue::Unknown,
+                };
+                self.registers.insert(*dest, value);
+                Ok(None)
+            }
+
+            // Object creation; the expression is completed by the <init> call
+            Instruction::NewInstance { dest, type_idx } => {
+                let value = match self.resolver.resolve_type(*type_idx) {
+                    Ok(type_name) => RegisterValue::NewObject { type_name, args: Vec::new() },
+                    Err(_) => RegisterValue::Unknown,
+                };
+                self.registers.insert(*dest, value);
+                Ok(None)
+            }
+
             // Method invocations - this is where we reconstruct expressions
+            Instruction::InvokeStatic { args, method_idx } => {
+                self.process_method_call(args, *method_idx, true)
+            }
             Instruction::InvokeVirtual { args, method_idx }
             | Instruction::InvokeSuper { args, method_idx }
             | Instruction::InvokeDirect { args, method_idx }
-            | Instruction::InvokeStatic { args, method_idx }
             | Instruction::InvokeInterface { args, method_idx } => {
-                self.process_method_call(args, *method_idx)
+                self.process_method_call(args, *method_idx, false)
             }
 
-            // All other instructions (including moves) - just skip for now
+            // All other instructions - just skip for now
             _ => Ok(None),
         }
     }
 
+    /// Copy a register's tracked value, e.g. for `v2 = v1; v2.loadUrl(...)`
+    ///
+    /// Only v0-v255 are tracked, since invoke arguments never use higher
+    /// registers outside range calls; a copy from an untracked register
+    /// leaves the destination unknown.
+    fn copy_register(&mut self, dest: u16, src: u16) {
+        let Ok(dest) = u8::try_from(dest) else { return };
+        let value = u8::try_from(src)
+            .ok()
+            .and_then(|src| self.registers.get(&src).cloned())
+            .unwrap_or(RegisterValue::Unknown);
+        self.registers.insert(dest, value);
+    }
+
     /// Process method call and reconstruct expression
+    ///
+    /// Instance invokes pass the receiver in the first register; static
+    /// invokes have no receiver, so every register is a method argument and
+    /// the declaring class stands in as the receiver.
     fn process_method_call(
         &mut self,
         args: &[u8],
         method_idx: u32,
+        is_static: bool,
     ) -> std::result::Result<Option<ReconstructedExpression>, String> {
         // Resolve method signature
         let signature = match self.resolver.resolve(method_idx) {
@@ -206,28 +291,26 @@ impl ExpressionBuilder {
             Err(_) => return Ok(None), // Skip if we can't resolve
         };
 
-        if args.is_empty() {
-            return Ok(None);
-        }
-
-        // First arg is the receiver (for non-static methods)
-        let receiver_reg = args[0];
-        let receiver = self
-            .registers
-            .get(&receiver_reg)
-            .cloned()
-            .unwrap_or(RegisterValue::Unknown);
+        let (receiver, arg_regs) = if is_static {
+            (RegisterValue::Class(signature.class_name.clone()), args)
+        } else if let Some((&receiver_reg, rest)) = args
+            .split_first()
+            .filter(|(reg, _)| signature.method_name == "<init>" && self.is_new_object(**reg))
+        {
+            return Ok(self.process_constructor(receiver_reg, rest, signature));
+        } else {
+            let Some((&receiver_reg, rest)) = args.split_first() else {
+                return Ok(None);
+            };
+            let receiver = self
+                .registers
+                .get(&receiver_reg)
+                .cloned()
+                .unwrap_or(RegisterValue::Unknown);
+            (receiver, rest)
+        };
 
-        // Rest are method arguments
-        let method_args: Vec<RegisterValue> = args[1..]
-            .iter()
-            .map(|&reg| {
-                self.registers
-                    .get(&reg)
-                    .cloned()
-                    .unwrap_or(RegisterValue::Unknown)
-            })
-            .collect();
+        let method_args = self.collect_args(arg_regs, &signature);
 
         // Create method call value
         let call_value = RegisterValue::MethodCall {
@@ -238,10 +321,13 @@ impl ExpressionBuilder {
 
         // Format as expression
         let expression = call_value.format();
+        self.pending_result = Some(call_value);
 
         // Determine if this is a significant call we want to report
         let is_significant = signature.is_set_javascript_enabled()
             || signature.is_webview_method()
+            || signature.is_crypto_method()
+            || signature.is_reflection_method()
             || expression.contains("WebSettings")
             || expression.contains("WebView");
 
@@ -260,6 +346,62 @@ impl ExpressionBuilder {
     }
 }
 
+impl ExpressionBuilder {
+    /// Whether `reg` holds a new-instance object not yet constructed
+    fn is_new_object(&self, reg: u8) -> bool {
+        matches!(self.registers.get(&reg), Some(RegisterValue::NewObject { args, .. }) if args.is_empty())
+    }
+
+    /// Argument values for the parameters of `signature`
+    ///
+    /// long/double parameters occupy a register pair; only the low register
+    /// carries the value, so the high half is skipped.
+    fn collect_args(&self, arg_regs: &[u8], signature: &MethodSignature) -> Vec<RegisterValue> {
+        let mut regs = arg_regs.iter();
+        let mut method_args = Vec::with_capacity(signature.parameters.len());
+        for parameter in &signature.parameters {
+            let Some(reg) = regs.next() else { break };
+            method_args.push(self.registers.get(reg).cloned().unwrap_or(RegisterValue::Unknown));
+            if is_wide_type(parameter) {
+                regs.next();
+            }
+        }
+        method_args
+    }
+
+    /// Fold an `<init>` call into the new-instance object in `receiver_reg`,
+    /// so later uses of the register format as `new Type(args...)`
+    fn process_constructor(
+        &mut self,
+        receiver_reg: u8,
+        arg_regs: &[u8],
+        signature: MethodSignature,
+    ) -> Option<ReconstructedExpression> {
+        let object = RegisterValue::NewObject {
+            type_name: signature.class_name.clone(),
+            args: self.collect_args(arg_regs, &signature),
+        };
+        let expression = object.format();
+        self.registers.insert(receiver_reg, object);
+
+        let is_significant = signature.is_webview_method()
+            || signature.is_crypto_method()
+            || signature.is_reflection_method()
+            || expression.contains("WebView");
+        is_significant.then(|| ReconstructedExpression {
+            expression,
+            value_type: signature.class_name.clone(),
+            is_method_call: true,
+            method_signature: Some(signature.full_signature),
+        })
+    }
+}
+
+/// Whether a Java type name occupies two registers
+fn is_wide_type(java_type: &str) -> bool {
+    java_type == "long" || java_type == "double"
+}
+
 /// Python wrapper for ExpressionBuilder
 #[pyclass]
 pub struct ExpressionBuilderPy {
@@ -302,19 +444,222 @@ pub fn reconstruct_expressions_from_apk(
         .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
 
     for dex_entry in extractor.dex_entries() {
-        if let Ok(parser) = DexParser::new(dex_entry.data.clone()) {
+        if let Ok(parser) = DexParser::from_arc(dex_entry.data.clone()) {
             // Try to find the method
             if let Ok(Some(bytecode)) = find_method_bytecode(&parser, &class_name, &method_name) {
-                // Create a new parser for the builder
-                if let Ok(parser2) = DexParser::new(dex_entry.data.clone()) {
-                    let mut builder = ExpressionBuilder::new(parser2);
-                    return builder
-                        .process_bytecode(&bytecode)
-                        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e));
-                }
+                // The builder gets its own handle on the same DEX buffer
+                let mut builder = ExpressionBuilder::new(parser.clone());
+                return builder
+                    .process_bytecode(&bytecode)
+                    .map_err(pyo3::exceptions::PyRuntimeError::new_err);
             }
         }
     }
 
     Ok(Vec::new())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dex::test_support::DexBuilder;
+
+    #[test]
+    fn test_crypto_argument_through_move() {
+        let mut b = DexBuilder::new();
+        let get_instance = b.method(
+            "Ljavax/crypto/Cipher;",
+            "getInstance",
+            "Ljavax/crypto/Cipher;",
+            &["Ljava/lang/String;"],
+        );
+        let algorithm = b.string("AES/ECB/PKCS5Padding");
+        let parser = DexParser::new(b.build()).unwrap();
+
+        // const-string v2, "AES/ECB/PKCS5Padding"
+        // move-object v3, v2
+        // invoke-static {v3}, Cipher.getInstance(String)
+        let bytecode = vec![0x021a, algorithm as u16, 0x2307, 0x1071, get_instance as u16, 0x0003];
+
+        let mut builder = ExpressionBuilder::new(parser);
+        let expressions = builder.process_bytecode(&bytecode).unwrap();
+
+        assert_eq!(expressions.len(), 1);
+        assert_eq!(expressions[0].expression, "Cipher.getInstance(\"AES/ECB/PKCS5Padding\")");
+        assert!(expressions[0]
+            .method_signature
+            .as_deref()
+            .unwrap()
+            .starts_with("javax.crypto.Cipher.getInstance"));
+    }
+
+    #[test]
+    fn test_static_vs_virtual_call() {
+        let mut b = DexBuilder::new();
+        let get_instance = b.method(
+            "Ljavax/crypto/Cipher;",
+            "getInstance",
+            "Ljavax/crypto/Cipher;",
+            &["Ljava/lang/String;", "Ljava/lang/String;"],
+        );
+        let load_url = b.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let aes = b.string("AES");
+        let provider = b.string("BC");
+        let url = b.string("https://example.com");
+        let parser = DexParser::new(b.build()).unwrap();
+
+        // const-string v1, "AES"
+        // const-string v2, "BC"
+        // invoke-static {v1, v2}, Cipher.getInstance(String, String)
+        // const-string v2, "https://example.com"
+        // invoke-virtual {v0, v2}, WebView.loadUrl(String)
+        let bytecode = vec![
+            0x011a, aes as u16,
+            0x021a, provider as u16,
+            0x2071, get_instance as u16, 0x0021,
+            0x021a, url as u16,
+            0x206e, load_url as u16, 0x0020,
+        ];
+
+        let mut builder = ExpressionBuilder::new(parser);
+        builder.registers.insert(0, RegisterValue::This);
+        let expressions = builder.process_bytecode(&bytecode).unwrap();
+
+        assert_eq!(expressions.len(), 2);
+        // Static: both registers are arguments, the class is the receiver
+        assert_eq!(expressions[0].expression, "Cipher.getInstance(\"AES\", \"BC\")");
+        // Virtual: the first register is the receiver
+        assert_eq!(expressions[1].expression, "this.loadUrl(\"https://example.com\")");
+    }
+
+    #[test]
+    fn test_wide_parameter_skips_register_pair() {
+        let mut b = DexBuilder::new();
+        let post_url = b.method("Landroid/webkit/WebView;", "postUrl", "V", &["J", "Ljava/lang/String;"]);
+        let url = b.string("https://example.com");
+        let parser = DexParser::new(b.build()).unwrap();
+
+        // const/4 v1, 5          (low half of the long)
+        // const-string v3, "https://example.com"
+        // invoke-virtual {v0, v1, v2, v3}, WebView.postUrl(long, String)
+        let bytecode = vec![0x5112, 0x031a, url as u16, 0x406e, post_url as u16, 0x3210];
+
+        let mut builder = ExpressionBuilder::new(parser);
+        builder.registers.insert(0, RegisterValue::This);
+        builder.registers.insert(2, RegisterValue::ConstString("high half".to_string()));
+        let expressions = builder.process_bytecode(&bytecode).unwrap();
+
+        assert_eq!(expressions.len(), 1);
+        assert_eq!(expressions[0].expression, "this.postUrl(5, \"https://example.com\")");
+    }
+    #[test]
+    fn test_receiver_and_argument_through_wide_moves() {
+        let mut b = DexBuilder::new();
+        let load_url = b.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let url = b.string("https://example.com");
+        let parser = DexParser::new(b.build()).unwrap();
+
+        // const-string v1, "https://example.com"
+        // move-object/from16 v3, v1
+        // move-object/16 v2, v0
+        // invoke-virtual {v2, v3}, WebView.loadUrl(String)
+        let bytecode = vec![0x011a, url as u16, 0x0308, 0x0001, 0x0009, 0x0002, 0x0000, 0x206e, load_url as u16, 0x0032];
+
+        let mut builder = ExpressionBuilder::new(parser);
+        builder.registers.insert(0, RegisterValue::This);
+        let expressions = builder.process_bytecode(&bytecode).unwrap();
+
+        assert_eq!(expressions.len(), 1);
+        assert_eq!(expressions[0].expression, "this.loadUrl(\"https://example.com\")");
+    }
+    #[test]
+    fn test_move_result_chains_calls() {
+        let mut b = DexBuilder::new();
+        let get_settings = b.method("Landroid/webkit/WebView;", "getSettings", "Landroid/webkit/WebSettings;", &[]);
+        let set_js = b.method("Landroid/webkit/WebSettings;", "setJavaScriptEnabled", "V", &["Z"]);
+        let parser = DexParser::new(b.build()).unwrap();
+
+        // invoke-virtual {v1}, WebView.getSettings()
+        // move-result-object v0
+        // const/4 v2, 1
+        // invoke-virtual {v0, v2}, WebSettings.setJavaScriptEnabled(boolean)
+        let bytecode = vec![0x106e, get_settings as u16, 0x0001, 0x000c, 0x1212, 0x206e, set_js as u16, 0x0020];
+
+        let mut builder = ExpressionBuilder::new(parser);
+        builder.registers.insert(1, RegisterValue::This);
+        let expressions = builder.process_bytecode(&bytecode[..4]).unwrap();
+
+        // The getSettings call is still reported on its own
+        assert_eq!(expressions.len(), 1);
+        assert!(matches!(
+            builder.registers.get(&0),
+            Some(RegisterValue::MethodCall { signature, .. }) if signature.method_name == "getSettings"
+        ));
+
+        let mut builder = ExpressionBuilder::new(DexParser::new(b.build()).unwrap());
+        builder.registers.insert(1, RegisterValue::This);
+        let expressions = builder.process_bytecode(&bytecode).unwrap();
+        assert_eq!(expressions.last().unwrap().expression, "this.getSettings().setJavaScriptEnabled(true)");
+
+        // A move-result that does not directly follow the invoke gets nothing
+        let mut builder = ExpressionBuilder::new(DexParser::new(b.build()).unwrap());
+        builder.process_bytecode(&[0x106e, get_settings as u16, 0x0001, 0x1212, 0x000c]).unwrap();
+        assert!(matches!(builder.registers.get(&0), Some(RegisterValue::Unknown)));
+    }
+
+    #[test]
+    fn test_jumbo_string_and_class_literal() {
+        let mut b = DexBuilder::new();
+        let bridge = b.type_id("Lcom/example/Bridge;");
+        let get_method = b.method(
+            "Ljava/lang/Class;",
+            "getMethod",
+            "Ljava/lang/reflect/Method;",
+            &["Ljava/lang/String;", "[Ljava/lang/Class;"],
+        );
+        let name = b.string("run");
+        let parser = DexParser::new(b.build()).unwrap();
+
+        // const-class v0, Lcom/example/Bridge;
+        // const-string/jumbo v1, "run"
+        // invoke-virtual {v0, v1, v2}, Class.getMethod(String, Class[])
+        let bytecode = vec![0x001c, bridge as u16, 0x011b, name as u16, 0x0000, 0x306e, get_method as u16, 0x0210];
+
+        let mut builder = ExpressionBuilder::new(parser);
+        let expressions = builder.process_bytecode(&bytecode).unwrap();
+
+        assert_eq!(expressions.len(), 1);
+        assert_eq!(expressions[0].expression, "Bridge.class.getMethod(\"run\", ?)");
+    }
+
+    #[test]
+    fn test_new_instance_and_constructor() {
+        let mut b = DexBuilder::new();
+        let webview = b.type_id("Landroid/webkit/WebView;");
+        let init = b.method("Landroid/webkit/WebView;", "<init>", "V", &["Landroid/content/Context;"]);
+        let load_url = b.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let url = b.string("https://example.com");
+        let parser = DexParser::new(b.build()).unwrap();
+
+        // new-instance v0, Landroid/webkit/WebView;
+        // invoke-direct {v0, v1}, WebView.<init>(Context)
+        // const-string v2, "https://example.com"
+        // invoke-virtual {v0, v2}, WebView.loadUrl(String)
+        let bytecode = vec![
+            0x0022, webview as u16,
+            0x2070, init as u16, 0x0010,
+            0x021a, url as u16,
+            0x206e, load_url as u16, 0x0020,
+        ];
+        assert_eq!(InstructionDecoder::decode(&bytecode)[0], Instruction::NewInstance { dest: 0, type_idx: webview });
+
+        let mut builder = ExpressionBuilder::new(parser);
+        builder.registers.insert(1, RegisterValue::Parameter(0));
+        let expressions = builder.process_bytecode(&bytecode).unwrap();
+
+        assert_eq!(expressions.len(), 2);
+        assert_eq!(expressions[0].expression, "new WebView(param0)");
+        assert_eq!(expressions[0].value_type, "android.webkit.WebView");
+        assert_eq!(expressions[1].expression, "new WebView(param0).loadUrl(\"https://example.com\")");
+    }
+}
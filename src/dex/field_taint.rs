@@ -0,0 +1,629 @@
+//! Field-mediated taint
+//!
+//! Follows values read from a source through instance and static fields: a
+//! value stored with `iput`/`sput` in one method and loaded with
+//! `iget`/`sget` in another before being passed to a sink. Tracking is
+//! register-level but flow-insensitive within a method: instructions are
+//! visited in code order and branches are not modeled.
+//!
+//! The same pass also yields flows that never leave registers
+//! (`find_call_flows`): from a source call to a sink call in one method, or
+//! across methods by following tainted call arguments into the callee's
+//! parameters.
+//!
+//! A call with a tainted argument inside a try block may throw an exception
+//! built from that argument (e.g. `NumberFormatException` from
+//! `Integer.parseInt`), so the `move-exception` of each of its catch
+//! handlers is tainted too.
+
+use std::collections::{HashMap, HashSet};
+
+use super::instruction::{Instruction, InstructionDecoder, Opcode};
+use super::data_flow_analyzer::default_sink_arguments;
+use super::parser::{CodeItem, DexParser};
+
+/// A source value that reached a sink
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldFlow {
+    /// Source method, e.g. "android.content.Intent.getDataString"
+    pub source: String,
+    /// Fields the value passed through, in order ("pkg.Class.field");
+    /// empty when source and sink are in the same method
+    pub fields: Vec<String>,
+    /// Methods that handled the value, from the one reading the source to
+    /// the one calling the sink
+    pub methods: Vec<String>,
+    /// Sink method, e.g. "android.webkit.WebView.loadUrl"
+    pub sink: String,
+}
+
+/// A sink and the arguments through which taint makes it dangerous
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SinkSpec {
+    /// Substring of "pkg.Class.method" identifying the sink
+    pub pattern: String,
+    /// Dangerous parameter positions; None uses the positions the built-in
+    /// sink tables give the matched method, or every argument if they
+    /// give none
+    pub arguments: Option<Vec<usize>>,
+}
+
+impl SinkSpec {
+    /// Sink whose dangerous arguments come from the default table
+    pub fn new(pattern: &str) -> Self {
+        Self { pattern: pattern.to_string(), arguments: None }
+    }
+
+    /// Sink that is only dangerous through the given parameter positions
+    pub fn with_arguments(pattern: &str, arguments: Vec<usize>) -> Self {
+        Self { pattern: pattern.to_string(), arguments: Some(arguments) }
+    }
+
+    /// Dangerous parameter positions of `target`, None meaning all of them
+    fn arguments_for(&self, target: &str) -> Option<&[usize]> {
+        if let Some(arguments) = &self.arguments {
+            return Some(arguments);
+        }
+        default_sink_arguments(target)
+    }
+}
+
+/// Where a tainted register's value came from
+#[derive(Debug, Clone)]
+struct Origin {
+    source: String,
+    fields: Vec<String>,
+    methods: Vec<String>,
+}
+
+/// Tainted values stored in fields, keyed by "pkg.Class.field"
+type FieldTaint = HashMap<String, Origin>;
+
+/// Upper bound on whole-program passes; each pass can only add fields
+const MAX_PASSES: usize = 8;
+
+/// A call made with tainted registers among its arguments
+struct TaintedCall {
+    /// Called method, "pkg.Class.method"
+    target: String,
+    /// Tainted argument slots of the invoke; slot 0 is the receiver of
+    /// instance calls and wide values take two slots
+    args: Vec<(usize, Origin)>,
+}
+
+/// Rules and results shared by every method scanned in one analysis
+struct Scan<'a> {
+    source_patterns: &'a [&'a str],
+    sinks: &'a [SinkSpec],
+    field_taint: FieldTaint,
+    flows: Vec<FieldFlow>,
+    calls: Vec<TaintedCall>,
+}
+
+/// Find source-to-sink flows that pass through at least one field
+///
+/// Field writes are collected across every DEX file before reads are
+/// resolved, so a field written in `classes.dex` and read in
+/// `classes2.dex` is still followed. Sources and sinks are matched as
+/// substrings of "pkg.Class.method"; a sink call is only reported when
+/// taint reaches one of its dangerous arguments.
+pub fn find_field_flows(parsers: &[DexParser], source_patterns: &[&str], sinks: &[SinkSpec]) -> Vec<FieldFlow> {
+    let mut scan = Scan::new(source_patterns, sinks);
+
+    for _ in 0..MAX_PASSES {
+        let known = scan.field_taint.len();
+        scan.flows.clear();
+        for parser in parsers {
+            for (method, code_off) in method_bodies(parser) {
+                scan.scan_body(parser, &method, code_off, HashMap::new());
+            }
+        }
+        if scan.field_taint.len() == known {
+            break;
+        }
+    }
+
+    let mut flows = scan.flows;
+    flows.retain(|flow| !flow.fields.is_empty());
+    flows.dedup();
+    flows
+}
+
+/// Find source-to-sink flows through registers, following tainted call
+/// arguments into the called methods
+///
+/// When a tainted register is passed as an argument to a method with code
+/// in `parsers`, the matching parameter register is tainted and that method
+/// is scanned in turn, up to `max_depth` calls away from the source.
+/// With `max_depth` 0 only flows within one method are found. `methods`
+/// lists the chain from the method reading the source to the one calling
+/// the sink. Overloads share a "pkg.Class.method" ID, so a call
+/// taints every overload with enough parameters.
+pub fn find_call_flows(parsers: &[DexParser], source_patterns: &[&str], sinks: &[SinkSpec], max_depth: usize) -> Vec<FieldFlow> {
+    let mut scan = Scan::new(source_patterns, sinks);
+    let mut bodies: HashMap<String, Vec<(usize, u32)>> = HashMap::new();
+
+    for (index, parser) in parsers.iter().enumerate() {
+        for (method, code_off) in method_bodies(parser) {
+            scan.scan_body(parser, &method, code_off, HashMap::new());
+            bodies.entry(method).or_default().push((index, code_off));
+        }
+    }
+
+    // Each callee is entered once per set of tainted slots and sources
+    let mut visited = HashSet::new();
+    for _ in 0..max_depth {
+        let calls = std::mem::take(&mut scan.calls);
+        if calls.is_empty() {
+            break;
+        }
+        for call in calls {
+            let Some(locations) = bodies.get(&call.target) else { continue };
+            let key: Vec<(usize, String)> = call.args.iter().map(|(slot, origin)| (*slot, origin.source.clone())).collect();
+            if !visited.insert((call.target.clone(), key)) {
+                continue;
+            }
+
+            for &(index, code_off) in locations {
+                let parser = &parsers[index];
+                let Ok(code) = parser.get_code_item(code_off) else { continue };
+                // Arguments arrive in the last ins_size registers, in slot order
+                let first_in = code.registers_size.saturating_sub(code.ins_size);
+                let tainted = call
+                    .args
+                    .iter()
+                    .filter(|(slot, _)| *slot < code.ins_size as usize)
+                    .map(|(slot, origin)| {
+                        let mut origin = origin.clone();
+                        if origin.methods.last() != Some(&call.target) {
+                            origin.methods.push(call.target.clone());
+                        }
+                        (first_in + *slot as u16, origin)
+                    })
+                    .collect();
+                scan.scan_method(parser, &call.target, &code, tainted);
+            }
+        }
+    }
+
+    let mut flows = scan.flows;
+    flows.retain(|flow| flow.fields.is_empty());
+    flows.dedup();
+    flows
+}
+
+/// "pkg.Class.method" and code offset of every method with code
+fn method_bodies(parser: &DexParser) -> Vec<(String, u32)> {
+    let mut bodies = Vec::new();
+    for class_idx in 0..parser.class_count() {
+        let Ok(class_def) = parser.get_class_def(class_idx) else { continue };
+        if class_def.class_data_off == 0 {
+            continue;
+        }
+        let Ok(class_data) = parser.parse_class_data(class_def.class_data_off) else { continue };
+
+        for encoded_method in class_data.direct_methods.iter().chain(class_data.virtual_methods.iter()) {
+            if encoded_method.code_off == 0 {
+                continue;
+            }
+            let Some(method) = method_id(parser, encoded_method.method_idx) else { continue };
+            bodies.push((method, encoded_method.code_off));
+        }
+    }
+    bodies
+}
+
+impl<'a> Scan<'a> {
+    fn new(source_patterns: &'a [&'a str], sinks: &'a [SinkSpec]) -> Self {
+        Self { source_patterns, sinks, field_taint: FieldTaint::new(), flows: Vec::new(), calls: Vec::new() }
+    }
+
+    fn scan_body(&mut self, parser: &DexParser, method: &str, code_off: u32, tainted: HashMap<u16, Origin>) {
+        if let Ok(code) = parser.get_code_item(code_off) {
+            self.scan_method(parser, method, &code, tainted);
+        }
+    }
+
+    fn scan_method(&mut self, parser: &DexParser, method: &str, code: &CodeItem, mut tainted: HashMap<u16, Origin>) {
+        // Taint of the last invoke's return value, consumed by move-result
+        let mut result: Option<Origin> = None;
+        // Taint of exceptions thrown by tainted calls, by handler address;
+        // handlers follow their try block, so code order sees the throw first
+        let mut thrown: HashMap<u32, Origin> = HashMap::new();
+
+        let mut address = 0;
+        while address < code.insns.len() {
+            let (instruction, width) = InstructionDecoder::decode_one(&code.insns, address);
+            let at = address as u32;
+            address += width;
+            let pending = result.take();
+
+            let (args, method_idx, is_static): (Vec<u16>, u32, bool) = match &instruction {
+                Instruction::InvokeVirtual { args, method_idx }
+                | Instruction::InvokeSuper { args, method_idx }
+                | Instruction::InvokeDirect { args, method_idx }
+                | Instruction::InvokeInterface { args, method_idx } => {
+                    (args.iter().map(|&reg| reg as u16).collect(), *method_idx, false)
+                }
+                Instruction::InvokeStatic { args, method_idx } => {
+                    (args.iter().map(|&reg| reg as u16).collect(), *method_idx, true)
+                }
+                Instruction::InvokeVirtualRange { first_arg, arg_count, method_idx } => {
+                    ((*first_arg..first_arg.saturating_add(*arg_count as u16)).collect(), *method_idx, false)
+                }
+                Instruction::InvokeStaticRange { first_arg, arg_count, method_idx } => {
+                    ((*first_arg..first_arg.saturating_add(*arg_count as u16)).collect(), *method_idx, true)
+                }
+                Instruction::Move { dest, src, .. } => {
+                    copy_taint(&mut tainted, *dest as u16, *src as u16);
+                    continue;
+                }
+                Instruction::MoveFrom16 { dest, src, .. } => {
+                    copy_taint(&mut tainted, *dest as u16, *src);
+                    continue;
+                }
+                Instruction::Move16 { dest, src, .. } => {
+                    copy_taint(&mut tainted, *dest, *src);
+                    continue;
+                }
+                Instruction::MoveResult { dest, .. } => {
+                    match pending {
+                        Some(origin) => tainted.insert(*dest as u16, origin),
+                        None => tainted.remove(&(*dest as u16)),
+                    };
+                    continue;
+                }
+                Instruction::Const4 { dest, .. }
+                | Instruction::Const16 { dest, .. }
+                | Instruction::Const { dest, .. }
+                | Instruction::ConstString { dest, .. }
+                | Instruction::ConstStringJumbo { dest, .. }
+                | Instruction::ConstClass { dest, .. }
+                | Instruction::NewInstance { dest, .. } => {
+                    tainted.remove(&(*dest as u16));
+                    continue;
+                }
+                Instruction::Unknown { opcode, data } if *opcode == Opcode::MoveException as u8 => {
+                    let dest = data.first().map_or(0, |word| word >> 8);
+                    match thrown.get(&at) {
+                        Some(origin) => tainted.insert(dest, origin.clone()),
+                        None => tainted.remove(&dest),
+                    };
+                    continue;
+                }
+                Instruction::Unknown { opcode, data } => {
+                    field_op(parser, method, *opcode, data, &mut tainted, &mut self.field_taint);
+                    continue;
+                }
+                Instruction::PackedSwitchPayload { .. }
+                | Instruction::SparseSwitchPayload { .. }
+                | Instruction::FillArrayDataPayload { .. } => continue,
+            };
+
+            let Some(target) = method_id(parser, method_idx) else { continue };
+            let tainted_arg = args.iter().find_map(|reg| tainted.get(reg));
+
+            if let (Some(origin), Some(handler)) = (tainted_arg, code.handler_at(at)) {
+                let handler_addrs = handler.catches.iter().map(|clause| clause.handler_addr).chain(handler.catch_all_addr);
+                for handler_addr in handler_addrs {
+                    thrown.entry(handler_addr).or_insert_with(|| origin.clone());
+                }
+            }
+
+            if let Some(sink) = self.sinks.iter().find(|sink| target.contains(sink.pattern.as_str())) {
+                let dangerous = match sink.arguments_for(&target) {
+                    Some(positions) => argument_registers(parser, method_idx, &args, is_static, positions),
+                    None => args.clone(),
+                };
+                if let Some(origin) = dangerous.iter().find_map(|reg| tainted.get(reg)) {
+                    self.flows.push(FieldFlow {
+                        source: origin.source.clone(),
+                        fields: origin.fields.clone(),
+                        methods: origin.methods.clone(),
+                        sink: target.clone(),
+                    });
+                }
+            }
+
+            if tainted_arg.is_some() {
+                let args = args
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(slot, reg)| tainted.get(reg).map(|origin| (slot, origin.clone())))
+                    .collect();
+                self.calls.push(TaintedCall { target: target.clone(), args });
+            }
+
+            // A call on or with tainted data (e.g. Uri.getQueryParameter) returns tainted data
+            result = if self.source_patterns.iter().any(|pattern| target.contains(pattern)) {
+                Some(Origin { source: target, fields: Vec::new(), methods: vec![method.to_string()] })
+            } else {
+                tainted_arg.cloned()
+            };
+        }
+    }
+}
+
+fn copy_taint(tainted: &mut HashMap<u16, Origin>, dest: u16, src: u16) {
+    match tainted.get(&src).cloned() {
+        Some(origin) => tainted.insert(dest, origin),
+        None => tainted.remove(&dest),
+    };
+}
+
+/// Apply field reads and writes, which decode as `Unknown`
+fn field_op(
+    parser: &DexParser,
+    method: &str,
+    opcode: u8,
+    data: &[u16],
+    tainted: &mut HashMap<u16, Origin>,
+    field_taint: &mut FieldTaint,
+) {
+    let word = data.first().copied().unwrap_or(0);
+    let field = || data.get(1).and_then(|&field_idx| field_id(parser, field_idx as u32));
+
+    const IGET: std::ops::RangeInclusive<u8> = Opcode::Iget as u8..=Opcode::IgetShort as u8;
+    const IPUT: std::ops::RangeInclusive<u8> = Opcode::Iput as u8..=Opcode::IputShort as u8;
+    const SGET: std::ops::RangeInclusive<u8> = Opcode::Sget as u8..=Opcode::SgetShort as u8;
+    const SPUT: std::ops::RangeInclusive<u8> = Opcode::Sput as u8..=Opcode::SputShort as u8;
+
+    // Format 22c (iget/iput) keeps the value register in a nibble, 21c in a byte
+    let register = if IGET.contains(&opcode) || IPUT.contains(&opcode) { (word >> 8) & 0xF } else { word >> 8 };
+
+    if IGET.contains(&opcode) || SGET.contains(&opcode) {
+        match field().and_then(|field| field_taint.get(&field).cloned()) {
+            Some(mut origin) => {
+                if origin.methods.last().map(String::as_str) != Some(method) {
+                    origin.methods.push(method.to_string());
+                }
+                tainted.insert(register, origin);
+            }
+            None => {
+                tainted.remove(&register);
+            }
+        }
+    } else if IPUT.contains(&opcode) || SPUT.contains(&opcode) {
+        let (Some(origin), Some(field)) = (tainted.get(&register), field()) else { return };
+        if origin.fields.contains(&field) {
+            return;
+        }
+        let mut origin = origin.clone();
+        origin.fields.push(field.clone());
+        // The first tainted write wins, so passes always terminate
+        field_taint.entry(field).or_insert(origin);
+    }
+}
+
+/// Registers holding the given parameter positions of an invoke
+///
+/// Skips the receiver of instance calls and the high half of each wide
+/// (long/double) parameter.
+fn argument_registers(parser: &DexParser, method_idx: u32, args: &[u16], is_static: bool, positions: &[usize]) -> Vec<u16> {
+    let Some(parameters) = parser
+        .get_method_info(method_idx)
+        .and_then(|info| parser.get_proto_info(info.proto_idx))
+        .ok()
+        .map(|proto| proto.parameters)
+    else {
+        return Vec::new();
+    };
+
+    let mut offset = usize::from(!is_static);
+    let mut registers = Vec::new();
+    for (position, type_idx) in parameters.iter().enumerate() {
+        if positions.contains(&position) {
+            registers.extend(args.get(offset));
+        }
+        let wide = matches!(parser.get_type_name(*type_idx).as_deref(), Ok("long") | Ok("double"));
+        offset += if wide { 2 } else { 1 };
+    }
+    registers
+}
+
+/// "pkg.Class.method" for a method_ids index
+fn method_id(parser: &DexParser, method_idx: u32) -> Option<String> {
+    let info = parser.get_method_info(method_idx).ok()?;
+    let class_name = parser.get_type_name(info.class_idx).ok()?;
+    let name = parser.get_string(info.name_idx).ok()?;
+    Some(format!("{}.{}", class_name, name))
+}
+
+/// "pkg.Class.field" for a field_ids index
+fn field_id(parser: &DexParser, field_idx: u32) -> Option<String> {
+    let info = parser.get_field_info(field_idx).ok()?;
+    let class_name = parser.get_type_name(info.class_idx).ok()?;
+    let name = parser.get_string(info.name_idx).ok()?;
+    Some(format!("{}.{}", class_name, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dex::test_support::DexBuilder;
+
+    #[test]
+    fn test_field_mediated_flow() {
+        let mut builder = DexBuilder::new();
+        let class_name = "Lcom/example/LinkActivity;";
+        let on_create = builder.method(class_name, "onCreate", "V", &[]);
+        let load = builder.method(class_name, "load", "V", &[]);
+        let log = builder.method(class_name, "log", "V", &[]);
+        let get_data = builder.method("Landroid/content/Intent;", "getDataString", "Ljava/lang/String;", &[]);
+        let load_url = builder.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let url = builder.field(class_name, "Ljava/lang/String;", "url");
+        let tag = builder.field(class_name, "Ljava/lang/String;", "tag");
+
+        let class = builder.class(class_name, Some("Landroid/app/Activity;"), 0x0001);
+        builder.instance_field(class, url, 0x0002);
+        builder.instance_field(class, tag, 0x0002);
+        builder.virtual_method(
+            class,
+            on_create,
+            0x0001,
+            Some(vec![
+                0x106e, get_data as u16, 0x0002, // invoke-virtual {v2}, Intent.getDataString
+                0x000c, // move-result-object v0
+                0x105b, url as u16, // iput-object v0, v1, url
+                0x001a, 0, // const-string v0, ""
+                0x105b, tag as u16, // iput-object v0, v1, tag
+                0x000e,
+            ]),
+        );
+        builder.virtual_method(
+            class,
+            load,
+            0x0001,
+            Some(vec![
+                0x1054, url as u16, // iget-object v0, v1, url
+                0x206e, load_url as u16, 0x0002, // invoke-virtual {v2, v0}, WebView.loadUrl
+                0x000e,
+            ]),
+        );
+        builder.virtual_method(
+            class,
+            log,
+            0x0001,
+            Some(vec![
+                0x1054, tag as u16, // iget-object v0, v1, tag
+                0x206e, load_url as u16, 0x0002, // invoke-virtual {v2, v0}, WebView.loadUrl
+                0x000e,
+            ]),
+        );
+
+        let parser = DexParser::new(builder.build()).unwrap();
+        let flows = find_field_flows(&[parser], &["getDataString"], &[SinkSpec::new("loadUrl")]);
+
+        assert_eq!(
+            flows,
+            vec![FieldFlow {
+                source: "android.content.Intent.getDataString".to_string(),
+                fields: vec!["com.example.LinkActivity.url".to_string()],
+                methods: vec!["com.example.LinkActivity.onCreate".to_string(), "com.example.LinkActivity.load".to_string()],
+                sink: "android.webkit.WebView.loadUrl".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_taint_through_catch_handler() {
+        let mut builder = DexBuilder::new();
+        let class_name = "Lcom/example/PortActivity;";
+        let on_create = builder.method(class_name, "onCreate", "V", &[]);
+        let get_extra = builder.method("Landroid/content/Intent;", "getStringExtra", "Ljava/lang/String;", &["Ljava/lang/String;"]);
+        let parse_int = builder.method("Ljava/lang/Integer;", "parseInt", "I", &["Ljava/lang/String;"]);
+        let get_message = builder.method("Ljava/lang/Throwable;", "getMessage", "Ljava/lang/String;", &[]);
+        let load_url = builder.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let number_format = builder.type_id("Ljava/lang/NumberFormatException;");
+
+        let class = builder.class(class_name, Some("Landroid/app/Activity;"), 0x0001);
+        builder.virtual_method(
+            class,
+            on_create,
+            0x0001,
+            Some(vec![
+                0x206e, get_extra as u16, 0x0032, // invoke-virtual {v2, v3}, Intent.getStringExtra
+                0x000c, // move-result-object v0
+                0x1071, parse_int as u16, 0x0000, // invoke-static {v0}, Integer.parseInt
+                0x000e, // return-void
+                0x010d, // move-exception v1
+                0x106e, get_message as u16, 0x0001, // invoke-virtual {v1}, Throwable.getMessage
+                0x010c, // move-result-object v1
+                0x206e, load_url as u16, 0x0012, // invoke-virtual {v2, v1}, WebView.loadUrl
+                0x000e,
+            ]),
+        );
+        // try { Integer.parseInt(v0) } catch (NumberFormatException) -> 8
+        builder.try_catch(class, on_create, 4, 3, number_format, 8);
+
+        let parser = DexParser::new(builder.build()).unwrap();
+        let flows = find_call_flows(std::slice::from_ref(&parser), &["getStringExtra"], &[SinkSpec::new("loadUrl")], 0);
+
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].source, "android.content.Intent.getStringExtra");
+        assert_eq!(flows[0].methods, vec!["com.example.PortActivity.onCreate"]);
+
+
+        // Without the try block the handler's exception is clean
+        let (_, code_off) = method_bodies(&parser).remove(0);
+        let mut code = parser.get_code_item(code_off).unwrap();
+        code.tries.clear();
+        let sinks = [SinkSpec::new("loadUrl")];
+        let mut scan = Scan::new(&["getStringExtra"], &sinks);
+        scan.scan_method(&parser, "com.example.PortActivity.onCreate", &code, HashMap::new());
+        assert!(scan.flows.is_empty());
+    }
+
+    #[test]
+    fn test_sink_argument_positions() {
+        let mut builder = DexBuilder::new();
+        let class_name = "Lcom/example/ScriptActivity;";
+        let on_create = builder.method(class_name, "onCreate", "V", &[]);
+        let run_script = builder.method(class_name, "runScript", "V", &[]);
+        let run_callback = builder.method(class_name, "runCallback", "V", &[]);
+        let get_extra = builder.method("Landroid/content/Intent;", "getStringExtra", "Ljava/lang/String;", &["Ljava/lang/String;"]);
+        let evaluate = builder.method(
+            "Landroid/webkit/WebView;",
+            "evaluateJavascript",
+            "V",
+            &["Ljava/lang/String;", "Landroid/webkit/ValueCallback;"],
+        );
+        let pending = builder.field(class_name, "Ljava/lang/String;", "pending");
+
+        let class = builder.class(class_name, Some("Landroid/app/Activity;"), 0x0001);
+        builder.instance_field(class, pending, 0x0002);
+        builder.virtual_method(
+            class,
+            on_create,
+            0x0001,
+            Some(vec![
+                0x206e, get_extra as u16, 0x0032, // invoke-virtual {v2, v3}, Intent.getStringExtra
+                0x000c, // move-result-object v0
+                0x105b, pending as u16, // iput-object v0, v1, pending
+                0x000e,
+            ]),
+        );
+        // Tainted value as the script: dangerous
+        builder.virtual_method(
+            class,
+            run_script,
+            0x0001,
+            Some(vec![
+                0x1054, pending as u16, // iget-object v0, v1, pending
+                0x306e, evaluate as u16, 0x0302, // invoke-virtual {v2, v0, v3}, WebView.evaluateJavascript
+                0x000e,
+            ]),
+        );
+        // Tainted value as the callback: not dangerous
+        builder.virtual_method(
+            class,
+            run_callback,
+            0x0001,
+            Some(vec![
+                0x1054, pending as u16, // iget-object v0, v1, pending
+                0x306e, evaluate as u16, 0x0032, // invoke-virtual {v2, v3, v0}, WebView.evaluateJavascript
+                0x000e,
+            ]),
+        );
+
+        let parser = DexParser::new(builder.build()).unwrap();
+        let readers = |sinks: &[SinkSpec]| -> Vec<String> {
+            find_field_flows(std::slice::from_ref(&parser), &["getStringExtra"], sinks)
+                .into_iter()
+                .map(|flow| flow.methods.last().unwrap().clone())
+                .collect()
+        };
+
+        assert_eq!(readers(&[SinkSpec::new("evaluateJavascript")]), vec!["com.example.ScriptActivity.runScript"]);
+        assert_eq!(
+            readers(&[SinkSpec::with_arguments("evaluateJavascript", vec![1])]),
+            vec!["com.example.ScriptActivity.runCallback"]
+        );
+        assert_eq!(readers(&[SinkSpec::with_arguments("evaluateJavascript", vec![0, 1])]).len(), 2);
+
+        // Built-in positions come from the sink tables, by exact method name
+        assert_eq!(default_sink_arguments("android.webkit.WebView.loadData"), Some(&[0][..]));
+        assert_eq!(default_sink_arguments("android.webkit.WebView.loadDataWithBaseURL"), Some(&[0, 1][..]));
+        assert_eq!(default_sink_arguments("android.database.sqlite.SQLiteDatabase.query"), None);
+    }
+}
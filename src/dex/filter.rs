@@ -5,32 +5,39 @@ use pyo3::prelude::*;
 #[pyclass]
 #[derive(Debug, Clone, Default)]
 pub struct ClassFilter {
+    /// Package prefixes or globs (`com.example.*`, `com.example.**`)
     #[pyo3(get, set)]
     pub packages: Vec<String>,
+    /// Same syntax as `packages`
     #[pyo3(get, set)]
     pub exclude_packages: Vec<String>,
     #[pyo3(get, set)]
     pub class_name: Option<String>,
     #[pyo3(get, set)]
     pub modifiers: Option<u32>,
+    /// Drop AndroidX, support library and Kotlin stdlib classes
+    #[pyo3(get, set)]
+    pub exclude_framework: bool,
 }
 
 #[pymethods]
 impl ClassFilter {
     /// Create a new empty ClassFilter
     #[new]
-    #[pyo3(signature = (packages=None, exclude_packages=None, class_name=None, modifiers=None))]
+    #[pyo3(signature = (packages=None, exclude_packages=None, class_name=None, modifiers=None, exclude_framework=false))]
     pub fn new(
         packages: Option<Vec<String>>,
         exclude_packages: Option<Vec<String>>,
         class_name: Option<String>,
         modifiers: Option<u32>,
+        exclude_framework: bool,
     ) -> Self {
         Self {
             packages: packages.unwrap_or_default(),
             exclude_packages: exclude_packages.unwrap_or_default(),
             class_name,
             modifiers,
+            exclude_framework,
         }
     }
 
@@ -39,7 +46,7 @@ impl ClassFilter {
         // Check packages (include)
         if !self.packages.is_empty() {
             let matches_package = self.packages.iter().any(|pkg| {
-                class.package_name.starts_with(pkg)
+                package_matches(pkg, &class.package_name)
             });
             if !matches_package {
                 return false;
@@ -49,13 +56,18 @@ impl ClassFilter {
         // Check excluded packages
         if !self.exclude_packages.is_empty() {
             let excluded = self.exclude_packages.iter().any(|pkg| {
-                class.package_name.starts_with(pkg)
+                package_matches(pkg, &class.package_name)
             });
             if excluded {
                 return false;
             }
         }
 
+        // Check library noise
+        if self.exclude_framework && (class.is_androidx() || class.is_support_lib() || class.is_kotlin_stdlib()) {
+            return false;
+        }
+
         // Check class name
         if let Some(ref name) = self.class_name {
             if !class.class_name.contains(name) && !class.simple_name.contains(name) {
@@ -74,6 +86,32 @@ impl ClassFilter {
     }
 }
 
+/// Match a package name against a package pattern
+///
+/// A pattern without `*` is a plain prefix, as before. Otherwise it is
+/// matched segment by segment: `*` stands for exactly one segment and `**`
+/// for any number of segments, including none. So `com.x.*` matches
+/// `com.x.a` but neither `com.x` nor `com.x.a.b`, while `com.x.**` matches
+/// all three.
+fn package_matches(pattern: &str, package: &str) -> bool {
+    if !pattern.contains('*') {
+        return package.starts_with(pattern);
+    }
+
+    fn segments_match(pattern: &[&str], package: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => package.is_empty(),
+            Some((&"**", rest)) => (0..=package.len()).any(|skip| segments_match(rest, &package[skip..])),
+            Some((&"*", rest)) => !package.is_empty() && segments_match(rest, &package[1..]),
+            Some((segment, rest)) => package.first() == Some(segment) && segments_match(rest, &package[1..]),
+        }
+    }
+
+    let pattern: Vec<&str> = pattern.split('.').collect();
+    let package: Vec<&str> = if package.is_empty() { Vec::new() } else { package.split('.').collect() };
+    segments_match(&pattern, &package)
+}
+
 /// Builder for ClassFilter
 /// Note: Reserved for future use. Python API uses ClassFilter directly.
 #[allow(dead_code)]
@@ -83,6 +121,7 @@ pub struct ClassFilterBuilder {
     exclude_packages: Vec<String>,
     class_name: Option<String>,
     modifiers: Option<u32>,
+    exclude_framework: bool,
 }
 
 impl ClassFilterBuilder {
@@ -110,12 +149,18 @@ impl ClassFilterBuilder {
         self
     }
 
+    pub fn exclude_framework(mut self, exclude: bool) -> Self {
+        self.exclude_framework = exclude;
+        self
+    }
+
     pub fn build(self) -> ClassFilter {
         ClassFilter {
             packages: self.packages,
             exclude_packages: self.exclude_packages,
             class_name: self.class_name,
             modifiers: self.modifiers,
+            exclude_framework: self.exclude_framework,
         }
     }
 }
@@ -267,12 +312,53 @@ mod tests {
             None,
             Some("MainActivity".to_string()),
             None,
+            false,
         );
 
         assert_eq!(filter.packages.len(), 1);
         assert_eq!(filter.class_name, Some("MainActivity".to_string()));
     }
 
+    #[test]
+    fn test_class_filter_exclude_framework() {
+        let androidx = RustDexClass::new("androidx.core.app.ComponentActivity".to_string());
+        let stdlib = RustDexClass::new("kotlin.jvm.internal.Intrinsics".to_string());
+        let app = RustDexClass::new("com.example.MainActivity".to_string());
+
+        let filter = ClassFilter { exclude_framework: true, ..Default::default() };
+        assert!(!filter.matches(&androidx));
+        assert!(!filter.matches(&stdlib));
+        assert!(filter.matches(&app));
+
+        let unfiltered = ClassFilter::default();
+        assert!(unfiltered.matches(&androidx) && unfiltered.matches(&stdlib));
+    }
+
+    #[test]
+    fn test_package_globs() {
+        assert!(package_matches("com.x.*", "com.x.ui"));
+        assert!(!package_matches("com.x.*", "com.x"));
+        assert!(!package_matches("com.x.*", "com.x.ui.widget"));
+        assert!(!package_matches("com.x.*", "com.xy.ui"));
+
+        assert!(package_matches("com.x.**", "com.x"));
+        assert!(package_matches("com.x.**", "com.x.ui.widget"));
+        assert!(package_matches("com.**.internal", "com.x.y.internal"));
+        assert!(!package_matches("com.x.**", "com.xy"));
+
+        // Plain patterns keep prefix semantics
+        assert!(package_matches("com.x", "com.xy"));
+
+        let filter = ClassFilter {
+            packages: vec!["com.x.**".to_string()],
+            exclude_packages: vec!["com.x.internal".to_string()],
+            ..Default::default()
+        };
+        assert!(filter.matches(&RustDexClass::new("com.x.ui.MainActivity".to_string())));
+        assert!(!filter.matches(&RustDexClass::new("com.x.internal.Secret".to_string())));
+        assert!(!filter.matches(&RustDexClass::new("org.y.Util".to_string())));
+    }
+
     #[test]
     fn test_method_filter_builder() {
         let filter = MethodFilter::new(
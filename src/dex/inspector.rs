@@ -0,0 +1,115 @@
+//! Raw DEX pool inspection
+//!
+//! Read-only access to the string, type and method tables of a single DEX
+//! file, for tooling that needs the pools without extracting classes.
+
+use pyo3::prelude::*;
+
+use super::parser::DexParser;
+
+/// Read-only view of one DEX file's string, type and method tables
+#[pyclass]
+pub struct DexInspector {
+    parser: DexParser,
+}
+
+impl DexInspector {
+    /// IndexError for an index past the end of `table`
+    fn check_index(index: u32, count: u32, table: &str) -> PyResult<()> {
+        if index >= count {
+            return Err(pyo3::exceptions::PyIndexError::new_err(format!(
+                "{} index {} out of range ({} entries)",
+                table, index, count
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl DexInspector {
+    /// Parse DEX bytes
+    ///
+    /// Raises:
+    ///     ValueError: If `dex_data` is not a valid DEX file
+    #[new]
+    pub fn new(dex_data: Vec<u8>) -> PyResult<Self> {
+        let parser = DexParser::new(dex_data)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(Self { parser })
+    }
+
+    /// Number of entries in string_ids
+    pub fn string_count(&self) -> u32 {
+        self.parser.header().string_ids_size
+    }
+
+    /// String at `index` in string_ids
+    pub fn get_string(&self, index: u32) -> PyResult<String> {
+        Self::check_index(index, self.string_count(), "string")?;
+        self.parser
+            .get_string(index)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Number of entries in type_ids
+    pub fn type_count(&self) -> u32 {
+        self.parser.header().type_ids_size
+    }
+
+    /// Java name of the type at `index` in type_ids (e.g. "java.lang.String")
+    pub fn get_type(&self, index: u32) -> PyResult<String> {
+        Self::check_index(index, self.type_count(), "type")?;
+        self.parser
+            .get_type_name(index)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Number of entries in method_ids
+    pub fn method_count(&self) -> u32 {
+        self.parser.header().method_ids_size
+    }
+
+    /// Signature of the method at `index` in method_ids, as
+    /// "Class.name(params): return"
+    pub fn get_method_signature(&self, index: u32) -> PyResult<String> {
+        Self::check_index(index, self.method_count(), "method")?;
+        self.parser
+            .method_signature(index)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DexInspector(strings={}, types={}, methods={})",
+            self.string_count(),
+            self.type_count(),
+            self.method_count()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dex::test_support::DexBuilder;
+
+    #[test]
+    fn test_inspector_reads_pools() {
+        let mut builder = DexBuilder::new();
+        let greeting = builder.string("hello inspector");
+        let load_url = builder.method("Landroid/webkit/WebView;", "loadUrl", "V", &["Ljava/lang/String;"]);
+        let dex = builder.build();
+
+        let inspector = DexInspector::new(dex).unwrap();
+        assert_eq!(inspector.get_string(greeting).unwrap(), "hello inspector");
+        assert_eq!(
+            inspector.get_method_signature(load_url).unwrap(),
+            "android.webkit.WebView.loadUrl(java.lang.String): void"
+        );
+        assert!((0..inspector.type_count()).any(|i| inspector.get_type(i).unwrap() == "android.webkit.WebView"));
+        assert!(inspector.get_string(inspector.string_count()).is_err());
+
+        assert!(DexInspector::new(b"not a dex".to_vec()).is_err());
+    }
+}
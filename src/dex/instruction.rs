@@ -6,6 +6,8 @@
 
 use std::fmt;
 
+use super::error::{DexError, Result};
+
 /// Dalvik instruction opcodes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -501,9 +503,43 @@ impl Opcode {
     }
 }
 
+/// Which register kind a move family instruction copies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveKind {
+    /// 32-bit value (move, move-result)
+    Single,
+    /// 64-bit register pair (move-wide, move-result-wide)
+    Wide,
+    /// Object reference (move-object, move-result-object)
+    Object,
+}
+
+impl MoveKind {
+    /// Mnemonic suffix after "move" / "move-result"
+    pub fn suffix(self) -> &'static str {
+        match self {
+            MoveKind::Single => "",
+            MoveKind::Wide => "-wide",
+            MoveKind::Object => "-object",
+        }
+    }
+}
+
 /// Decoded Dalvik instruction
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Instruction {
+    /// move vA, vB / move-wide vA, vB / move-object vA, vB
+    Move { kind: MoveKind, dest: u8, src: u8 },
+
+    /// move/from16 vAA, vBBBB (also the -wide and -object forms)
+    MoveFrom16 { kind: MoveKind, dest: u8, src: u16 },
+
+    /// move/16 vAAAA, vBBBB (also the -wide and -object forms)
+    Move16 { kind: MoveKind, dest: u16, src: u16 },
+
+    /// move-result vAA (also the -wide and -object forms)
+    MoveResult { kind: MoveKind, dest: u8 },
+
     /// const/4 vA, #+B
     Const4 { dest: u8, value: i8 },
 
@@ -516,7 +552,19 @@ pub enum Instruction {
     /// const-string vAA, string@BBBB
     ConstString { dest: u8, string_idx: u32 },
 
+    /// const-string/jumbo vAA, string@BBBBBBBB
+    ConstStringJumbo { dest: u8, string_idx: u32 },
+
+    /// const-class vAA, type@BBBB
+    ConstClass { dest: u8, type_idx: u32 },
+
+    /// new-instance vAA, type@BBBB
+    NewInstance { dest: u8, type_idx: u32 },
+
     /// invoke-virtual {vC, vD, vE, vF, vG}, meth@BBBB
+    ///
+    /// `args` lists registers, not parameters: a wide (long/double) argument
+    /// occupies a register pair and appears twice as consecutive registers.
     InvokeVirtual { args: Vec<u8>, method_idx: u32 },
 
     /// invoke-super {vC, vD, vE, vF, vG}, meth@BBBB
@@ -532,18 +580,76 @@ pub enum Instruction {
     InvokeInterface { args: Vec<u8>, method_idx: u32 },
 
     /// invoke-virtual/range {vCCCC .. vNNNN}, meth@BBBB
+    ///
+    /// `arg_count` counts registers (wide arguments use two) and may be 0.
     InvokeVirtualRange { first_arg: u16, arg_count: u8, method_idx: u32 },
 
     /// invoke-static/range {vCCCC .. vNNNN}, meth@BBBB
     InvokeStaticRange { first_arg: u16, arg_count: u8, method_idx: u32 },
 
+    /// packed-switch-payload: jump targets for consecutive keys from `first_key`
+    ///
+    /// Targets are relative to the packed-switch instruction, in code units.
+    PackedSwitchPayload { first_key: i32, targets: Vec<i32> },
+
+    /// sparse-switch-payload: sorted keys and their jump targets
+    SparseSwitchPayload { keys: Vec<i32>, targets: Vec<i32> },
+
+    /// fill-array-data-payload: `element_width`-byte elements, little-endian
+    FillArrayDataPayload { element_width: u16, data: Vec<u8> },
+
     /// Unknown or unimplemented instruction
+    ///
+    /// `data` holds every code unit of the instruction (opcode word first),
+    /// truncated if the bytecode ends early.
     Unknown { opcode: u8, data: Vec<u16> },
 }
 
+impl Instruction {
+    /// Instruction mnemonic (e.g. "const/4", "invoke-virtual")
+    pub fn mnemonic(&self) -> String {
+        match self {
+            Instruction::Move { kind, .. } => format!("move{}", kind.suffix()),
+            Instruction::MoveFrom16 { kind, .. } => format!("move{}/from16", kind.suffix()),
+            Instruction::Move16 { kind, .. } => format!("move{}/16", kind.suffix()),
+            Instruction::MoveResult { kind, .. } => format!("move-result{}", kind.suffix()),
+            Instruction::Const4 { .. } => "const/4".to_string(),
+            Instruction::Const16 { .. } => "const/16".to_string(),
+            Instruction::Const { .. } => "const".to_string(),
+            Instruction::ConstString { .. } => "const-string".to_string(),
+            Instruction::ConstStringJumbo { .. } => "const-string/jumbo".to_string(),
+            Instruction::ConstClass { .. } => "const-class".to_string(),
+            Instruction::NewInstance { .. } => "new-instance".to_string(),
+            Instruction::InvokeVirtual { .. } => "invoke-virtual".to_string(),
+            Instruction::InvokeStatic { .. } => "invoke-static".to_string(),
+            Instruction::InvokeDirect { .. } => "invoke-direct".to_string(),
+            Instruction::InvokeSuper { .. } => "invoke-super".to_string(),
+            Instruction::InvokeInterface { .. } => "invoke-interface".to_string(),
+            Instruction::InvokeVirtualRange { .. } => "invoke-virtual/range".to_string(),
+            Instruction::InvokeStaticRange { .. } => "invoke-static/range".to_string(),
+            Instruction::PackedSwitchPayload { .. } => "packed-switch-payload".to_string(),
+            Instruction::SparseSwitchPayload { .. } => "sparse-switch-payload".to_string(),
+            Instruction::FillArrayDataPayload { .. } => "fill-array-data-payload".to_string(),
+            Instruction::Unknown { opcode, .. } => format!("unknown(0x{:02x})", opcode),
+        }
+    }
+}
+
 impl fmt::Display for Instruction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Instruction::Move { dest, src, .. } => {
+                write!(f, "{} v{}, v{}", self.mnemonic(), dest, src)
+            }
+            Instruction::MoveFrom16 { dest, src, .. } => {
+                write!(f, "{} v{}, v{}", self.mnemonic(), dest, src)
+            }
+            Instruction::Move16 { dest, src, .. } => {
+                write!(f, "{} v{}, v{}", self.mnemonic(), dest, src)
+            }
+            Instruction::MoveResult { dest, .. } => {
+                write!(f, "{} v{}", self.mnemonic(), dest)
+            }
             Instruction::Const4 { dest, value } => {
                 write!(f, "const/4 v{}, #{}", dest, value)
             }
@@ -556,38 +662,46 @@ impl fmt::Display for Instruction {
             Instruction::ConstString { dest, string_idx } => {
                 write!(f, "const-string v{}, string@{}", dest, string_idx)
             }
+            Instruction::ConstStringJumbo { dest, string_idx } => {
+                write!(f, "const-string/jumbo v{}, string@{}", dest, string_idx)
+            }
+            Instruction::ConstClass { dest, type_idx } => {
+                write!(f, "const-class v{}, type@{}", dest, type_idx)
+            }
+            Instruction::NewInstance { dest, type_idx } => {
+                write!(f, "new-instance v{}, type@{}", dest, type_idx)
+            }
             Instruction::InvokeVirtual { args, method_idx } => {
-                write!(f, "invoke-virtual {{v{}}}, method@{}",
-                    args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", v"),
-                    method_idx)
+                write!(f, "invoke-virtual {}, method@{}", format_register_list(args), method_idx)
             }
             Instruction::InvokeStatic { args, method_idx } => {
-                write!(f, "invoke-static {{v{}}}, method@{}",
-                    args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", v"),
-                    method_idx)
+                write!(f, "invoke-static {}, method@{}", format_register_list(args), method_idx)
             }
             Instruction::InvokeDirect { args, method_idx } => {
-                write!(f, "invoke-direct {{v{}}}, method@{}",
-                    args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", v"),
-                    method_idx)
+                write!(f, "invoke-direct {}, method@{}", format_register_list(args), method_idx)
             }
             Instruction::InvokeSuper { args, method_idx } => {
-                write!(f, "invoke-super {{v{}}}, method@{}",
-                    args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", v"),
-                    method_idx)
+                write!(f, "invoke-super {}, method@{}", format_register_list(args), method_idx)
             }
             Instruction::InvokeInterface { args, method_idx } => {
-                write!(f, "invoke-interface {{v{}}}, method@{}",
-                    args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", v"),
-                    method_idx)
+                write!(f, "invoke-interface {}, method@{}", format_register_list(args), method_idx)
             }
             Instruction::InvokeVirtualRange { first_arg, arg_count, method_idx } => {
-                write!(f, "invoke-virtual/range {{v{} .. v{}}}, method@{}",
-                    first_arg, first_arg + *arg_count as u16 - 1, method_idx)
+                write!(f, "invoke-virtual/range {}, method@{}",
+                    format_register_range(*first_arg, *arg_count), method_idx)
             }
             Instruction::InvokeStaticRange { first_arg, arg_count, method_idx } => {
-                write!(f, "invoke-static/range {{v{} .. v{}}}, method@{}",
-                    first_arg, first_arg + *arg_count as u16 - 1, method_idx)
+                write!(f, "invoke-static/range {}, method@{}",
+                    format_register_range(*first_arg, *arg_count), method_idx)
+            }
+            Instruction::PackedSwitchPayload { first_key, targets } => {
+                write!(f, "packed-switch-payload first_key={}, targets={:?}", first_key, targets)
+            }
+            Instruction::SparseSwitchPayload { keys, targets } => {
+                write!(f, "sparse-switch-payload keys={:?}, targets={:?}", keys, targets)
+            }
+            Instruction::FillArrayDataPayload { element_width, data } => {
+                write!(f, "fill-array-data-payload element_width={}, {} bytes", element_width, data.len())
             }
             Instruction::Unknown { opcode, .. } => {
                 write!(f, "unknown (opcode: 0x{:02x})", opcode)
@@ -596,267 +710,439 @@ impl fmt::Display for Instruction {
     }
 }
 
+/// Format invoke argument registers as "{v1, v2}", or "{}" when empty
+pub(crate) fn format_register_list(args: &[u8]) -> String {
+    let registers = args.iter().map(|a| format!("v{}", a)).collect::<Vec<_>>();
+    format!("{{{}}}", registers.join(", "))
+}
+
+/// Format a register range as "{v16 .. v18}", or "{}" when `count` is 0
+pub(crate) fn format_register_range(first: u16, count: u8) -> String {
+    match count {
+        0 => "{}".to_string(),
+        1 => format!("{{v{}}}", first),
+        _ => format!("{{v{} .. v{}}}", first, first as u32 + count as u32 - 1),
+    }
+}
+
+/// Dalvik instruction formats
+///
+/// The first digit of each name is the instruction length in 16-bit code
+/// units; see https://source.android.com/docs/core/runtime/instruction-formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionFormat {
+    F10x,
+    F12x,
+    F11n,
+    F11x,
+    F10t,
+    F20t,
+    F22x,
+    F21t,
+    F21s,
+    F21h,
+    F21c,
+    F23x,
+    F22b,
+    F22t,
+    F22s,
+    F22c,
+    F32x,
+    F30t,
+    F31t,
+    F31i,
+    F31c,
+    F35c,
+    F3rc,
+    F45cc,
+    F4rcc,
+    F51l,
+}
+
+impl InstructionFormat {
+    /// Format of the instruction with this opcode byte, or `None` for unused opcodes
+    pub fn of(opcode: u8) -> Option<Self> {
+        use InstructionFormat::*;
+
+        let format = match opcode {
+            0x00 | 0x0e => F10x,
+            0x01 | 0x04 | 0x07 | 0x21 => F12x,
+            0x02 | 0x05 | 0x08 => F22x,
+            0x03 | 0x06 | 0x09 => F32x,
+            0x0a..=0x0d | 0x0f..=0x11 | 0x1d | 0x1e | 0x27 => F11x,
+            0x12 => F11n,
+            0x13 | 0x16 => F21s,
+            0x14 | 0x17 => F31i,
+            0x15 | 0x19 => F21h,
+            0x18 => F51l,
+            0x1a | 0x1c | 0x1f | 0x22 | 0x60..=0x6d | 0xfe | 0xff => F21c,
+            0x1b => F31c,
+            0x20 | 0x23 | 0x52..=0x5f => F22c,
+            0x24 | 0x6e..=0x72 | 0xfc => F35c,
+            0x25 | 0x74..=0x78 | 0xfd => F3rc,
+            0x26 | 0x2b | 0x2c => F31t,
+            0x28 => F10t,
+            0x29 => F20t,
+            0x2a => F30t,
+            0x2d..=0x31 | 0x44..=0x51 | 0x90..=0xaf => F23x,
+            0x32..=0x37 => F22t,
+            0x38..=0x3d => F21t,
+            0x7b..=0x8f | 0xb0..=0xcf => F12x,
+            0xd0..=0xd7 => F22s,
+            0xd8..=0xe2 => F22b,
+            0xfa => F45cc,
+            0xfb => F4rcc,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// Instruction length in 16-bit code units
+    pub fn width(self) -> usize {
+        use InstructionFormat::*;
+
+        match self {
+            F10x | F12x | F11n | F11x | F10t => 1,
+            F20t | F22x | F21t | F21s | F21h | F21c | F23x | F22b | F22t | F22s | F22c => 2,
+            F32x | F30t | F31t | F31i | F31c | F35c | F3rc => 3,
+            F45cc | F4rcc => 4,
+            F51l => 5,
+        }
+    }
+}
+
+/// How the decoder treats opcodes it does not recognize
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Emit `Instruction::Unknown` and advance one word
+    /// (opcodes with a known format still advance by their full width)
+    #[default]
+    Lenient,
+    /// Stop with `DexError::UnknownOpcode`, since the instruction length
+    /// (and so everything after it) cannot be trusted
+    Strict,
+}
+
 /// Decode Dalvik bytecode instructions
 pub struct InstructionDecoder;
 
 impl InstructionDecoder {
     /// Decode instructions from bytecode
     pub fn decode(bytecode: &[u16]) -> Vec<Instruction> {
+        Self::decode_limited(bytecode, None).0
+    }
+
+    /// Decode at most `max_instructions` instructions from bytecode
+    ///
+    /// Decoding stops as soon as the limit is reached, so only the prologue
+    /// of a method is touched. Returns the decoded prefix and a flag that is
+    /// `true` when bytecode remained undecoded.
+    pub fn decode_limited(bytecode: &[u16], max_instructions: Option<usize>) -> (Vec<Instruction>, bool) {
         let mut instructions = Vec::new();
         let mut i = 0;
 
         while i < bytecode.len() {
-            let word = bytecode[i];
-            let opcode_byte = (word & 0xFF) as u8;
-            let opcode = Opcode::from_u8(opcode_byte);
-
-            let instruction = match opcode {
-                // const/4 vA, #+B
-                // Format: |B|A|op where op=0x12, A=dest (low nibble of high byte), B=value (high nibble of high byte)
-                Opcode::Const4 => {
-                    let dest = ((word >> 8) & 0xF) as u8;  // Low nibble of high byte
-                    let value_bits = ((word >> 12) & 0xF) as u8;  // High nibble of high byte
-                    // Sign extend 4-bit value to 8-bit
-                    let value = if value_bits & 0x8 != 0 {
-                        // Negative: extend with 1s
-                        (value_bits | 0xF0u8) as i8
-                    } else {
-                        // Positive: just cast
-                        value_bits as i8
-                    };
-                    i += 1;
-                    Instruction::Const4 { dest, value }
+            if let Some(max) = max_instructions {
+                if instructions.len() >= max {
+                    return (instructions, true);
                 }
+            }
 
-                // const/16 vAA, #+BBBB
-                Opcode::Const16 => {
-                    let dest = (word >> 8) as u8;
-                    let value = if i + 1 < bytecode.len() {
-                        bytecode[i + 1] as i16
-                    } else {
-                        0
-                    };
-                    i += 2;
-                    Instruction::Const16 { dest, value }
-                }
+            let (instruction, consumed) = Self::decode_one(bytecode, i);
+            i += consumed;
+            instructions.push(instruction);
+        }
 
-                // const vAA, #+BBBBBBBB
-                Opcode::Const => {
-                    let dest = (word >> 8) as u8;
-                    let value = if i + 2 < bytecode.len() {
-                        let low = bytecode[i + 1] as u32;
-                        let high = bytecode[i + 2] as u32;
-                        ((high << 16) | low) as i32
-                    } else {
-                        0
-                    };
-                    i += 3;
-                    Instruction::Const { dest, value }
-                }
+        (instructions, false)
+    }
 
-                // const-string vAA, string@BBBB
-                Opcode::ConstString => {
-                    let dest = (word >> 8) as u8;
-                    let string_idx = if i + 1 < bytecode.len() {
-                        bytecode[i + 1] as u32
-                    } else {
-                        0
-                    };
-                    i += 2;
-                    Instruction::ConstString { dest, string_idx }
-                }
+    /// Decode instructions from bytecode using the given mode
+    ///
+    /// In `DecodeMode::Strict` an unrecognized opcode fails with its word
+    /// offset instead of being guessed as a one-word instruction.
+    pub fn decode_with_mode(bytecode: &[u16], mode: DecodeMode) -> Result<Vec<Instruction>> {
+        if mode == DecodeMode::Lenient {
+            return Ok(Self::decode(bytecode));
+        }
 
-                // invoke-virtual {vC, vD, vE, vF, vG}, meth@BBBB
-                Opcode::InvokeVirtual => {
-                    let arg_count = ((word >> 12) & 0xF) as u8;
-                    let method_idx = if i + 1 < bytecode.len() {
-                        bytecode[i + 1] as u32
-                    } else {
-                        0
-                    };
-                    let args_word = if i + 2 < bytecode.len() {
-                        bytecode[i + 2]
-                    } else {
-                        0
-                    };
-
-                    let mut args = Vec::new();
-                    for j in 0..arg_count {
-                        let arg = if j == 0 {
-                            ((word >> 8) & 0xF) as u8
-                        } else if j <= 4 {
-                            ((args_word >> ((j - 1) * 4)) & 0xF) as u8
-                        } else {
-                            0
-                        };
-                        args.push(arg);
-                    }
-                    i += 3;
-                    Instruction::InvokeVirtual { args, method_idx }
-                }
+        let mut instructions = Vec::new();
+        let mut i = 0;
 
-                // invoke-static {vC, vD, vE, vF, vG}, meth@BBBB
-                Opcode::InvokeStatic => {
-                    let arg_count = ((word >> 12) & 0xF) as u8;
-                    let method_idx = if i + 1 < bytecode.len() {
-                        bytecode[i + 1] as u32
-                    } else {
-                        0
-                    };
-                    let args_word = if i + 2 < bytecode.len() {
-                        bytecode[i + 2]
-                    } else {
-                        0
-                    };
-
-                    let mut args = Vec::new();
-                    for j in 0..arg_count {
-                        let arg = if j == 0 {
-                            ((word >> 8) & 0xF) as u8
-                        } else if j <= 4 {
-                            ((args_word >> ((j - 1) * 4)) & 0xF) as u8
-                        } else {
-                            0
-                        };
-                        args.push(arg);
-                    }
-                    i += 3;
-                    Instruction::InvokeStatic { args, method_idx }
-                }
+        while i < bytecode.len() {
+            let opcode = (bytecode[i] & 0xFF) as u8;
+            if InstructionFormat::of(opcode).is_none() {
+                return Err(DexError::UnknownOpcode { opcode, offset: i });
+            }
 
-                // invoke-direct {vC, vD, vE, vF, vG}, meth@BBBB
-                Opcode::InvokeDirect => {
-                    let arg_count = ((word >> 12) & 0xF) as u8;
-                    let method_idx = if i + 1 < bytecode.len() {
-                        bytecode[i + 1] as u32
-                    } else {
-                        0
-                    };
-                    let args_word = if i + 2 < bytecode.len() {
-                        bytecode[i + 2]
-                    } else {
-                        0
-                    };
-
-                    let mut args = Vec::new();
-                    for j in 0..arg_count {
-                        let arg = if j == 0 {
-                            ((word >> 8) & 0xF) as u8
-                        } else if j <= 4 {
-                            ((args_word >> ((j - 1) * 4)) & 0xF) as u8
-                        } else {
-                            0
-                        };
-                        args.push(arg);
-                    }
-                    i += 3;
-                    Instruction::InvokeDirect { args, method_idx }
-                }
+            let (instruction, consumed) = Self::decode_one(bytecode, i);
+            i += consumed;
+            instructions.push(instruction);
+        }
 
-                // invoke-super {vC, vD, vE, vF, vG}, meth@BBBB
-                Opcode::InvokeSuper => {
-                    let arg_count = ((word >> 12) & 0xF) as u8;
-                    let method_idx = if i + 1 < bytecode.len() {
-                        bytecode[i + 1] as u32
-                    } else {
-                        0
-                    };
-                    let args_word = if i + 2 < bytecode.len() {
-                        bytecode[i + 2]
-                    } else {
-                        0
-                    };
-
-                    let mut args = Vec::new();
-                    for j in 0..arg_count {
-                        let arg = if j == 0 {
-                            ((word >> 8) & 0xF) as u8
-                        } else if j <= 4 {
-                            ((args_word >> ((j - 1) * 4)) & 0xF) as u8
-                        } else {
-                            0
-                        };
-                        args.push(arg);
-                    }
-                    i += 3;
-                    Instruction::InvokeSuper { args, method_idx }
-                }
+        Ok(instructions)
+    }
 
-                // invoke-interface {vC, vD, vE, vF, vG}, meth@BBBB
-                Opcode::InvokeInterface => {
-                    let arg_count = ((word >> 12) & 0xF) as u8;
-                    let method_idx = if i + 1 < bytecode.len() {
-                        bytecode[i + 1] as u32
-                    } else {
-                        0
-                    };
-                    let args_word = if i + 2 < bytecode.len() {
-                        bytecode[i + 2]
-                    } else {
-                        0
-                    };
-
-                    let mut args = Vec::new();
-                    for j in 0..arg_count {
-                        let arg = if j == 0 {
-                            ((word >> 8) & 0xF) as u8
-                        } else if j <= 4 {
-                            ((args_word >> ((j - 1) * 4)) & 0xF) as u8
-                        } else {
-                            0
-                        };
-                        args.push(arg);
-                    }
-                    i += 3;
-                    Instruction::InvokeInterface { args, method_idx }
-                }
+    /// Kind of a move/move-wide/move-object opcode; each form's three
+    /// opcodes are spaced three apart starting at `single`
+    fn move_kind(opcode: u8, single: u8) -> MoveKind {
+        match opcode.wrapping_sub(single) {
+            3 => MoveKind::Wide,
+            6 => MoveKind::Object,
+            _ => MoveKind::Single,
+        }
+    }
 
-                // invoke-virtual/range {vCCCC .. vNNNN}, meth@BBBB
-                Opcode::InvokeVirtualRange => {
-                    let arg_count = (word >> 8) as u8;
-                    let method_idx = if i + 1 < bytecode.len() {
-                        bytecode[i + 1] as u32
-                    } else {
-                        0
-                    };
-                    let first_arg = if i + 2 < bytecode.len() {
-                        bytecode[i + 2]
-                    } else {
-                        0
-                    };
-                    i += 3;
-                    Instruction::InvokeVirtualRange { first_arg, arg_count, method_idx }
-                }
+    /// Decode the single instruction starting at word offset `i`
+    ///
+    /// Returns the instruction and the number of 16-bit code units it
+    /// occupies, so `i + consumed` is the offset of the next instruction.
+    /// Opcodes that are not fully modeled decode as `Instruction::Unknown`
+    /// holding all of their code units, so the stream stays aligned.
+    /// Operands that run past the end of `bytecode` read as zero. `i` must
+    /// be less than `bytecode.len()`.
+    pub fn decode_one(bytecode: &[u16], i: usize) -> (Instruction, usize) {
+        let word = bytecode[i];
+        let opcode_byte = (word & 0xFF) as u8;
+        let opcode = Opcode::from_u8(opcode_byte);
+
+        // Switch and array payloads sit inline after the code and start
+        // with a nop-like ident word; they must be skipped whole
+        if let Some(payload) = Self::decode_payload(bytecode, i) {
+            return payload;
+        }
 
-                // invoke-static/range {vCCCC .. vNNNN}, meth@BBBB
-                Opcode::InvokeStaticRange => {
-                    let arg_count = (word >> 8) as u8;
-                    let method_idx = if i + 1 < bytecode.len() {
-                        bytecode[i + 1] as u32
-                    } else {
-                        0
-                    };
-                    let first_arg = if i + 2 < bytecode.len() {
-                        bytecode[i + 2]
-                    } else {
-                        0
-                    };
-                    i += 3;
-                    Instruction::InvokeStaticRange { first_arg, arg_count, method_idx }
-                }
+        match opcode {
+            // move vA, vB / move-wide vA, vB / move-object vA, vB
+            // Format 12x: |B|A|op
+            Opcode::Move | Opcode::MoveWide | Opcode::MoveObject => {
+                let kind = Self::move_kind(opcode_byte, 0x01);
+                let dest = ((word >> 8) & 0xF) as u8;
+                let src = ((word >> 12) & 0xF) as u8;
+                (Instruction::Move { kind, dest, src }, 1)
+            }
+
+            // move/from16 vAA, vBBBB
+            // Format 22x: AA|op BBBB
+            Opcode::MoveFrom16 | Opcode::MoveWideFrom16 | Opcode::MoveObjectFrom16 => {
+                let kind = Self::move_kind(opcode_byte, 0x02);
+                let dest = (word >> 8) as u8;
+                let src = bytecode.get(i + 1).copied().unwrap_or(0);
+                (Instruction::MoveFrom16 { kind, dest, src }, 2)
+            }
+
+            // move/16 vAAAA, vBBBB
+            // Format 32x: ØØ|op AAAA BBBB
+            Opcode::Move16 | Opcode::MoveWide16 | Opcode::MoveObject16 => {
+                let kind = Self::move_kind(opcode_byte, 0x03);
+                let dest = bytecode.get(i + 1).copied().unwrap_or(0);
+                let src = bytecode.get(i + 2).copied().unwrap_or(0);
+                (Instruction::Move16 { kind, dest, src }, 3)
+            }
+
+            // move-result vAA
+            // Format 11x: AA|op
+            Opcode::MoveResult | Opcode::MoveResultWide | Opcode::MoveResultObject => {
+                let kind = match opcode {
+                    Opcode::MoveResultWide => MoveKind::Wide,
+                    Opcode::MoveResultObject => MoveKind::Object,
+                    _ => MoveKind::Single,
+                };
+                let dest = (word >> 8) as u8;
+                (Instruction::MoveResult { kind, dest }, 1)
+            }
+
+            // const/4 vA, #+B
+            // Format: |B|A|op where op=0x12, A=dest (low nibble of high byte), B=value (high nibble of high byte)
+            Opcode::Const4 => {
+                let dest = ((word >> 8) & 0xF) as u8;  // Low nibble of high byte
+                let value_bits = ((word >> 12) & 0xF) as u8;  // High nibble of high byte
+                // Sign extend 4-bit value to 8-bit
+                let value = if value_bits & 0x8 != 0 {
+                    // Negative: extend with 1s
+                    (value_bits | 0xF0u8) as i8
+                } else {
+                    // Positive: just cast
+                    value_bits as i8
+                };
+                (Instruction::Const4 { dest, value }, 1)
+            }
+
+            // const/16 vAA, #+BBBB
+            Opcode::Const16 => {
+                let dest = (word >> 8) as u8;
+                let value = if i + 1 < bytecode.len() {
+                    bytecode[i + 1] as i16
+                } else {
+                    0
+                };
+                (Instruction::Const16 { dest, value }, 2)
+            }
+
+            // const vAA, #+BBBBBBBB
+            Opcode::Const => {
+                let dest = (word >> 8) as u8;
+                let value = if i + 2 < bytecode.len() {
+                    let low = bytecode[i + 1] as u32;
+                    let high = bytecode[i + 2] as u32;
+                    ((high << 16) | low) as i32
+                } else {
+                    0
+                };
+                (Instruction::Const { dest, value }, 3)
+            }
+
+            // const-string vAA, string@BBBB
+            Opcode::ConstString => {
+                let dest = (word >> 8) as u8;
+                let string_idx = if i + 1 < bytecode.len() {
+                    bytecode[i + 1] as u32
+                } else {
+                    0
+                };
+                (Instruction::ConstString { dest, string_idx }, 2)
+            }
+
+            // const-string/jumbo vAA, string@BBBBBBBB
+            Opcode::ConstStringJumbo => {
+                let dest = (word >> 8) as u8;
+                let string_idx = if i + 2 < bytecode.len() {
+                    let low = bytecode[i + 1] as u32;
+                    let high = bytecode[i + 2] as u32;
+                    (high << 16) | low
+                } else {
+                    0
+                };
+                (Instruction::ConstStringJumbo { dest, string_idx }, 3)
+            }
+
+            // const-class vAA, type@BBBB
+            Opcode::ConstClass => {
+                let dest = (word >> 8) as u8;
+                let type_idx = bytecode.get(i + 1).copied().unwrap_or(0) as u32;
+                (Instruction::ConstClass { dest, type_idx }, 2)
+            }
+
+            // new-instance vAA, type@BBBB
+            Opcode::NewInstance => {
+                let dest = (word >> 8) as u8;
+                let type_idx = bytecode.get(i + 1).copied().unwrap_or(0) as u32;
+                (Instruction::NewInstance { dest, type_idx }, 2)
+            }
+
+            // invoke-kind {vC, vD, vE, vF, vG}, meth@BBBB
+            Opcode::InvokeVirtual => {
+                let (args, method_idx) = Self::decode_35c(bytecode, i);
+                (Instruction::InvokeVirtual { args, method_idx }, 3)
+            }
+            Opcode::InvokeSuper => {
+                let (args, method_idx) = Self::decode_35c(bytecode, i);
+                (Instruction::InvokeSuper { args, method_idx }, 3)
+            }
+            Opcode::InvokeDirect => {
+                let (args, method_idx) = Self::decode_35c(bytecode, i);
+                (Instruction::InvokeDirect { args, method_idx }, 3)
+            }
+            Opcode::InvokeStatic => {
+                let (args, method_idx) = Self::decode_35c(bytecode, i);
+                (Instruction::InvokeStatic { args, method_idx }, 3)
+            }
+            Opcode::InvokeInterface => {
+                let (args, method_idx) = Self::decode_35c(bytecode, i);
+                (Instruction::InvokeInterface { args, method_idx }, 3)
+            }
+
+            // invoke-kind/range {vCCCC .. vNNNN}, meth@BBBB
+            Opcode::InvokeVirtualRange => {
+                let (first_arg, arg_count, method_idx) = Self::decode_3rc(bytecode, i);
+                (Instruction::InvokeVirtualRange { first_arg, arg_count, method_idx }, 3)
+            }
+            Opcode::InvokeStaticRange => {
+                let (first_arg, arg_count, method_idx) = Self::decode_3rc(bytecode, i);
+                (Instruction::InvokeStaticRange { first_arg, arg_count, method_idx }, 3)
+            }
 
-                // Unknown instruction - skip
-                _ => {
-                    let data = vec![word];
-                    i += 1;
-                    Instruction::Unknown { opcode: opcode_byte, data }
+            // Not modeled - skip its full width (one word if the opcode is unused)
+            _ => {
+                let width = InstructionFormat::of(opcode_byte).map_or(1, InstructionFormat::width);
+                let end = (i + width).min(bytecode.len());
+                let data = bytecode[i..end].to_vec();
+                (Instruction::Unknown { opcode: opcode_byte, data }, width)
+            }
+        }
+    }
+
+    /// Operands of a format 35c instruction: A|G|op BBBB F|E|D|C
+    ///
+    /// Returns the argument registers (vC, vD, vE, vF, vG, truncated to the
+    /// count A) and the index BBBB.
+    fn decode_35c(bytecode: &[u16], i: usize) -> (Vec<u8>, u32) {
+        let word = bytecode[i];
+        let arg_count = ((word >> 12) & 0xF) as u8;
+        let method_idx = bytecode.get(i + 1).copied().unwrap_or(0) as u32;
+        let args_word = bytecode.get(i + 2).copied().unwrap_or(0);
+
+        let args = (0..arg_count.min(5))
+            .map(|j| {
+                if j < 4 {
+                    ((args_word >> (j * 4)) & 0xF) as u8
+                } else {
+                    ((word >> 8) & 0xF) as u8
                 }
-            };
+            })
+            .collect();
 
-            instructions.push(instruction);
+        (args, method_idx)
+    }
+
+    /// Decode the payload pseudo-instruction at `i`, if there is one
+    ///
+    /// Returns the payload and its full width, which is derived from its
+    /// size field even when the bytecode is truncated.
+    fn decode_payload(bytecode: &[u16], i: usize) -> Option<(Instruction, usize)> {
+        let word_at = |offset: usize| bytecode.get(i + offset).copied().unwrap_or(0);
+        let u32_at = |offset: usize| (word_at(offset + 1) as u32) << 16 | word_at(offset) as u32;
+        // Only the entries actually present are read, whatever the size field says
+        let i32s = |offset: usize, count: usize| -> Vec<i32> {
+            let available = bytecode.len().saturating_sub(i + offset) / 2;
+            (0..count.min(available)).map(|n| u32_at(offset + 2 * n) as i32).collect()
+        };
+
+        match bytecode[i] {
+            // ident, size, first_key (int), targets (int[size])
+            0x0100 => {
+                let size = word_at(1) as usize;
+                let instruction = Instruction::PackedSwitchPayload { first_key: u32_at(2) as i32, targets: i32s(4, size) };
+                Some((instruction, 4 + size * 2))
+            }
+            // ident, size, keys (int[size]), targets (int[size])
+            0x0200 => {
+                let size = word_at(1) as usize;
+                let instruction = Instruction::SparseSwitchPayload { keys: i32s(2, size), targets: i32s(2 + size * 2, size) };
+                Some((instruction, 2 + size * 4))
+            }
+            // ident, element_width, size (uint), data (ubyte[size * element_width], padded to a word)
+            0x0300 => {
+                let element_width = word_at(1);
+                let byte_len = u32_at(2) as usize * element_width as usize;
+                let words = byte_len.div_ceil(2);
+                let present = bytecode.get(i + 4..(i + 4 + words).min(bytecode.len())).unwrap_or_default();
+                let mut data: Vec<u8> = present.iter().flat_map(|w| w.to_le_bytes()).collect();
+                data.truncate(byte_len);
+                Some((Instruction::FillArrayDataPayload { element_width, data }, 4 + words))
+            }
+            _ => None,
         }
+    }
 
-        instructions
+    /// Operands of a format 3rc instruction: AA|op BBBB CCCC
+    ///
+    /// Returns the first register CCCC, the register count AA and the index BBBB.
+    fn decode_3rc(bytecode: &[u16], i: usize) -> (u16, u8, u32) {
+        let arg_count = (bytecode[i] >> 8) as u8;
+        let method_idx = bytecode.get(i + 1).copied().unwrap_or(0) as u32;
+        let first_arg = bytecode.get(i + 2).copied().unwrap_or(0);
+
+        (first_arg, arg_count, method_idx)
     }
 }
 
@@ -895,4 +1181,303 @@ mod tests {
             _ => panic!("Expected Const4 instruction"),
         }
     }
+
+    #[test]
+    fn test_decode_limited_stops_early() {
+        // const/16 v0, #1; const/16 v1, #2; const/16 v2, #3
+        let bytecode = vec![0x0013, 0x0001, 0x0113, 0x0002, 0x0213, 0x0003];
+
+        let (instructions, truncated) = InstructionDecoder::decode_limited(&bytecode, Some(2));
+        assert_eq!(instructions.len(), 2);
+        assert!(truncated);
+        match &instructions[1] {
+            Instruction::Const16 { dest, value } => {
+                assert_eq!(*dest, 1);
+                assert_eq!(*value, 2);
+            }
+            _ => panic!("Expected Const16 instruction"),
+        }
+
+        let (instructions, truncated) = InstructionDecoder::decode_limited(&bytecode, Some(3));
+        assert_eq!(instructions.len(), 3);
+        assert!(!truncated);
+
+        let (instructions, truncated) = InstructionDecoder::decode_limited(&bytecode, None);
+        assert_eq!(instructions.len(), 3);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn test_move_decode() {
+        // move-object v3, v2; move v1, v0
+        let bytecode = vec![0x2307, 0x0101];
+        let instructions = InstructionDecoder::decode(&bytecode);
+
+        assert_eq!(instructions.len(), 2);
+        match &instructions[0] {
+            Instruction::Move { kind, dest, src } => {
+                assert_eq!(*kind, MoveKind::Object);
+                assert_eq!(*dest, 3);
+                assert_eq!(*src, 2);
+            }
+            _ => panic!("Expected Move instruction"),
+        }
+        assert!(matches!(instructions[1], Instruction::Move { kind: MoveKind::Single, dest: 1, src: 0 }));
+        assert_eq!(instructions[0].mnemonic(), "move-object");
+        assert_eq!(instructions[1].to_string(), "move v1, v0");
+    }
+
+    #[test]
+    fn test_move_wide_forms_decode() {
+        // move-wide v1, v0; move/from16 v200, v1000; move-object/16 v300, v1000; return-void
+        let bytecode = vec![0x0104, 0xc802, 0x03e8, 0x0009, 0x012c, 0x03e8, 0x000e];
+        let instructions = InstructionDecoder::decode(&bytecode);
+
+        assert_eq!(instructions.len(), 4);
+        assert_eq!(instructions[0], Instruction::Move { kind: MoveKind::Wide, dest: 1, src: 0 });
+        assert_eq!(instructions[1], Instruction::MoveFrom16 { kind: MoveKind::Single, dest: 200, src: 1000 });
+        assert_eq!(instructions[2], Instruction::Move16 { kind: MoveKind::Object, dest: 300, src: 1000 });
+        assert_eq!(instructions[0].mnemonic(), "move-wide");
+        assert_eq!(instructions[1].to_string(), "move/from16 v200, v1000");
+        assert_eq!(instructions[2].mnemonic(), "move-object/16");
+        assert!(matches!(instructions[3], Instruction::Unknown { opcode: 0x0e, .. }));
+    }
+
+    #[test]
+    fn test_move_result_decode() {
+        // move-result v1; move-result-wide v2; move-result-object v200
+        let instructions = InstructionDecoder::decode(&[0x010a, 0x020b, 0xc80c]);
+
+        assert_eq!(
+            instructions,
+            vec![
+                Instruction::MoveResult { kind: MoveKind::Single, dest: 1 },
+                Instruction::MoveResult { kind: MoveKind::Wide, dest: 2 },
+                Instruction::MoveResult { kind: MoveKind::Object, dest: 200 },
+            ]
+        );
+        assert_eq!(instructions[1].mnemonic(), "move-result-wide");
+        assert_eq!(instructions[2].to_string(), "move-result-object v200");
+    }
+
+    #[test]
+    fn test_invoke_argument_order() {
+        // invoke-virtual {v1, v2}, method@66
+        let bytecode = vec![0x206e, 0x0042, 0x0021];
+        let instructions = InstructionDecoder::decode(&bytecode);
+
+        match &instructions[0] {
+            Instruction::InvokeVirtual { args, method_idx } => {
+                assert_eq!(args, &vec![1, 2]);
+                assert_eq!(*method_idx, 0x42);
+            }
+            _ => panic!("Expected InvokeVirtual instruction"),
+        }
+
+        // invoke-static {v1, v2, v3, v4, v5} - fifth register lives in G
+        let bytecode = vec![0x5571, 0x0007, 0x4321];
+        let instructions = InstructionDecoder::decode(&bytecode);
+        match &instructions[0] {
+            Instruction::InvokeStatic { args, .. } => assert_eq!(args, &vec![1, 2, 3, 4, 5]),
+            _ => panic!("Expected InvokeStatic instruction"),
+        }
+    }
+
+    #[test]
+    fn test_decode_matches_decode_one_loop() {
+        let bytecode = vec![
+            0x0113, 0x002a, // const/16 v1, #42
+            0x021a, 0x0007, // const-string v2, string@7
+            0x2070, 0x0004, 0x0021, // invoke-direct {v1, v2}, method@4
+            0x306e, 0x0005, 0x0321, // invoke-virtual {v1, v2, v3}, method@5
+            0x1072, 0x0006, 0x0001, // invoke-interface {v1}, method@6
+            0x206f, 0x0008, 0x0021, // invoke-super {v1, v2}, method@8
+            0x5571, 0x0009, 0x4321, // invoke-static {v1, v2, v3, v4, v5}, method@9
+            0x0374, 0x000a, 0x0010, // invoke-virtual/range {v16 .. v18}, method@10
+            0x0277, 0x000b, 0x0020, // invoke-static/range {v32 .. v33}, method@11
+            0x0101, // move v1, v0
+            0x000e, // return-void
+        ];
+
+        let mut stepped = Vec::new();
+        let mut i = 0;
+        while i < bytecode.len() {
+            let (insn, consumed) = InstructionDecoder::decode_one(&bytecode, i);
+            stepped.push(insn);
+            i += consumed;
+        }
+
+        let decoded = InstructionDecoder::decode(&bytecode);
+        assert_eq!(decoded, stepped);
+        assert_eq!(decoded.len(), 11);
+
+        match &decoded[6] {
+            Instruction::InvokeStatic { args, method_idx } => {
+                assert_eq!(args, &vec![1, 2, 3, 4, 5]);
+                assert_eq!(*method_idx, 9);
+            }
+            other => panic!("Expected InvokeStatic, got {:?}", other),
+        }
+        match &decoded[7] {
+            Instruction::InvokeVirtualRange { first_arg, arg_count, method_idx } => {
+                assert_eq!((*first_arg, *arg_count, *method_idx), (16, 3, 10));
+            }
+            other => panic!("Expected InvokeVirtualRange, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_unknown_opcode() {
+        // const/16 v0, #1; <unused 0x3e>; return-void
+        let bytecode = vec![0x0013, 0x0001, 0x003e, 0x000e];
+
+        let lenient = InstructionDecoder::decode_with_mode(&bytecode, DecodeMode::default()).unwrap();
+        assert_eq!(lenient, InstructionDecoder::decode(&bytecode));
+        assert!(matches!(lenient[1], Instruction::Unknown { opcode: 0x3e, .. }));
+
+        match InstructionDecoder::decode_with_mode(&bytecode, DecodeMode::Strict) {
+            Err(DexError::UnknownOpcode { opcode, offset }) => assert_eq!((opcode, offset), (0x3e, 2)),
+            other => panic!("Expected UnknownOpcode error, got {:?}", other),
+        }
+
+        // invoke-custom has a known format, so strict mode can step over it
+        let known = vec![0x0013, 0x0001, 0x10fc, 0x0000, 0x0000, 0x000e];
+        assert_eq!(InstructionDecoder::decode_with_mode(&known, DecodeMode::Strict).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_const_string_jumbo_decode() {
+        // const-string/jumbo v5, string@0x00012345; const-string v0, string@1; return-void
+        let bytecode = vec![0x051b, 0x2345, 0x0001, 0x001a, 0x0001, 0x000e];
+        let instructions = InstructionDecoder::decode(&bytecode);
+
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0], Instruction::ConstStringJumbo { dest: 5, string_idx: 0x12345 });
+        assert_eq!(instructions[0].to_string(), "const-string/jumbo v5, string@74565");
+        assert_eq!(instructions[1], Instruction::ConstString { dest: 0, string_idx: 1 });
+        assert_eq!(InstructionDecoder::decode_one(&bytecode, 0).1, 3);
+
+        // Low word first: 0xbeef, 0xdead -> 0xdeadbeef
+        let (insn, width) = InstructionDecoder::decode_one(&[0x001b, 0xbeef, 0xdead], 0);
+        assert_eq!(insn, Instruction::ConstStringJumbo { dest: 0, string_idx: 0xdead_beef });
+        assert_eq!(width, 3);
+    }
+
+    #[test]
+    fn test_const_class_decode() {
+        // const-class v3, type@7; return-void
+        let instructions = InstructionDecoder::decode(&[0x031c, 0x0007, 0x000e]);
+
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0], Instruction::ConstClass { dest: 3, type_idx: 7 });
+        assert_eq!(instructions[0].to_string(), "const-class v3, type@7");
+    }
+
+    #[test]
+    fn test_format_widths_keep_decode_aligned() {
+        let bytecode = vec![
+            0x0160, 0x0005, // sget v1, field@5 (21c)
+            0x0338, 0x0004, // if-eqz v3, +4 (21t)
+            0x1054, 0x0002, // iget-object v0, v1, field@2 (22c)
+            0x0018, 0x5678, 0x1234, 0x0000, 0x0000, // const-wide v0, #0x12345678 (51l)
+            0x1071, 0x0003, 0x0001, // invoke-static {v1}, method@3
+            0x0028, // goto +0 (10t)
+            0x20fa, 0x0007, 0x0010, 0x0009, // invoke-polymorphic {v0, v1}, method@7, proto@9 (45cc)
+            0x021a, 0x0008, // const-string v2, string@8
+            0x000e, // return-void
+        ];
+        let instructions = InstructionDecoder::decode(&bytecode);
+
+        let opcodes: Vec<String> = instructions.iter().map(Instruction::mnemonic).collect();
+        assert_eq!(
+            opcodes,
+            vec![
+                "unknown(0x60)", "unknown(0x38)", "unknown(0x54)", "unknown(0x18)",
+                "invoke-static", "unknown(0x28)", "unknown(0xfa)", "const-string", "unknown(0x0e)",
+            ]
+        );
+        assert_eq!(instructions[0], Instruction::Unknown { opcode: 0x60, data: vec![0x0160, 0x0005] });
+        assert!(matches!(instructions[4], Instruction::InvokeStatic { method_idx: 3, .. }));
+        assert!(matches!(instructions[7], Instruction::ConstString { dest: 2, string_idx: 8 }));
+
+        // A truncated trailing instruction keeps the words that are present
+        let truncated = InstructionDecoder::decode(&[0x0160]);
+        assert_eq!(truncated, vec![Instruction::Unknown { opcode: 0x60, data: vec![0x0160] }]);
+    }
+
+    #[test]
+    fn test_unknown_multiword_op_then_const4() {
+        // fill-array-data v0, +0x10 (31t); const/4 v1, #-2; sget-object v2, field@0x1212 (21c); const/4 v3, #1
+        let bytecode = vec![0x0026, 0x0010, 0x0000, 0xe112, 0x0262, 0x1212, 0x1312];
+        let instructions = InstructionDecoder::decode(&bytecode);
+
+        assert_eq!(instructions.len(), 4);
+        assert_eq!(instructions[0], Instruction::Unknown { opcode: 0x26, data: vec![0x0026, 0x0010, 0x0000] });
+        assert_eq!(instructions[1], Instruction::Const4 { dest: 1, value: -2 });
+        // The index word 0x1212 would decode as const/4 v2, #1 if the sget were one word long
+        assert!(matches!(instructions[2], Instruction::Unknown { opcode: 0x62, .. }));
+        assert_eq!(instructions[3], Instruction::Const4 { dest: 3, value: 1 });
+    }
+
+    #[test]
+    fn test_switch_and_array_payloads_are_skipped() {
+        let bytecode = vec![
+            0x002b, 0x0005, 0x0000, // packed-switch v0, +5
+            0x000e, // return-void
+            0x0000, // nop (alignment)
+            0x0100, 0x0002, 0x000a, 0x0000, 0x0003, 0x0000, 0x0004, 0x0000, // keys 10..11 -> +3, +4
+            0x0200, 0x0001, 0xffff, 0xffff, 0x0006, 0x0000, // key -1 -> +6
+            0x0300, 0x0001, 0x0003, 0x0000, 0x0201, 0x0003, // bytes [1, 2, 3]
+            0x021a, 0x0008, // const-string v2, string@8
+        ];
+        let instructions = InstructionDecoder::decode(&bytecode);
+
+        assert_eq!(instructions.len(), 7);
+        assert_eq!(instructions[3], Instruction::PackedSwitchPayload { first_key: 10, targets: vec![3, 4] });
+        assert_eq!(instructions[4], Instruction::SparseSwitchPayload { keys: vec![-1], targets: vec![6] });
+        assert_eq!(instructions[5], Instruction::FillArrayDataPayload { element_width: 1, data: vec![1, 2, 3] });
+        assert_eq!(instructions[6], Instruction::ConstString { dest: 2, string_idx: 8 });
+        assert_eq!(instructions[3].mnemonic(), "packed-switch-payload");
+
+        // A payload whose size runs past the end neither panics nor over-reads
+        let truncated = InstructionDecoder::decode(&[0x0100, 0xffff, 0x0000, 0x0000, 0x0007, 0x0000]);
+        assert_eq!(truncated, vec![Instruction::PackedSwitchPayload { first_key: 0, targets: vec![7] }]);
+    }
+
+    #[test]
+    fn test_format_table() {
+        assert_eq!(InstructionFormat::of(0x6e), Some(InstructionFormat::F35c));
+        assert_eq!(InstructionFormat::of(0x18).map(InstructionFormat::width), Some(5));
+        assert_eq!(InstructionFormat::of(0xfb).map(InstructionFormat::width), Some(4));
+        assert_eq!(InstructionFormat::of(0x73), None);
+        assert_eq!(InstructionFormat::of(0xe3), None);
+
+        // Every opcode the decoder models has a format of matching width
+        for opcode in [0x01u8, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x12, 0x13, 0x14, 0x1a, 0x6e, 0x6f, 0x70, 0x71, 0x72, 0x74, 0x77] {
+            let (_, consumed) = InstructionDecoder::decode_one(&[opcode as u16, 0, 0], 0);
+            assert_eq!(InstructionFormat::of(opcode).unwrap().width(), consumed, "opcode 0x{:02x}", opcode);
+        }
+    }
+
+    #[test]
+    fn test_invoke_display_edge_cases() {
+        // invoke-virtual/range {}, method@7
+        let bytecode = vec![0x0074, 0x0007, 0x0000];
+        let instructions = InstructionDecoder::decode(&bytecode);
+        assert_eq!(instructions[0].to_string(), "invoke-virtual/range {}, method@7");
+
+        // invoke-static/range {v65535 .. v65536} must not overflow
+        let range = Instruction::InvokeStaticRange { first_arg: 0xFFFF, arg_count: 2, method_idx: 1 };
+        assert_eq!(range.to_string(), "invoke-static/range {v65535 .. v65536}, method@1");
+
+        // invoke-static {v3}, method@2
+        let bytecode = vec![0x1071, 0x0002, 0x0003];
+        let instructions = InstructionDecoder::decode(&bytecode);
+        assert_eq!(instructions[0].to_string(), "invoke-static {v3}, method@2");
+
+        // invoke-static {}, method@2
+        let bytecode = vec![0x0071, 0x0002, 0x0000];
+        let instructions = InstructionDecoder::decode(&bytecode);
+        assert_eq!(instructions[0].to_string(), "invoke-static {}, method@2");
+    }
 }
@@ -0,0 +1,137 @@
+//! DEX integrity verification
+//!
+//! Checks each DEX file's header checksum (Adler-32) and signature (SHA-1)
+//! against its contents, to catch corrupted or tampered files before they
+//! produce confusing errors deeper in the analysis.
+
+use pyo3::prelude::*;
+use std::sync::Arc;
+
+use super::parser::DexParser;
+
+/// Integrity check result for one DEX file
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DexIntegrity {
+    /// Entry name in the APK, e.g. "classes2.dex"
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub checksum_valid: bool,
+    #[pyo3(get)]
+    pub signature_valid: bool,
+    /// Checksum stored in the header
+    #[pyo3(get)]
+    pub stored_checksum: u32,
+    /// Adler-32 of the file contents, None if the file could not be read
+    #[pyo3(get)]
+    pub computed_checksum: Option<u32>,
+    /// Why the file could not be checked, if it could not
+    #[pyo3(get)]
+    pub error: Option<String>,
+}
+
+#[pymethods]
+impl DexIntegrity {
+    /// Whether both the checksum and the signature match
+    pub fn is_valid(&self) -> bool {
+        self.checksum_valid && self.signature_valid
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DexIntegrity(name='{}', checksum_valid={}, signature_valid={})",
+            self.name, self.checksum_valid, self.signature_valid
+        )
+    }
+}
+
+impl DexIntegrity {
+    /// Check the DEX file `name` with contents `data`
+    pub fn check(name: &str, data: Arc<Vec<u8>>) -> Self {
+        let mut integrity = Self {
+            name: name.to_string(),
+            checksum_valid: false,
+            signature_valid: false,
+            stored_checksum: 0,
+            computed_checksum: None,
+            error: None,
+        };
+
+        let parser = match DexParser::from_arc(data) {
+            Ok(parser) => parser,
+            Err(e) => {
+                integrity.error = Some(e.to_string());
+                return integrity;
+            }
+        };
+        integrity.stored_checksum = parser.header().checksum;
+
+        match parser.compute_checksum() {
+            Ok(checksum) => {
+                integrity.computed_checksum = Some(checksum);
+                integrity.checksum_valid = checksum == integrity.stored_checksum;
+            }
+            Err(e) => integrity.error = Some(e.to_string()),
+        }
+        integrity.signature_valid = parser.verify_signature().unwrap_or(false);
+        integrity
+    }
+}
+
+/// Verify the header checksum and SHA-1 signature of every DEX in an APK
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     list[DexIntegrity]: One result per DEX file, in classes.dex,
+///     classes2.dex, ... order
+///
+/// Raises:
+///     IOError: If the APK cannot be opened
+#[pyfunction]
+pub fn verify_dex_integrity(apk_path: &str) -> PyResult<Vec<DexIntegrity>> {
+    let extractor = crate::apk::ApkExtractor::new(apk_path)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+
+    Ok(extractor
+        .dex_entries()
+        .iter()
+        .map(|dex_entry| DexIntegrity::check(&dex_entry.name, dex_entry.data.clone()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dex::test_support::{sample_dex, write_test_apk};
+
+    #[test]
+    fn test_verify_dex_integrity() {
+        let good = sample_dex();
+        let parser = DexParser::new(good.clone()).unwrap();
+        assert!(parser.verify_checksum().unwrap());
+        assert!(parser.verify_signature().unwrap());
+
+        // Flip a byte in the data section
+        let mut corrupted = good.clone();
+        *corrupted.last_mut().unwrap() ^= 0xFF;
+        let parser = DexParser::new(corrupted.clone()).unwrap();
+        assert!(!parser.verify_checksum().unwrap());
+        assert!(!parser.verify_signature().unwrap());
+        assert!(DexParser::new_verified(corrupted.clone()).is_err());
+        assert!(DexParser::new_verified(good.clone()).is_ok());
+
+        let apk = write_test_apk("integrity", &[good, corrupted]);
+        let results = verify_dex_integrity(apk.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "classes.dex");
+        assert!(results[0].is_valid());
+        assert_eq!(results[0].computed_checksum, Some(results[0].stored_checksum));
+        assert!(!results[1].checksum_valid && !results[1].signature_valid);
+        assert!(results[1].error.is_none());
+    }
+}
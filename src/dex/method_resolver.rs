@@ -4,7 +4,10 @@
 
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
 
+use super::data_flow_analyzer::{SinkTable, CRYPTO_SINK_PATTERNS, REFLECTION_SINK_PATTERNS};
 use super::error::Result;
 use super::parser::DexParser;
 
@@ -59,6 +62,17 @@ impl MethodSignature {
         self.method_name == "addJavascriptInterface"
     }
 
+    /// Check if this is a crypto API whose arguments select an algorithm or key
+    /// (e.g. `Cipher.getInstance("AES/ECB/PKCS5Padding")`)
+    pub fn is_crypto_method(&self) -> bool {
+        self.matches_any(CRYPTO_SINK_PATTERNS)
+    }
+
+    /// Check if this is a reflection / dynamic code loading API
+    pub fn is_reflection_method(&self) -> bool {
+        self.matches_any(REFLECTION_SINK_PATTERNS)
+    }
+
     /// Format as Java-like method call
     pub fn format_call(&self, args: Vec<String>) -> String {
         format!(
@@ -91,15 +105,40 @@ impl MethodSignature {
     }
 }
 
+impl MethodSignature {
+    /// Whether this method is one of the data-flow sink patterns
+    ///
+    /// A pattern is "Class.method", a class alone for its constructor
+    /// ("SecretKeySpec"), or a method alone on any class
+    /// ("getDeclaredMethod"). Classes are compared by simple name, and every
+    /// part must match exactly.
+    fn matches_any(&self, sinks: SinkTable) -> bool {
+        let class_name = self.simple_class_name();
+        sinks.iter().any(|(pattern, _)| match pattern.split_once('.') {
+            Some((class, method)) => class_name == class && self.method_name == method,
+            None if pattern.starts_with(char::is_uppercase) => class_name == *pattern && self.method_name == "<init>",
+            None => self.method_name == *pattern,
+        })
+    }
+}
+
 /// Method Resolver
+///
+/// Resolved signatures are memoized by method index, so repeated lookups
+/// (every call site of a popular API) read the DEX tables only once. The
+/// cache sits behind an `RwLock` rather than a `RefCell` because
+/// `MethodResolverPy` and `ExpressionBuilderPy` are Python classes and must
+/// be `Sync`; concurrent readers don't block each other, and two threads
+/// missing on the same index both resolve it and store equal values.
 pub struct MethodResolver {
     pub parser: DexParser,
+    cache: RwLock<HashMap<u32, MethodSignature>>,
 }
 
 impl MethodResolver {
     /// Create a new method resolver
     pub fn new(parser: DexParser) -> Self {
-        Self { parser }
+        Self { parser, cache: RwLock::new(HashMap::new()) }
     }
 
     /// Resolve string index
@@ -107,8 +146,38 @@ impl MethodResolver {
         self.parser.get_string(string_idx)
     }
 
+    /// Resolve type index to a Java class name
+    pub fn resolve_type(&self, type_idx: u32) -> Result<String> {
+        self.parser.get_type_name(type_idx)
+    }
+
     /// Resolve method index to method signature
     pub fn resolve(&self, method_idx: u32) -> Result<MethodSignature> {
+        // A poisoned lock only means another thread panicked mid-insert;
+        // the map itself is still consistent
+        let cached = self.cache.read().unwrap_or_else(|e| e.into_inner()).get(&method_idx).cloned();
+        if let Some(signature) = cached {
+            return Ok(signature);
+        }
+
+        let signature = self.resolve_uncached(method_idx)?;
+        self.cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(method_idx, signature.clone());
+        Ok(signature)
+    }
+
+    /// Resolve every entry of the method_ids table, filling the cache
+    ///
+    /// Results are in method index order; indices that fail to resolve are
+    /// returned as errors in place.
+    pub fn resolve_all(&self) -> Vec<Result<MethodSignature>> {
+        (0..self.parser.header().method_ids_size).map(|idx| self.resolve(idx)).collect()
+    }
+
+    /// Read a signature from the DEX tables
+    fn resolve_uncached(&self, method_idx: u32) -> Result<MethodSignature> {
         // Get method_id_item
         let method_info = self.parser.get_method_info(method_idx)?;
 
@@ -163,67 +232,158 @@ impl MethodResolver {
 pub fn create_method_resolver(dex_data: Vec<u8>) -> PyResult<MethodResolverPy> {
     let parser = DexParser::new(dex_data)
         .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
-    let resolver = MethodResolver::new(parser);
 
-    Ok(MethodResolverPy { resolver })
+    Ok(MethodResolverPy {
+        resolvers: vec![Some(MethodResolver::new(parser))],
+    })
+}
+
+/// Create a method resolver over every DEX file in an APK (Python API)
+///
+/// Each DEX is parsed once up front, so resolving many indices (e.g. the
+/// output of `extract_method_calls`) doesn't re-read the APK per lookup.
+#[pyfunction]
+pub fn create_apk_method_resolver(apk_path: &str) -> PyResult<MethodResolverPy> {
+    use crate::apk::ApkExtractor;
+
+    let extractor = ApkExtractor::new(apk_path)
+        .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
+
+    Ok(MethodResolverPy::from_extractor(&extractor))
 }
 
 /// Python wrapper for MethodResolver
+///
+/// Holds one resolver per DEX file, in APK order (classes.dex first). A DEX
+/// that fails to parse keeps its slot as `None`, so `dex_index` values from
+/// `DexEntry` order still line up.
 #[pyclass]
 pub struct MethodResolverPy {
-    resolver: MethodResolver,
+    resolvers: Vec<Option<MethodResolver>>,
+}
+
+impl MethodResolverPy {
+    /// Parse every DEX in the APK once
+    pub fn from_extractor(extractor: &crate::apk::ApkExtractor) -> Self {
+        let resolvers = extractor
+            .dex_entries()
+            .iter()
+            .map(|entry| DexParser::from_arc(entry.data.clone()).ok().map(MethodResolver::new))
+            .collect();
+
+        Self { resolvers }
+    }
+
+    /// Resolver for one DEX, failing if the index is out of range or the DEX didn't parse
+    fn resolver(&self, dex_index: usize) -> Result<&MethodResolver> {
+        use super::error::DexError;
+
+        self.resolvers
+            .get(dex_index)
+            .ok_or_else(|| DexError::DexFileError(format!("DEX index {} out of range", dex_index)))?
+            .as_ref()
+            .ok_or_else(|| DexError::DexFileError(format!("DEX index {} could not be parsed", dex_index)))
+    }
+
+    /// `resolver` for the Python API: IndexError for an index past the last
+    /// DEX, Exception for a DEX that didn't parse
+    fn py_resolver(&self, dex_index: usize) -> PyResult<&MethodResolver> {
+        if dex_index >= self.resolvers.len() {
+            return Err(pyo3::exceptions::PyIndexError::new_err(format!(
+                "DEX index {} out of range ({} DEX files)",
+                dex_index,
+                self.resolvers.len()
+            )));
+        }
+        self.resolver(dex_index)
+            .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))
+    }
+
+    /// Resolve against one DEX, or the first DEX that has the index
+    fn lookup(&self, method_idx: u32, dex_index: Option<usize>) -> Result<MethodSignature> {
+        use super::error::DexError;
+
+        match dex_index {
+            Some(i) => self.resolver(i)?.resolve(method_idx),
+            None => self
+                .resolvers
+                .iter()
+                .flatten()
+                .find_map(|resolver| resolver.resolve(method_idx).ok())
+                .ok_or_else(|| DexError::MethodNotFound(format!("index {} in any DEX file", method_idx))),
+        }
+    }
 }
 
 #[pymethods]
 impl MethodResolverPy {
     /// Resolve a single method index
-    pub fn resolve(&self, method_idx: u32) -> PyResult<MethodSignature> {
-        self.resolver
-            .resolve(method_idx)
+    ///
+    /// Method indices are local to a DEX file. Pass `dex_index` when it is
+    /// known (e.g. from `DexEntry` order); otherwise the first DEX containing
+    /// the index is used.
+    #[pyo3(signature = (method_idx, dex_index=None))]
+    pub fn resolve(&self, method_idx: u32, dex_index: Option<usize>) -> PyResult<MethodSignature> {
+        self.lookup(method_idx, dex_index)
             .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))
     }
 
     /// Resolve multiple method indices
-    pub fn resolve_many(&self, method_indices: Vec<u32>) -> PyResult<Vec<MethodSignature>> {
-        let results = self.resolver.resolve_many(&method_indices);
+    ///
+    /// The result lines up with `method_indices`; indices that cannot be
+    /// resolved are `None`.
+    #[pyo3(signature = (method_indices, dex_index=None))]
+    pub fn resolve_many(&self, method_indices: Vec<u32>, dex_index: Option<usize>) -> PyResult<Vec<Option<MethodSignature>>> {
+        let results = match dex_index {
+            Some(i) => self.py_resolver(i)?.resolve_many(&method_indices),
+            None => method_indices.iter().map(|&idx| self.lookup(idx, None)).collect(),
+        };
 
-        // Filter out errors and collect successes
-        let signatures: Vec<MethodSignature> = results
+        Ok(results.into_iter().map(|r| r.ok()).collect())
+    }
+
+    /// Resolve `(dex_index, method_idx)` pairs, each against its own DEX
+    ///
+    /// This is the unambiguous form for multi-DEX apps, where the same index
+    /// names different methods in different files. The result lines up with
+    /// `method_refs`; pairs that cannot be resolved are `None`.
+    pub fn resolve_refs(&self, method_refs: Vec<(usize, u32)>) -> Vec<Option<MethodSignature>> {
+        method_refs
             .into_iter()
-            .filter_map(|r| r.ok())
-            .collect();
+            .map(|(dex_index, method_idx)| self.lookup(method_idx, Some(dex_index)).ok())
+            .collect()
+    }
+
+    /// Resolve the whole method_ids table of one DEX
+    ///
+    /// Fills the resolver's cache, so later `resolve` calls against that
+    /// DEX are lookups. The result is indexed by method index; entries that
+    /// cannot be resolved are `None`.
+    #[pyo3(signature = (dex_index=0))]
+    pub fn resolve_all(&self, dex_index: usize) -> PyResult<Vec<Option<MethodSignature>>> {
+        Ok(self.py_resolver(dex_index)?.resolve_all().into_iter().map(|r| r.ok()).collect())
+    }
 
-        Ok(signatures)
+    /// Number of DEX files in the APK, including any that failed to parse
+    pub fn dex_count(&self) -> usize {
+        self.resolvers.len()
     }
 }
 
 /// Resolve method index from APK
 ///
-/// Searches through all DEX files in the APK until it finds the method index.
+/// Method indices are per-DEX. With `dex_index` the index is resolved in
+/// that DEX; without it, the first DEX that has the index wins, which may
+/// be the wrong method in a multi-DEX app. To resolve many indices, use
+/// `create_apk_method_resolver` instead, which parses each DEX only once.
 #[pyfunction]
+#[pyo3(signature = (apk_path, method_idx, dex_index=None))]
 pub fn resolve_method_from_apk(
     apk_path: String,
     method_idx: u32,
+    dex_index: Option<usize>,
 ) -> PyResult<MethodSignature> {
-    use crate::apk::ApkExtractor;
-
-    let extractor = ApkExtractor::new(&apk_path)
-        .map_err(|e| pyo3::exceptions::PyException::new_err(e.to_string()))?;
-
-    // Try each DEX file
-    for dex_entry in extractor.dex_entries() {
-        if let Ok(parser) = DexParser::new(dex_entry.data.clone()) {
-            let resolver = MethodResolver::new(parser);
-            if let Ok(signature) = resolver.resolve(method_idx) {
-                return Ok(signature);
-            }
-        }
-    }
-
-    Err(pyo3::exceptions::PyException::new_err(format!(
-        "Method index {} not found in any DEX file",
-        method_idx
-    )))
+    create_apk_method_resolver(&apk_path)?.resolve(method_idx, dex_index)
 }
 
 #[cfg(test)]
@@ -246,6 +406,177 @@ mod tests {
         assert!(sig.is_set_javascript_enabled());
     }
 
+    #[test]
+    fn test_crypto_and_reflection_methods() {
+        let sig = |class_name: &str, method_name: &str| MethodSignature {
+            class_name: class_name.to_string(),
+            method_name: method_name.to_string(),
+            parameters: vec![],
+            return_type: "void".to_string(),
+            full_signature: String::new(),
+        };
+
+        assert!(sig("javax.crypto.Cipher", "getInstance").is_crypto_method());
+        assert!(sig("javax.crypto.spec.SecretKeySpec", "<init>").is_crypto_method());
+        assert!(!sig("javax.crypto.Cipher", "doFinal").is_crypto_method());
+
+        assert!(sig("java.lang.Class", "forName").is_reflection_method());
+        assert!(sig("java.lang.reflect.Method", "invoke").is_reflection_method());
+        assert!(!sig("java.lang.Class", "getName").is_reflection_method());
+
+        // Names that only contain a pattern are not sinks
+        assert!(!sig("javax.crypto.spec.SecretKeySpec", "getEncoded").is_crypto_method());
+        assert!(!sig("dalvik.system.InMemoryDexClassLoader", "<init>").is_reflection_method());
+        assert!(sig("dalvik.system.DexClassLoader", "<init>").is_reflection_method());
+        assert!(!sig("java.lang.Class", "getMethods").is_reflection_method());
+        assert!(sig("java.lang.Class", "getDeclaredMethod").is_reflection_method());
+        assert!(!sig("java.lang.Class", "getDeclaredMethods").is_reflection_method());
+    }
+
+    #[test]
+    fn test_apk_resolver_caches_each_dex() {
+        use crate::apk::ApkExtractor;
+        use crate::dex::test_support::{sample_dex, write_test_apk, DexBuilder};
+
+        let mut feature = DexBuilder::new();
+        let run = feature.method("Lcom/example/Feature;", "run", "Z", &["I"]);
+        let class = feature.class("Lcom/example/Feature;", Some("Ljava/lang/Object;"), 0x0001);
+        feature.direct_method(class, run, 0x0009, Some(vec![0x000e]));
+
+        let apk = write_test_apk("method-resolver-cache", &[sample_dex(), feature.build()]);
+        let extractor = ApkExtractor::new(&apk).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        let resolver = MethodResolverPy::from_extractor(&extractor);
+        assert_eq!(resolver.dex_count(), 2);
+
+        let in_feature = resolver.resolve(run, Some(1)).unwrap();
+        assert_eq!(in_feature.full_signature, "com.example.Feature.run(int): boolean");
+
+        let resolved = resolver.resolve_many(vec![0, 1, 2, 3, 99], None).unwrap();
+        assert_eq!(resolved.len(), 5);
+        assert!(resolved[4].is_none());
+        let names: Vec<String> = resolved
+            .into_iter()
+            .flatten()
+            .map(|sig| format!("{}.{}", sig.simple_class_name(), sig.method_name))
+            .collect();
+        assert_eq!(names.len(), 4);
+        assert!(names.contains(&"MainActivity.onCreate".to_string()));
+        assert!(names.contains(&"Helper.log".to_string()));
+
+        let in_feature = resolver.resolve_many(vec![run, 99], Some(1)).unwrap();
+        assert!(in_feature[0].is_some() && in_feature[1].is_none());
+        assert!(resolver.resolve(99, None).is_err());
+        assert!(resolver.resolve(0, Some(2)).is_err());
+
+        let all = resolver.resolve_all(1).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[run as usize].as_ref().unwrap().method_name, "run");
+
+        pyo3::Python::initialize();
+        pyo3::Python::attach(|py| {
+            let err = resolver.resolve_all(2).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyIndexError>(py));
+            let err = resolver.resolve_many(vec![run], Some(2)).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyIndexError>(py));
+        });
+    }
+
+    #[test]
+    fn test_repeated_resolve_is_cached() {
+        use crate::dex::test_support::sample_dex;
+
+        let resolver = MethodResolver::new(DexParser::new(sample_dex()).unwrap());
+        let first: Vec<String> = resolver.resolve_all().into_iter().map(|sig| sig.unwrap().full_signature).collect();
+        let method_count = resolver.parser.header().method_ids_size as usize;
+        assert_eq!(first.len(), method_count);
+        assert_eq!(resolver.cache.read().unwrap().len(), method_count);
+
+        let indices: Vec<u32> = (0..method_count as u32).cycle().take(10_000).collect();
+        for (idx, result) in indices.iter().zip(resolver.resolve_many(&indices)) {
+            assert_eq!(result.unwrap().full_signature, first[*idx as usize]);
+        }
+        assert_eq!(resolver.cache.read().unwrap().len(), method_count);
+
+        // Failures are not cached
+        assert!(resolver.resolve(method_count as u32).is_err());
+        assert_eq!(resolver.cache.read().unwrap().len(), method_count);
+    }
+
+    /// DEX whose method 5 is `<class>.<target>()` and whose `<class>.run()` calls it
+    fn dex_calling_index_5(class: &str, target: &str) -> Vec<u8> {
+        use crate::dex::test_support::DexBuilder;
+
+        let mut b = DexBuilder::new();
+        for i in 0..5 {
+            b.method("Ljava/lang/Object;", &format!("filler{}", i), "V", &[]);
+        }
+        let target = b.method(class, target, "V", &[]);
+        let run = b.method(class, "run", "V", &[]);
+        assert_eq!(target, 5);
+
+        let class = b.class(class, Some("Ljava/lang/Object;"), 0x0001);
+        b.direct_method(class, target, 0x0009, Some(vec![0x000e]));
+        b.direct_method(class, run, 0x0009, Some(vec![0x0071, target as u16, 0x0000, 0x000e]));
+        b.build()
+    }
+
+    #[test]
+    fn test_method_refs_resolve_against_their_own_dex() {
+        use crate::apk::ApkExtractor;
+        use crate::dex::code_extractor::method_call_refs;
+        use crate::dex::test_support::write_test_apk;
+
+        let apk = write_test_apk(
+            "method-refs-multidex",
+            &[
+                dex_calling_index_5("Lcom/example/Pay;", "charge"),
+                dex_calling_index_5("Lcom/example/Log;", "flush"),
+            ],
+        );
+        let extractor = ApkExtractor::new(&apk).unwrap();
+        std::fs::remove_file(&apk).ok();
+        let resolver = MethodResolverPy::from_extractor(&extractor);
+
+        let pay_calls = method_call_refs(&extractor, "com.example.Pay", "run").unwrap();
+        let log_calls = method_call_refs(&extractor, "com.example.Log", "run").unwrap();
+        assert_eq!(pay_calls, vec![(0, 5)]);
+        assert_eq!(log_calls, vec![(1, 5)]);
+
+        let resolved = resolver.resolve_refs(log_calls);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].as_ref().unwrap().full_signature, "com.example.Log.flush(): void");
+
+        // A bare index picks the first DEX, which is a different method
+        assert_eq!(resolver.resolve(5, None).unwrap().full_signature, "com.example.Pay.charge(): void");
+    }
+
+    #[test]
+    fn test_unparseable_dex_keeps_its_index() {
+        use crate::apk::ApkExtractor;
+        use crate::dex::code_extractor::method_call_refs;
+        use crate::dex::test_support::write_test_apk;
+
+        let apk = write_test_apk(
+            "method-refs-broken-dex",
+            &[b"not a dex file".to_vec(), dex_calling_index_5("Lcom/example/Log;", "flush")],
+        );
+        let extractor = ApkExtractor::new(&apk).unwrap();
+        std::fs::remove_file(&apk).ok();
+        let resolver = MethodResolverPy::from_extractor(&extractor);
+        assert_eq!(resolver.dex_count(), 2);
+
+        let log_calls = method_call_refs(&extractor, "com.example.Log", "run").unwrap();
+        assert_eq!(log_calls, vec![(1, 5)]);
+
+        let resolved = resolver.resolve_refs(vec![(0, 5), log_calls[0]]);
+        assert!(resolved[0].is_none());
+        assert_eq!(resolved[1].as_ref().unwrap().full_signature, "com.example.Log.flush(): void");
+        assert!(resolver.resolve(5, Some(0)).is_err());
+        assert!(resolver.resolve_many(vec![5], Some(0)).is_err());
+    }
+
     #[test]
     fn test_format_call() {
         let sig = MethodSignature {
@@ -5,6 +5,9 @@ pub mod container;
 pub mod search;
 pub mod parser;
 pub mod constants;
+pub mod descriptor;
+pub mod inspector;
+pub mod integrity;
 pub mod instruction;
 pub mod bytecode;
 pub mod code_extractor;
@@ -14,6 +17,12 @@ pub mod class_decompiler;
 pub mod entry_point_analyzer;
 pub mod call_graph;
 pub mod data_flow_analyzer;
+pub mod field_taint;
+pub mod api_level;
+pub mod secret_scanner;
+
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use error::DexError;
 // New generic API
@@ -55,6 +55,22 @@ impl RustReferencePool {
         self.methods.iter().any(|s| s.contains(value))
     }
 
+    /// Add entries from another pool, skipping values already present
+    pub fn merge(&mut self, other: &RustReferencePool) {
+        fn extend_unique(target: &mut Vec<String>, values: &[String]) {
+            for value in values {
+                if !target.contains(value) {
+                    target.push(value.clone());
+                }
+            }
+        }
+
+        extend_unique(&mut self.strings, &other.strings);
+        extend_unique(&mut self.types, &other.types);
+        extend_unique(&mut self.fields, &other.fields);
+        extend_unique(&mut self.methods, &other.methods);
+    }
+
     /// Convert to Python dictionary
     pub fn to_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
         let dict = pyo3::types::PyDict::new(py);
@@ -279,6 +295,10 @@ pub struct RustDexClass {
     pub methods: Vec<RustDexMethod>,
     #[pyo3(get)]
     pub references: RustReferencePool,
+    /// Bundle module the class was loaded from ("base" or a split name);
+    /// None for a plain APK
+    #[pyo3(get)]
+    pub source_module: Option<String>,
 }
 
 #[pymethods]
@@ -297,6 +317,7 @@ impl RustDexClass {
             fields: Vec::new(),
             methods: Vec::new(),
             references: RustReferencePool::new(),
+            source_module: None,
         }
     }
 
@@ -335,6 +356,71 @@ impl RustDexClass {
         self.fields.len()
     }
 
+    /// Check if class belongs to AndroidX (`androidx.*`)
+    pub fn is_androidx(&self) -> bool {
+        self.in_package("androidx")
+    }
+
+    /// Check if class belongs to the legacy support library (`android.support.*`)
+    pub fn is_support_lib(&self) -> bool {
+        self.in_package("android.support")
+    }
+
+    /// Check if class belongs to the Kotlin standard library (`kotlin.*`, `kotlinx.*`)
+    pub fn is_kotlin_stdlib(&self) -> bool {
+        self.in_package("kotlin") || self.in_package("kotlinx")
+    }
+
+    /// Check if class is Kotlin stdlib or Kotlin compiler output
+    ///
+    /// DEX annotations are not parsed, so `kotlin.Metadata` cannot be seen
+    /// directly; this falls back to the stdlib packages and the names only
+    /// the Kotlin compiler generates (`$WhenMappings`, `$DefaultImpls`,
+    /// `$Companion`). D8's `$$ExternalSynthetic` and `$$Lambda` classes are
+    /// not counted, since Java lambdas get them too.
+    pub fn is_kotlin_metadata(&self) -> bool {
+        self.is_kotlin_stdlib()
+            || self.simple_name.ends_with("$WhenMappings")
+            || self.simple_name.ends_with("$DefaultImpls")
+            || self.simple_name.ends_with("$Companion")
+    }
+
+    /// Check if class looks like test code
+    ///
+    /// Matches test frameworks (JUnit, Mockito, Robolectric, androidx.test),
+    /// `test`/`tests`/`androidTest` package segments and `*Test`/`*Tests`
+    /// class names.
+    pub fn is_test(&self) -> bool {
+        const TEST_PACKAGES: &[&str] = &["junit", "org.junit", "org.mockito", "org.robolectric", "androidx.test"];
+
+        TEST_PACKAGES.iter().any(|pkg| self.in_package(pkg))
+            || self
+                .package_name
+                .split('.')
+                .any(|segment| matches!(segment, "test" | "tests" | "androidTest"))
+            || self.simple_name.ends_with("Test")
+            || self.simple_name.ends_with("Tests")
+    }
+
+    /// Total method references across all methods' reference pools
+    pub fn total_referenced_methods(&self) -> usize {
+        self.methods.iter().map(|m| m.references.methods.len()).sum()
+    }
+
+    /// Total string references across all methods' reference pools
+    pub fn total_referenced_strings(&self) -> usize {
+        self.methods.iter().map(|m| m.references.strings.len()).sum()
+    }
+
+    /// Combined reference pool of all methods, without duplicates
+    pub fn method_references(&self) -> RustReferencePool {
+        let mut pool = RustReferencePool::new();
+        for method in &self.methods {
+            pool.merge(&method.references);
+        }
+        pool
+    }
+
     /// Convert to Python dictionary
     pub fn to_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
         let dict = pyo3::types::PyDict::new(py);
@@ -351,6 +437,8 @@ impl RustDexClass {
         dict.set_item("is_enum", self.is_enum())?;
         dict.set_item("method_count", self.method_count())?;
         dict.set_item("field_count", self.field_count())?;
+        dict.set_item("total_referenced_methods", self.total_referenced_methods())?;
+        dict.set_item("total_referenced_strings", self.total_referenced_strings())?;
 
         let fields_list = pyo3::types::PyList::empty(py);
         for field in &self.fields {
@@ -365,6 +453,7 @@ impl RustDexClass {
         dict.set_item("methods", methods_list)?;
 
         dict.set_item("references", self.references.to_dict(py)?)?;
+        dict.set_item("source_module", &self.source_module)?;
         Ok(dict.into())
     }
 
@@ -379,6 +468,13 @@ impl RustDexClass {
 }
 
 impl RustDexClass {
+    /// Whether the class is in `package` or one of its subpackages
+    fn in_package(&self, package: &str) -> bool {
+        self.package_name
+            .strip_prefix(package)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    }
+
     /// Split a full class name into package and simple name
     /// e.g., "com.example.app.MainActivity" -> ("com.example.app", "MainActivity")
     fn split_class_name(class_name: &str) -> (String, String) {
@@ -417,6 +513,33 @@ mod tests {
         assert_eq!(simple, "SimpleClass");
     }
 
+    #[test]
+    fn test_class_classifiers() {
+        let androidx = RustDexClass::new("androidx.appcompat.app.AppCompatActivity".to_string());
+        assert!(androidx.is_androidx() && !androidx.is_support_lib() && !androidx.is_test());
+
+        let support = RustDexClass::new("android.support.v4.app.Fragment".to_string());
+        assert!(support.is_support_lib() && !support.is_androidx());
+
+        let stdlib = RustDexClass::new("kotlin.collections.CollectionsKt".to_string());
+        assert!(stdlib.is_kotlin_stdlib() && stdlib.is_kotlin_metadata());
+
+        let mappings = RustDexClass::new("com.example.MainActivity$WhenMappings".to_string());
+        assert!(mappings.is_kotlin_metadata() && !mappings.is_kotlin_stdlib());
+
+        // D8 desugars Java lambdas into the same synthetic classes
+        let lambda = RustDexClass::new("com.example.MainActivity$$ExternalSyntheticLambda0".to_string());
+        assert!(!lambda.is_kotlin_metadata());
+
+        assert!(RustDexClass::new("com.example.test.FakeApi".to_string()).is_test());
+        assert!(RustDexClass::new("com.example.LoginViewModelTest".to_string()).is_test());
+        assert!(RustDexClass::new("org.junit.Assert".to_string()).is_test());
+
+        let app = RustDexClass::new("com.example.kotlinapp.MainActivity".to_string());
+        assert!(!app.is_androidx() && !app.is_kotlin_metadata() && !app.is_test());
+        assert!(!RustDexClass::new("androidxyz.Foo".to_string()).is_androidx());
+    }
+
     #[test]
     fn test_access_flags() {
         let field = RustDexField::new(
@@ -430,4 +553,28 @@ mod tests {
         assert!(field.is_final());
         assert!(!field.is_private());
     }
+
+    #[test]
+    fn test_class_reference_totals() {
+        let mut class = RustDexClass::new("com.example.Api".to_string());
+        for (name, string) in [("login", "https://example.com/login"), ("logout", "https://example.com/logout")] {
+            let mut method = RustDexMethod::new(
+                name.to_string(),
+                Vec::new(),
+                "void".to_string(),
+                "com.example.Api".to_string(),
+                0x0001,
+            );
+            method.references.strings.push(string.to_string());
+            method.references.methods.push("okhttp3.OkHttpClient.newCall".to_string());
+            class.methods.push(method);
+        }
+
+        assert_eq!(class.total_referenced_strings(), 2);
+        assert_eq!(class.total_referenced_methods(), 2);
+
+        let pool = class.method_references();
+        assert_eq!(pool.strings, vec!["https://example.com/login", "https://example.com/logout"]);
+        assert_eq!(pool.methods, vec!["okhttp3.OkHttpClient.newCall"]);
+    }
 }
@@ -1,7 +1,10 @@
 use crate::dex::error::{DexError, Result};
 use crate::dex::constants::{dex_magic, structure, type_descriptors};
 use byteorder::{LittleEndian, ReadBytesExt};
+use sha1::{Digest, Sha1};
+use std::collections::HashSet;
 use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::sync::Arc;
 
 /// DEX file header structure
 /// Reference: https://source.android.com/docs/core/runtime/dex-format
@@ -34,21 +37,72 @@ pub struct DexHeader {
 }
 
 /// Simple DEX parser
+///
+/// The DEX bytes are reference-counted, so cloning a parser or building one
+/// per task from a shared `DexEntry` buffer never copies the file.
+#[derive(Clone)]
 pub struct DexParser {
-    data: Vec<u8>,
+    data: Arc<Vec<u8>>,
     header: DexHeader,
 }
 
 impl DexParser {
     /// Create a new DEX parser from raw bytes
     pub fn new(data: Vec<u8>) -> Result<Self> {
+        Self::from_arc(Arc::new(data))
+    }
+
+    /// Create a parser over a shared buffer, e.g. `DexEntry::data`, without
+    /// copying it (measured savings in docs/performance/MEMORY_OPTIMIZATION.md)
+    pub fn from_arc(data: Arc<Vec<u8>>) -> Result<Self> {
         let header = Self::parse_header(&data)?;
 
         Ok(Self { data, header })
     }
 
+    /// Create a parser that rejects files whose header checksum or SHA-1
+    /// signature does not match their contents
+    pub fn new_verified(data: Vec<u8>) -> Result<Self> {
+        let parser = Self::new(data)?;
+        if !parser.verify_checksum()? {
+            return Err(DexError::InvalidDex("Adler-32 checksum mismatch".to_string()));
+        }
+        if !parser.verify_signature()? {
+            return Err(DexError::InvalidDex("SHA-1 signature mismatch".to_string()));
+        }
+        Ok(parser)
+    }
+
+    /// Whether the header checksum matches the Adler-32 of everything after it
+    pub fn verify_checksum(&self) -> Result<bool> {
+        Ok(self.compute_checksum()? == self.header.checksum)
+    }
+
+    /// Whether the header signature matches the SHA-1 of everything after it
+    pub fn verify_signature(&self) -> Result<bool> {
+        Ok(self.compute_signature()? == self.header.signature)
+    }
+
+    /// Adler-32 over the file past the magic and checksum fields
+    pub fn compute_checksum(&self) -> Result<u32> {
+        Ok(adler32(self.checked_contents(12)?))
+    }
+
+    /// SHA-1 over the file past the magic, checksum and signature fields
+    pub fn compute_signature(&self) -> Result<[u8; 20]> {
+        Ok(Sha1::digest(self.checked_contents(32)?).into())
+    }
+
+    /// File bytes from `start` up to the header's file_size
+    fn checked_contents(&self, start: usize) -> Result<&[u8]> {
+        let end = self.header.file_size as usize;
+        self.data.get(start..end).ok_or_else(|| {
+            DexError::InvalidDex(format!("file_size {} exceeds data length {}", end, self.data.len()))
+        })
+    }
+
     /// Parse the DEX file header
-    fn parse_header(data: &[u8]) -> Result<DexHeader> {
+    pub(crate) fn parse_header(data: &[u8]) -> Result<DexHeader> {
         if data.len() < 112 {
             return Err(DexError::InvalidDex("File too small".to_string()));
         }
@@ -177,7 +231,7 @@ impl DexParser {
 
         // Get string data offset from string_ids table
         let string_id_offset = self.header.string_ids_off as usize + (string_idx as usize * structure::STRING_ID_SIZE);
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(&*self.data);
         cursor.seek(SeekFrom::Start(string_id_offset as u64))?;
         let string_data_off = cursor.read_u32::<LittleEndian>()? as usize;
 
@@ -197,8 +251,33 @@ impl DexParser {
             bytes.push(b);
         }
 
-        // Convert MUTF-8 to UTF-8 (simplified - works for most cases)
-        String::from_utf8(bytes).map_err(|e| DexError::ParseError(format!("Invalid UTF-8: {}", e)))
+        decode_mutf8(&bytes)
+    }
+
+    /// Read SLEB128 (signed little-endian base 128)
+    fn read_sleb128(&self, cursor: &mut Cursor<&Vec<u8>>) -> Result<i32> {
+        let mut result = 0i32;
+        let mut shift = 0;
+
+        loop {
+            let byte = cursor.read_u8()?;
+            result |= ((byte & 0x7F) as i32) << shift;
+            shift += 7;
+
+            if (byte & 0x80) == 0 {
+                // Sign-extend from the last byte's top bit
+                if shift < 32 && (byte & 0x40) != 0 {
+                    result |= -1 << shift;
+                }
+                break;
+            }
+
+            if shift >= 35 {
+                return Err(DexError::ParseError("SLEB128 overflow".to_string()));
+            }
+        }
+
+        Ok(result)
     }
 
     /// Read ULEB128 (unsigned little-endian base 128)
@@ -234,7 +313,7 @@ impl DexParser {
 
         // Get string index from type_ids table
         let type_id_offset = self.header.type_ids_off as usize + (type_idx as usize * structure::TYPE_ID_SIZE);
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(&*self.data);
         cursor.seek(SeekFrom::Start(type_id_offset as u64))?;
         let descriptor_idx = cursor.read_u32::<LittleEndian>()?;
 
@@ -304,7 +383,7 @@ impl DexParser {
 
         // Each class_def_item is 32 bytes
         let class_def_offset = self.header.class_defs_off as usize + (class_idx as usize * structure::CLASS_DEF_SIZE);
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(&*self.data);
         cursor.seek(SeekFrom::Start(class_def_offset as u64))?;
 
         let class_idx = cursor.read_u32::<LittleEndian>()?;
@@ -334,7 +413,7 @@ impl DexParser {
             return Ok(ClassData::default());
         }
 
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(&*self.data);
         cursor.seek(SeekFrom::Start(class_data_off as u64))?;
 
         let static_fields_size = self.read_uleb128(&mut cursor)?;
@@ -413,7 +492,7 @@ impl DexParser {
 
         // Each field_id_item is 8 bytes
         let field_id_offset = self.header.field_ids_off as usize + (field_idx as usize * structure::FIELD_ID_SIZE);
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(&*self.data);
         cursor.seek(SeekFrom::Start(field_id_offset as u64))?;
 
         let class_idx = cursor.read_u16::<LittleEndian>()?;
@@ -438,7 +517,7 @@ impl DexParser {
 
         // Each method_id_item is 8 bytes
         let method_id_offset = self.header.method_ids_off as usize + (method_idx as usize * structure::METHOD_ID_SIZE);
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(&*self.data);
         cursor.seek(SeekFrom::Start(method_id_offset as u64))?;
 
         let class_idx = cursor.read_u16::<LittleEndian>()?;
@@ -454,31 +533,273 @@ impl DexParser {
 
     /// Get method bytecode (instructions) from code_off
     pub fn get_method_bytecode(&self, code_off: u32) -> Result<Vec<u16>> {
-        if code_off == 0 {
-            // No code (abstract/native method)
+        // No code (abstract/native method) yields an empty instruction array
+        Ok(self.get_code_item(code_off)?.insns)
+    }
+
+    /// Decode the encoded_array at `static_values_off` (a class's static
+    /// field initializers, in static field order)
+    ///
+    /// Trailing static fields without an entry keep their type's default.
+    /// ref: https://source.android.com/docs/core/runtime/dex-format#encoding
+    pub fn parse_static_values(&self, static_values_off: u32) -> Result<Vec<StaticValue>> {
+        if static_values_off == 0 {
             return Ok(Vec::new());
         }
 
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(&*self.data);
+        cursor.seek(SeekFrom::Start(static_values_off as u64))?;
+        self.read_encoded_array(&mut cursor, 0)
+    }
+
+    /// Read an encoded_array: uleb128 size followed by that many encoded_values
+    fn read_encoded_array(&self, cursor: &mut Cursor<&Vec<u8>>, depth: usize) -> Result<Vec<StaticValue>> {
+        let size = self.read_uleb128(cursor)?;
+        // Every encoded_value takes at least one byte
+        let remaining = self.data.len().saturating_sub(cursor.position() as usize);
+        let mut values = Vec::with_capacity((size as usize).min(remaining));
+        for _ in 0..size {
+            values.push(self.read_encoded_value(cursor, depth)?);
+        }
+        Ok(values)
+    }
+
+    /// Read one encoded_value: a (value_arg << 5 | value_type) byte, then
+    /// value_arg + 1 little-endian bytes for the sized types
+    fn read_encoded_value(&self, cursor: &mut Cursor<&Vec<u8>>, depth: usize) -> Result<StaticValue> {
+        // Nested arrays and annotations recurse; bound them on crafted input
+        const MAX_DEPTH: usize = 32;
+        if depth > MAX_DEPTH {
+            return Err(DexError::ParseError("encoded_value nested too deeply".to_string()));
+        }
+
+        let header = cursor.read_u8()?;
+        let value_type = header & 0x1F;
+        let value_arg = (header >> 5) as usize;
+
+        // Little-endian value of value_arg + 1 bytes
+        let read_raw = |cursor: &mut Cursor<&Vec<u8>>| -> Result<(u64, usize)> {
+            let size = value_arg + 1;
+            if size > 8 {
+                return Err(DexError::ParseError(format!("encoded_value size {} too large", size)));
+            }
+            let mut raw = 0u64;
+            for i in 0..size {
+                raw |= (cursor.read_u8()? as u64) << (8 * i);
+            }
+            Ok((raw, size))
+        };
+        let signed = |raw: u64, size: usize| ((raw << (64 - 8 * size)) as i64) >> (64 - 8 * size);
+        let index = |raw: u64| u32::try_from(raw).map_err(|_| DexError::ParseError("encoded index too large".to_string()));
+
+        let value = match value_type {
+            // VALUE_BYTE, VALUE_SHORT, VALUE_INT
+            0x00 | 0x02 | 0x04 => {
+                let (raw, size) = read_raw(cursor)?;
+                StaticValue::Int(signed(raw, size) as i32)
+            }
+            // VALUE_CHAR
+            0x03 => StaticValue::Char(read_raw(cursor)?.0 as u16),
+            // VALUE_LONG
+            0x06 => {
+                let (raw, size) = read_raw(cursor)?;
+                StaticValue::Long(signed(raw, size))
+            }
+            // VALUE_FLOAT, VALUE_DOUBLE: zero-extended to the right
+            0x10 => {
+                let (raw, size) = read_raw(cursor)?;
+                StaticValue::Float(f32::from_bits(((raw << (8 * (4usize.saturating_sub(size)))) & 0xFFFF_FFFF) as u32))
+            }
+            0x11 => {
+                let (raw, size) = read_raw(cursor)?;
+                StaticValue::Double(f64::from_bits(raw << (8 * (8 - size))))
+            }
+            // VALUE_STRING
+            0x17 => StaticValue::String(self.get_string(index(read_raw(cursor)?.0)?)?),
+            // VALUE_TYPE
+            0x18 => StaticValue::Type(self.get_type_name(index(read_raw(cursor)?.0)?)?),
+            // VALUE_METHOD_TYPE, VALUE_METHOD_HANDLE, VALUE_FIELD, VALUE_METHOD, VALUE_ENUM
+            0x15 | 0x16 | 0x19 | 0x1a | 0x1b => {
+                let kind = match value_type {
+                    0x15 => "proto",
+                    0x16 => "method_handle",
+                    0x1a => "method",
+                    _ => "field",
+                };
+                StaticValue::Reference(format!("{}@{}", kind, read_raw(cursor)?.0))
+            }
+            // VALUE_ARRAY
+            0x1c => StaticValue::Array(self.read_encoded_array(cursor, depth + 1)?),
+            // VALUE_ANNOTATION: type_idx, then (name_idx, value) pairs
+            0x1d => {
+                let type_idx = self.read_uleb128(cursor)?;
+                let size = self.read_uleb128(cursor)?;
+                for _ in 0..size {
+                    self.read_uleb128(cursor)?;
+                    self.read_encoded_value(cursor, depth + 1)?;
+                }
+                StaticValue::Reference(format!("annotation@{}", type_idx))
+            }
+            // VALUE_NULL
+            0x1e => StaticValue::Null,
+            // VALUE_BOOLEAN: the value is value_arg itself
+            0x1f => StaticValue::Boolean(value_arg != 0),
+            other => {
+                return Err(DexError::ParseError(format!("Unknown encoded_value type 0x{:02x}", other)));
+            }
+        };
+        Ok(value)
+    }
+
+    /// debug_info_off of the code_item at `code_off`, 0 if it has none
+    pub fn get_debug_info_off(&self, code_off: u32) -> Result<u32> {
+        Ok(self.get_code_item(code_off)?.debug_info_off)
+    }
+
+    /// Run the debug_info state machine and return its line table as
+    /// (address, line) pairs, with addresses in 16-bit code units
+    ///
+    /// Local variable and file events are skipped; only position entries
+    /// (emitted by special opcodes) are recorded.
+    /// ref: https://source.android.com/docs/core/runtime/dex-format#debug-info-item
+    pub fn parse_debug_info(&self, debug_info_off: u32) -> Result<Vec<(u32, u32)>> {
+        if debug_info_off == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut cursor = Cursor::new(&*self.data);
+        cursor.seek(SeekFrom::Start(debug_info_off as u64))?;
+
+        let mut line = self.read_uleb128(&mut cursor)?;
+        let parameters_size = self.read_uleb128(&mut cursor)?;
+        for _ in 0..parameters_size {
+            self.read_uleb128(&mut cursor)?; // parameter name (uleb128p1)
+        }
+
+        let mut address = 0u32;
+        let mut positions = Vec::new();
+        loop {
+            match cursor.read_u8()? {
+                // DBG_END_SEQUENCE
+                0x00 => break,
+                // DBG_ADVANCE_PC
+                0x01 => address = address.wrapping_add(self.read_uleb128(&mut cursor)?),
+                // DBG_ADVANCE_LINE
+                0x02 => line = line.wrapping_add_signed(self.read_sleb128(&mut cursor)?),
+                // DBG_START_LOCAL: register, name, type
+                0x03 => {
+                    for _ in 0..3 {
+                        self.read_uleb128(&mut cursor)?;
+                    }
+                }
+                // DBG_START_LOCAL_EXTENDED: register, name, type, signature
+                0x04 => {
+                    for _ in 0..4 {
+                        self.read_uleb128(&mut cursor)?;
+                    }
+                }
+                // DBG_END_LOCAL, DBG_RESTART_LOCAL: register; DBG_SET_FILE: name
+                0x05 | 0x06 | 0x09 => {
+                    self.read_uleb128(&mut cursor)?;
+                }
+                // DBG_SET_PROLOGUE_END, DBG_SET_EPILOGUE_BEGIN
+                0x07 | 0x08 => {}
+                // Special opcodes advance both registers and emit a position
+                opcode => {
+                    let adjusted = (opcode - 0x0a) as i32;
+                    line = line.wrapping_add_signed(-4 + adjusted % 15);
+                    address = address.wrapping_add((adjusted / 15) as u32);
+                    positions.push((address, line));
+                }
+            }
+        }
+
+        Ok(positions)
+    }
+
+    /// Parse the full code_item at `code_off`: instructions plus try blocks
+    /// and their catch handlers
+    ///
+    /// Returns an empty `CodeItem` for methods without code.
+    /// ref: https://source.android.com/docs/core/runtime/dex-format#code-item
+    pub fn get_code_item(&self, code_off: u32) -> Result<CodeItem> {
+        if code_off == 0 {
+            return Ok(CodeItem::default());
+        }
+
+        let mut cursor = Cursor::new(&*self.data);
         cursor.seek(SeekFrom::Start(code_off as u64))?;
 
-        // Read code_item structure
-        // ref: https://source.android.com/docs/core/runtime/dex-format#code-item
-        let _registers_size = cursor.read_u16::<LittleEndian>()?;  // u16
-        let _ins_size = cursor.read_u16::<LittleEndian>()?;        // u16
-        let _outs_size = cursor.read_u16::<LittleEndian>()?;       // u16
-        let _tries_size = cursor.read_u16::<LittleEndian>()?;      // u16
-        let _debug_info_off = cursor.read_u32::<LittleEndian>()?;  // u32
-        let insns_size = cursor.read_u32::<LittleEndian>()?;       // u32
-
-        // Read bytecode instructions (array of u16)
-        let mut instructions = Vec::new();
+        let registers_size = cursor.read_u16::<LittleEndian>()?;
+        let ins_size = cursor.read_u16::<LittleEndian>()?;
+        let outs_size = cursor.read_u16::<LittleEndian>()?;
+        let tries_size = cursor.read_u16::<LittleEndian>()?;
+        let debug_info_off = cursor.read_u32::<LittleEndian>()?;
+        let insns_size = cursor.read_u32::<LittleEndian>()?;
+
+        let mut insns = Vec::new();
         for _ in 0..insns_size {
-            let insn = cursor.read_u16::<LittleEndian>()?;
-            instructions.push(insn);
+            insns.push(cursor.read_u16::<LittleEndian>()?);
+        }
+
+        let mut code_item = CodeItem {
+            registers_size,
+            ins_size,
+            outs_size,
+            debug_info_off,
+            insns,
+            tries: Vec::new(),
+            handlers: Vec::new(),
+        };
+        if tries_size == 0 {
+            return Ok(code_item);
+        }
+
+        // try_item[] is 4-byte aligned: skip a padding unit after an odd insns_size
+        if insns_size % 2 == 1 {
+            cursor.read_u16::<LittleEndian>()?;
+        }
+
+        let mut raw_tries = Vec::with_capacity(tries_size as usize);
+        for _ in 0..tries_size {
+            let start_addr = cursor.read_u32::<LittleEndian>()?;
+            let insn_count = cursor.read_u16::<LittleEndian>()?;
+            let handler_off = cursor.read_u16::<LittleEndian>()?;
+            raw_tries.push((start_addr, insn_count, handler_off));
+        }
+
+        // encoded_catch_handler_list; try items point at handlers by byte
+        // offset from the start of the list
+        let list_start = cursor.position();
+        let handlers_size = self.read_uleb128(&mut cursor)?;
+        let mut handler_offsets = Vec::new();
+        for _ in 0..handlers_size {
+            handler_offsets.push((cursor.position() - list_start) as u16);
+
+            // Non-positive size: |size| typed catches followed by a catch-all
+            let size = self.read_sleb128(&mut cursor)?;
+            let mut catches = Vec::new();
+            for _ in 0..size.unsigned_abs() {
+                let type_idx = self.read_uleb128(&mut cursor)?;
+                let handler_addr = self.read_uleb128(&mut cursor)?;
+                catches.push(CatchClause {
+                    exception_type: self.get_type_name(type_idx)?,
+                    type_idx,
+                    handler_addr,
+                });
+            }
+            let catch_all_addr = if size <= 0 { Some(self.read_uleb128(&mut cursor)?) } else { None };
+            code_item.handlers.push(CatchHandler { catches, catch_all_addr });
+        }
+
+        for (start_addr, insn_count, handler_off) in raw_tries {
+            let handler_index = handler_offsets.iter().position(|&off| off == handler_off).ok_or_else(|| {
+                DexError::ParseError(format!("try_item handler_off {} matches no catch handler", handler_off))
+            })?;
+            code_item.tries.push(TryItem { start_addr, insn_count, handler_index });
         }
 
-        Ok(instructions)
+        Ok(code_item)
     }
 
     /// Get prototype (method signature) information
@@ -492,7 +813,7 @@ impl DexParser {
 
         // Each proto_id_item is 12 bytes
         let proto_id_offset = self.header.proto_ids_off as usize + (proto_idx as usize * structure::PROTO_ID_SIZE);
-        let mut cursor = Cursor::new(&self.data);
+        let mut cursor = Cursor::new(&*self.data);
         cursor.seek(SeekFrom::Start(proto_id_offset as u64))?;
 
         let _shorty_idx = cursor.read_u32::<LittleEndian>()?;
@@ -515,6 +836,97 @@ impl DexParser {
             parameters,
         })
     }
+
+    /// Format a method reference as "Class.name(params): return"
+    pub fn method_signature(&self, method_idx: u32) -> Result<String> {
+        let method_info = self.get_method_info(method_idx)?;
+        let class_name = self.get_type_name(method_info.class_idx)?;
+        let method_name = self.get_string(method_info.name_idx)?;
+        let proto = self.get_proto_info(method_info.proto_idx)?;
+        let return_type = self.get_type_name(proto.return_type_idx)?;
+
+        let parameters = proto
+            .parameters
+            .iter()
+            .map(|&idx| self.get_type_name(idx))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(format!(
+            "{}.{}({}): {}",
+            class_name,
+            method_name,
+            parameters.join(", "),
+            return_type
+        ))
+    }
+
+    /// Format a field reference as "Class.name: type"
+    pub fn field_signature(&self, field_idx: u32) -> Result<String> {
+        let field_info = self.get_field_info(field_idx)?;
+        let class_name = self.get_type_name(field_info.class_idx)?;
+        let field_name = self.get_string(field_info.name_idx)?;
+        let field_type = self.get_type_name(field_info.type_idx)?;
+
+        Ok(format!("{}.{}: {}", class_name, field_name, field_type))
+    }
+
+    /// Signatures of every entry in method_ids, in index order
+    ///
+    /// Includes methods only referenced from this DEX (e.g. framework APIs),
+    /// not just those defined by its classes.
+    pub fn all_method_signatures(&self) -> Result<Vec<String>> {
+        (0..self.header.method_ids_size)
+            .map(|idx| self.method_signature(idx))
+            .collect()
+    }
+
+    /// Indices into method_ids of methods defined by this DEX's classes
+    ///
+    /// Collected from the direct and virtual method lists in each class_data;
+    /// anything in method_ids outside this set is only referenced here.
+    pub fn defined_method_indices(&self) -> Result<HashSet<u32>> {
+        let mut defined = HashSet::new();
+
+        for class_idx in 0..self.class_count() {
+            let class_def = self.get_class_def(class_idx)?;
+            let class_data = self.parse_class_data(class_def.class_data_off)?;
+
+            defined.extend(
+                class_data
+                    .direct_methods
+                    .iter()
+                    .chain(class_data.virtual_methods.iter())
+                    .map(|method| method.method_idx),
+            );
+        }
+
+        Ok(defined)
+    }
+
+    /// Signatures of methods defined (with code or abstract) in this DEX
+    pub fn defined_method_signatures(&self) -> Result<Vec<String>> {
+        let defined = self.defined_method_indices()?;
+        (0..self.header.method_ids_size)
+            .filter(|idx| defined.contains(idx))
+            .map(|idx| self.method_signature(idx))
+            .collect()
+    }
+
+    /// Signatures of methods referenced by this DEX but defined elsewhere
+    pub fn referenced_method_signatures(&self) -> Result<Vec<String>> {
+        let defined = self.defined_method_indices()?;
+        (0..self.header.method_ids_size)
+            .filter(|idx| !defined.contains(idx))
+            .map(|idx| self.method_signature(idx))
+            .collect()
+    }
+
+    /// Signatures of every entry in field_ids, in index order
+    pub fn all_field_signatures(&self) -> Result<Vec<String>> {
+        (0..self.header.field_ids_size)
+            .map(|idx| self.field_signature(idx))
+            .collect()
+    }
 }
 
 /// Class definition structure
@@ -530,6 +942,146 @@ pub struct ClassDef {
     pub static_values_off: u32,
 }
 
+/// Static field initializer from a class's static_values encoded_array
+#[derive(Debug, Clone, PartialEq)]
+pub enum StaticValue {
+    /// byte, short or int
+    Int(i32),
+    Char(u16),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    /// Class literal (Java type name)
+    Type(String),
+    Boolean(bool),
+    Null,
+    Array(Vec<StaticValue>),
+    /// Unresolved method, field, enum, method handle or annotation, as "kind@index"
+    Reference(String),
+}
+
+impl StaticValue {
+    /// Format as a Java literal, e.g. `"https://example.com"`, `42L`
+    pub fn to_java_literal(&self) -> String {
+        match self {
+            StaticValue::Int(value) => value.to_string(),
+            StaticValue::Char(value) => match char::from_u32(*value as u32) {
+                Some(c) => format!("'{}'", escape_java(&c.to_string(), '\'')),
+                None => format!("'\\u{:04x}'", value),
+            },
+            StaticValue::Long(value) => format!("{}L", value),
+            StaticValue::Float(value) if value.is_finite() => format!("{:?}f", value),
+            StaticValue::Float(value) => non_finite_literal("Float", *value as f64),
+            StaticValue::Double(value) if value.is_finite() => format!("{:?}", value),
+            StaticValue::Double(value) => non_finite_literal("Double", *value),
+            StaticValue::String(value) => format!("\"{}\"", escape_java(value, '"')),
+            StaticValue::Type(name) => format!("{}.class", name),
+            StaticValue::Boolean(value) => value.to_string(),
+            StaticValue::Null => "null".to_string(),
+            StaticValue::Array(values) => {
+                let items: Vec<String> = values.iter().map(StaticValue::to_java_literal).collect();
+                format!("{{{}}}", items.join(", "))
+            }
+            StaticValue::Reference(reference) => reference.clone(),
+        }
+    }
+}
+
+/// Escape `text` for the inside of a Java literal delimited by `quote`
+///
+/// Control characters without a short escape become `\uXXXX`.
+fn escape_java(text: &str, quote: char) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{8}' => escaped.push_str("\\b"),
+            '\u{c}' => escaped.push_str("\\f"),
+            c if c == quote => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// NaN or an infinity as the `Float`/`Double` constant naming it
+fn non_finite_literal(class: &str, value: f64) -> String {
+    if value.is_nan() {
+        format!("{}.NaN", class)
+    } else if value > 0.0 {
+        format!("{}.POSITIVE_INFINITY", class)
+    } else {
+        format!("{}.NEGATIVE_INFINITY", class)
+    }
+}
+
+/// Parsed code_item
+#[derive(Debug, Clone, Default)]
+pub struct CodeItem {
+    pub registers_size: u16,
+    pub ins_size: u16,
+    pub outs_size: u16,
+    pub debug_info_off: u32,
+    pub insns: Vec<u16>,
+    pub tries: Vec<TryItem>,
+    pub handlers: Vec<CatchHandler>,
+}
+
+impl CodeItem {
+    /// Handler guarding the instruction at `address`
+    ///
+    /// Try blocks never overlap; nested Java try statements are flattened
+    /// into one handler listing every enclosing catch.
+    pub fn handler_at(&self, address: u32) -> Option<&CatchHandler> {
+        let try_item = self.tries.iter().find(|try_item| try_item.covers(address))?;
+        self.handlers.get(try_item.handler_index)
+    }
+}
+
+/// Range of instructions guarded by a catch handler
+#[derive(Debug, Clone, PartialEq)]
+pub struct TryItem {
+    /// First covered address, in code units
+    pub start_addr: u32,
+    /// Number of covered code units
+    pub insn_count: u16,
+    /// Index into `CodeItem::handlers`
+    pub handler_index: usize,
+}
+
+impl TryItem {
+    /// Whether the instruction at `address` is inside this try block
+    pub fn covers(&self, address: u32) -> bool {
+        address >= self.start_addr && address - self.start_addr < self.insn_count as u32
+    }
+}
+
+/// Catch clauses of one encoded_catch_handler
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchHandler {
+    /// Typed catches, in the order they are tried
+    pub catches: Vec<CatchClause>,
+    /// Handler for any other Throwable (`catch (Throwable)` / `finally`)
+    pub catch_all_addr: Option<u32>,
+}
+
+/// `catch (ExceptionType e)` jumping to `handler_addr`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchClause {
+    pub type_idx: u32,
+    /// Java name of the caught type, e.g. "java.io.IOException"
+    pub exception_type: String,
+    pub handler_addr: u32,
+}
+
 /// Class data (fields and methods)
 #[derive(Debug, Clone, Default)]
 pub struct ClassData {
@@ -577,14 +1129,69 @@ pub struct ProtoInfo {
     pub parameters: Vec<u32>,
 }
 
+/// Adler-32 as used by the DEX header checksum
+pub(crate) fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    // Largest block whose sums cannot overflow u32 before reduction
+    const BLOCK: usize = 5552;
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for block in data.chunks(BLOCK) {
+        for &byte in block {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD_ADLER;
+        b %= MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
+/// Decode modified UTF-8 as used by the DEX string pool
+///
+/// MUTF-8 encodes NUL as 0xC0 0x80 and supplementary characters as two
+/// 3-byte surrogates, so the bytes are decoded to UTF-16 first. A lone
+/// surrogate is legal in a Java string and becomes U+FFFD.
+fn decode_mutf8(bytes: &[u8]) -> Result<String> {
+    let invalid = |at: usize| DexError::ParseError(format!("Invalid MUTF-8 at byte {}", at));
+    let continuation = |at: usize| match bytes.get(at) {
+        Some(&b) if b & 0xC0 == 0x80 => Ok((b & 0x3F) as u16),
+        _ => Err(invalid(at)),
+    };
+
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match b {
+            0x01..=0x7F => {
+                units.push(b as u16);
+                i += 1;
+            }
+            0xC0..=0xDF => {
+                units.push(((b & 0x1F) as u16) << 6 | continuation(i + 1)?);
+                i += 2;
+            }
+            0xE0..=0xEF => {
+                units.push(((b & 0x0F) as u16) << 12 | continuation(i + 1)? << 6 | continuation(i + 2)?);
+                i += 3;
+            }
+            _ => return Err(invalid(i)),
+        }
+    }
+
+    Ok(char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dex::test_support::sample_dex;
 
     #[test]
     fn test_descriptor_conversion() {
         let parser = DexParser {
-            data: vec![],
+            data: Arc::new(vec![]),
             header: unsafe { std::mem::zeroed() },
         };
 
@@ -594,4 +1201,225 @@ mod tests {
         assert_eq!(parser.descriptor_to_java_type("[I"), "int[]");
         assert_eq!(parser.descriptor_to_java_type("[[Ljava/lang/String;"), "java.lang.String[][]");
     }
+
+    #[test]
+    fn test_all_method_and_field_signatures() {
+        let parser = DexParser::new(sample_dex()).unwrap();
+
+        let methods = parser.all_method_signatures().unwrap();
+        assert_eq!(
+            methods,
+            vec![
+                "android.app.Activity.<init>(): void",
+                "com.example.MainActivity.<init>(): void",
+                "com.example.MainActivity.onCreate(android.os.Bundle): void",
+                "com.example.Helper.log(java.lang.String): void",
+            ]
+        );
+
+        let fields = parser.all_field_signatures().unwrap();
+        assert_eq!(fields, vec!["com.example.Helper.TAG: java.lang.String"]);
+    }
+
+    #[test]
+    fn test_defined_vs_referenced_methods() {
+        let parser = DexParser::new(sample_dex()).unwrap();
+
+        // Activity.<init> is called from MainActivity.<init> but has no
+        // class_data entry in this DEX
+        assert_eq!(
+            parser.referenced_method_signatures().unwrap(),
+            vec!["android.app.Activity.<init>(): void"]
+        );
+        assert_eq!(
+            parser.defined_method_signatures().unwrap(),
+            vec![
+                "com.example.MainActivity.<init>(): void",
+                "com.example.MainActivity.onCreate(android.os.Bundle): void",
+                "com.example.Helper.log(java.lang.String): void",
+            ]
+        );
+        assert_eq!(
+            parser.defined_method_indices().unwrap(),
+            HashSet::from([1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_parse_debug_info() {
+        let mut data = sample_dex();
+        let debug_info_off = data.len() as u32;
+        data.extend_from_slice(&[
+            0x0a, // line_start = 10
+            0x01, 0x00, // one parameter, unnamed (uleb128p1 -1)
+            0x07, // DBG_SET_PROLOGUE_END
+            0x0e, // special: line +0, address +0 -> (0, 10)
+            0x03, 0x01, 0x05, 0x02, // DBG_START_LOCAL v1, name@4, type@1
+            0x2e, // special: adjusted 36 -> line +2, address +2 -> (2, 12)
+            0x01, 0x03, // DBG_ADVANCE_PC +3
+            0x02, 0x7d, // DBG_ADVANCE_LINE -3
+            0x0e, // special -> (5, 9)
+            0x02, 0x80, 0x01, // DBG_ADVANCE_LINE +128
+            0x1e, // special: adjusted 20 -> line +1, address +1 -> (6, 138)
+            0x00, // DBG_END_SEQUENCE
+        ]);
+        let parser = DexParser::new(data).unwrap();
+
+        assert_eq!(
+            parser.parse_debug_info(debug_info_off).unwrap(),
+            vec![(0, 10), (2, 12), (5, 9), (6, 138)]
+        );
+        assert!(parser.parse_debug_info(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_sleb128() {
+        let parser = DexParser::new(sample_dex()).unwrap();
+        let cases: &[(&[u8], i32)] = &[
+            (&[0x00], 0),
+            (&[0x01], 1),
+            (&[0x7f], -1),
+            (&[0x40], -64),
+            (&[0x80, 0x7f], -128),
+            (&[0xff, 0x00], 127),
+            (&[0x80, 0x80, 0x80, 0x80, 0x78], i32::MIN),
+            (&[0xff, 0xff, 0xff, 0xff, 0x07], i32::MAX),
+        ];
+        for (bytes, expected) in cases {
+            let data = bytes.to_vec();
+            assert_eq!(parser.read_sleb128(&mut Cursor::new(&data)).unwrap(), *expected, "{:02x?}", bytes);
+        }
+        let too_long = vec![0x80; 6];
+        assert!(parser.read_sleb128(&mut Cursor::new(&too_long)).is_err());
+    }
+
+    #[test]
+    fn test_parse_static_values() {
+        let mut builder = crate::dex::test_support::DexBuilder::new();
+        let url = builder.string("https://example.com");
+        let mut data = builder.build();
+        let static_values_off = data.len() as u32;
+        data.extend_from_slice(&[
+            0x07, // 7 values
+            0x17, url as u8, // VALUE_STRING, 1 byte
+            0x24, 0xfe, 0xff, // VALUE_INT, 2 bytes: -2
+            0x46, 0x00, 0x00, 0x80, // VALUE_LONG, 3 bytes: -0x800000
+            0x3f, // VALUE_BOOLEAN true
+            0x1e, // VALUE_NULL
+            0x10, 0x40, // VALUE_FLOAT, 1 byte: 0x40000000 = 2.0
+            0x1c, 0x02, 0x00, 0x01, 0x1f, // VALUE_ARRAY {1, false}
+        ]);
+        let parser = DexParser::new(data).unwrap();
+        let values = parser.parse_static_values(static_values_off).unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                StaticValue::String("https://example.com".to_string()),
+                StaticValue::Int(-2),
+                StaticValue::Long(-0x80_0000),
+                StaticValue::Boolean(true),
+                StaticValue::Null,
+                StaticValue::Float(2.0),
+                StaticValue::Array(vec![StaticValue::Int(1), StaticValue::Boolean(false)]),
+            ]
+        );
+        assert_eq!(values[0].to_java_literal(), "\"https://example.com\"");
+        assert_eq!(values[2].to_java_literal(), "-8388608L");
+        assert_eq!(values[6].to_java_literal(), "{1, false}");
+        assert_eq!(values[5].to_java_literal(), "2.0f");
+
+        // Java escapes, not Rust's
+        let quoted = StaticValue::String("say \"hi\"\n\u{1}é".to_string());
+        assert_eq!(quoted.to_java_literal(), r#""say \"hi\"\n\u0001é""#);
+        assert_eq!(StaticValue::Char(b'\'' as u16).to_java_literal(), r"'\''");
+        assert_eq!(StaticValue::Char(b'"' as u16).to_java_literal(), "'\"'");
+        assert_eq!(StaticValue::Char(0xd800).to_java_literal(), r"'\ud800'");
+        assert_eq!(StaticValue::Float(f32::NAN).to_java_literal(), "Float.NaN");
+        assert_eq!(StaticValue::Double(f64::NEG_INFINITY).to_java_literal(), "Double.NEGATIVE_INFINITY");
+        assert_eq!(StaticValue::Double(0.1).to_java_literal(), "0.1");
+        assert!(parser.parse_static_values(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_code_item_try_catch() {
+        let mut builder = crate::dex::test_support::DexBuilder::new();
+        let io_exception = builder.type_id("Ljava/io/IOException;");
+        let mut data = builder.build();
+        while !data.len().is_multiple_of(4) {
+            data.push(0);
+        }
+        let code_off = data.len() as u32;
+
+        // registers, ins, outs, tries_size = 2, debug_info_off, insns_size = 5
+        for value in [3u16, 1, 1, 2] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&5u32.to_le_bytes());
+        for insn in [0x0000u16, 0x0000, 0x000e, 0x000e, 0x000e] {
+            data.extend_from_slice(&insn.to_le_bytes());
+        }
+        data.extend_from_slice(&[0, 0]); // padding after odd insns_size
+        // try 0..2 -> handler at +1, try 2..3 -> handler at +4
+        data.extend_from_slice(&[0, 0, 0, 0, 2, 0, 1, 0]);
+        data.extend_from_slice(&[2, 0, 0, 0, 1, 0, 4, 0]);
+        data.extend_from_slice(&[
+            0x02, // two handlers
+            0x01, io_exception as u8, 0x03, // catch (IOException) -> 3
+            0x00, 0x04, // catch-all only -> 4
+        ]);
+        let parser = DexParser::new(data).unwrap();
+        let code = parser.get_code_item(code_off).unwrap();
+
+        assert_eq!(code.insns.len(), 5);
+        assert_eq!(code.tries.len(), 2);
+        assert_eq!(code.tries[0], TryItem { start_addr: 0, insn_count: 2, handler_index: 0 });
+        assert!(code.tries[0].covers(1) && !code.tries[0].covers(2));
+        assert_eq!(code.handlers[0].catches[0].exception_type, "java.io.IOException");
+        assert_eq!(code.handlers[0].catches[0].handler_addr, 3);
+        assert_eq!(code.handlers[0].catch_all_addr, None);
+        assert_eq!(code.handlers[1], CatchHandler { catches: Vec::new(), catch_all_addr: Some(4) });
+
+        assert_eq!(code.handler_at(1), Some(&code.handlers[0]));
+        assert_eq!(code.handler_at(2), Some(&code.handlers[1]));
+        assert_eq!(code.handler_at(3), None);
+        assert_eq!(parser.get_code_item(code_off).unwrap().insns, parser.get_method_bytecode(code_off).unwrap());
+    }
+
+    #[test]
+    fn test_parsers_share_buffer() {
+        let data = Arc::new(sample_dex());
+        let parser = DexParser::from_arc(Arc::clone(&data)).unwrap();
+        let copy = parser.clone();
+        assert_eq!(Arc::strong_count(&data), 3);
+        assert_eq!(copy.get_string(0).unwrap(), parser.get_string(0).unwrap());
+
+        drop((parser, copy));
+        assert_eq!(Arc::strong_count(&data), 1);
+    }
+
+    #[test]
+    fn test_adler32() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        // Long enough to need the block-wise reduction
+        assert_eq!(adler32(&vec![0xFF; 100_000]), 0x149A_302C);
+    }
+
+    #[test]
+    fn test_mutf8_decoding() {
+        // Embedded NUL in its two-byte form
+        assert_eq!(decode_mutf8(&[b'a', 0xC0, 0x80, b'b']).unwrap(), "a\0b");
+        // 3-byte BMP character
+        assert_eq!(decode_mutf8(&[0xE4, 0xB8, 0xAD, 0xE6, 0x96, 0x87]).unwrap(), "中文");
+        // U+1F600 as the surrogate pair D83D DE00, each 3 bytes
+        assert_eq!(decode_mutf8(&[0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80]).unwrap(), "\u{1F600}");
+        // Lone surrogate
+        assert_eq!(decode_mutf8(&[0xED, 0xA0, 0xBD, b'x']).unwrap(), "\u{FFFD}x");
+
+        // Standard UTF-8 4-byte form and truncated sequences are rejected
+        assert!(decode_mutf8(&[0xF0, 0x9F, 0x98, 0x80]).is_err());
+        assert!(decode_mutf8(&[0xE4, 0xB8]).is_err());
+    }
 }
@@ -0,0 +1,533 @@
+//! Test helpers for building small in-memory DEX files and APKs
+//!
+//! Only compiled for tests. The builder lays out a minimal but valid DEX
+//! (header, id tables, class defs, class data and code items) that
+//! `DexParser` can read back.
+
+#![allow(dead_code)]
+
+use crate::dex::constants::structure;
+use crate::dex::parser::adler32;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Method entry inside a class definition
+struct MethodSpec {
+    method_idx: u32,
+    access_flags: u32,
+    registers: u16,
+    ins: u16,
+    code: Option<Vec<u16>>,
+    /// (start_addr, insn_count, catch type_idx, handler_addr) try blocks
+    tries: Vec<(u32, u16, u32, u32)>,
+}
+
+/// Class definition being built
+struct ClassSpec {
+    type_idx: u32,
+    access_flags: u32,
+    superclass_idx: u32,
+    static_fields: Vec<(u32, u32)>,
+    instance_fields: Vec<(u32, u32)>,
+    direct_methods: Vec<MethodSpec>,
+    virtual_methods: Vec<MethodSpec>,
+    /// Encoded static_values array, if any
+    static_values: Option<Vec<u8>>,
+}
+
+/// Builder for synthetic DEX files
+#[derive(Default)]
+pub struct DexBuilder {
+    strings: Vec<String>,
+    string_index: HashMap<String, u32>,
+    types: Vec<u32>,
+    type_index: HashMap<String, u32>,
+    protos: Vec<(u32, u32, Vec<u32>)>,
+    fields: Vec<(u32, u32, u32)>,
+    methods: Vec<(u32, u32, u32)>,
+    classes: Vec<ClassSpec>,
+}
+
+impl DexBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intern a string and return its index
+    pub fn string(&mut self, value: &str) -> u32 {
+        if let Some(&idx) = self.string_index.get(value) {
+            return idx;
+        }
+        let idx = self.strings.len() as u32;
+        self.strings.push(value.to_string());
+        self.string_index.insert(value.to_string(), idx);
+        idx
+    }
+
+    /// Intern a type descriptor (e.g. "Lcom/example/Foo;") and return its index
+    pub fn type_id(&mut self, descriptor: &str) -> u32 {
+        if let Some(&idx) = self.type_index.get(descriptor) {
+            return idx;
+        }
+        let string_idx = self.string(descriptor);
+        let idx = self.types.len() as u32;
+        self.types.push(string_idx);
+        self.type_index.insert(descriptor.to_string(), idx);
+        idx
+    }
+
+    /// Add a prototype and return its index
+    pub fn proto(&mut self, return_type: &str, parameters: &[&str]) -> u32 {
+        let shorty: String = std::iter::once(return_type)
+            .chain(parameters.iter().copied())
+            .map(|d| match d.chars().next() {
+                Some('L') | Some('[') => 'L',
+                Some(c) => c,
+                None => 'V',
+            })
+            .collect();
+        let shorty_idx = self.string(&shorty);
+        let return_idx = self.type_id(return_type);
+        let params: Vec<u32> = parameters.iter().map(|p| self.type_id(p)).collect();
+
+        if let Some(pos) = self
+            .protos
+            .iter()
+            .position(|(_, r, p)| *r == return_idx && *p == params)
+        {
+            return pos as u32;
+        }
+
+        self.protos.push((shorty_idx, return_idx, params));
+        (self.protos.len() - 1) as u32
+    }
+
+    /// Add a field reference and return its index
+    pub fn field(&mut self, class: &str, field_type: &str, name: &str) -> u32 {
+        let class_idx = self.type_id(class);
+        let type_idx = self.type_id(field_type);
+        let name_idx = self.string(name);
+        self.fields.push((class_idx, type_idx, name_idx));
+        (self.fields.len() - 1) as u32
+    }
+
+    /// Add a method reference and return its index
+    pub fn method(&mut self, class: &str, name: &str, return_type: &str, parameters: &[&str]) -> u32 {
+        let class_idx = self.type_id(class);
+        let proto_idx = self.proto(return_type, parameters);
+        let name_idx = self.string(name);
+        self.methods.push((class_idx, proto_idx, name_idx));
+        (self.methods.len() - 1) as u32
+    }
+
+    /// Add a class definition and return its position in the class_defs table
+    pub fn class(&mut self, descriptor: &str, superclass: Option<&str>, access_flags: u32) -> usize {
+        let type_idx = self.type_id(descriptor);
+        let superclass_idx = match superclass {
+            Some(s) => self.type_id(s),
+            None => structure::NO_INDEX,
+        };
+        self.classes.push(ClassSpec {
+            type_idx,
+            access_flags,
+            superclass_idx,
+            static_fields: Vec::new(),
+            instance_fields: Vec::new(),
+            direct_methods: Vec::new(),
+            virtual_methods: Vec::new(),
+            static_values: None,
+        });
+        self.classes.len() - 1
+    }
+
+    pub fn static_field(&mut self, class: usize, field_idx: u32, access_flags: u32) {
+        self.classes[class].static_fields.push((field_idx, access_flags));
+    }
+
+    /// Set a class's static field initializers as a raw encoded_array
+    /// (uleb128 count, then encoded_values in static field order)
+    pub fn static_values(&mut self, class: usize, encoded_array: &[u8]) {
+        self.classes[class].static_values = Some(encoded_array.to_vec());
+    }
+
+    pub fn instance_field(&mut self, class: usize, field_idx: u32, access_flags: u32) {
+        self.classes[class].instance_fields.push((field_idx, access_flags));
+    }
+
+    /// Add a direct method (constructor, static or private) with optional code
+    pub fn direct_method(&mut self, class: usize, method_idx: u32, access_flags: u32, code: Option<Vec<u16>>) {
+        let ins = self.ins_size(method_idx, access_flags & 0x0008 != 0);
+        self.classes[class].direct_methods.push(MethodSpec {
+            method_idx,
+            access_flags,
+            registers: 16,
+            ins,
+            code,
+            tries: Vec::new(),
+        });
+    }
+
+    /// Add a virtual method with optional code
+    pub fn virtual_method(&mut self, class: usize, method_idx: u32, access_flags: u32, code: Option<Vec<u16>>) {
+        let ins = self.ins_size(method_idx, false);
+        self.classes[class].virtual_methods.push(MethodSpec {
+            method_idx,
+            access_flags,
+            registers: 16,
+            ins,
+            code,
+            tries: Vec::new(),
+        });
+    }
+
+    /// Guard `insn_count` code units of a method's code from `start_addr`
+    /// with one typed catch jumping to `handler_addr`
+    pub fn try_catch(&mut self, class: usize, method_idx: u32, start_addr: u32, insn_count: u16, catch_type: u32, handler_addr: u32) {
+        let class = &mut self.classes[class];
+        let method = class
+            .direct_methods
+            .iter_mut()
+            .chain(class.virtual_methods.iter_mut())
+            .find(|method| method.method_idx == method_idx)
+            .expect("method added to the class");
+        method.tries.push((start_addr, insn_count, catch_type, handler_addr));
+    }
+
+    /// Registers taken by a method's arguments, including `this` unless static
+    fn ins_size(&self, method_idx: u32, is_static: bool) -> u16 {
+        let (_, proto_idx, _) = self.methods[method_idx as usize];
+        let (_, _, parameters) = &self.protos[proto_idx as usize];
+        let slots: u16 = parameters
+            .iter()
+            .map(|&type_idx| match self.strings[self.types[type_idx as usize] as usize].as_str() {
+                "J" | "D" => 2,
+                _ => 1,
+            })
+            .sum();
+        slots + u16::from(!is_static)
+    }
+
+    /// Serialize to DEX bytes
+    pub fn build(&self) -> Vec<u8> {
+        let header_size = structure::HEADER_SIZE as usize;
+        let string_ids_off = header_size;
+        let type_ids_off = string_ids_off + self.strings.len() * structure::STRING_ID_SIZE;
+        let proto_ids_off = type_ids_off + self.types.len() * structure::TYPE_ID_SIZE;
+        let field_ids_off = proto_ids_off + self.protos.len() * structure::PROTO_ID_SIZE;
+        let method_ids_off = field_ids_off + self.fields.len() * structure::FIELD_ID_SIZE;
+        let class_defs_off = method_ids_off + self.methods.len() * structure::METHOD_ID_SIZE;
+        let data_off = class_defs_off + self.classes.len() * structure::CLASS_DEF_SIZE;
+
+        let mut data: Vec<u8> = Vec::new();
+        let at = |data: &Vec<u8>| (data_off + data.len()) as u32;
+
+        // String data
+        let mut string_offsets = Vec::new();
+        for s in &self.strings {
+            string_offsets.push(at(&data));
+            write_uleb128(&mut data, s.encode_utf16().count() as u32);
+            data.extend_from_slice(s.as_bytes());
+            data.push(0);
+        }
+
+        // Parameter type lists
+        let mut param_offsets = Vec::new();
+        for (_, _, params) in &self.protos {
+            if params.is_empty() {
+                param_offsets.push(0);
+                continue;
+            }
+            align4(&mut data, data_off);
+            param_offsets.push(at(&data));
+            data.extend_from_slice(&(params.len() as u32).to_le_bytes());
+            for p in params {
+                data.extend_from_slice(&(*p as u16).to_le_bytes());
+            }
+        }
+
+        // Code items
+        let mut code_offsets: Vec<Vec<(u32, u32)>> = Vec::new();
+        for class in &self.classes {
+            let mut offsets = Vec::new();
+            for method in class.direct_methods.iter().chain(class.virtual_methods.iter()) {
+                let off = match &method.code {
+                    Some(code) => {
+                        align4(&mut data, data_off);
+                        let off = at(&data);
+                        data.extend_from_slice(&method.registers.to_le_bytes());
+                        data.extend_from_slice(&method.ins.to_le_bytes());
+                        data.extend_from_slice(&4u16.to_le_bytes()); // outs_size
+                        data.extend_from_slice(&(method.tries.len() as u16).to_le_bytes());
+                        data.extend_from_slice(&0u32.to_le_bytes()); // debug_info_off
+                        data.extend_from_slice(&(code.len() as u32).to_le_bytes());
+                        for word in code {
+                            data.extend_from_slice(&word.to_le_bytes());
+                        }
+                        if !method.tries.is_empty() {
+                            write_tries(&mut data, code.len(), &method.tries);
+                        }
+                        off
+                    }
+                    None => 0,
+                };
+                offsets.push((method.method_idx, off));
+            }
+            code_offsets.push(offsets);
+        }
+
+        // Class data
+        let mut class_data_offsets = Vec::new();
+        for (class, offsets) in self.classes.iter().zip(code_offsets.iter()) {
+            let off = at(&data);
+            write_uleb128(&mut data, class.static_fields.len() as u32);
+            write_uleb128(&mut data, class.instance_fields.len() as u32);
+            write_uleb128(&mut data, class.direct_methods.len() as u32);
+            write_uleb128(&mut data, class.virtual_methods.len() as u32);
+
+            for fields in [&class.static_fields, &class.instance_fields] {
+                let mut sorted = fields.clone();
+                sorted.sort();
+                let mut prev = 0;
+                for (idx, flags) in sorted {
+                    write_uleb128(&mut data, idx - prev);
+                    write_uleb128(&mut data, flags);
+                    prev = idx;
+                }
+            }
+
+            let direct_count = class.direct_methods.len();
+            for (methods, code) in [
+                (&class.direct_methods, &offsets[..direct_count]),
+                (&class.virtual_methods, &offsets[direct_count..]),
+            ] {
+                let mut sorted: Vec<(u32, u32, u32)> = methods
+                    .iter()
+                    .zip(code.iter())
+                    .map(|(m, (_, off))| (m.method_idx, m.access_flags, *off))
+                    .collect();
+                sorted.sort();
+                let mut prev = 0;
+                for (idx, flags, off) in sorted {
+                    write_uleb128(&mut data, idx - prev);
+                    write_uleb128(&mut data, flags);
+                    write_uleb128(&mut data, off);
+                    prev = idx;
+                }
+            }
+            class_data_offsets.push(off);
+        }
+
+        // Static values
+        let static_values_offsets: Vec<u32> = self
+            .classes
+            .iter()
+            .map(|class| match &class.static_values {
+                Some(encoded) => {
+                    let off = at(&data);
+                    data.extend_from_slice(encoded);
+                    off
+                }
+                None => 0,
+            })
+            .collect();
+
+        let file_size = data_off + data.len();
+        let mut out = Vec::with_capacity(file_size);
+
+        // Header
+        out.extend_from_slice(b"dex\n035\0");
+        out.extend_from_slice(&0u32.to_le_bytes()); // checksum
+        out.extend_from_slice(&[0u8; 20]); // signature
+        for value in [
+            file_size as u32,
+            structure::HEADER_SIZE,
+            structure::ENDIAN_CONSTANT,
+            0, // link_size
+            0, // link_off
+            0, // map_off
+            self.strings.len() as u32,
+            string_ids_off as u32,
+            self.types.len() as u32,
+            type_ids_off as u32,
+            self.protos.len() as u32,
+            proto_ids_off as u32,
+            self.fields.len() as u32,
+            field_ids_off as u32,
+            self.methods.len() as u32,
+            method_ids_off as u32,
+            self.classes.len() as u32,
+            class_defs_off as u32,
+            data.len() as u32,
+            data_off as u32,
+        ] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+
+        for off in &string_offsets {
+            out.extend_from_slice(&off.to_le_bytes());
+        }
+        for string_idx in &self.types {
+            out.extend_from_slice(&string_idx.to_le_bytes());
+        }
+        for ((shorty, ret, _), params_off) in self.protos.iter().zip(param_offsets.iter()) {
+            out.extend_from_slice(&shorty.to_le_bytes());
+            out.extend_from_slice(&ret.to_le_bytes());
+            out.extend_from_slice(&params_off.to_le_bytes());
+        }
+        for (class_idx, type_idx, name_idx) in &self.fields {
+            out.extend_from_slice(&(*class_idx as u16).to_le_bytes());
+            out.extend_from_slice(&(*type_idx as u16).to_le_bytes());
+            out.extend_from_slice(&name_idx.to_le_bytes());
+        }
+        for (class_idx, proto_idx, name_idx) in &self.methods {
+            out.extend_from_slice(&(*class_idx as u16).to_le_bytes());
+            out.extend_from_slice(&(*proto_idx as u16).to_le_bytes());
+            out.extend_from_slice(&name_idx.to_le_bytes());
+        }
+        for ((class, class_data_off), static_values_off) in
+            self.classes.iter().zip(class_data_offsets.iter()).zip(static_values_offsets.iter())
+        {
+            for value in [
+                class.type_idx,
+                class.access_flags,
+                class.superclass_idx,
+                0, // interfaces_off
+                structure::NO_INDEX, // source_file_idx
+                0, // annotations_off
+                *class_data_off,
+                *static_values_off,
+            ] {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        out.extend_from_slice(&data);
+
+        // Signature first: the checksum covers it
+        let signature: [u8; 20] = Sha1::digest(&out[32..]).into();
+        out[12..32].copy_from_slice(&signature);
+        let checksum = adler32(&out[12..]);
+        out[8..12].copy_from_slice(&checksum.to_le_bytes());
+        out
+    }
+}
+
+fn write_uleb128(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Write try_items and their handlers after an instruction array of
+/// `insns_len` code units, one single-catch handler per try block
+fn write_tries(data: &mut Vec<u8>, insns_len: usize, tries: &[(u32, u16, u32, u32)]) {
+    if insns_len % 2 == 1 {
+        data.extend_from_slice(&[0, 0]); // pad to 4 bytes before the tries
+    }
+
+    let mut handlers = Vec::new();
+    write_uleb128(&mut handlers, tries.len() as u32);
+    let mut handler_offsets = Vec::new();
+    for &(_, _, catch_type, handler_addr) in tries {
+        handler_offsets.push(handlers.len() as u16);
+        handlers.push(0x01); // sleb128 size: one typed catch, no catch-all
+        write_uleb128(&mut handlers, catch_type);
+        write_uleb128(&mut handlers, handler_addr);
+    }
+
+    for (&(start_addr, insn_count, _, _), handler_off) in tries.iter().zip(handler_offsets) {
+        data.extend_from_slice(&start_addr.to_le_bytes());
+        data.extend_from_slice(&insn_count.to_le_bytes());
+        data.extend_from_slice(&handler_off.to_le_bytes());
+    }
+    data.extend_from_slice(&handlers);
+}
+
+fn align4(data: &mut Vec<u8>, base: usize) {
+    while !(base + data.len()).is_multiple_of(4) {
+        data.push(0);
+    }
+}
+
+/// Write an APK (ZIP) containing the given DEX files and a placeholder manifest
+///
+/// DEX files are named classes.dex, classes2.dex, ... in order. The file is
+/// created in the system temp directory under a name derived from `name`.
+pub fn write_test_apk(name: &str, dex_files: &[Vec<u8>]) -> PathBuf {
+    write_test_apk_with_entries(name, dex_files, &[("AndroidManifest.xml", b"".as_slice())])
+}
+
+/// Write an APK containing the given DEX files plus arbitrary extra entries
+pub fn write_test_apk_with_entries(name: &str, dex_files: &[Vec<u8>], entries: &[(&str, &[u8])]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("playfast-test-{}-{}.apk", name, std::process::id()));
+    let file = std::fs::File::create(&path).expect("create test apk");
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default();
+
+    for (name, data) in entries {
+        zip.start_file(*name, options).expect("start entry");
+        zip.write_all(data).expect("write entry");
+    }
+    for (i, dex) in dex_files.iter().enumerate() {
+        let dex_name = if i == 0 {
+            "classes.dex".to_string()
+        } else {
+            format!("classes{}.dex", i + 1)
+        };
+        zip.start_file(dex_name, options).expect("start dex entry");
+        zip.write_all(dex).expect("write dex entry");
+    }
+
+    zip.finish().expect("finish test apk");
+    path
+}
+
+/// A small two-class DEX used across tests
+///
+/// - `com.example.MainActivity extends android.app.Activity`
+///   - `<init>()V` (direct): invoke-direct {v0}, Activity.<init>; return-void
+///   - `onCreate(Landroid/os/Bundle;)V` (virtual): const-string v0, "hello";
+///     invoke-static {v0}, Helper.log(String); return-void
+/// - `com.example.Helper extends java.lang.Object`
+///   - `log(Ljava/lang/String;)V` (direct, static): return-void
+///   - static field `TAG: String`
+pub fn sample_dex() -> Vec<u8> {
+    let mut b = DexBuilder::new();
+    let main = "Lcom/example/MainActivity;";
+    let helper = "Lcom/example/Helper;";
+
+    let activity_init = b.method("Landroid/app/Activity;", "<init>", "V", &[]);
+    let main_init = b.method(main, "<init>", "V", &[]);
+    let on_create = b.method(main, "onCreate", "V", &["Landroid/os/Bundle;"]);
+    let log = b.method(helper, "log", "V", &["Ljava/lang/String;"]);
+    let tag = b.field(helper, "Ljava/lang/String;", "TAG");
+    let hello = b.string("hello");
+
+    let main_class = b.class(main, Some("Landroid/app/Activity;"), 0x0001);
+    b.direct_method(
+        main_class,
+        main_init,
+        0x10001,
+        Some(vec![0x1070, activity_init as u16, 0x0000, 0x000e]),
+    );
+    b.virtual_method(
+        main_class,
+        on_create,
+        0x0004,
+        Some(vec![0x001a, hello as u16, 0x1071, log as u16, 0x0000, 0x000e]),
+    );
+
+    let helper_class = b.class(helper, Some("Ljava/lang/Object;"), 0x0001);
+    b.direct_method(helper_class, log, 0x0009, Some(vec![0x000e]));
+    b.static_field(helper_class, tag, 0x0019);
+
+    b.build()
+}
@@ -23,6 +23,9 @@ pub enum PlayfastError {
     #[error("JSON parsing failed: {0}")]
     JsonError(#[from] serde_json::Error),
 
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
     #[error("Regex error: {0}")]
     RegexError(#[from] regex::Error),
 
@@ -181,6 +184,7 @@ mod tests {
                 PlayfastError::RateLimitError |
                 PlayfastError::JsonError(_) |
                 PlayfastError::HttpError(_) |
+                PlayfastError::IoError(_) |
                 PlayfastError::RegexError(_) |
                 PlayfastError::DexError(_) |
                 PlayfastError::ApkError(_) |
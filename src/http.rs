@@ -1,15 +1,25 @@
 use crate::error::{PlayfastError, Result};
 use crate::models::{RustAppInfo, RustReview, RustSearchResult};
-use crate::parser::{parse_app_page, parse_search_results, parse_batchexecute_list_response, parse_batchexecute_reviews_response};
+use crate::parser::{parse_app_page, parse_search_results, parse_batchexecute_list_response, parse_batchexecute_list_page_response, parse_batchexecute_reviews_response, parse_batchexecute_similar_response};
+use std::io::Write;
+use std::path::Path;
 use std::time::Duration;
 
+/// Largest page the list endpoint serves; larger `num` values are clamped
+pub const LIST_MAX_PAGE_SIZE: u32 = 250;
+
+/// Upper bound on list pages fetched when `num` exceeds one page
+const LIST_MAX_PAGES: usize = 20;
+
 /// Build request body for batchexecute list API (optimized for minimal allocations)
 /// This is a standalone function that can be used by Python for async HTTP + Rust parsing
+///
+/// `num` is clamped to `LIST_MAX_PAGE_SIZE`.
 pub fn build_list_request_body(category: Option<&str>, collection: &str, num: u32) -> String {
     let template = include_str!("freq_template.txt");
 
     // Pre-convert values once to avoid multiple conversions
-    let num_str = num.to_string();
+    let num_str = num.min(LIST_MAX_PAGE_SIZE).to_string();
     let category_str = category.unwrap_or("APPLICATION");
 
     // Pre-allocate with extra capacity for replaced values
@@ -52,6 +62,47 @@ pub fn build_list_request_body(category: Option<&str>, collection: &str, num: u3
     result
 }
 
+/// Percent-encode a caller-supplied value (continuation token, package id)
+/// for a form-encoded `f.req` body
+///
+/// Tokens are base64 and may contain `+`, `/` and `=`, which a form decoder
+/// would otherwise turn into a space or treat as a separator.
+fn encode_form_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len() + 8);
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Build request body for one page of the batchexecute list API
+///
+/// Without a token this is the same body as `build_list_request_body`. With a
+/// token, it is appended to the `[20, num]` paging tuple, which is where the
+/// similar-apps request carries its token as well.
+pub fn build_list_page_request_body(
+    category: Option<&str>,
+    collection: &str,
+    num: u32,
+    continuation_token: Option<&str>,
+) -> String {
+    let num = num.min(LIST_MAX_PAGE_SIZE);
+    let body = build_list_request_body(category, collection, num);
+
+    match continuation_token {
+        Some(token) => body.replacen(
+            &format!("%5B8%2C%5B20%2C{}%5D%5D", num),
+            &format!("%5B8%2C%5B20%2C{}%2C%5C%22{}%5C%22%5D%5D", num, encode_form_value(token)),
+            1,
+        ),
+        None => body,
+    }
+}
+
 /// Build request body for batchexecute reviews API (optimized for minimal allocations)
 /// This is a standalone function that can be used by Python for async HTTP + Rust parsing
 pub fn build_reviews_request_body(
@@ -103,6 +154,145 @@ pub fn build_reviews_request_body(
     result
 }
 
+/// Build request body for batchexecute similar-apps API
+///
+/// This is the dedicated "similar apps" cluster request for a package, which
+/// pages through the full list rather than the handful shown on the app page.
+pub fn build_similar_request_body(app_id: &str, num: u32, continuation_token: Option<&str>) -> String {
+    let token_value = match continuation_token {
+        Some(t) => format!("%5C%22{}%5C%22", encode_form_value(t)),
+        None => "null".to_string(),
+    };
+
+    format!(
+        "f.req=%5B%5B%5B%22ag2B9c%22%2C%22%5B%5Bnull%2C%5B%5C%22{}%5C%22%2C7%5D%2Cnull%2C\
+         %5B%5B3%2C%5B{}%2C{}%5D%5D%5D%5D%2C%5Btrue%5D%5D%22%2Cnull%2C%22generic%22%5D%5D%5D",
+        encode_form_value(app_id), num, token_value
+    )
+}
+
+/// Extract the package id from a Play Store details URL
+///
+/// Handles locale path prefixes (`/intl/en_us/store/apps/details`), missing
+/// scheme and extra query parameters. Returns `None` for non-Play URLs or URLs
+/// without an `id` parameter.
+pub fn parse_play_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let without_scheme = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+
+    let (location, query) = without_scheme.split_once('?')?;
+    let (host, path) = location.split_once('/')?;
+    if host != "play.google.com" {
+        return None;
+    }
+
+    // Optional locale prefix, e.g. "intl/en_us/store/apps/details"
+    let path = path.trim_end_matches('/');
+    let path = match path.strip_prefix("intl/") {
+        Some(rest) => rest.split_once('/')?.1,
+        None => path,
+    };
+    if path != "store/apps/details" {
+        return None;
+    }
+
+    query
+        .split('#')
+        .next()?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("id="))
+        .filter(|id| !id.is_empty())
+        .map(|id| id.to_string())
+}
+
+/// Validate an app ID, accepting a full Play Store details URL as a convenience
+///
+/// `https://play.google.com/store/apps/details?id=com.example.app&hl=en` is
+/// reduced to `com.example.app`. The result must follow the Android package-name
+/// grammar: at least two dot-separated segments, each starting with a letter and
+/// containing only letters, digits and underscores.
+pub fn normalize_app_id(app_id: &str) -> Result<String> {
+    let trimmed = app_id.trim();
+
+    let candidate = if trimmed.contains("://") || trimmed.starts_with("play.google.com") {
+        parse_play_url(trimmed)
+            .ok_or_else(|| PlayfastError::InvalidAppId(format!("Not a Play Store details URL: {}", trimmed)))?
+    } else {
+        trimmed.to_string()
+    };
+
+    if is_valid_package_name(&candidate) {
+        Ok(candidate)
+    } else {
+        Err(PlayfastError::InvalidAppId(app_id.to_string()))
+    }
+}
+
+/// Check an Android package name (e.g. "com.example.app")
+fn is_valid_package_name(name: &str) -> bool {
+    let mut segments = 0;
+
+    for segment in name.split('.') {
+        let mut chars = segment.chars();
+        match chars.next() {
+            Some(c) if c.is_ascii_alphabetic() => {}
+            _ => return false,
+        }
+        if !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return false;
+        }
+        segments += 1;
+    }
+
+    segments >= 2
+}
+
+/// Write reviews as JSON lines (one object per line)
+///
+/// Returns the number of reviews written. The writer is flushed before
+/// returning so each completed batch survives a later crash.
+pub fn write_reviews_jsonl<W: Write>(writer: &mut W, reviews: &[RustReview]) -> Result<usize> {
+    for review in reviews {
+        serde_json::to_writer(&mut *writer, review)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(reviews.len())
+}
+
+/// Page size requested for similar-apps pagination
+const SIMILAR_PAGE_SIZE: u32 = 100;
+
+/// Upper bound on similar-apps pages fetched per call
+const SIMILAR_MAX_PAGES: usize = 10;
+
+/// Production Play Store host
+pub const DEFAULT_BASE_URL: &str = "https://play.google.com";
+
+/// Validate a scraping base URL and return it without a trailing slash
+///
+/// Only http(s) URLs with a host and no path, query or fragment are accepted,
+/// since request paths are appended directly to the base.
+pub fn validate_base_url(url: &str) -> Result<String> {
+    let trimmed = url.trim().trim_end_matches('/');
+    let host = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"))
+        .ok_or_else(|| PlayfastError::Other(format!("Base URL must start with http:// or https://: {}", url)))?;
+
+    let valid_host = !host.is_empty()
+        && !host.contains(['/', '?', '#', '@'])
+        && !host.chars().any(char::is_whitespace);
+    if !valid_host {
+        return Err(PlayfastError::Other(format!("Invalid base URL: {}", url)));
+    }
+
+    Ok(trimmed.to_string())
+}
+
 /// HTTP client for Google Play Store (async)
 pub struct PlayStoreClient {
     client: reqwest::Client,  // Changed from blocking::Client
@@ -112,6 +302,13 @@ pub struct PlayStoreClient {
 impl PlayStoreClient {
     /// Create a new PlayStore HTTP client with optimized connection settings
     pub fn new(timeout_secs: u64) -> Result<Self> {
+        Self::new_with_base(timeout_secs, DEFAULT_BASE_URL)
+    }
+
+    /// Create a client that sends every request to `base_url` instead of the
+    /// production host (e.g. a regional mirror or a local fixture server)
+    pub fn new_with_base(timeout_secs: u64, base_url: &str) -> Result<Self> {
+        let base_url = validate_base_url(base_url)?;
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(timeout_secs))
             .user_agent(
@@ -125,19 +322,29 @@ impl PlayStoreClient {
             .tcp_keepalive(Duration::from_secs(60))  // TCP keepalive
             .build()?;
 
-        Ok(Self {
-            client,
-            base_url: "https://play.google.com".to_string(),
-        })
+        Ok(Self { client, base_url })
+    }
+
+    /// Host requests are sent to
+    #[cfg(test)]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Apply a per-request timeout, overriding the client default
+    fn with_timeout(request: reqwest::RequestBuilder, timeout: Option<Duration>) -> reqwest::RequestBuilder {
+        match timeout {
+            Some(t) => request.timeout(t),
+            None => request,
+        }
     }
 
     /// Fetch HTML from URL with query parameters (async)
-    async fn fetch_html(&self, path: &str, params: &[(&str, &str)]) -> Result<String> {
+    async fn fetch_html(&self, path: &str, params: &[(&str, &str)], timeout: Option<Duration>) -> Result<String> {
         let url = format!("{}{}", self.base_url, path);
 
-        let response = self.client
-            .get(&url)
-            .query(params)
+        let request = self.client.get(&url).query(params);
+        let response = Self::with_timeout(request, timeout)
             .send()
             .await?;  // Added .await
 
@@ -168,20 +375,24 @@ impl PlayStoreClient {
     }
 
     /// Fetch and parse app information (async, GIL-free)
+    ///
+    /// `timeout` overrides the client-wide timeout for this request only.
     pub async fn fetch_and_parse_app(
         &self,
         app_id: &str,
         lang: &str,
         country: &str,
+        timeout: Option<Duration>,
     ) -> Result<RustAppInfo> {
+        let app_id = normalize_app_id(app_id)?;
         let params = [
-            ("id", app_id),
+            ("id", app_id.as_str()),
             ("hl", lang),
             ("gl", country),
         ];
 
-        let html = self.fetch_html("/store/apps/details", &params).await?;  // Added .await
-        parse_app_page(&html, app_id)
+        let html = self.fetch_html("/store/apps/details", &params, timeout).await?;  // Added .await
+        parse_app_page(&html, &app_id)
     }
 
     /// Fetch and parse reviews (async, GIL-free)
@@ -192,8 +403,10 @@ impl PlayStoreClient {
         country: &str,
         sort: u8,
         continuation_token: Option<&str>,
+        timeout: Option<Duration>,
     ) -> Result<(Vec<RustReview>, Option<String>)> {
-        let body = build_reviews_request_body(app_id, sort, continuation_token, lang, country);
+        let app_id = normalize_app_id(app_id)?;
+        let body = build_reviews_request_body(&app_id, sort, continuation_token, lang, country);
 
         let url = format!(
             "{}/_/PlayStoreUi/data/batchexecute?\
@@ -212,10 +425,11 @@ impl PlayStoreClient {
             self.base_url, lang, country
         );
 
-        let response = self.client
+        let request = self.client
             .post(&url)
             .header("Content-Type", "application/x-www-form-urlencoded;charset=UTF-8")
-            .body(body)
+            .body(body);
+        let response = Self::with_timeout(request, timeout)
             .send()
             .await?;  // Added .await
 
@@ -230,12 +444,121 @@ impl PlayStoreClient {
         parse_batchexecute_reviews_response(&text)
     }
 
+    /// Page through reviews and append them to a JSON-lines file (async, GIL-free)
+    ///
+    /// Each page is written and flushed as soon as it arrives, so memory use
+    /// is bounded by one page and an interrupted export keeps what it has
+    /// written so far. Stops after `max_reviews` reviews or when no
+    /// continuation token is returned. Returns the number of reviews written.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn fetch_reviews_to_jsonl(
+        &self,
+        app_id: &str,
+        lang: &str,
+        country: &str,
+        sort: u8,
+        max_reviews: usize,
+        path: &Path,
+        timeout: Option<Duration>,
+    ) -> Result<usize> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let mut written = 0;
+        let mut token: Option<String> = None;
+
+        while written < max_reviews {
+            let (reviews, next_token) = self
+                .fetch_and_parse_reviews(app_id, lang, country, sort, token.as_deref(), timeout)
+                .await?;
+
+            if reviews.is_empty() {
+                break;
+            }
+
+            let remaining = max_reviews - written;
+            written += write_reviews_jsonl(&mut writer, &reviews[..reviews.len().min(remaining)])?;
+
+            match next_token {
+                Some(next) => token = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Fetch the full similar-apps list for a package (async, GIL-free)
+    ///
+    /// Follows continuation tokens until the list is exhausted or
+    /// `SIMILAR_MAX_PAGES` pages have been read. Apps already seen on an
+    /// earlier page are skipped.
+    pub async fn fetch_similar_apps(
+        &self,
+        app_id: &str,
+        lang: &str,
+        country: &str,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<RustSearchResult>> {
+        let app_id = normalize_app_id(app_id)?;
+
+        let url = format!(
+            "{}/_/PlayStoreUi/data/batchexecute?\
+             rpcids=ag2B9c&\
+             source-path=%2Fstore%2Fapps%2Fdetails&\
+             authuser=0&\
+             soc-app=121&\
+             soc-platform=1&\
+             soc-device=1&\
+             rt=c&\
+             hl={}&\
+             gl={}",
+            self.base_url, lang, country
+        );
+
+        let mut seen = std::collections::HashSet::new();
+        let mut results = Vec::new();
+        let mut token: Option<String> = None;
+
+        for _ in 0..SIMILAR_MAX_PAGES {
+            let body = build_similar_request_body(&app_id, SIMILAR_PAGE_SIZE, token.as_deref());
+            let request = self.client
+                .post(&url)
+                .header("Content-Type", "application/x-www-form-urlencoded;charset=UTF-8")
+                .body(body);
+            let response = Self::with_timeout(request, timeout)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(PlayfastError::Other(
+                    format!("batchexecute similar apps failed: {}", response.status())
+                ));
+            }
+
+            let text = response.text().await?;
+            let (apps, next_token) = parse_batchexecute_similar_response(&text)?;
+
+            if apps.is_empty() {
+                break;
+            }
+
+            results.extend(apps.into_iter().filter(|app| seen.insert(app.app_id.clone())));
+
+            match next_token {
+                Some(next) => token = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(results)
+    }
+
     /// Fetch and parse search results (async, GIL-free)
     pub async fn fetch_and_parse_search(
         &self,
         query: &str,
         lang: &str,
         country: &str,
+        timeout: Option<Duration>,
     ) -> Result<Vec<RustSearchResult>> {
         let params = [
             ("q", query),
@@ -244,12 +567,18 @@ impl PlayStoreClient {
             ("gl", country),
         ];
 
-        let html = self.fetch_html("/store/search", &params).await?;  // Added .await
+        let html = self.fetch_html("/store/search", &params, timeout).await?;  // Added .await
 
         parse_search_results(&html)
     }
 
     /// Fetch and parse list results (async, GIL-free)
+    ///
+    /// A single page holds at most `LIST_MAX_PAGE_SIZE` apps. Larger `num`
+    /// values are served by following continuation tokens until `num` apps
+    /// have been collected, the list ends, or `LIST_MAX_PAGES` pages have been
+    /// read. Pages are merged in order and apps seen on an earlier page are
+    /// skipped.
     pub async fn fetch_and_parse_list(
         &self,
         category: Option<&str>,
@@ -257,9 +586,70 @@ impl PlayStoreClient {
         lang: &str,
         country: &str,
         num: u32,
+        timeout: Option<Duration>,
     ) -> Result<Vec<RustSearchResult>> {
-        let body = build_list_request_body(category, collection, num);
+        if num <= LIST_MAX_PAGE_SIZE {
+            let body = build_list_request_body(category, collection, num);
+            let text = self.post_list_request(body, lang, country, timeout).await?;
+
+            return parse_batchexecute_list_response(&text);
+        }
+
+        let wanted = num as usize;
+        let mut seen = std::collections::HashSet::new();
+        let mut results = Vec::new();
+        let mut token: Option<String> = None;
+
+        for _ in 0..LIST_MAX_PAGES {
+            let (apps, next_token) = self
+                .fetch_and_parse_list_page(category, collection, lang, country, LIST_MAX_PAGE_SIZE, token.as_deref(), timeout)
+                .await?;
+
+            if apps.is_empty() {
+                break;
+            }
+
+            results.extend(apps.into_iter().filter(|app| seen.insert(app.app_id.clone())));
+
+            match next_token {
+                Some(next) if results.len() < wanted => token = Some(next),
+                _ => break,
+            }
+        }
+
+        results.truncate(wanted);
+        Ok(results)
+    }
 
+    /// Fetch one page of list results and the token for the next page
+    ///
+    /// Pass `continuation_token = None` for the first page. The returned token
+    /// is None once the list is exhausted.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn fetch_and_parse_list_page(
+        &self,
+        category: Option<&str>,
+        collection: &str,
+        lang: &str,
+        country: &str,
+        num: u32,
+        continuation_token: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<(Vec<RustSearchResult>, Option<String>)> {
+        let body = build_list_page_request_body(category, collection, num, continuation_token);
+        let text = self.post_list_request(body, lang, country, timeout).await?;
+
+        parse_batchexecute_list_page_response(&text)
+    }
+
+    /// POST a list request body to the batchexecute endpoint
+    async fn post_list_request(
+        &self,
+        body: String,
+        lang: &str,
+        country: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String> {
         let url = format!(
             "{}/_/PlayStoreUi/data/batchexecute?\
              rpcids=vyAe2&\
@@ -277,10 +667,11 @@ impl PlayStoreClient {
             self.base_url, lang, country
         );
 
-        let response = self.client
+        let request = self.client
             .post(&url)
             .header("Content-Type", "application/x-www-form-urlencoded;charset=UTF-8")
-            .body(body)
+            .body(body);
+        let response = Self::with_timeout(request, timeout)
             .send()
             .await?;  // Added .await
 
@@ -290,9 +681,7 @@ impl PlayStoreClient {
             ));
         }
 
-        let text = response.text().await?;  // Added .await
-
-        parse_batchexecute_list_response(&text)
+        Ok(response.text().await?)
     }
 }
 
@@ -305,6 +694,7 @@ impl Default for PlayStoreClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::test_support::{batchexecute_app, batchexecute_list_page_response, batchexecute_reviews_response};
 
     #[test]
     fn test_client_creation() {
@@ -317,6 +707,322 @@ mod tests {
         let _client = PlayStoreClient::default();
     }
 
+    #[test]
+    fn test_normalize_app_id_valid() {
+        assert_eq!(normalize_app_id("com.spotify.music").unwrap(), "com.spotify.music");
+        assert_eq!(normalize_app_id(" com.app_1.x2 ").unwrap(), "com.app_1.x2");
+    }
+
+    #[test]
+    fn test_normalize_app_id_url() {
+        let url = "https://play.google.com/store/apps/details?id=com.spotify.music&hl=en";
+        assert_eq!(normalize_app_id(url).unwrap(), "com.spotify.music");
+
+        let result = normalize_app_id("https://play.google.com/store/apps");
+        assert!(matches!(result, Err(PlayfastError::InvalidAppId(_))));
+    }
+
+    #[test]
+    fn test_parse_play_url() {
+        assert_eq!(
+            parse_play_url("https://play.google.com/store/apps/details?id=com.spotify.music"),
+            Some("com.spotify.music".to_string())
+        );
+        assert_eq!(
+            parse_play_url("https://play.google.com/store/apps/details?hl=en&id=com.spotify.music&gl=us"),
+            Some("com.spotify.music".to_string())
+        );
+        assert_eq!(
+            parse_play_url("play.google.com/intl/en_us/store/apps/details?id=com.spotify.music&hl=en"),
+            Some("com.spotify.music".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_play_url_non_matching() {
+        assert_eq!(parse_play_url("https://example.com/store/apps/details?id=com.spotify.music"), None);
+        assert_eq!(parse_play_url("https://play.google.com/store/search?q=maps"), None);
+        assert_eq!(parse_play_url("https://play.google.com/store/apps/details?hl=en"), None);
+        assert_eq!(parse_play_url("com.spotify.music"), None);
+    }
+
+    #[test]
+    fn test_normalize_app_id_invalid() {
+        for bad in ["", "spotify", "com..music", "com.1app", "com.spotify music", "com.spotify.music/"] {
+            assert!(
+                matches!(normalize_app_id(bad), Err(PlayfastError::InvalidAppId(_))),
+                "expected {:?} to be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_list_page_request_body() {
+        let first = build_list_page_request_body(Some("GAME_ACTION"), "topselling_free", 50, None);
+        assert_eq!(first, build_list_request_body(Some("GAME_ACTION"), "topselling_free", 50));
+
+        let next = build_list_page_request_body(Some("GAME_ACTION"), "topselling_free", 50, Some("CgYIZBCQAxgB"));
+        assert!(next.contains("%5B8%2C%5B20%2C50%2C%5C%22CgYIZBCQAxgB%5C%22%5D%5D"));
+
+        let padded = build_list_page_request_body(None, "topselling_free", 50, Some("Cg+I/A=="));
+        assert!(padded.contains("%5C%22Cg%2BI%2FA%3D%3D%5C%22"), "{}", padded);
+    }
+
+    #[test]
+    fn test_validate_base_url() {
+        assert_eq!(validate_base_url("https://play.google.com/").unwrap(), "https://play.google.com");
+        assert_eq!(validate_base_url("http://127.0.0.1:8080").unwrap(), "http://127.0.0.1:8080");
+
+        for bad in ["", "play.google.com", "ftp://play.google.com", "https://", "https://host/path", "https://a b"] {
+            assert!(validate_base_url(bad).is_err(), "expected {:?} to be rejected", bad);
+        }
+    }
+
+    /// Serve a single canned HTTP response on a local port
+    ///
+    /// Returns the base URL to inject into `PlayStoreClient::new_with_base`
+    /// and a receiver yielding the raw request the client sent.
+    fn serve_once(status: &str, body: String) -> (String, std::sync::mpsc::Receiver<String>) {
+        serve_sequence(vec![(status.to_string(), body)])
+    }
+
+    /// Serve canned responses, one per connection, in order
+    ///
+    /// Each request is read in full (headers and body) before replying, and
+    /// sent on the returned receiver.
+    fn serve_sequence(responses: Vec<(String, String)>) -> (String, std::sync::mpsc::Receiver<String>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((mut stream, _)) = listener.accept() else { return };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                    request.push_str(&line);
+                }
+                let mut payload = vec![0u8; content_length];
+                let _ = reader.read_exact(&mut payload);
+                request.push_str(&String::from_utf8_lossy(&payload));
+
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+                let _ = tx.send(request);
+            }
+        });
+
+        (format!("http://{}", addr), rx)
+    }
+
+    /// Minimal details page with the fields parse_app_page requires
+    fn app_page_fixture() -> String {
+        let mut app_data = vec![serde_json::Value::Null; 146];
+        app_data[0] = serde_json::json!(["Fixture App"]);
+        app_data[9] = serde_json::json!([null, [null, null, null, [null, null, "https://example.com/icon.png"]]]);
+        app_data[37] = serde_json::json!(["Fixture Dev"]);
+        app_data[51] = serde_json::json!([[null, 4.5], null, [null, 1234]]);
+        let ds5 = serde_json::json!([null, [null, null, app_data]]);
+        format!(
+            "<html><script>AF_initDataCallback({{key: 'ds:5', hash: '7', data:{}, sideChannel: {{}}}});</script></html>",
+            ds5
+        )
+    }
+
+    #[tokio::test]
+    async fn test_requests_use_configured_base_url() {
+        let (base_url, requests) = serve_once("404 Not Found", String::new());
+
+        let client = PlayStoreClient::new_with_base(5, &format!("{}/", base_url)).unwrap();
+        assert_eq!(client.base_url(), base_url);
+
+        let result = client.fetch_and_parse_app("com.test.app", "en", "us", None).await;
+        assert!(matches!(result, Err(PlayfastError::AppNotFound(_))));
+
+        let request = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(request.starts_with("GET /store/apps/details?id=com.test.app"), "{}", request);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_and_parse_app_from_fixture_server() {
+        let (base_url, requests) = serve_once("200 OK", app_page_fixture());
+        let client = PlayStoreClient::new_with_base(5, &base_url).unwrap();
+
+        let app = client
+            .fetch_and_parse_app("https://play.google.com/store/apps/details?id=com.fixture.app", "en", "us", None)
+            .await
+            .unwrap();
+
+        assert_eq!(app.app_id, "com.fixture.app");
+        assert_eq!(app.title, "Fixture App");
+        assert_eq!(app.developer, "Fixture Dev");
+        assert_eq!(app.icon, "https://example.com/icon.png");
+        assert_eq!(app.score, Some(4.5));
+        assert_eq!(app.ratings, 1234);
+
+        let request = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(request.contains("hl=en") && request.contains("gl=us"), "{}", request);
+    }
+
+    #[test]
+    fn test_build_list_request_body_clamps_num() {
+        assert_eq!(
+            build_list_request_body(None, "topselling_free", 400),
+            build_list_request_body(None, "topselling_free", LIST_MAX_PAGE_SIZE)
+        );
+        assert!(build_list_request_body(None, "topselling_free", 400).contains("%5B20%2C250%5D"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_list_paginates_past_page_size() {
+        let page = |ids: &[&str], token| {
+            let apps = ids.iter().map(|id| batchexecute_app(id, serde_json::Value::Null)).collect();
+            ("200 OK".to_string(), batchexecute_list_page_response(serde_json::Value::Array(apps), token))
+        };
+        let (base_url, requests) = serve_sequence(vec![
+            page(&["com.page.a", "com.page.b", "com.page.c"], Some("TOKEN_2")),
+            page(&["com.page.c", "com.page.d"], None),
+        ]);
+        let client = PlayStoreClient::new_with_base(5, &base_url).unwrap();
+
+        let apps = client
+            .fetch_and_parse_list(None, "topselling_free", "en", "us", 400, None)
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = apps.iter().map(|a| a.app_id.as_str()).collect();
+        assert_eq!(ids, vec!["com.page.a", "com.page.b", "com.page.c", "com.page.d"]);
+
+        let first = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+        let second = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(first.contains("%5B8%2C%5B20%2C250%5D%5D"), "{}", first);
+        assert!(!first.contains("TOKEN_2"));
+        assert!(second.contains("%5B8%2C%5B20%2C250%2C%5C%22TOKEN_2%5C%22%5D%5D"), "{}", second);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reviews_to_jsonl_follows_pages() {
+        let page = |ids: &[&str], token| ("200 OK".to_string(), batchexecute_reviews_response(ids, token));
+        let export = |base_url: String, max_reviews: usize| async move {
+            let client = PlayStoreClient::new_with_base(5, &base_url).unwrap();
+            let path = std::env::temp_dir().join(format!("playfast_export_{}_{}.jsonl", max_reviews, std::process::id()));
+            let written = client
+                .fetch_reviews_to_jsonl("com.fixture.app", "en", "us", 2, max_reviews, &path, None)
+                .await
+                .unwrap();
+            let contents = std::fs::read_to_string(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            let ids: Vec<String> = contents
+                .lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["review_id"].as_str().unwrap().to_string())
+                .collect();
+            (written, ids)
+        };
+
+        // The second page is cut short by max_reviews; its token is never followed
+        let (base_url, requests) = serve_sequence(vec![
+            page(&["gp:1", "gp:2"], Some("PAGE_TWO")),
+            page(&["gp:3", "gp:4"], Some("PAGE_THREE")),
+        ]);
+        let (written, ids) = export(base_url, 3).await;
+        assert_eq!(written, 3);
+        assert_eq!(ids, vec!["gp:1", "gp:2", "gp:3"]);
+
+        let first = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+        let second = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(!first.contains("PAGE_TWO"), "{}", first);
+        assert!(second.contains("PAGE_TWO"), "{}", second);
+        assert!(requests.recv_timeout(Duration::from_millis(200)).is_err());
+
+        // An empty page ends the export even when it carries a token
+        let (base_url, requests) = serve_sequence(vec![
+            page(&["gp:1", "gp:2"], Some("PAGE_TWO")),
+            page(&[], Some("PAGE_THREE")),
+            page(&["gp:5"], None),
+        ]);
+        let (written, ids) = export(base_url, 10).await;
+        assert_eq!(written, 2);
+        assert_eq!(ids, vec!["gp:1", "gp:2"]);
+
+        requests.recv_timeout(Duration::from_secs(5)).unwrap();
+        let second = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(second.contains("PAGE_TWO"), "{}", second);
+        assert!(requests.recv_timeout(Duration::from_millis(200)).is_err());
+    }
+
+    #[test]
+    fn test_build_similar_request_body() {
+        let first = build_similar_request_body("com.spotify.music", 100, None);
+        assert!(first.starts_with("f.req="));
+        assert!(first.contains("ag2B9c"));
+        assert!(first.contains("com.spotify.music"));
+        assert!(first.contains("%5B100%2Cnull%5D"));
+
+        let next = build_similar_request_body("com.spotify.music", 100, Some("CgQIZBAB"));
+        assert!(next.contains("%5B100%2C%5C%22CgQIZBAB%5C%22%5D"), "{}", next);
+        assert!(!next.contains("%2Cnull%5D%5D"));
+
+        let padded = build_similar_request_body("com.spotify.music", 100, Some("CgQ+/A=="));
+        assert!(padded.contains("%5C%22CgQ%2B%2FA%3D%3D%5C%22"), "{}", padded);
+        assert!(!padded.contains('+'));
+
+        let odd = build_similar_request_body("com.example&x=1", 100, None);
+        assert!(odd.contains("%5C%22com.example%26x%3D1%5C%22"), "{}", odd);
+        assert!(!odd.contains('&'));
+    }
+
+    #[test]
+    fn test_write_reviews_jsonl() {
+        let reviews: Vec<RustReview> = (0..3)
+            .map(|i| RustReview {
+                review_id: format!("gp:{}", i),
+                user_name: "User".to_string(),
+                user_image: None,
+                content: "Line one\nline two".to_string(),
+                score: 5,
+                thumbs_up: i,
+                created_at: Some(1_700_000_000),
+                reply_content: None,
+                reply_at: None,
+            })
+            .collect();
+
+        let path = std::env::temp_dir().join(format!("playfast_reviews_{}.jsonl", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        assert_eq!(write_reviews_jsonl(&mut file, &reviews[..2]).unwrap(), 2);
+        assert_eq!(write_reviews_jsonl(&mut file, &reviews[2..]).unwrap(), 1);
+        drop(file);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        for (i, line) in lines.iter().enumerate() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["review_id"], format!("gp:{}", i));
+            assert_eq!(value["content"], "Line one\nline two");
+        }
+    }
+
     #[tokio::test]
     #[ignore] // Requires network
     async fn test_fetch_real_app() {
@@ -324,7 +1030,8 @@ mod tests {
         let result = client.fetch_and_parse_app(
             "com.google.android.apps.maps",
             "en",
-            "us"
+            "us",
+            None
         ).await;
 
         if result.is_ok() {
@@ -341,7 +1048,8 @@ mod tests {
         let result = client.fetch_and_parse_app(
             "com.nonexistent.app.that.does.not.exist",
             "en",
-            "us"
+            "us",
+            None
         ).await;
 
         assert!(result.is_err());
@@ -360,7 +1068,8 @@ mod tests {
         let results = client.fetch_and_parse_search(
             "maps",
             "en",
-            "us"
+            "us",
+            None
         ).await;
 
         if results.is_ok() {
@@ -369,4 +1078,34 @@ mod tests {
             assert!(search_results.iter().any(|r| r.title.to_lowercase().contains("maps")));
         }
     }
+
+    #[tokio::test]
+    async fn test_request_timeout_is_honored() {
+        use std::io::Read;
+        use std::net::TcpListener;
+
+        // Server that accepts the connection but never responds in time
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                std::thread::sleep(Duration::from_secs(5));
+            }
+        });
+
+        let client = PlayStoreClient::new_with_base(30, &format!("http://{}", addr)).unwrap();
+
+        let started = std::time::Instant::now();
+        let result = client
+            .fetch_and_parse_app("com.test.app", "en", "us", Some(Duration::from_millis(200)))
+            .await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        match result {
+            Err(PlayfastError::HttpError(e)) => assert!(e.is_timeout()),
+            other => panic!("Expected timeout error, got {:?}", other.map(|a| a.app_id)),
+        }
+    }
 }
@@ -10,7 +10,7 @@ mod dex;
 // Google Play APK download module
 mod download;
 
-use http::{PlayStoreClient, build_list_request_body as build_list_request_body_impl, build_reviews_request_body as build_reviews_request_body_impl};
+use http::{PlayStoreClient, build_list_request_body as build_list_request_body_impl, build_reviews_request_body as build_reviews_request_body_impl, build_similar_request_body as build_similar_request_body_impl, parse_play_url as parse_play_url_impl};
 use models::{RustAppInfo, RustPermission, RustReview, RustSearchResult};
 use parser::{
     parse_app_page as parse_app_page_impl,
@@ -18,31 +18,42 @@ use parser::{
     parse_search_results as parse_search_results_impl,
     parse_batchexecute_list_response as parse_batchexecute_list_response_impl,
     parse_batchexecute_reviews_response as parse_batchexecute_reviews_response_impl,
+    parse_batchexecute_similar_response as parse_batchexecute_similar_response_impl,
     extract_continuation_token as extract_continuation_token_impl
 };
 use pyo3::prelude::*;
 use once_cell::sync::Lazy;
 use futures::future::try_join_all;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 // Import DEX and APK types
-use apk::{ApkExtractor, RustManifestInfo, parse_manifest, IntentFilterData, ActivityIntentFilter, PyResourceResolver, PyResolvedResource, parse_resources_from_apk};
+use apk::{ApkExtractor, RustManifestInfo, parse_manifest_with_resources, IntentFilterData, ActivityIntentFilter, DeclaredPermission, ProviderInfo, PyResourceResolver, PyResolvedResource, parse_resources_from_apk, DomainConfig, NetworkSecurityReport, network_security_report, apk_size_breakdown, ApkHealth, validate_apk, CertInfo, extract_signing_info};
 use dex::models::{RustDexClass, RustDexMethod, RustDexField, RustReferencePool};
 use dex::filter::{ClassFilter, MethodFilter};
-use dex::container::DexContainer;
-use dex::bytecode::{RustInstruction, decode_bytecode, extract_constants, extract_method_calls};
-use dex::code_extractor::{extract_methods_bytecode, get_method_bytecode_from_apk};
-use dex::method_resolver::{MethodSignature, MethodResolverPy, create_method_resolver, resolve_method_from_apk};
+use dex::container::{DexContainer, ClassIterator, MethodIterator};
+use dex::descriptor::MethodDescriptor;
+use dex::bytecode::{RustInstruction, decode_bytecode, decode_instruction_at, extract_constants, extract_method_calls, extract_string_constants, opcode_histogram};
+use dex::code_extractor::{extract_methods_bytecode, extract_method_calls_from_apk, get_method_bytecode_from_apk, preview_method, apk_opcode_profile};
+use dex::method_resolver::{MethodSignature, MethodResolverPy, create_apk_method_resolver, create_method_resolver, resolve_method_from_apk};
+use dex::inspector::DexInspector;
+use dex::integrity::{DexIntegrity, verify_dex_integrity};
 use dex::expression_builder::{ReconstructedExpression, ExpressionBuilderPy, create_expression_builder, reconstruct_expressions_from_apk};
 use dex::class_decompiler::{DecompiledClass, DecompiledMethod, decompile_class_from_apk};
 use dex::entry_point_analyzer::{EntryPoint, ComponentType, PyEntryPointAnalyzer, analyze_entry_points_from_apk};
-use dex::call_graph::{CallGraph, CallPath, MethodCall, PyCallGraphBuilder, build_call_graph_from_apk, build_call_graph_from_apk_parallel};
+use dex::api_level::{ApiLevelViolation, find_api_level_violations};
+use dex::secret_scanner::{SecretFinding, find_secrets};
+use dex::call_graph::{BuildStats, CallGraph, CallPath, MethodCall, PyCallGraphBuilder, build_call_graph_from_apk, build_call_graph_from_apk_parallel, build_call_graph_with_stats};
 use dex::data_flow_analyzer::{
-    Flow, DataFlow, DataFlowAnalyzer,
+    Flow, DataFlow, DataSourceKind, DeeplinkFinding, DataFlowAnalyzer, TaintMode, WeakCryptoUse,
     create_data_flow_analyzer,
     find_flows_from_apk,
+    find_data_flows_from_apk,
     find_webview_flows_from_apk,
     find_file_flows_from_apk,
     find_network_flows_from_apk,
+    find_weak_crypto_from_apk,
+    deeplink_attack_surface,
     analyze_webview_flows_from_apk,
     create_webview_analyzer_from_apk,
 };
@@ -66,8 +77,11 @@ static TOKIO_RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
 });
 
 /// Global HTTP client (connection pooling)
-static HTTP_CLIENT: Lazy<PlayStoreClient> = Lazy::new(|| {
-    PlayStoreClient::new(30).expect("Failed to create HTTP client")
+///
+/// Held behind a lock so `configure_base_url` can swap in a client pointed at
+/// a different host; in-flight requests keep their own `Arc`.
+static HTTP_CLIENT: Lazy<RwLock<Arc<PlayStoreClient>>> = Lazy::new(|| {
+    RwLock::new(Arc::new(PlayStoreClient::new(30).expect("Failed to create HTTP client")))
 });
 
 /// Get the global tokio runtime
@@ -76,8 +90,75 @@ fn get_runtime() -> &'static tokio::runtime::Runtime {
 }
 
 /// Get the global HTTP client (with connection pooling)
-fn get_client() -> &'static PlayStoreClient {
-    &HTTP_CLIENT
+///
+/// The fetch pyfunctions are thin wrappers over `PlayStoreClient` methods, so
+/// Rust tests exercise those methods directly on a client built with
+/// `PlayStoreClient::new_with_base` against a local server.
+fn get_client() -> Arc<PlayStoreClient> {
+    HTTP_CLIENT.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Point all scraping functions at a different host
+///
+/// Useful for regional mirrors or for integration-testing the parsers against
+/// a local fixture server. Pass "https://play.google.com" to restore the
+/// default.
+///
+/// Args:
+///     url (str): Base URL such as "http://127.0.0.1:8080" (no path)
+///
+/// Raises:
+///     ValueError: If the URL is not a valid http(s) base URL
+#[pyfunction]
+fn configure_base_url(url: &str) -> PyResult<()> {
+    let client = PlayStoreClient::new_with_base(30, url)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    *HTTP_CLIENT.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(client);
+    Ok(())
+}
+
+/// Thread pool settings shared by the global pool and tests
+fn rayon_pool_builder(threads: usize) -> rayon::ThreadPoolBuilder {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("playfast-rayon-{}", i))
+}
+
+/// Size the global rayon pool used by parallel DEX analysis
+fn configure_rayon_pool(threads: usize) -> PyResult<()> {
+    // rayon reads 0 as "one thread per CPU"
+    if threads == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err("threads must be greater than 0"));
+    }
+
+    rayon_pool_builder(threads).build_global().map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!(
+            "Parallelism already configured ({} threads): {}",
+            rayon::current_num_threads(),
+            e
+        ))
+    })
+}
+
+/// Configure the number of worker threads for parallel APK analysis
+///
+/// Sizes the global rayon pool used by `extract_classes_from_apk(parallel=True)`,
+/// `build_call_graph_from_apk_parallel` and other parallel DEX analysis.
+/// By default rayon uses one thread per CPU, which over-subscribes containers
+/// with CPU limits and may be too conservative on large machines.
+///
+/// Must be called before the first parallel analysis; the pool cannot be
+/// resized once it has been initialized.
+///
+/// Args:
+///     threads (int): Number of worker threads (must be > 0)
+///
+/// Raises:
+///     ValueError: If threads is 0
+///     RuntimeError: If the pool was already initialized
+#[pyfunction]
+fn configure_parallelism(threads: usize) -> PyResult<()> {
+    configure_rayon_pool(threads)
 }
 
 /// Parse app information from HTML (CPU-intensive, GIL-free operation)
@@ -164,7 +245,7 @@ fn parse_batchexecute_list_response(response_text: &str) -> PyResult<Vec<RustSea
 /// Args:
 ///     category (str | None): Category code (e.g., "GAME_ACTION"), None for all apps
 ///     collection (str): Collection type (e.g., "topselling_free")
-///     num (int): Number of results (default: 100, max: 250)
+///     num (int): Number of results (default: 100, clamped to 250)
 ///
 /// Returns:
 ///     str: URL-encoded POST body ready for HTTP request
@@ -217,6 +298,48 @@ fn build_reviews_request_body(
     build_reviews_request_body_impl(app_id, sort, continuation_token, lang, country)
 }
 
+/// Build request body for batchexecute similar-apps API
+///
+/// Args:
+///     app_id (str): App package ID (e.g., "com.spotify.music")
+///     num (int): Number of apps per page (default: 100)
+///     continuation_token (str | None): Token for pagination (None for first page)
+///
+/// Returns:
+///     str: URL-encoded POST body ready for HTTP request
+///
+/// Example:
+///     >>> body = build_similar_request_body("com.spotify.music")
+///     >>> async with session.post(url, data=body) as response:
+///     ...     text = await response.text()
+///     ...     apps, next_token = parse_batchexecute_similar_response(text)
+#[pyfunction]
+#[pyo3(signature = (app_id, num=100, continuation_token=None))]
+fn build_similar_request_body(
+    app_id: &str,
+    num: u32,
+    continuation_token: Option<&str>,
+) -> String {
+    build_similar_request_body_impl(app_id, num, continuation_token)
+}
+
+/// Extract the package id from a Play Store URL
+///
+/// Args:
+///     url (str): Play Store details URL, optionally with a locale prefix
+///                and extra query parameters
+///
+/// Returns:
+///     str | None: Package id, or None if the URL is not a Play Store details URL
+///
+/// Example:
+///     >>> parse_play_url("https://play.google.com/store/apps/details?id=com.spotify.music&hl=en")
+///     'com.spotify.music'
+#[pyfunction]
+fn parse_play_url(url: &str) -> Option<String> {
+    parse_play_url_impl(url)
+}
+
 /// Parse batchexecute API response for reviews
 ///
 /// This parses the response from Google Play's batchexecute API endpoint
@@ -235,6 +358,44 @@ fn parse_batchexecute_reviews_response(response_text: &str) -> PyResult<(Vec<Rus
     parse_batchexecute_reviews_response_impl(response_text).map_err(Into::into)
 }
 
+/// Parse batchexecute API response for similar apps
+///
+/// Args:
+///     response_text (str): The raw text response from batchexecute API
+///
+/// Returns:
+///     tuple: (list[RustSearchResult], str | None) - Apps and next continuation token
+///
+/// Raises:
+///     Exception: If parsing fails
+#[pyfunction]
+fn parse_batchexecute_similar_response(response_text: &str) -> PyResult<(Vec<RustSearchResult>, Option<String>)> {
+    parse_batchexecute_similar_response_impl(response_text).map_err(Into::into)
+}
+
+/// Request timeout in seconds when the caller passes none
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Per-request timeout from `timeout` or the deprecated `_timeout` keyword
+///
+/// `_timeout` emits a DeprecationWarning; passing both is a TypeError.
+fn request_timeout(py: Python<'_>, timeout: Option<u64>, legacy_timeout: Option<u64>) -> PyResult<Duration> {
+    let seconds = match (timeout, legacy_timeout) {
+        (Some(_), Some(_)) => {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "pass timeout or the deprecated _timeout, not both",
+            ));
+        }
+        (None, Some(legacy)) => {
+            let category = py.get_type::<pyo3::exceptions::PyDeprecationWarning>();
+            PyErr::warn(py, &category, c"_timeout is deprecated, use timeout", 1)?;
+            legacy
+        }
+        (timeout, None) => timeout.unwrap_or(DEFAULT_TIMEOUT_SECS),
+    };
+    Ok(Duration::from_secs(seconds))
+}
+
 /// Fetch and parse app information (combined HTTP + parsing, GIL-free)
 ///
 /// This function performs both HTTP request and parsing in Rust,
@@ -252,18 +413,22 @@ fn parse_batchexecute_reviews_response(response_text: &str) -> PyResult<(Vec<Rus
 /// Raises:
 ///     Exception: If request or parsing fails
 #[pyfunction]
-#[pyo3(signature = (app_id, lang, country, _timeout=30))]
+#[pyo3(signature = (app_id, lang, country, timeout=None, _timeout=None))]
+#[allow(clippy::duplicate_underscore_argument)] // `_timeout` is the legacy keyword name
 fn fetch_and_parse_app(
+    py: Python<'_>,
     app_id: &str,
     lang: &str,
     country: &str,
-    _timeout: u64,  // Ignored, using global client timeout
+    timeout: Option<u64>,
+    _timeout: Option<u64>,
 ) -> PyResult<RustAppInfo> {
+    let timeout = request_timeout(py, timeout, _timeout)?;
     let client = get_client();
     let runtime = get_runtime();
 
     runtime.block_on(async {
-        client.fetch_and_parse_app(app_id, lang, country).await
+        client.fetch_and_parse_app(app_id, lang, country, Some(timeout)).await
     }).map_err(Into::into)
 }
 
@@ -283,20 +448,94 @@ fn fetch_and_parse_app(
 /// Raises:
 ///     Exception: If request or parsing fails
 #[pyfunction]
-#[pyo3(signature = (app_id, lang, country, sort=1, continuation_token=None, _timeout=30))]
+#[pyo3(signature = (app_id, lang, country, sort=1, continuation_token=None, timeout=None, _timeout=None))]
+#[allow(clippy::duplicate_underscore_argument, clippy::too_many_arguments)] // `_timeout` is the legacy keyword name
 fn fetch_and_parse_reviews(
+    py: Python<'_>,
     app_id: &str,
     lang: &str,
     country: &str,
     sort: u8,
     continuation_token: Option<&str>,
-    _timeout: u64,  // Ignored, using global client timeout
+    timeout: Option<u64>,
+    _timeout: Option<u64>,
 ) -> PyResult<(Vec<RustReview>, Option<String>)> {
+    let timeout = request_timeout(py, timeout, _timeout)?;
+    let client = get_client();
+    let runtime = get_runtime();
+
+    runtime.block_on(async {
+        client.fetch_and_parse_reviews(app_id, lang, country, sort, continuation_token, Some(timeout)).await
+    }).map_err(Into::into)
+}
+
+/// Export reviews to a JSON-lines file, paginating until `max_reviews`
+///
+/// Reviews are written and flushed page by page, so memory stays bounded and
+/// a failed export leaves the reviews fetched so far on disk.
+///
+/// Args:
+///     app_id (str): App package ID or Play Store URL
+///     lang (str): Language code
+///     country (str): Country code
+///     sort (int): Sort order (1=newest, 2=highest rating, 3=most helpful)
+///     max_reviews (int): Maximum number of reviews to write
+///     path (str): Output file path (overwritten)
+///     timeout (int): Per-request timeout in seconds (default: 30)
+///
+/// Returns:
+///     int: Number of reviews written
+///
+/// Raises:
+///     Exception: If a request fails or the file cannot be written
+#[pyfunction]
+#[pyo3(signature = (app_id, lang, country, sort, max_reviews, path, timeout=30))]
+fn fetch_reviews_to_jsonl(
+    app_id: &str,
+    lang: &str,
+    country: &str,
+    sort: u8,
+    max_reviews: usize,
+    path: std::path::PathBuf,
+    timeout: u64,
+) -> PyResult<usize> {
+    let client = get_client();
+    let runtime = get_runtime();
+
+    runtime.block_on(async {
+        client.fetch_reviews_to_jsonl(app_id, lang, country, sort, max_reviews, &path, Some(Duration::from_secs(timeout))).await
+    }).map_err(Into::into)
+}
+
+/// Fetch the full list of apps similar to a package (GIL-free)
+///
+/// Uses the dedicated similar-apps request and follows pagination, so it
+/// returns more apps than the "Similar apps" cluster on the details page.
+///
+/// Args:
+///     app_id (str): App package ID or Play Store URL
+///     lang (str): Language code
+///     country (str): Country code
+///     timeout (int): Per-request timeout in seconds (default: 30)
+///
+/// Returns:
+///     list[RustSearchResult]: Similar apps, without duplicates
+///
+/// Raises:
+///     Exception: If request or parsing fails
+#[pyfunction]
+#[pyo3(signature = (app_id, lang, country, timeout=30))]
+fn fetch_similar_apps(
+    app_id: &str,
+    lang: &str,
+    country: &str,
+    timeout: u64,
+) -> PyResult<Vec<RustSearchResult>> {
     let client = get_client();
     let runtime = get_runtime();
 
     runtime.block_on(async {
-        client.fetch_and_parse_reviews(app_id, lang, country, sort, continuation_token).await
+        client.fetch_similar_apps(app_id, lang, country, Some(Duration::from_secs(timeout))).await
     }).map_err(Into::into)
 }
 
@@ -314,18 +553,22 @@ fn fetch_and_parse_reviews(
 /// Raises:
 ///     Exception: If request or parsing fails
 #[pyfunction]
-#[pyo3(signature = (query, lang, country, _timeout=30))]
+#[pyo3(signature = (query, lang, country, timeout=None, _timeout=None))]
+#[allow(clippy::duplicate_underscore_argument)] // `_timeout` is the legacy keyword name
 fn fetch_and_parse_search(
+    py: Python<'_>,
     query: &str,
     lang: &str,
     country: &str,
-    _timeout: u64,  // Ignored, using global client timeout
+    timeout: Option<u64>,
+    _timeout: Option<u64>,
 ) -> PyResult<Vec<RustSearchResult>> {
+    let timeout = request_timeout(py, timeout, _timeout)?;
     let client = get_client();
     let runtime = get_runtime();
 
     runtime.block_on(async {
-        client.fetch_and_parse_search(query, lang, country).await
+        client.fetch_and_parse_search(query, lang, country, Some(timeout)).await
     }).map_err(Into::into)
 }
 
@@ -336,7 +579,9 @@ fn fetch_and_parse_search(
 ///     collection (str): Collection type (e.g., "topselling_free")
 ///     lang (str): Language code
 ///     country (str): Country code
-///     num (int): Number of results (default: 100, max: 250)
+///     num (int): Number of results (default: 100). Above 250, pages of 250
+///         are fetched and merged via continuation tokens until `num` apps
+///         are collected or the list ends.
 ///     timeout (int): Request timeout in seconds (default: 30)
 ///
 /// Returns:
@@ -345,20 +590,64 @@ fn fetch_and_parse_search(
 /// Raises:
 ///     Exception: If request or parsing fails
 #[pyfunction]
-#[pyo3(signature = (category, collection, lang, country, num=100, _timeout=30))]
+#[pyo3(signature = (category, collection, lang, country, num=100, timeout=None, _timeout=None))]
+#[allow(clippy::duplicate_underscore_argument, clippy::too_many_arguments)] // `_timeout` is the legacy keyword name
 fn fetch_and_parse_list(
+    py: Python<'_>,
     category: Option<&str>,
     collection: &str,
     lang: &str,
     country: &str,
     num: u32,
-    _timeout: u64,  // Ignored, using global client timeout
+    timeout: Option<u64>,
+    _timeout: Option<u64>,
 ) -> PyResult<Vec<RustSearchResult>> {
+    let timeout = request_timeout(py, timeout, _timeout)?;
+    let client = get_client();
+    let runtime = get_runtime();
+
+    runtime.block_on(async {
+        client.fetch_and_parse_list(category, collection, lang, country, num, Some(timeout)).await
+    }).map_err(Into::into)
+}
+
+/// Fetch one page of list results with its continuation token (GIL-free)
+///
+/// Args:
+///     category (str | None): Category code (e.g., "GAME_ACTION"), None for all apps
+///     collection (str): Collection type (e.g., "topselling_free")
+///     lang (str): Language code
+///     country (str): Country code
+///     num (int): Number of results per page (default: 100, clamped to 250)
+///     token (str | None): Continuation token from the previous page (None for the first page)
+///     timeout (int): Request timeout in seconds (default: 30)
+///
+/// Returns:
+///     tuple: (list[RustSearchResult], str | None) - Apps and next continuation token
+///
+/// Raises:
+///     Exception: If request or parsing fails
+///
+/// Example:
+///     >>> apps, token = fetch_and_parse_list_page(None, "topselling_free", "en", "us", 50)
+///     >>> while token:
+///     ...     more, token = fetch_and_parse_list_page(None, "topselling_free", "en", "us", 50, token)
+#[pyfunction]
+#[pyo3(signature = (category, collection, lang, country, num=100, token=None, timeout=30))]
+fn fetch_and_parse_list_page(
+    category: Option<&str>,
+    collection: &str,
+    lang: &str,
+    country: &str,
+    num: u32,
+    token: Option<String>,
+    timeout: u64,
+) -> PyResult<(Vec<RustSearchResult>, Option<String>)> {
     let client = get_client();
     let runtime = get_runtime();
 
     runtime.block_on(async {
-        client.fetch_and_parse_list(category, collection, lang, country, num).await
+        client.fetch_and_parse_list_page(category, collection, lang, country, num, token.as_deref(), Some(Duration::from_secs(timeout))).await
     }).map_err(Into::into)
 }
 
@@ -393,7 +682,7 @@ fn fetch_and_parse_apps_batch(
     runtime.block_on(async {
         let futures: Vec<_> = requests.iter()
             .map(|(app_id, lang, country)| {
-                client.fetch_and_parse_app(app_id, lang, country)
+                client.fetch_and_parse_app(app_id, lang, country, None)
             })
             .collect();
 
@@ -434,7 +723,8 @@ fn fetch_and_parse_list_batch(
                     collection,
                     lang,
                     country,
-                    *num
+                    *num,
+                    None
                 )
             })
             .collect();
@@ -463,7 +753,7 @@ fn fetch_and_parse_search_batch(
     runtime.block_on(async {
         let futures: Vec<_> = requests.iter()
             .map(|(query, lang, country)| {
-                client.fetch_and_parse_search(query, lang, country)
+                client.fetch_and_parse_search(query, lang, country, None)
             })
             .collect();
 
@@ -496,7 +786,8 @@ fn fetch_and_parse_reviews_batch(
                     lang,
                     country,
                     *sort,
-                    continuation_token.as_deref()
+                    continuation_token.as_deref(),
+                    None
                 )
             })
             .collect();
@@ -533,6 +824,42 @@ fn extract_apk_info(apk_path: &str) -> PyResult<(usize, bool, bool, Vec<String>)
     ))
 }
 
+/// Describe a split APK bundle (`.xapk` / `.apks`)
+///
+/// The base APK is the split carrying both the manifest and code; DEX files
+/// from feature splits are listed as "<split>!classes.dex". All other APK
+/// functions accept bundles too and analyze the base plus merged DEX.
+///
+/// Args:
+///     path (str): Path to the bundle
+///
+/// Returns:
+///     dict: Dictionary with keys: base_apk, splits, dex_files, has_resources
+///
+/// Raises:
+///     ValueError: If the file is a plain APK rather than a bundle
+///     Exception: If the bundle cannot be opened or has no base APK
+#[pyfunction]
+fn analyze_bundle(py: Python<'_>, path: &str) -> PyResult<Py<pyo3::types::PyAny>> {
+    let extractor = ApkExtractor::new(path)
+        .map_err(error::PlayfastError::from)?;
+    let layout = extractor.bundle().ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!("Not a split APK bundle: {}", path))
+    })?;
+
+    let dex_files: Vec<String> = extractor.dex_entries()
+        .iter()
+        .map(|e| e.name.clone())
+        .collect();
+
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("base_apk", &layout.base_apk)?;
+    dict.set_item("splits", &layout.splits)?;
+    dict.set_item("dex_files", dex_files)?;
+    dict.set_item("has_resources", extractor.has_resources())?;
+    Ok(dict.into())
+}
+
 /// Extract AndroidManifest.xml from APK (returns raw binary XML)
 ///
 /// Args:
@@ -551,10 +878,61 @@ fn extract_manifest_raw(apk_path: &str) -> PyResult<Vec<u8>> {
         .map_err(|e| error::PlayfastError::from(e).into())
 }
 
+/// List every entry of an APK
+///
+/// Only the ZIP central directory is read; nothing is decompressed. For a
+/// split bundle these are the bundle's own entries (the APKs inside it).
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     list[dict]: One dict per entry, in archive order, with keys: name,
+///     size, compressed_size, crc32
+///
+/// Raises:
+///     Exception: If APK cannot be opened or is invalid
+#[pyfunction]
+fn list_apk_entries(py: Python<'_>, apk_path: &str) -> PyResult<Vec<Py<pyo3::types::PyAny>>> {
+    let entries = apk::extractor::read_zip_entries(apk_path)
+        .map_err(error::PlayfastError::from)?;
+
+    entries
+        .iter()
+        .map(|entry| {
+            let dict = pyo3::types::PyDict::new(py);
+            dict.set_item("name", &entry.name)?;
+            dict.set_item("size", entry.size)?;
+            dict.set_item("compressed_size", entry.compressed_size)?;
+            dict.set_item("crc32", entry.crc32)?;
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+/// Extract any entry from an APK by name (e.g. "assets/config.json")
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///     name (str): Full entry name as listed by `list_apk_entries`
+///
+/// Returns:
+///     bytes: Uncompressed entry contents
+///
+/// Raises:
+///     Exception: If APK cannot be opened or has no such entry
+#[pyfunction]
+fn extract_apk_entry(apk_path: &str, name: &str) -> PyResult<Vec<u8>> {
+    apk::extractor::read_zip_entry(apk_path, name)
+        .map_err(|e| error::PlayfastError::from(e).into())
+}
+
 /// Parse AndroidManifest.xml from APK
 ///
 /// Args:
 ///     apk_path (str): Path to the APK file
+///     resolve_resources (bool): Replace `@string/...` labels with their text
+///         from resources.arsc, when the APK has one (default: True)
 ///
 /// Returns:
 ///     RustManifestInfo: Parsed manifest information including package name,
@@ -563,20 +941,81 @@ fn extract_manifest_raw(apk_path: &str) -> PyResult<Vec<u8>> {
 /// Raises:
 ///     Exception: If APK cannot be opened, manifest not found, or parsing fails
 #[pyfunction]
-fn parse_manifest_from_apk(apk_path: &str) -> PyResult<RustManifestInfo> {
+#[pyo3(signature = (apk_path, resolve_resources=true))]
+fn parse_manifest_from_apk(apk_path: &str, resolve_resources: bool) -> PyResult<RustManifestInfo> {
     let extractor = ApkExtractor::new(apk_path)
         .map_err(|e| error::PlayfastError::from(e))?;
+
+    // resources.arsc is optional; keep the raw labels if missing or unparseable
+    let resolver = if resolve_resources {
+        extractor
+            .extract_resources()
+            .ok()
+            .and_then(|bytes| apk::resources::ResourceResolver::from_bytes(bytes).ok())
+    } else {
+        None
+    };
+
+    manifest_with_resources(&extractor, resolver.as_ref())
+}
+
+/// Parse the APK's manifest, resolving string-reference labels with `resolver`
+fn manifest_with_resources(
+    extractor: &ApkExtractor,
+    resolver: Option<&apk::resources::ResourceResolver>,
+) -> PyResult<RustManifestInfo> {
     let manifest_data = extractor.extract_manifest()
         .map_err(|e| error::PlayfastError::from(e))?;
-    parse_manifest(&manifest_data)
-        .map_err(|e| error::PlayfastError::from(e).into())
+    Ok(parse_manifest_with_resources(&manifest_data, resolver)
+        .map_err(error::PlayfastError::from)?)
 }
 
-/// Extract all classes from an APK file
+/// Find the launcher activity of an APK
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     str | None: First activity with a MAIN/LAUNCHER intent filter, or None
+///
+/// Raises:
+///     Exception: If APK cannot be opened, manifest not found, or parsing fails
+#[pyfunction]
+fn find_main_activity(apk_path: &str) -> PyResult<Option<String>> {
+    Ok(parse_manifest_from_apk(apk_path, false)?.get_launcher_activities().into_iter().next())
+}
+
+/// Find exported components that no permission guards
+///
+/// A component is exported through `android:exported="true"` or, when the
+/// attribute is absent, by declaring an intent filter (providers: by
+/// targeting API 16 or lower). Components with an `android:permission`
+/// attribute are considered protected.
 ///
 /// Args:
 ///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     list[str]: Fully qualified names of unprotected exported activities,
+///     services, receivers and providers, in manifest order
+///
+/// Raises:
+///     Exception: If APK cannot be opened, manifest not found, or parsing fails
+#[pyfunction]
+fn find_unprotected_exported_components(apk_path: &str) -> PyResult<Vec<String>> {
+    Ok(parse_manifest_from_apk(apk_path, false)?.unprotected_exported_components())
+}
+
+/// Extract all classes from an APK file
+///
+/// For split bundles (.xapk/.apks), classes from feature-module splits are
+/// included and each class's `source_module` names the module it came from.
+///
+/// Args:
+///     apk_path (str): Path to the APK file or split bundle
 ///     parallel (bool): Use parallel processing (default: True)
+///     include_splits (bool): For bundles, also load DEX files from feature
+///         splits rather than only the base APK (default: True)
 ///
 /// Returns:
 ///     list[RustDexClass]: List of all classes from all DEX files
@@ -584,12 +1023,17 @@ fn parse_manifest_from_apk(apk_path: &str) -> PyResult<RustManifestInfo> {
 /// Raises:
 ///     Exception: If APK cannot be opened or DEX parsing fails
 #[pyfunction]
-#[pyo3(signature = (apk_path, parallel=true))]
-fn extract_classes_from_apk(apk_path: &str, parallel: bool) -> PyResult<Vec<RustDexClass>> {
+#[pyo3(signature = (apk_path, parallel=true, include_splits=true))]
+fn extract_classes_from_apk(apk_path: &str, parallel: bool, include_splits: bool) -> PyResult<Vec<RustDexClass>> {
     let extractor = ApkExtractor::new(apk_path)
         .map_err(|e| error::PlayfastError::from(e))?;
 
-    let dex_entries = extractor.dex_entries().to_vec();
+    let dex_entries: Vec<_> = extractor
+        .dex_entries()
+        .iter()
+        .filter(|e| include_splits || e.is_base_module())
+        .cloned()
+        .collect();
     let container = dex::container::DexContainer::new(dex_entries);
 
     let classes = if parallel {
@@ -601,6 +1045,243 @@ fn extract_classes_from_apk(apk_path: &str, parallel: bool) -> PyResult<Vec<Rust
     classes.map_err(|e| error::PlayfastError::from(e).into())
 }
 
+/// List every method referenced by the APK's DEX files
+///
+/// Unlike `extract_classes_from_apk`, which only covers classes defined in the
+/// APK, this walks the method_ids tables and therefore also includes external
+/// methods the app calls (e.g. framework APIs). Duplicates across DEX files
+/// are removed.
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     list[str]: Signatures like "android.app.Activity.onCreate(android.os.Bundle): void"
+///
+/// Raises:
+///     Exception: If APK cannot be opened or a DEX file is invalid
+#[pyfunction]
+fn list_all_methods(apk_path: &str) -> PyResult<Vec<String>> {
+    collect_signatures(apk_path, |parser| parser.all_method_signatures())
+}
+
+/// List methods the APK defines itself, i.e. those with an entry in class_data
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     list[str]: Signatures of methods belonging to the app's own classes
+///
+/// Raises:
+///     Exception: If APK cannot be opened or a DEX file is invalid
+#[pyfunction]
+fn list_defined_methods(apk_path: &str) -> PyResult<Vec<String>> {
+    collect_signatures(apk_path, |parser| parser.defined_method_signatures())
+}
+
+/// List methods the APK calls but does not define (framework/library APIs)
+///
+/// A method defined in one DEX file and referenced from another is treated
+/// as defined, so multidex apps don't report their own code as external.
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     list[str]: Signatures of external methods
+///
+/// Raises:
+///     Exception: If APK cannot be opened or a DEX file is invalid
+#[pyfunction]
+fn list_external_methods(apk_path: &str) -> PyResult<Vec<String>> {
+    let extractor = ApkExtractor::new(apk_path).map_err(error::PlayfastError::from)?;
+    let defined: std::collections::HashSet<String> =
+        collect_extractor_signatures(&extractor, |parser| parser.defined_method_signatures())?
+            .into_iter()
+            .collect();
+
+    Ok(collect_extractor_signatures(&extractor, |parser| parser.referenced_method_signatures())?
+        .into_iter()
+        .filter(|signature| !defined.contains(signature))
+        .collect())
+}
+
+/// List every field referenced by the APK's DEX files
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     list[str]: Signatures like "com.example.Config.DEBUG: boolean"
+///
+/// Raises:
+///     Exception: If APK cannot be opened or a DEX file is invalid
+#[pyfunction]
+fn list_all_fields(apk_path: &str) -> PyResult<Vec<String>> {
+    collect_signatures(apk_path, |parser| parser.all_field_signatures())
+}
+
+/// Collect signatures from every DEX file, keeping first-seen order
+fn collect_signatures<F>(apk_path: &str, signatures: F) -> PyResult<Vec<String>>
+where
+    F: Fn(&dex::parser::DexParser) -> dex::error::Result<Vec<String>>,
+{
+    let extractor = ApkExtractor::new(apk_path).map_err(error::PlayfastError::from)?;
+    collect_extractor_signatures(&extractor, signatures)
+}
+
+/// Collect signatures from every DEX file of an opened APK, keeping first-seen order
+fn collect_extractor_signatures<F>(extractor: &ApkExtractor, signatures: F) -> PyResult<Vec<String>>
+where
+    F: Fn(&dex::parser::DexParser) -> dex::error::Result<Vec<String>>,
+{
+    let mut seen = std::collections::HashSet::new();
+    let mut results = Vec::new();
+
+    for dex_entry in extractor.dex_entries() {
+        let parser = dex::parser::DexParser::from_arc(dex_entry.data.clone())
+            .map_err(error::PlayfastError::from)?;
+
+        for signature in signatures(&parser).map_err(error::PlayfastError::from)? {
+            if seen.insert(signature.clone()) {
+                results.push(signature);
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Iterate over classes in an APK without loading them all at once
+///
+/// Classes are parsed lazily, one per iteration, across all DEX files.
+/// Prefer this over `extract_classes_from_apk` for very large APKs or
+/// memory-limited environments.
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     ClassIterator: Iterator yielding RustDexClass objects
+///
+/// Raises:
+///     Exception: If APK cannot be opened or a DEX file is invalid
+#[pyfunction]
+fn iter_classes_from_apk(apk_path: &str) -> PyResult<ClassIterator> {
+    let extractor = ApkExtractor::new(apk_path)
+        .map_err(error::PlayfastError::from)?;
+
+    let dex_entries = extractor.dex_entries().to_vec();
+    Ok(ClassIterator::new(DexContainer::new(dex_entries)))
+}
+
+/// Iterate over methods matching a filter without materializing classes
+///
+/// A streaming alternative to `search_methods` for method-only queries on
+/// large APKs: only class headers and method metadata are parsed, and
+/// fields are skipped entirely. Matches come in the same order as
+/// `search_methods(..., parallel=False)`, but each class carries header
+/// information only (its `methods` and `fields` lists are empty).
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///     class_filter (ClassFilter): Filter for selecting classes
+///     method_filter (MethodFilter): Filter for selecting methods
+///
+/// Returns:
+///     MethodIterator: Iterator yielding (RustDexClass, RustDexMethod) tuples
+///
+/// Raises:
+///     Exception: If APK cannot be opened or a DEX file is invalid
+#[pyfunction]
+fn iter_methods_from_apk(
+    apk_path: &str,
+    class_filter: &ClassFilter,
+    method_filter: &MethodFilter,
+) -> PyResult<MethodIterator> {
+    let extractor = ApkExtractor::new(apk_path)
+        .map_err(error::PlayfastError::from)?;
+
+    let dex_entries = extractor.dex_entries().to_vec();
+    Ok(MethodIterator::new(DexContainer::new(dex_entries), class_filter.clone(), method_filter.clone()))
+}
+
+/// Find which DEX file of an APK defines a class
+///
+/// Useful for multidex layout work: classes outside the primary DEX are
+/// loaded later during startup.
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///     class_name (str): Fully qualified class name (e.g. "com.example.MainActivity")
+///
+/// Returns:
+///     tuple[str, int] | None: DEX file name and its index, or None if not found
+///
+/// Raises:
+///     Exception: If APK cannot be opened or a DEX file is invalid
+#[pyfunction]
+fn find_class_dex(apk_path: &str, class_name: &str) -> PyResult<Option<(String, usize)>> {
+    let extractor = ApkExtractor::new(apk_path)
+        .map_err(error::PlayfastError::from)?;
+
+    DexContainer::new(extractor.dex_entries().to_vec())
+        .find_class_dex(class_name)
+        .map_err(|e| error::PlayfastError::from(e).into())
+}
+
+/// Find a defined method from a Dalvik method descriptor
+///
+/// Accepts the form emitted by hooking tools such as Frida
+/// (`Lcom/example/Foo;->bar(I)V`) as well as dotted forms
+/// (`com.example.Foo->bar(I)V`, `com.example.Foo.bar(I)V`). Without a
+/// signature, the first method with a matching name is returned.
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///     descriptor (str): Method descriptor
+///
+/// Returns:
+///     tuple[RustDexClass, RustDexMethod] | None: The declaring class and
+///     method, or None if the APK does not define it
+///
+/// Raises:
+///     ValueError: If the descriptor cannot be parsed
+///     Exception: If APK cannot be opened or a DEX file is invalid
+#[pyfunction]
+fn find_method_by_descriptor(apk_path: &str, descriptor: &str) -> PyResult<Option<(RustDexClass, RustDexMethod)>> {
+    let descriptor = MethodDescriptor::parse(descriptor).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!("Invalid method descriptor: {}", descriptor))
+    })?;
+    let extractor = ApkExtractor::new(apk_path)
+        .map_err(error::PlayfastError::from)?;
+
+    DexContainer::new(extractor.dex_entries().to_vec())
+        .find_method_by_descriptor(&descriptor)
+        .map_err(|e| error::PlayfastError::from(e).into())
+}
+
+/// Count the classes defined in each DEX file of an APK
+///
+/// Args:
+///     apk_path (str): Path to the APK file
+///
+/// Returns:
+///     dict[str, int]: Class count keyed by DEX file name
+///
+/// Raises:
+///     Exception: If APK cannot be opened or a DEX file is invalid
+#[pyfunction]
+fn classes_per_dex(apk_path: &str) -> PyResult<std::collections::HashMap<String, usize>> {
+    let extractor = ApkExtractor::new(apk_path)
+        .map_err(error::PlayfastError::from)?;
+
+    DexContainer::new(extractor.dex_entries().to_vec())
+        .classes_per_dex()
+        .map_err(|e| error::PlayfastError::from(e).into())
+}
+
 /// Search for classes matching a filter in an APK
 ///
 /// Args:
@@ -721,6 +1402,22 @@ fn search_methods(
 ///
 /// All functions release the GIL for true parallel execution.
 ///
+/// Deprecated names live in the `playfast.core.legacy` submodule, which maps
+/// them onto the current API:
+///
+///     legacy.analyze_webview_flows_from_apk   -> find_webview_flows_from_apk
+///     legacy.create_webview_analyzer_from_apk -> create_data_flow_analyzer
+///     legacy.WebViewFlow                      -> Flow
+///     legacy.WebViewFlowAnalyzer              -> DataFlowAnalyzer
+///
+/// The two functions are still importable from `core` directly for existing
+/// callers, but emit a DeprecationWarning either way.
+///
+/// `fetch_and_parse_app`, `fetch_and_parse_reviews`, `fetch_and_parse_search`
+/// and `fetch_and_parse_list` still accept the old `_timeout` keyword as an
+/// alias for `timeout`. It emits a DeprecationWarning, and passing both is a
+/// TypeError.
+///
 /// Note: `gil_used = false` declares this module is safe to run without the GIL.
 /// This only affects Python 3.14t (free-threading). On Python 3.13 and earlier,
 /// this attribute is ignored and has no effect.
@@ -738,12 +1435,19 @@ fn core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(build_list_request_body, m)?)?;
     m.add_function(wrap_pyfunction!(parse_batchexecute_reviews_response, m)?)?;
     m.add_function(wrap_pyfunction!(build_reviews_request_body, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_batchexecute_similar_response, m)?)?;
+    m.add_function(wrap_pyfunction!(build_similar_request_body, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_play_url, m)?)?;
 
     // New combined HTTP+parsing functions (recommended for performance)
     m.add_function(wrap_pyfunction!(fetch_and_parse_app, m)?)?;
     m.add_function(wrap_pyfunction!(fetch_and_parse_reviews, m)?)?;
+    m.add_function(wrap_pyfunction!(fetch_reviews_to_jsonl, m)?)?;
+    m.add_function(wrap_pyfunction!(fetch_similar_apps, m)?)?;
     m.add_function(wrap_pyfunction!(fetch_and_parse_search, m)?)?;
     m.add_function(wrap_pyfunction!(fetch_and_parse_list, m)?)?;
+    m.add_function(wrap_pyfunction!(fetch_and_parse_list_page, m)?)?;
+    m.add_function(wrap_pyfunction!(configure_base_url, m)?)?;
 
     // Batch functions for true parallel processing in Rust
     m.add_function(wrap_pyfunction!(fetch_and_parse_apps_batch, m)?)?;
@@ -752,22 +1456,44 @@ fn core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(fetch_and_parse_list_batch, m)?)?;
 
     // DEX and APK analysis functions
+    m.add_function(wrap_pyfunction!(configure_parallelism, m)?)?;
     m.add_function(wrap_pyfunction!(extract_apk_info, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_bundle, m)?)?;
     m.add_function(wrap_pyfunction!(extract_manifest_raw, m)?)?;
+    m.add_function(wrap_pyfunction!(list_apk_entries, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_apk_entry, m)?)?;
     m.add_function(wrap_pyfunction!(parse_manifest_from_apk, m)?)?;
+    m.add_function(wrap_pyfunction!(find_main_activity, m)?)?;
+    m.add_function(wrap_pyfunction!(find_unprotected_exported_components, m)?)?;
     m.add_function(wrap_pyfunction!(extract_classes_from_apk, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_classes_from_apk, m)?)?;
+    m.add_function(wrap_pyfunction!(iter_methods_from_apk, m)?)?;
+    m.add_function(wrap_pyfunction!(find_class_dex, m)?)?;
+    m.add_function(wrap_pyfunction!(classes_per_dex, m)?)?;
+    m.add_function(wrap_pyfunction!(find_method_by_descriptor, m)?)?;
+    m.add_function(wrap_pyfunction!(list_all_methods, m)?)?;
+    m.add_function(wrap_pyfunction!(list_all_fields, m)?)?;
+    m.add_function(wrap_pyfunction!(list_defined_methods, m)?)?;
+    m.add_function(wrap_pyfunction!(list_external_methods, m)?)?;
     m.add_function(wrap_pyfunction!(search_classes, m)?)?;
     m.add_function(wrap_pyfunction!(search_methods, m)?)?;
 
     // Bytecode analysis functions
     m.add_function(wrap_pyfunction!(decode_bytecode, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_instruction_at, m)?)?;
     m.add_function(wrap_pyfunction!(extract_constants, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_string_constants, m)?)?;
     m.add_function(wrap_pyfunction!(extract_method_calls, m)?)?;
+    m.add_function(wrap_pyfunction!(opcode_histogram, m)?)?;
     m.add_function(wrap_pyfunction!(extract_methods_bytecode, m)?)?;
     m.add_function(wrap_pyfunction!(get_method_bytecode_from_apk, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_method_calls_from_apk, m)?)?;
+    m.add_function(wrap_pyfunction!(preview_method, m)?)?;
+    m.add_function(wrap_pyfunction!(apk_opcode_profile, m)?)?;
 
     // Method resolution functions
     m.add_function(wrap_pyfunction!(create_method_resolver, m)?)?;
+    m.add_function(wrap_pyfunction!(create_apk_method_resolver, m)?)?;
     m.add_function(wrap_pyfunction!(resolve_method_from_apk, m)?)?;
 
     // Expression reconstruction functions (Phase 2)
@@ -780,19 +1506,47 @@ fn core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Resources.arsc parsing
     m.add_function(wrap_pyfunction!(parse_resources_from_apk, m)?)?;
 
+    // Network security config
+    m.add_function(wrap_pyfunction!(network_security_report, m)?)?;
+
+    // APK size breakdown
+    m.add_function(wrap_pyfunction!(apk_size_breakdown, m)?)?;
+
+    // APK health check
+    m.add_class::<ApkHealth>()?;
+    m.add_function(wrap_pyfunction!(validate_apk, m)?)?;
+
+    // APK signing certificates
+    m.add_class::<CertInfo>()?;
+    m.add_function(wrap_pyfunction!(extract_signing_info, m)?)?;
+
+    // DEX integrity
+    m.add_class::<DexIntegrity>()?;
+    m.add_function(wrap_pyfunction!(verify_dex_integrity, m)?)?;
+
+    // API level compatibility
+    m.add_function(wrap_pyfunction!(find_api_level_violations, m)?)?;
+
+    // Hardcoded secrets
+    m.add_function(wrap_pyfunction!(find_secrets, m)?)?;
+
     // Entry point analysis
     m.add_function(wrap_pyfunction!(analyze_entry_points_from_apk, m)?)?;
 
     // Call graph analysis
     m.add_function(wrap_pyfunction!(build_call_graph_from_apk, m)?)?;
     m.add_function(wrap_pyfunction!(build_call_graph_from_apk_parallel, m)?)?;
+    m.add_function(wrap_pyfunction!(build_call_graph_with_stats, m)?)?;
 
     // Data flow analysis (generic, WebView is a special case)
     m.add_function(wrap_pyfunction!(create_data_flow_analyzer, m)?)?;
     m.add_function(wrap_pyfunction!(find_flows_from_apk, m)?)?;
+    m.add_function(wrap_pyfunction!(find_data_flows_from_apk, m)?)?;
     m.add_function(wrap_pyfunction!(find_webview_flows_from_apk, m)?)?;
     m.add_function(wrap_pyfunction!(find_file_flows_from_apk, m)?)?;
     m.add_function(wrap_pyfunction!(find_network_flows_from_apk, m)?)?;
+    m.add_function(wrap_pyfunction!(find_weak_crypto_from_apk, m)?)?;
+    m.add_function(wrap_pyfunction!(deeplink_attack_surface, m)?)?;
 
     // Backward compatibility (deprecated)
     m.add_function(wrap_pyfunction!(analyze_webview_flows_from_apk, m)?)?;
@@ -809,18 +1563,27 @@ fn core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<RustDexMethod>()?;
     m.add_class::<RustDexField>()?;
     m.add_class::<RustReferencePool>()?;
+    m.add_class::<ClassIterator>()?;
+    m.add_class::<MethodIterator>()?;
     m.add_class::<RustManifestInfo>()?;
     m.add_class::<IntentFilterData>()?;
     m.add_class::<ActivityIntentFilter>()?;
+    m.add_class::<DeclaredPermission>()?;
+    m.add_class::<ProviderInfo>()?;
     m.add_class::<RustInstruction>()?;
     m.add_class::<MethodSignature>()?;
     m.add_class::<MethodResolverPy>()?;
+    m.add_class::<DexInspector>()?;
     m.add_class::<ReconstructedExpression>()?;
     m.add_class::<ExpressionBuilderPy>()?;
     m.add_class::<DecompiledClass>()?;
     m.add_class::<DecompiledMethod>()?;
     m.add_class::<PyResourceResolver>()?;
     m.add_class::<PyResolvedResource>()?;
+    m.add_class::<DomainConfig>()?;
+    m.add_class::<NetworkSecurityReport>()?;
+    m.add_class::<ApiLevelViolation>()?;
+    m.add_class::<SecretFinding>()?;
     m.add_class::<EntryPoint>()?;
     m.add_class::<ComponentType>()?;
     m.add_class::<PyEntryPointAnalyzer>()?;
@@ -828,12 +1591,18 @@ fn core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<CallPath>()?;
     m.add_class::<MethodCall>()?;
     m.add_class::<PyCallGraphBuilder>()?;
+    m.add_class::<BuildStats>()?;
     // New generic API
     m.add_class::<Flow>()?;
     m.add_class::<DataFlow>()?;
+    m.add_class::<DataSourceKind>()?;
+    m.add_class::<TaintMode>()?;
+    m.add_class::<DeeplinkFinding>()?;
+    m.add_class::<WeakCryptoUse>()?;
     m.add_class::<DataFlowAnalyzer>()?;
 
-    // Backward compatibility (WebViewFlow/WebViewFlowAnalyzer are type aliases)
+    // Backward compatibility: deprecated names, namespaced under core.legacy
+    register_legacy(m)?;
 
     // Add DEX filter classes
     m.add_class::<ClassFilter>()?;
@@ -844,3 +1613,157 @@ fn core(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     Ok(())
 }
+
+/// Build the `core.legacy` submodule holding deprecated aliases
+fn register_legacy(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    let legacy = PyModule::new(py, "legacy")?;
+    legacy.add("__doc__", "Deprecated aliases kept for backward compatibility; see playfast.core for replacements.")?;
+    legacy.add_function(wrap_pyfunction!(analyze_webview_flows_from_apk, &legacy)?)?;
+    legacy.add_function(wrap_pyfunction!(create_webview_analyzer_from_apk, &legacy)?)?;
+    legacy.add("WebViewFlow", py.get_type::<Flow>())?;
+    legacy.add("WebViewFlowAnalyzer", py.get_type::<DataFlowAnalyzer>())?;
+    m.add_submodule(&legacy)?;
+
+    // Make `from playfast.core.legacy import ...` work, not just attribute access
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item("playfast.core.legacy", &legacy)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configure_base_url() {
+        configure_base_url("http://127.0.0.1:8080/").unwrap();
+        assert_eq!(get_client().base_url(), "http://127.0.0.1:8080");
+
+        // A rejected URL leaves the current client in place
+        assert!(configure_base_url("ftp://example.com").is_err());
+        assert_eq!(get_client().base_url(), "http://127.0.0.1:8080");
+
+        configure_base_url("https://play.google.com").unwrap();
+        assert_eq!(get_client().base_url(), "https://play.google.com");
+    }
+
+    #[test]
+    fn test_request_timeout_alias() {
+        use pyo3::exceptions::{PyDeprecationWarning, PyTypeError};
+
+        Python::initialize();
+        Python::attach(|py| {
+            assert_eq!(request_timeout(py, None, None).unwrap(), Duration::from_secs(30));
+            assert_eq!(request_timeout(py, Some(10), None).unwrap(), Duration::from_secs(10));
+
+            let err = request_timeout(py, Some(10), Some(5)).unwrap_err();
+            assert!(err.is_instance_of::<PyTypeError>(py));
+
+            let warnings = py.import("warnings").unwrap();
+            let catch = warnings.call_method0("catch_warnings").unwrap();
+            catch.call_method0("__enter__").unwrap();
+
+            // With warnings raised as errors, the alias fails with a DeprecationWarning
+            warnings.call_method1("simplefilter", ("error",)).unwrap();
+            let err = request_timeout(py, None, Some(5)).unwrap_err();
+            assert!(err.is_instance_of::<PyDeprecationWarning>(py));
+
+            warnings.call_method1("simplefilter", ("ignore",)).unwrap();
+            assert_eq!(request_timeout(py, None, Some(5)).unwrap(), Duration::from_secs(5));
+
+            catch.call_method1("__exit__", (py.None(), py.None(), py.None())).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_list_external_methods_across_dex_files() {
+        use dex::test_support::{sample_dex, write_test_apk, DexBuilder};
+
+        // A second DEX calling Helper.log (defined in the first) and Activity.finish
+        let mut builder = DexBuilder::new();
+        let log = builder.method("Lcom/example/Helper;", "log", "V", &["Ljava/lang/String;"]);
+        let finish = builder.method("Landroid/app/Activity;", "finish", "V", &[]);
+        let run = builder.method("Lcom/example/Worker;", "run", "V", &[]);
+        let class = builder.class("Lcom/example/Worker;", Some("Ljava/lang/Object;"), 0x0001);
+        builder.virtual_method(
+            class,
+            run,
+            0x0001,
+            Some(vec![0x1071, log as u16, 0x0000, 0x1071, finish as u16, 0x0000, 0x000e]),
+        );
+
+        let apk = write_test_apk("external-methods", &[sample_dex(), builder.build()]);
+        let external = list_external_methods(apk.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&apk).ok();
+
+        assert_eq!(external.len(), 2);
+        assert!(external.iter().all(|signature| signature.starts_with("android.app.Activity")));
+        assert!(external.iter().any(|signature| signature.contains("finish")));
+    }
+
+    #[test]
+    fn test_manifest_labels_from_apk() {
+        use apk::binary_xml::XmlValue;
+        use apk::resources::{ResolvedResource, ResourceData, ResourceResolver};
+        use apk::test_support::{element, encode_binary_xml, s};
+        use dex::test_support::{sample_dex, write_test_apk_with_entries};
+
+        // android:label="@string/app_name"
+        let manifest = encode_binary_xml(
+            &element("manifest")
+                .with_attr("package", s("com.example.app"))
+                .with_child(element("application").with_attr("label", XmlValue::Reference(0x7f0b0001))),
+        );
+        let apk = write_test_apk_with_entries("manifest-labels", &[sample_dex()], &[("AndroidManifest.xml", &manifest)]);
+
+        // No resources.arsc: the reference is kept
+        let raw = parse_manifest_from_apk(apk.to_str().unwrap(), true).unwrap();
+        assert_eq!(raw.application_label.as_deref(), Some("0x7f0b0001"));
+
+        let resolver = ResourceResolver::from_resources(vec![ResolvedResource {
+            id: 0x7f0b0001,
+            type_name: "string".to_string(),
+            name: "app_name".to_string(),
+            value: ResourceData::String("Playfast Demo".to_string()),
+        }]);
+        let extractor = ApkExtractor::new(&apk).unwrap();
+        let resolved = manifest_with_resources(&extractor, Some(&resolver)).unwrap();
+        std::fs::remove_file(&apk).ok();
+        assert_eq!(resolved.application_label.as_deref(), Some("Playfast Demo"));
+    }
+
+    #[test]
+    fn test_configure_rayon_pool() {
+        // Asking for the thread count initializes the global pool with
+        // defaults, so the other tests keep one thread per CPU
+        let current = rayon::current_num_threads();
+
+        Python::initialize();
+        Python::attach(|py| {
+            let zero = configure_rayon_pool(0).unwrap_err();
+            assert!(zero.is_instance_of::<pyo3::exceptions::PyValueError>(py));
+
+            let resized = configure_rayon_pool(current + 1).unwrap_err();
+            assert!(resized.is_instance_of::<pyo3::exceptions::PyRuntimeError>(py));
+            assert!(resized.to_string().contains(&format!("already configured ({} threads)", current)));
+        });
+        assert_eq!(rayon::current_num_threads(), current);
+
+        // A local pool built from the same settings leaves the global pool
+        // alone for the other tests
+        let pool = rayon_pool_builder(3).build().unwrap();
+        let counted: Vec<(usize, String)> = pool.install(|| {
+            use rayon::prelude::*;
+            (0..64)
+                .into_par_iter()
+                .map(|_| {
+                    let name = std::thread::current().name().unwrap_or_default().to_string();
+                    (rayon::current_num_threads(), name)
+                })
+                .collect()
+        });
+        assert!(counted.iter().all(|(n, name)| *n == 3 && name.starts_with("playfast-rayon-")));
+    }
+}
@@ -214,6 +214,9 @@ pub struct RustSearchResult {
     #[pyo3(get)]
     pub score: Option<f32>,
 
+    #[pyo3(get)]
+    pub review_count: Option<u64>,
+
     #[pyo3(get)]
     pub price: f32,
 
@@ -230,6 +233,7 @@ impl RustSearchResult {
         dict.set_item("developer", &self.developer)?;
         dict.set_item("icon", &self.icon)?;
         dict.set_item("score", self.score)?;
+        dict.set_item("review_count", self.review_count)?;
         dict.set_item("price", self.price)?;
         dict.set_item("currency", &self.currency)?;
         Ok(dict.into())
@@ -435,6 +439,7 @@ mod tests {
             developer: "Test Dev".to_string(),
             icon: "icon.png".to_string(),
             score: Some(4.5),
+            review_count: Some(1200),
             price: 4.99,
             currency: "USD".to_string(),
         };
@@ -453,6 +458,7 @@ mod tests {
             developer: "Dev".to_string(),
             icon: "icon.png".to_string(),
             score: None,
+            review_count: None,
             price: 0.0,
             currency: "USD".to_string(),
         };
@@ -469,6 +475,7 @@ mod tests {
             developer: "Dev".to_string(),
             icon: "icon.png".to_string(),
             score: Some(4.0),
+            review_count: Some(1200),
             price: 0.0,
             currency: "USD".to_string(),
         };
@@ -486,6 +493,7 @@ mod tests {
             developer: "Dev".to_string(),
             icon: "icon.png".to_string(),
             score: Some(4.5),
+            review_count: Some(1200),
             price: 0.0,
             currency: "USD".to_string(),
         };
@@ -496,6 +504,7 @@ mod tests {
         let deserialized: RustSearchResult = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.app_id, result.app_id);
         assert_eq!(deserialized.score, result.score);
+        assert_eq!(deserialized.review_count, result.review_count);
     }
 
     #[test]
@@ -545,6 +554,7 @@ mod tests {
             developer: "Dev".to_string(),
             icon: "icon.png".to_string(),
             score: Some(4.0),
+            review_count: Some(1200),
             price: 0.0,
             currency: "USD".to_string(),
         };
@@ -215,6 +215,8 @@ fn parse_single_search_result(data: &Value) -> Result<RustSearchResult> {
         developer: extract_string(data, &[4, 0, 0, 0])?,
         icon: extract_string(data, &[1, 1, 0, 3, 2])?,
         score: extract_f32(data, &[6, 0, 2, 1, 1]),
+        // Same [text, score, [text, count]] block as the batchexecute format
+        review_count: extract_u64(data, &[6, 0, 2, 1, 2, 1]),
         price: extract_price(data)?,
         currency: extract_string(data, &[7, 0, 3, 2, 0, 2]).unwrap_or_else(|_| "USD".to_string()),
     })
@@ -260,6 +262,14 @@ fn parse_batchexecute_search_result(app: &Value) -> Result<RustSearchResult> {
         .and_then(|v| v.as_f64())
         .map(|f| f as f32);
 
+    // Rating count sits next to the score at [4][2][1]; absent for unrated
+    // apps and non-app cluster items
+    let review_count = app_data
+        .get(4)
+        .and_then(|v| v.get(2))
+        .and_then(|v| v.get(1))
+        .and_then(|v| v.as_u64());
+
     // Price and currency are at [8][1][0]
     // Structure: [price_in_micros, "CURRENCY", "display_string"]
     let price_data = app_data.get(8).and_then(|v| v.get(1)).and_then(|v| v.get(0));
@@ -282,6 +292,7 @@ fn parse_batchexecute_search_result(app: &Value) -> Result<RustSearchResult> {
         developer,
         icon,
         score,
+        review_count,
         price,
         currency,
     })
@@ -348,6 +359,11 @@ fn extract_f32(data: &Value, path: &[usize]) -> Option<f32> {
         .map(|f| f as f32)
 }
 
+/// Navigate JSON path and extract u64
+fn extract_u64(data: &Value, path: &[usize]) -> Option<u64> {
+    navigate_json(data, path).ok().and_then(|v| v.as_u64())
+}
+
 /// Navigate JSON path and extract i32
 fn extract_i32(data: &Value, path: &[usize]) -> Result<i32> {
     let value = navigate_json(data, path)?;
@@ -544,6 +560,87 @@ pub fn parse_batchexecute_list_response(text: &str) -> Result<Vec<RustSearchResu
     Ok(results)
 }
 
+/// Extract the continuation token from a batchexecute list response
+///
+/// The list cluster sits at inner[0][1][0][28]; its apps are at [0] and, as
+/// with the similar-apps cluster, the next-page token is at [1][3][1].
+/// Returns None on the last page or if the response cannot be parsed.
+pub fn extract_list_continuation_token(text: &str) -> Option<String> {
+    let outer_data = text
+        .lines()
+        .skip(1)
+        .take(5)
+        .filter(|line| line.starts_with('['))
+        .find_map(|line| serde_json::from_str::<Value>(line).ok())?;
+
+    let inner_json_str = outer_data.get(0)?.get(2)?.as_str()?;
+    let inner_data: Value = serde_json::from_str(inner_json_str).ok()?;
+
+    inner_data
+        .get(0)
+        .and_then(|v| v.get(1))
+        .and_then(|v| v.get(0))
+        .and_then(|v| v.get(28))
+        .and_then(|v| v.get(1))
+        .and_then(|v| v.get(3))
+        .and_then(|v| v.get(1))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Parse a batchexecute list response along with its continuation token
+pub fn parse_batchexecute_list_page_response(text: &str) -> Result<(Vec<RustSearchResult>, Option<String>)> {
+    let apps = parse_batchexecute_list_response(text)?;
+    Ok((apps, extract_list_continuation_token(text)))
+}
+
+/// Parse batchexecute API response for the similar-apps cluster
+///
+/// Returns the apps on this page and the continuation token for the next
+/// one. Apps sit at inner[1][1][0][21][0] and the token at
+/// inner[1][1][0][21][1][3][1].
+pub fn parse_batchexecute_similar_response(text: &str) -> Result<(Vec<RustSearchResult>, Option<String>)> {
+    let outer_data = text
+        .lines()
+        .skip(1)
+        .take(5)
+        .filter(|line| line.starts_with('['))
+        .find_map(|line| serde_json::from_str::<Value>(line).ok())
+        .ok_or_else(|| PlayfastError::ParseError("No valid JSON found in response lines".to_string()))?;
+
+    let inner_json_str = match outer_data.get(0).and_then(|v| v.get(2)).and_then(|v| v.as_str()) {
+        Some(s) if !s.trim().is_empty() && s != "null" => s,
+        _ => return Ok((Vec::new(), None)),
+    };
+
+    let inner_data: Value = serde_json::from_str(inner_json_str)
+        .map_err(|e| PlayfastError::ParseError(format!("Failed to parse inner JSON: {}", e)))?;
+
+    let cluster = match inner_data.get(1).and_then(|v| v.get(1)).and_then(|v| v.get(0)).and_then(|v| v.get(21)) {
+        Some(cluster) => cluster,
+        None => return Ok((Vec::new(), None)),
+    };
+
+    let apps = cluster
+        .get(0)
+        .and_then(|v| v.as_array())
+        .map(|apps| {
+            apps.iter()
+                .filter_map(|app| parse_batchexecute_search_result(app).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let next_token = cluster
+        .get(1)
+        .and_then(|v| v.get(3))
+        .and_then(|v| v.get(1))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    Ok((apps, next_token))
+}
+
 /// Parse batchexecute API response format for reviews
 /// This is the response from the /_/PlayStoreUi/data/batchexecute endpoint for reviews
 pub fn parse_batchexecute_reviews_response(text: &str) -> Result<(Vec<RustReview>, Option<String>)> {
@@ -638,6 +735,7 @@ pub fn parse_batchexecute_reviews_response(text: &str) -> Result<(Vec<RustReview
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::test_support::{batchexecute_app, batchexecute_list_page_response};
 
     #[test]
     fn test_navigate_json_root() {
@@ -995,6 +1093,134 @@ mod tests {
         assert_eq!(result.app_id, "com.free.app");
         assert_eq!(result.price, 0.0);
         assert_eq!(result.currency, "USD"); // default
+        assert_eq!(result.review_count, None);
+    }
+
+    #[test]
+    fn test_parse_single_search_result_rated() {
+        let search_json = serde_json::json!([
+            null,
+            [null, [[null, null, null, [null, null, "icon.jpg"]]]],
+            "Rated App",
+            null,
+            [[["Developer"]]],
+            null,
+            [[null, null, [null, ["4.3", 4.3, ["12,345", 12345]]]]], // [6][0][2][1] rating block
+            [],
+            null, null, null, null,
+            ["com.rated.app"]
+        ]);
+
+        let result = parse_single_search_result(&search_json).unwrap();
+
+        assert_eq!(result.score, Some(4.3));
+        assert_eq!(result.review_count, Some(12345));
+    }
+
+    fn batchexecute_list_response(apps: serde_json::Value) -> String {
+        batchexecute_list_page_response(apps, None)
+    }
+
+    #[test]
+    fn test_parse_batchexecute_list_with_ratings() {
+        let text = batchexecute_list_response(serde_json::json!([
+            batchexecute_app("com.rated.app", serde_json::json!(["4.6", 4.6, [null, 123456]])),
+            batchexecute_app("com.unrated.app", serde_json::Value::Null),
+        ]));
+
+        let results = parse_batchexecute_list_response(&text).unwrap();
+        assert_eq!(results.len(), 2);
+
+        assert_eq!(results[0].app_id, "com.rated.app");
+        assert!((results[0].score.unwrap() - 4.6).abs() < 1e-6);
+        assert_eq!(results[0].review_count, Some(123456));
+
+        assert_eq!(results[1].app_id, "com.unrated.app");
+        assert_eq!(results[1].score, None);
+        assert_eq!(results[1].review_count, None);
+    }
+
+    #[test]
+    fn test_extract_list_continuation_token() {
+        let text = batchexecute_list_page_response(
+            serde_json::json!([batchexecute_app("com.page.one", serde_json::Value::Null)]),
+            Some("CgYIZBCQAxgB"),
+        );
+        assert_eq!(extract_list_continuation_token(&text).as_deref(), Some("CgYIZBCQAxgB"));
+
+        let (apps, token) = parse_batchexecute_list_page_response(&text).unwrap();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].app_id, "com.page.one");
+        assert_eq!(token.as_deref(), Some("CgYIZBCQAxgB"));
+
+        let last = batchexecute_list_response(serde_json::json!([batchexecute_app("com.page.last", serde_json::Value::Null)]));
+        assert_eq!(extract_list_continuation_token(&last), None);
+        assert_eq!(extract_list_continuation_token("not a response"), None);
+    }
+
+    fn batchexecute_similar_response(apps: serde_json::Value, token: Option<&str>) -> String {
+        // apps at inner[1][1][0][21][0], token at inner[1][1][0][21][1][3][1]
+        let mut section = vec![serde_json::Value::Null; 22];
+        section[21] = serde_json::json!([apps, [null, null, null, [null, token]]]);
+        let inner = serde_json::json!([null, [null, [section]]]);
+        let outer = serde_json::json!([["wrb.fr", "ag2B9c", inner.to_string(), null, null, null, "generic"]]);
+        format!(")]}}'\n\n2048\n{}\n", outer)
+    }
+
+    #[test]
+    fn test_parse_batchexecute_similar_response() {
+        let text = batchexecute_similar_response(
+            serde_json::json!([
+                batchexecute_app("com.similar.one", serde_json::json!(["4.2", 4.2, [null, 900]])),
+                batchexecute_app("com.similar.two", serde_json::Value::Null),
+                serde_json::json!([]),
+            ]),
+            Some("CgQIZBAB"),
+        );
+
+        let (apps, token) = parse_batchexecute_similar_response(&text).unwrap();
+        assert_eq!(apps.len(), 2);
+        assert_eq!(apps[0].app_id, "com.similar.one");
+        assert_eq!(apps[0].review_count, Some(900));
+        assert_eq!(apps[1].app_id, "com.similar.two");
+        assert_eq!(token.as_deref(), Some("CgQIZBAB"));
+    }
+
+    #[test]
+    fn test_parse_batchexecute_similar_fixture() {
+        // Synthetic response in the full wire format (not captured from
+        // Play): length-prefixed chunks, trailing di/af.httprm rows and an
+        // ad slot among the apps
+        let text = include_str!("../tests/fixtures/similar_apps_batchexecute_synthetic.txt");
+
+        let (apps, token) = parse_batchexecute_similar_response(text).unwrap();
+        let ids: Vec<&str> = apps.iter().map(|app| app.app_id.as_str()).collect();
+        assert_eq!(ids, ["com.soundcloud.android", "deezer.android.app", "com.poweramp.v3"]);
+
+        assert_eq!(apps[0].title, "SoundCloud: Play Music & Songs");
+        assert_eq!(apps[0].developer, "SoundCloud");
+        assert!(apps[0].icon.starts_with("https://play-lh.googleusercontent.com/"));
+        assert_eq!(apps[0].score, Some(4.6));
+        assert_eq!(apps[0].review_count, Some(2861147));
+        assert_eq!(apps[0].price, 0.0);
+
+        assert_eq!(apps[2].price, 4.99);
+        assert_eq!(apps[2].currency, "USD");
+        assert_eq!(token.as_deref(), Some("CgYIZBCQAxgBGhAKDmNvbS5zcG90aWZ5Lm11c2lj"));
+    }
+
+    #[test]
+    fn test_parse_batchexecute_similar_last_page() {
+        let text = batchexecute_similar_response(
+            serde_json::json!([batchexecute_app("com.similar.last", serde_json::Value::Null)]),
+            None,
+        );
+        let (apps, token) = parse_batchexecute_similar_response(&text).unwrap();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(token, None);
+
+        let empty = ")]}'\n\n20\n[[\"wrb.fr\",\"ag2B9c\",null,null,null,null,\"generic\"]]\n";
+        assert_eq!(parse_batchexecute_similar_response(empty).unwrap().0.len(), 0);
     }
 
     #[test]
@@ -1064,3 +1290,44 @@ mod tests {
         assert!(results.len() <= 1); // May find 0 or 1 depending on structure
     }
 }
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    /// Wrap list apps (and an optional next-page token) in a batchexecute response
+    pub fn batchexecute_list_page_response(apps: serde_json::Value, token: Option<&str>) -> String {
+        // apps live at inner[0][1][0][28][0], token at inner[0][1][0][28][1][3][1]
+        let mut cluster = vec![serde_json::Value::Null; 29];
+        cluster[28] = match token {
+            Some(token) => serde_json::json!([apps, [null, null, null, [null, token]]]),
+            None => serde_json::json!([apps]),
+        };
+        let inner = serde_json::json!([[null, [cluster]]]);
+        let outer = serde_json::json!([["wrb.fr", "vyAe2", inner.to_string()]]);
+        format!(")]}}'\n\n100\n{}\n", outer)
+    }
+
+    /// Wrap reviews (and an optional next-page token) in a batchexecute response
+    pub fn batchexecute_reviews_response(review_ids: &[&str], token: Option<&str>) -> String {
+        // reviews live at inner[0], token at inner[len - 2][last]
+        let reviews: Vec<serde_json::Value> = review_ids
+            .iter()
+            .map(|id| serde_json::json!([id, ["Reviewer"], 4, null, format!("Review {}", id), [1_700_000_000], 2]))
+            .collect();
+        let inner = match token {
+            Some(token) => serde_json::json!([reviews, [null, token], null]),
+            None => serde_json::json!([reviews]),
+        };
+        let outer = serde_json::json!([["wrb.fr", "UsvDTd", inner.to_string()]]);
+        format!(")]}}'\n\n100\n{}\n", outer)
+    }
+
+    /// One list entry with the given id and rating block
+    pub fn batchexecute_app(app_id: &str, rating: serde_json::Value) -> serde_json::Value {
+        let mut app = vec![serde_json::Value::Null; 15];
+        app[0] = serde_json::json!([app_id]);
+        app[3] = serde_json::json!("Rated App");
+        app[4] = rating;
+        app[14] = serde_json::json!("Dev");
+        serde_json::json!([app])
+    }
+}
@@ -110,6 +110,7 @@ class TestAsyncClientSearch:
         mock_rust_result.developer = "Dev 1"
         mock_rust_result.icon = "https://example.com/icon1.png"
         mock_rust_result.score = 4.5
+        mock_rust_result.review_count = None
         mock_rust_result.price = 0.0
         mock_rust_result.currency = "USD"
 
@@ -144,6 +145,7 @@ class TestAsyncClientSearch:
             mock_result.developer = "Dev"
             mock_result.icon = "https://example.com/icon.png"
             mock_result.score = 4.0
+            mock_result.review_count = None
             mock_result.price = 0.0
             mock_result.currency = "USD"
             mock_results.append(mock_result)
@@ -287,6 +289,7 @@ class TestAsyncClientListMethod:
         mock_rust_result.developer = "Dev"
         mock_rust_result.icon = "https://example.com/icon.png"
         mock_rust_result.score = 4.5
+        mock_rust_result.review_count = None
         mock_rust_result.price = 0.0
         mock_rust_result.currency = "USD"
 
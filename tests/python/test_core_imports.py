@@ -1,5 +1,7 @@
 """Test that core API classes and functions are importable and accessible."""
 
+import pytest
+
 
 def test_import_entry_point_analysis_classes():
     """Test that entry point analysis classes are importable."""
@@ -63,3 +65,36 @@ def test_component_type_enum():
     assert hasattr(ComponentType, "Service")
     assert hasattr(ComponentType, "BroadcastReceiver")
     assert hasattr(ComponentType, "ContentProvider")
+
+
+def test_legacy_submodule():
+    """Test that deprecated aliases are namespaced under core.legacy."""
+    from playfast import core
+    from playfast.core.legacy import (
+        WebViewFlow,
+        WebViewFlowAnalyzer,
+        analyze_webview_flows_from_apk,
+        create_webview_analyzer_from_apk,
+    )
+
+    assert WebViewFlow is core.Flow
+    assert WebViewFlowAnalyzer is core.DataFlowAnalyzer
+    assert callable(analyze_webview_flows_from_apk)
+    assert callable(create_webview_analyzer_from_apk)
+
+
+def test_legacy_functions_warn():
+    """Test that deprecated flow wrappers warn on every call."""
+    from playfast import core
+
+    calls = [
+        (core.analyze_webview_flows_from_apk, ("/nonexistent/app.apk", 5)),
+        (core.create_webview_analyzer_from_apk, ("/nonexistent/app.apk",)),
+    ]
+    for func, args in calls:
+        # Still functional (delegates); the warnings filters, not the
+        # wrapper, decide whether repeats are shown
+        for _ in range(2):
+            with pytest.warns(DeprecationWarning, match="deprecated"):
+                with pytest.raises(OSError):
+                    func(*args)